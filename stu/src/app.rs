@@ -1,38 +1,78 @@
 use futures::StreamExt;
 use ratatui::{
-    layout::{Constraint, Layout, Rect},
+    crossterm::event::{KeyEvent, MouseEvent},
+    layout::{Constraint, Layout, Position, Rect},
     style::Stylize,
     widgets::Block,
     Frame,
 };
 use std::{
+    collections::HashMap,
+    fs::File,
     io::{BufWriter, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
     rc::Rc,
     sync::Arc,
+    time::Duration,
 };
-use tokio::spawn;
+use tokio::{runtime::Handle, spawn, task::spawn_blocking};
 
 use crate::{
+    bookmark::{load_bookmarks, save_bookmarks, Bookmark},
     client::Client,
     color::ColorTheme,
-    config::Config,
+    command::{expand_custom_command, needs_local_path, CustomCommandTarget, PipedCommand},
+    compare::build_prefix_diff,
+    config::{Config, CustomCommandMode},
+    diff::DiffTarget,
     environment::Environment,
     error::{AppError, Result},
     event::{
-        AppEventType, CompleteDownloadObjectResult, CompleteDownloadObjectsResult,
-        CompleteInitializeResult, CompleteLoadAllDownloadObjectListResult,
-        CompleteLoadObjectDetailResult, CompleteLoadObjectVersionsResult,
-        CompleteLoadObjectsResult, CompletePreviewObjectResult, CompleteReloadBucketsResult,
-        CompleteReloadObjectsResult, CompleteSaveObjectResult, Sender,
+        AppEventType, CompleteCalculatePrefixSizeResult, CompleteCustomCommandResult,
+        CompleteDownloadObjectResult, CompleteDownloadObjectsResult,
+        CompleteDownloadOpenObjectResult, CompleteExecuteSyncResult,
+        CompleteGeneratePresignedUrlResult, CompleteGoToObjectKeyResult, CompleteInitializeResult,
+        CompleteLoadAllDownloadObjectListResult, CompleteLoadEditObjectResult,
+        CompleteLoadGoToCandidatesResult, CompleteLoadObjectDetailResult,
+        CompleteLoadObjectVersionsResult, CompleteLoadObjectsResult,
+        CompleteLoadPrefixAnalyticsResult, CompleteLoadPrefixDiffResult,
+        CompleteLoadSplitPreviewResult, CompleteLoadSyncPlanResult, CompleteLoadTextDiffResult,
+        CompletePipeObjectResult, CompletePrepareCustomCommandResult, CompletePreviewObjectResult,
+        CompleteReloadBucketsResult, CompleteReloadEditedObjectResult, CompleteReloadObjectsResult,
+        CompleteSaveObjectResult, CompleteSwitchConnectionResult, CompleteTransferObjectsResult,
+        CompleteUploadEditedObjectResult, Sender,
     },
     file::{copy_to_clipboard, create_binary_file, save_error_log},
-    keys::UserEventMapper,
-    object::{AppObjects, DownloadObjectInfo, FileDetail, ObjectItem, ObjectKey, RawObject},
-    pages::page::{Page, PageStack},
-    widget::{Header, LoadingDialog, Status, StatusType},
+    format::{format_datetime, format_size_byte},
+    handle_user_events,
+    help::{
+        build_command_palette_items, build_help_spans, build_short_help_spans, BuildHelpsItem,
+        BuildShortHelpsItem, SpansWithPriority,
+    },
+    keys::{KeyInput, KeyInputState, UserEvent, UserEventMapper},
+    location::{candidate_names, completion_query, CompletionQuery, CompletionSource, Location},
+    mouse::{MouseInput, MouseInputState},
+    object::{
+        AppObjects, DownloadObjectInfo, FileDetail, ObjectItem, ObjectKey, PrefixSize,
+        PresignedUrlMethod, RawObject, SplitPreviewContent,
+    },
+    pages::{
+        commander::CommanderPane,
+        object_list::ObjectListPage,
+        page::{Page, PageStack},
+    },
+    session::{save_session, ObjectListSession, Session},
+    sync::{
+        build_sync_plan, list_local_files, md5_hex, SyncAction, SyncDirection, SyncOptions,
+        SyncPlan,
+    },
+    transfer::{Transfer, TransferMode},
+    widget::{Header, LoadingDialog, PrefixTree, PrefixTreeState, Status, StatusType, TabBar},
 };
 
+// the size of the text shown in the preview of the split layout
+const SPLIT_PREVIEW_MAX_BYTES: usize = 1024;
+
 #[derive(Debug)]
 pub enum Notification {
     None,
@@ -42,6 +82,20 @@ pub enum Notification {
     Error(String),
 }
 
+// identifies the tab that started a background task, the index changes as tabs are opened and closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TabId(usize);
+
+// the state of an inactive tab, the active tab is held by the fields of App
+#[derive(Debug)]
+struct Tab<C: Client> {
+    id: TabId,
+    page_stack: PageStack,
+    client: Arc<C>,
+    app_objects: AppObjects,
+    connection: Option<String>,
+}
+
 #[derive(Debug, Default)]
 pub struct AppContext {
     pub config: Config,
@@ -59,13 +113,41 @@ impl AppContext {
 pub struct App<C: Client> {
     pub page_stack: PageStack,
     pub mapper: UserEventMapper,
+    key_input: KeyInputState,
+    mouse_input: MouseInputState,
+    // the areas at the last render, to find the target of the mouse input
+    header_area: Rect,
+    tab_bar_area: Rect,
+    content_area: Rect,
+    prefix_tree_area: Rect,
     app_objects: AppObjects,
     client: Arc<C>,
+    // all tabs, the active one is None since its state is held by the fields above
+    tabs: Vec<Option<Tab<C>>>,
+    active_tab: usize,
+    active_tab_id: TabId,
+    next_tab_id: usize,
+    // client of the connection being switched to, replaced when buckets are loaded successfully
+    pending_client: Option<Arc<C>>,
+    // clients of the connections opened in the commander panes, shared by all tabs
+    connection_clients: HashMap<String, Arc<C>>,
     ctx: Rc<AppContext>,
     tx: Sender,
 
+    // whether the object list pages show the preview of the selected item
+    split_preview: bool,
+    // the sidebar shown beside the bucket list and the object list pages
+    prefix_tree: PrefixTreeState,
     notification: Notification,
     is_loading: bool,
+    // open the preview once the object detail page is opened by go to
+    pending_preview: bool,
+    // location specified on the command line, opened after initialization
+    initial_location: Option<(Location, bool)>,
+    // the current connection and the recent locations
+    session: Session,
+    // the last session whose page views are restored after initialization
+    restored_session: Option<Session>,
 }
 
 impl<C: Client> App<C> {
@@ -75,15 +157,55 @@ impl<C: Client> App<C> {
             app_objects: AppObjects::default(),
             page_stack: PageStack::new(Rc::clone(&ctx), tx.clone()),
             mapper,
+            key_input: KeyInputState::default(),
+            mouse_input: MouseInputState::default(),
+            header_area: Rect::default(),
+            tab_bar_area: Rect::default(),
+            content_area: Rect::default(),
+            prefix_tree_area: Rect::default(),
             client: Arc::new(client),
+            tabs: vec![None],
+            active_tab: 0,
+            active_tab_id: TabId(0),
+            next_tab_id: 1,
+            pending_client: None,
+            connection_clients: HashMap::new(),
+            split_preview: ctx.config.ui.object_list.split_preview,
+            prefix_tree: PrefixTreeState::default(),
             ctx,
             tx,
             notification: Notification::None,
             is_loading: true,
+            pending_preview: false,
+            initial_location: None,
+            session: Session::default(),
+            restored_session: None,
+        }
+    }
+
+    pub fn set_session(&mut self, session: Session, connection: Option<String>, restore: bool) {
+        self.session = Session {
+            connection,
+            recent_locations: session.recent_locations.clone(),
+            ..Default::default()
+        };
+        if restore {
+            self.restored_session = Some(session);
         }
     }
 
-    pub fn initialize(&mut self, bucket: Option<String>) {
+    pub fn initialize(&mut self, location: Option<Location>, preview: bool) {
+        // load all buckets to restore the bucket list page of the last session
+        let restore_bucket_list = self
+            .restored_session
+            .as_ref()
+            .is_some_and(|s| s.bucket_list.is_some());
+        let bucket = location
+            .as_ref()
+            .filter(|_| !restore_bucket_list)
+            .map(|l| l.object_key.bucket_name.clone());
+        self.initial_location = location.map(|l| (l, preview));
+
         let client = self.client.clone();
         let tx = self.tx.clone();
         spawn(async move {
@@ -103,11 +225,20 @@ impl<C: Client> App<C> {
 
                 if self.app_objects.get_bucket_items().len() > 1 {
                     // if multiple buckets are found, show bucket list page
-                    let bucket_list_page = Page::of_bucket_list(
+                    let mut bucket_list_page = Page::of_bucket_list(
                         self.app_objects.get_bucket_items(),
                         Rc::clone(&self.ctx),
                         self.tx.clone(),
                     );
+                    let view = self
+                        .restored_session
+                        .as_ref()
+                        .and_then(|s| s.bucket_list.as_ref());
+                    if let Some(view) = view {
+                        bucket_list_page
+                            .as_mut_bucket_list()
+                            .restore_view_session(view);
+                    }
                     self.page_stack.push(bucket_list_page);
                 }
             }
@@ -117,17 +248,24 @@ impl<C: Client> App<C> {
         }
 
         let bucket_items = self.app_objects.get_bucket_items();
+        let initial_location = self.initial_location.take();
 
-        if bucket_items.len() == 1 {
+        if bucket_items.is_empty() {
+            let msg = format!("No bucket found (region: {})", self.client.region());
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            self.restored_session = None;
+            self.is_loading = false;
+        } else if let Some((location, preview)) = initial_location {
+            // open the location specified on the command line (or restored from the last session)
+            // with its intermediate pages. since continues to load object, is_loading is not reset.
+            self.go_to_location(location, preview);
+        } else if bucket_items.len() == 1 {
             // bucket name is specified, or if there is only one bucket, open it.
             // since continues to load object, is_loading is not reset.
             let object_key = ObjectKey::bucket(&bucket_items[0].name);
             self.bucket_list_move_down(object_key);
         } else {
-            if bucket_items.is_empty() {
-                let msg = format!("No bucket found (region: {})", self.client.region());
-                self.tx.send(AppEventType::NotifyWarn(msg));
-            }
+            self.restored_session = None;
             self.is_loading = false;
         }
     }
@@ -147,16 +285,66 @@ impl<C: Client> App<C> {
         self.complete_initialize(result.map(|r| r.into()));
     }
 
+    pub fn start_switch_connection(&mut self, name: String) {
+        if let Page::Commander(page) = self.page_stack.current_page() {
+            // only the active pane is switched, so that objects can be transferred between connections
+            let pane = page.active();
+            self.tx
+                .send(AppEventType::CommanderSwitchConnection(pane, name));
+        } else {
+            self.tx.send(AppEventType::SwitchConnection(name));
+        }
+        self.is_loading = true;
+    }
+
+    pub async fn switch_connection(&mut self, name: String) {
+        let Some(connection) = self.ctx.config.connections.get(&name) else {
+            let e = AppError::msg(format!("Connection not found: {}", name));
+            self.tx.send(AppEventType::NotifyError(e));
+            self.is_loading = false;
+            return;
+        };
+        let default_region = self.ctx.config.default_region.clone();
+        let client = Arc::new(C::connect(connection, default_region).await);
+        self.pending_client = Some(client.clone());
+
+        let tx = self.tx.clone();
+        spawn(async move {
+            let buckets = client.load_all_buckets().await;
+            let result = CompleteSwitchConnectionResult::new(name, buckets);
+            tx.send(AppEventType::CompleteSwitchConnection(result));
+        });
+    }
+
+    pub fn complete_switch_connection(&mut self, result: Result<CompleteSwitchConnectionResult>) {
+        let client = self.pending_client.take();
+        match result {
+            Ok(CompleteSwitchConnectionResult { name, buckets }) => {
+                if let Some(client) = client {
+                    self.client = client;
+                }
+                self.session.connection = Some(name.clone());
+                self.app_objects.clear_all();
+                self.page_stack = PageStack::new(Rc::clone(&self.ctx), self.tx.clone());
+
+                let msg = format!("Switched connection to {}", name);
+                self.tx.send(AppEventType::NotifySuccess(msg));
+
+                self.complete_initialize(Ok(CompleteInitializeResult { buckets }));
+            }
+            Err(e) => {
+                // keep using the current connection
+                self.tx.send(AppEventType::NotifyError(e));
+                self.is_loading = false;
+            }
+        }
+    }
+
     pub fn bucket_list_move_down(&mut self, object_key: ObjectKey) {
         if let Some(current_object_items) = self.app_objects.get_object_items(&object_key) {
             // object list has been already loaded
-            let object_list_page = Page::of_object_list(
-                current_object_items,
-                object_key,
-                Rc::clone(&self.ctx),
-                self.tx.clone(),
-            );
-            self.page_stack.push(object_list_page);
+            let object_list_page = self.build_object_list_page(current_object_items, object_key);
+            self.push_object_list_page(object_list_page);
         } else {
             self.tx.send(AppEventType::LoadObjects(object_key));
             self.is_loading = true;
@@ -199,13 +387,9 @@ impl<C: Client> App<C> {
                 let object_key = object_list_page.current_selected_object_key();
                 if let Some(current_object_items) = self.app_objects.get_object_items(&object_key) {
                     // object list has been already loaded
-                    let new_object_list_page = Page::of_object_list(
-                        current_object_items,
-                        object_key,
-                        Rc::clone(&self.ctx),
-                        self.tx.clone(),
-                    );
-                    self.page_stack.push(new_object_list_page);
+                    let new_object_list_page =
+                        self.build_object_list_page(current_object_items, object_key);
+                    self.push_object_list_page(new_object_list_page);
                 } else {
                     self.tx.send(AppEventType::LoadObjects(object_key));
                     self.is_loading = true;
@@ -230,421 +414,2281 @@ impl<C: Client> App<C> {
         self.is_loading = true;
     }
 
-    pub fn back_to_bucket_list(&mut self) {
-        if self.app_objects.get_bucket_items().len() == 1 {
+    pub fn calculate_prefix_size(&mut self, object_keys: Vec<ObjectKey>) {
+        let object_keys: Vec<ObjectKey> = object_keys
+            .into_iter()
+            .filter(|key| self.app_objects.get_prefix_size(key).is_none())
+            .collect();
+        if object_keys.is_empty() {
             return;
         }
-        self.page_stack.clear();
-    }
 
-    pub fn load_objects(&self, current_object_key: ObjectKey) {
-        let bucket = current_object_key.bucket_name.clone();
-        let prefix = current_object_key.joined_object_path(false);
+        for key in &object_keys {
+            // mark as calculating to avoid duplicate requests
+            self.app_objects
+                .set_prefix_size(key.clone(), PrefixSize::default());
+            update_prefix_size_in_pages(&mut self.page_stack, key, PrefixSize::default());
+        }
 
+        let tab_id = self.active_tab_id;
         let client = self.client.clone();
         let tx = self.tx.clone();
+        let max_concurrent_requests = self.ctx.config.max_concurrent_requests;
         spawn(async move {
-            let items = client.load_objects(&bucket, &prefix).await;
-            let result = CompleteLoadObjectsResult::new(items, current_object_key);
-            tx.send(AppEventType::CompleteLoadObjects(result));
+            futures::stream::iter(object_keys)
+                .for_each_concurrent(max_concurrent_requests, |object_key| {
+                    let client = client.clone();
+                    let tx = tx.clone();
+                    async move {
+                        let bucket = object_key.bucket_name.clone();
+                        let prefix = object_key.joined_object_path(false);
+                        let progress = |size: &PrefixSize| {
+                            let event = AppEventType::ProgressCalculatePrefixSize(
+                                tab_id,
+                                object_key.clone(),
+                                size.clone(),
+                            );
+                            tx.send(event);
+                        };
+                        let size = client
+                            .calculate_prefix_size(&bucket, &prefix, progress)
+                            .await;
+                        let result = CompleteCalculatePrefixSizeResult::new(size);
+                        tx.send(AppEventType::CompleteCalculatePrefixSize(
+                            tab_id, object_key, result,
+                        ));
+                    }
+                })
+                .await;
         });
     }
 
-    pub fn complete_load_objects(&mut self, result: Result<CompleteLoadObjectsResult>) {
-        match result {
-            Ok(CompleteLoadObjectsResult { items, object_key }) => {
-                self.app_objects
-                    .set_object_items(object_key.clone(), items.clone());
+    pub fn progress_calculate_prefix_size(
+        &mut self,
+        tab_id: TabId,
+        object_key: ObjectKey,
+        size: PrefixSize,
+    ) {
+        let Some((page_stack, app_objects)) = self.tab_state_mut(tab_id) else {
+            // the tab has been closed
+            return;
+        };
+        if app_objects.get_prefix_size(&object_key).is_none() {
+            // cache has been cleared by refresh
+            return;
+        }
+        app_objects.set_prefix_size(object_key.clone(), size.clone());
+        update_prefix_size_in_pages(page_stack, &object_key, size);
+    }
 
-                let object_list_page =
-                    Page::of_object_list(items, object_key, Rc::clone(&self.ctx), self.tx.clone());
-                self.page_stack.push(object_list_page);
+    pub fn complete_calculate_prefix_size(
+        &mut self,
+        tab_id: TabId,
+        object_key: ObjectKey,
+        result: Result<CompleteCalculatePrefixSizeResult>,
+    ) {
+        let Some((page_stack, app_objects)) = self.tab_state_mut(tab_id) else {
+            // the tab has been closed
+            return;
+        };
+        if app_objects.get_prefix_size(&object_key).is_none() {
+            // cache has been cleared by refresh
+            return;
+        }
+        match result {
+            Ok(CompleteCalculatePrefixSizeResult { size }) => {
+                app_objects.set_prefix_size(object_key.clone(), size.clone());
+                update_prefix_size_in_pages(page_stack, &object_key, size);
             }
             Err(e) => {
+                app_objects.remove_prefix_size(&object_key);
+                remove_prefix_size_in_pages(page_stack, &object_key);
                 self.tx.send(AppEventType::NotifyError(e));
             }
         }
-        self.is_loading = false;
     }
 
-    pub fn reload_objects(&self) {
-        let object_list_page = self.page_stack.current_page().as_object_list();
-        let object_key = object_list_page.current_dir_object_key().clone();
-        let bucket = object_key.bucket_name.clone();
-        let prefix = object_key.joined_object_path(false);
-
-        let client = self.client.clone();
-        let tx = self.tx.clone();
-        spawn(async move {
-            let items = client.load_objects(&bucket, &prefix).await;
-            let result = CompleteReloadObjectsResult::new(items, object_key);
-            tx.send(AppEventType::CompleteReloadObjects(result));
-        });
-    }
+    fn build_object_list_page(&self, items: Vec<ObjectItem>, object_key: ObjectKey) -> Page {
+        let prefix_sizes = self.cached_prefix_sizes(&items, &object_key);
 
-    pub fn complete_reload_objects(&mut self, result: Result<CompleteReloadObjectsResult>) {
-        self.page_stack.pop();
-        self.complete_load_objects(result.map(|r| r.into()));
+        let mut page =
+            Page::of_object_list(items, object_key, Rc::clone(&self.ctx), self.tx.clone());
+        let object_list_page = page.as_mut_object_list();
+        for (name, size) in prefix_sizes {
+            object_list_page.set_prefix_size(name, size);
+        }
+        object_list_page.set_split_preview_enabled(self.split_preview);
+        page
     }
 
-    pub fn load_object_detail(&self) {
-        let object_list_page = self.page_stack.current_page().as_object_list();
-
-        if let ObjectItem::File { name, .. } = object_list_page.current_selected_item() {
-            let name = name.clone();
-
-            let map_key = object_list_page.current_selected_object_key().clone();
-            let bucket = map_key.bucket_name.clone();
-            let key = map_key.joined_object_path(true);
+    // the pane of the commander, which does not show the split preview
+    fn build_commander_pane(
+        &self,
+        items: Vec<ObjectItem>,
+        object_key: ObjectKey,
+        use_cache: bool,
+    ) -> ObjectListPage {
+        let prefix_sizes = if use_cache {
+            self.cached_prefix_sizes(&items, &object_key)
+        } else {
+            Vec::new()
+        };
 
-            let client = self.client.clone();
-            let tx = self.tx.clone();
-            spawn(async move {
-                let detail = client.load_object_detail(&bucket, &key, &name).await;
-                let result = CompleteLoadObjectDetailResult::new(detail, map_key);
-                tx.send(AppEventType::CompleteLoadObjectDetail(result));
-            });
+        let mut page =
+            ObjectListPage::new(items, object_key, Rc::clone(&self.ctx), self.tx.clone());
+        for (name, size) in prefix_sizes {
+            page.set_prefix_size(name, size);
         }
+        page
     }
 
-    pub fn complete_load_object_detail(&mut self, result: Result<CompleteLoadObjectDetailResult>) {
-        match result {
-            Ok(CompleteLoadObjectDetailResult { detail, map_key }) => {
-                self.app_objects
-                    .set_object_detail(map_key.clone(), *detail.clone());
+    fn cached_prefix_sizes(
+        &self,
+        items: &[ObjectItem],
+        object_key: &ObjectKey,
+    ) -> Vec<(String, PrefixSize)> {
+        items
+            .iter()
+            .filter_map(|item| match item {
+                ObjectItem::Dir { name, .. } => self
+                    .app_objects
+                    .get_prefix_size(&object_key.child(name))
+                    .map(|size| (name.clone(), size.clone())),
+                ObjectItem::File { .. } => None,
+            })
+            .collect()
+    }
 
-                let object_page = self.page_stack.current_page().as_object_list();
+    fn push_object_list_page(&mut self, page: Page) {
+        let object_key = page.as_object_list().current_dir_object_key();
+        let max = self.ctx.config.session.max_recent_locations;
+        self.session.add_recent_location(object_key, max);
+        self.page_stack.push(page);
+    }
 
-                let object_detail_page = Page::of_object_detail(
-                    *detail.clone(),
-                    object_page.object_list(),
-                    map_key,
-                    object_page.list_state(),
-                    Rc::clone(&self.ctx),
-                    self.tx.clone(),
-                );
-                self.page_stack.push(object_detail_page);
-            }
-            Err(e) => {
-                self.tx.send(AppEventType::NotifyError(e));
+    pub fn object_list_toggle_split_preview(&mut self) {
+        self.split_preview = !self.split_preview;
+        for page in self.page_stack.iter_mut() {
+            if let Page::ObjectList(page) = page {
+                page.set_split_preview_enabled(self.split_preview);
             }
         }
-        self.is_loading = false;
     }
 
-    pub fn open_object_versions_tab(&mut self) {
-        let object_detail_page = self.page_stack.current_page().as_object_detail();
-
-        let current_object_key = object_detail_page.current_object_key().clone();
-        let versions = self.app_objects.get_object_versions(&current_object_key);
-
-        if let Some(versions) = versions {
-            // object versions has been already loaded
-            let result =
-                CompleteLoadObjectVersionsResult::new(Ok(versions.clone()), current_object_key);
-            self.tx
-                .send(AppEventType::CompleteLoadObjectVersions(result));
-        } else {
-            self.tx.send(AppEventType::LoadObjectVersions);
-            self.is_loading = true;
+    pub fn start_load_split_preview(&mut self, object_key: ObjectKey, dir: bool) {
+        if let Some(content) = self.cached_split_preview(&object_key, dir) {
+            set_split_preview_in_pages(&mut self.page_stack, &object_key, Ok(content));
+            return;
         }
-    }
-
-    pub fn load_object_versions(&self) {
-        let object_detail_page = self.page_stack.current_page().as_object_detail();
-
-        let map_key = object_detail_page.current_object_key().clone();
-        let bucket = map_key.bucket_name.clone();
-        let key = map_key.joined_object_path(true);
 
-        let client = self.client.clone();
+        // wait a moment so as not to load all the items passed by while moving the selection
+        let delay =
+            Duration::from_millis(self.ctx.config.ui.object_list.split_preview_delay_millis);
+        let tab_id = self.active_tab_id;
         let tx = self.tx.clone();
         spawn(async move {
-            let versions = client.load_object_versions(&bucket, &key).await;
-            let result = CompleteLoadObjectVersionsResult::new(versions, map_key);
-            tx.send(AppEventType::CompleteLoadObjectVersions(result));
+            tokio::time::sleep(delay).await;
+            tx.send(AppEventType::LoadSplitPreview(tab_id, object_key, dir));
         });
     }
 
-    pub fn complete_load_object_versions(
-        &mut self,
-        result: Result<CompleteLoadObjectVersionsResult>,
-    ) {
-        match result {
-            Ok(CompleteLoadObjectVersionsResult { versions, map_key }) => {
-                self.app_objects
-                    .set_object_versions(map_key, versions.clone());
-
-                let object_detail_page = self.page_stack.current_page_mut().as_mut_object_detail();
-                object_detail_page.set_versions(versions);
-                object_detail_page.select_versions_tab();
-            }
-            Err(e) => {
-                self.tx.send(AppEventType::NotifyError(e));
-            }
+    pub fn load_split_preview(&mut self, tab_id: TabId, object_key: ObjectKey, dir: bool) {
+        let Some(client) = self.tab_client(tab_id) else {
+            return;
+        };
+        let Some((page_stack, _)) = self.tab_state_mut(tab_id) else {
+            return;
+        };
+        let requested = page_stack.iter().any(|page| {
+            matches!(page, Page::ObjectList(page) if page.split_preview_loading_key() == Some(&object_key))
+        });
+        if !requested {
+            // another item has been selected while waiting
+            return;
         }
-        self.is_loading = false;
-    }
-
-    pub fn start_load_all_download_objects(&mut self, key: ObjectKey, download_as: bool) {
-        self.tx
-            .send(AppEventType::LoadAllDownloadObjectList(key, download_as));
-        self.is_loading = true;
-    }
-
-    pub fn load_all_download_objects(&self, key: ObjectKey, download_as: bool) {
-        let bucket = key.bucket_name.clone();
-        let prefix = key.joined_object_path(false);
 
-        let client = self.client.clone();
         let tx = self.tx.clone();
         spawn(async move {
-            let objects = client.list_all_download_objects(&bucket, &prefix).await;
-            let result = CompleteLoadAllDownloadObjectListResult::new(objects, download_as);
-            tx.send(AppEventType::CompleteLoadAllDownloadObjectList(result));
+            let bucket = object_key.bucket_name.clone();
+            let content = if dir {
+                let prefix = object_key.joined_object_path(false);
+                let items = client.load_objects(&bucket, &prefix).await;
+                items.map(SplitPreviewContent::Dir)
+            } else {
+                async {
+                    let key = object_key.joined_object_path(true);
+                    let name = object_key.object_path.last().cloned().unwrap_or_default();
+                    let detail = client.load_object_detail(&bucket, &key, &name).await?;
+                    let head = if detail.size_byte == 0 {
+                        // range request fails for an empty object
+                        Vec::new()
+                    } else {
+                        client
+                            .load_object_head(&bucket, &key, SPLIT_PREVIEW_MAX_BYTES)
+                            .await?
+                    };
+                    Ok(SplitPreviewContent::File(Box::new(detail), head))
+                }
+                .await
+            };
+            let result = CompleteLoadSplitPreviewResult::new(content);
+            tx.send(AppEventType::CompleteLoadSplitPreview(
+                tab_id, object_key, result,
+            ));
         });
     }
 
-    pub fn complete_load_all_download_objects(
+    pub fn complete_load_split_preview(
         &mut self,
-        result: Result<CompleteLoadAllDownloadObjectListResult>,
+        tab_id: TabId,
+        object_key: ObjectKey,
+        result: Result<CompleteLoadSplitPreviewResult>,
     ) {
+        let Some((page_stack, app_objects)) = self.tab_state_mut(tab_id) else {
+            // the tab has been closed
+            return;
+        };
         match result {
-            Ok(CompleteLoadAllDownloadObjectListResult { objs, download_as }) => {
-                match self.page_stack.current_page_mut() {
-                    Page::BucketList(page) => {
-                        page.open_download_confirm_dialog(objs, download_as);
+            Ok(CompleteLoadSplitPreviewResult { content }) => {
+                match &content {
+                    SplitPreviewContent::Dir(items) => {
+                        app_objects.set_object_items(object_key.clone(), items.clone());
                     }
-                    Page::ObjectList(page) => {
-                        page.open_download_confirm_dialog(objs, download_as);
+                    SplitPreviewContent::File(detail, head) => {
+                        app_objects.set_object_detail(object_key.clone(), *detail.clone());
+                        app_objects.set_object_head(object_key.clone(), head.clone());
                     }
-                    page => panic!("Invalid page: {:?}", page),
                 }
+                set_split_preview_in_pages(page_stack, &object_key, Ok(content));
             }
             Err(e) => {
-                self.tx.send(AppEventType::NotifyError(e));
+                // shown in the pane instead of the notification, which would cancel the next key input
+                set_split_preview_in_pages(page_stack, &object_key, Err(e.msg.clone()));
+                self.handle_error(&e);
             }
         }
-        self.is_loading = false;
     }
 
-    pub fn open_help(&mut self) {
-        let helps = self.page_stack.current_page().helps(&self.mapper);
-        if helps.is_empty() {
-            return;
+    fn cached_split_preview(
+        &self,
+        object_key: &ObjectKey,
+        dir: bool,
+    ) -> Option<SplitPreviewContent> {
+        if dir {
+            let items = self.app_objects.get_object_items(object_key)?;
+            Some(SplitPreviewContent::Dir(items))
+        } else {
+            let detail = self.app_objects.get_object_detail(object_key)?;
+            let head = self.app_objects.get_object_head(object_key)?;
+            Some(SplitPreviewContent::File(
+                Box::new(detail.clone()),
+                head.clone(),
+            ))
         }
-        let help_page = Page::of_help(helps, Rc::clone(&self.ctx), self.tx.clone());
-        self.page_stack.push(help_page);
     }
 
-    pub fn close_current_page(&mut self) {
-        self.page_stack.pop();
+    pub fn go_to_object_key(&mut self, object_key: ObjectKey, name: Option<String>) {
+        self.go_to_object_key_and_open(object_key, name, false);
     }
 
-    pub fn open_preview(
+    pub fn open_object_detail(&mut self, object_key: ObjectKey) {
+        let (Some(parent), Some(name)) = (object_key.parent(), object_key.object_path.last())
+        else {
+            return;
+        };
+        self.go_to_object_key_and_open(parent, Some(name.clone()), true);
+    }
+
+    fn go_to_object_key_and_open(
         &mut self,
         object_key: ObjectKey,
-        file_detail: FileDetail,
-        version_id: Option<String>,
+        name: Option<String>,
+        open_detail: bool,
     ) {
-        self.tx.send(AppEventType::PreviewObject(
-            object_key,
+        // the pages opened from the results are pushed on top of them, so that going back returns to the results
+        let on_top = matches!(
+            self.page_stack.current_page(),
+            Page::PrefixDiff(_) | Page::PrefixAnalytics(_)
+        );
+        let hierarchy = if on_top {
+            vec![object_key.clone()]
+        } else {
+            object_key.hierarchy()
+        };
+        let missing_keys: Vec<ObjectKey> = hierarchy
+            .into_iter()
+            .filter(|key| self.app_objects.get_object_items(key).is_none())
+            .collect();
+        // the object detail page is opened without its object list page
+        let missing_detail = name
+            .as_ref()
+            .map(|name| object_key.child(name))
+            .filter(|key| {
+                on_top && open_detail && self.app_objects.get_object_detail(key).is_none()
+            });
+
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            let loaded = async {
+                let mut loaded = Vec::new();
+                for key in missing_keys {
+                    let bucket = key.bucket_name.clone();
+                    let prefix = key.joined_object_path(false);
+                    let items = client.load_objects(&bucket, &prefix).await?;
+                    loaded.push((key, items));
+                }
+                let detail = match missing_detail {
+                    Some(key) => {
+                        let bucket = key.bucket_name.clone();
+                        let name = key.object_path.last().cloned().unwrap_or_default();
+                        let key = key.joined_object_path(true);
+                        Some(client.load_object_detail(&bucket, &key, &name).await?)
+                    }
+                    None => None,
+                };
+                Ok((loaded, detail))
+            }
+            .await;
+            let result =
+                CompleteGoToObjectKeyResult::new(loaded, object_key, name, open_detail, on_top);
+            tx.send(AppEventType::CompleteGoToObjectKey(result));
+        });
+        self.is_loading = true;
+    }
+
+    pub fn complete_go_to_object_key(&mut self, result: Result<CompleteGoToObjectKeyResult>) {
+        match result {
+            Ok(CompleteGoToObjectKeyResult {
+                object_key,
+                name,
+                open_detail,
+                on_top,
+                loaded,
+                detail,
+            }) => {
+                for (key, items) in loaded {
+                    self.app_objects.set_object_items(key, items);
+                }
+
+                if on_top {
+                    self.open_object_key_on_top(object_key, name, open_detail, detail);
+                    self.pending_preview = false;
+                    self.is_loading = false;
+                    return;
+                }
+
+                self.page_stack.clear();
+
+                let mut selected = false;
+                let hierarchy = object_key.hierarchy();
+                for (i, key) in hierarchy.iter().enumerate() {
+                    let already_opened = matches!(
+                        self.page_stack.current_page(),
+                        Page::ObjectList(page) if page.current_dir_object_key() == key
+                    );
+                    if !already_opened {
+                        // all items have been loaded above
+                        let items = self.app_objects.get_object_items(key).unwrap_or_default();
+                        let page = self.build_object_list_page(items, key.clone());
+                        self.push_object_list_page(page);
+
+                        let view = self
+                            .restored_session
+                            .as_ref()
+                            .and_then(|s| s.object_list_view(key));
+                        if let Some(view) = view {
+                            self.page_stack
+                                .current_page_mut()
+                                .as_mut_object_list()
+                                .restore_view_session(view);
+                        }
+                    }
+
+                    // select the next directory (or the target object) in each page
+                    let selected_name = match hierarchy.get(i + 1) {
+                        Some(next) => next.object_path.last().cloned(),
+                        None => name.clone(),
+                    };
+                    if let Some(selected_name) = selected_name {
+                        selected = self
+                            .page_stack
+                            .current_page_mut()
+                            .as_mut_object_list()
+                            .select_item_by_name(&selected_name);
+                    }
+                }
+
+                if open_detail {
+                    // open only if the target object was found and selected
+                    if selected {
+                        self.restored_session = None;
+                        self.is_loading = false;
+                        self.object_list_move_down();
+                        self.open_pending_preview();
+                        return;
+                    }
+                    let msg = format!("Object not found: {}", name.unwrap_or_default());
+                    self.tx.send(AppEventType::NotifyWarn(msg));
+                }
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.pending_preview = false;
+        self.restored_session = None;
+        self.is_loading = false;
+    }
+
+    fn open_object_key_on_top(
+        &mut self,
+        object_key: ObjectKey,
+        name: Option<String>,
+        open_detail: bool,
+        detail: Option<FileDetail>,
+    ) {
+        let items = self
+            .app_objects
+            .get_object_items(&object_key)
+            .unwrap_or_default();
+        let mut page = self.build_object_list_page(items, object_key);
+        let object_list_page = page.as_mut_object_list();
+        let selected = name
+            .as_ref()
+            .is_some_and(|name| object_list_page.select_item_by_name(name));
+        if !open_detail {
+            self.push_object_list_page(page);
+            return;
+        }
+
+        let object_key = object_list_page.current_selected_object_key();
+        if let Some(detail) = detail {
+            self.app_objects
+                .set_object_detail(object_key.clone(), detail);
+        }
+        let detail = self.app_objects.get_object_detail(&object_key);
+        let (true, Some(detail)) = (selected, detail) else {
+            let msg = format!("Object not found: {}", name.unwrap_or_default());
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        };
+        let object_detail_page = Page::of_object_detail(
+            detail.clone(),
+            object_list_page.object_list(),
+            object_key,
+            object_list_page.list_state(),
+            Rc::clone(&self.ctx),
+            self.tx.clone(),
+        );
+        self.page_stack.push(object_detail_page);
+    }
+
+    pub fn go_to_location(&mut self, location: Location, preview: bool) {
+        if location.is_dir {
+            self.go_to_object_key(location.object_key, None);
+        } else {
+            self.pending_preview = preview;
+            self.open_object_detail(location.object_key);
+        }
+    }
+
+    fn open_pending_preview(&mut self) {
+        if !self.pending_preview {
+            return;
+        }
+        // the object detail may still be loading
+        if let Page::ObjectDetail(page) = self.page_stack.current_page() {
+            self.pending_preview = false;
+            page.preview();
+        }
+    }
+
+    pub fn load_go_to_candidates(&mut self, input: String) {
+        let Some(query) = completion_query(&input) else {
+            return;
+        };
+        match query.source {
+            CompletionSource::Buckets => {
+                let names: Vec<String> = self
+                    .app_objects
+                    .get_bucket_items()
+                    .into_iter()
+                    .map(|item| item.name)
+                    .collect();
+                self.set_go_to_completion(&input, query, &names);
+            }
+            CompletionSource::Dir(ref object_key) => {
+                if let Some(items) = self.app_objects.get_object_items(object_key) {
+                    // object list has been already loaded
+                    let names = candidate_names(&items);
+                    self.set_go_to_completion(&input, query, &names);
+                    return;
+                }
+
+                let object_key = object_key.clone();
+                let bucket = object_key.bucket_name.clone();
+                let prefix = object_key.joined_object_path(false);
+
+                let client = self.client.clone();
+                let tx = self.tx.clone();
+                spawn(async move {
+                    let items = client.load_objects(&bucket, &prefix).await;
+                    let result = CompleteLoadGoToCandidatesResult::new(items, input, object_key);
+                    tx.send(AppEventType::CompleteLoadGoToCandidates(result));
+                });
+                self.is_loading = true;
+            }
+        }
+    }
+
+    pub fn complete_load_go_to_candidates(
+        &mut self,
+        result: Result<CompleteLoadGoToCandidatesResult>,
+    ) {
+        match result {
+            Ok(CompleteLoadGoToCandidatesResult {
+                input,
+                object_key,
+                items,
+            }) => {
+                let names = candidate_names(&items);
+                self.app_objects.set_object_items(object_key, items);
+                if let Some(query) = completion_query(&input) {
+                    self.set_go_to_completion(&input, query, &names);
+                }
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.is_loading = false;
+    }
+
+    fn set_go_to_completion(&mut self, input: &str, query: CompletionQuery, names: &[String]) {
+        let (completed, candidates) = query.complete(names);
+        match self.page_stack.current_page_mut() {
+            Page::BucketList(page) => {
+                page.set_go_to_completion(input, completed, candidates);
+            }
+            Page::ObjectList(page) => {
+                page.set_go_to_completion(input, completed, candidates);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn add_bookmark(&self) {
+        let bookmark = match self.page_stack.current_page() {
+            Page::BucketList(page) if page.non_empty() => {
+                Bookmark::dir(&page.current_selected_object_key())
+            }
+            Page::ObjectList(page) => Bookmark::dir(page.current_dir_object_key()),
+            Page::Commander(page) => Bookmark::dir(page.active_pane().current_dir_object_key()),
+            Page::ObjectDetail(page) => Bookmark::object(page.current_object_key()),
+            Page::ObjectPreview(page) => Bookmark::object(page.current_object_key()),
+            _ => {
+                let msg = "Nothing to bookmark on this page";
+                self.tx.send(AppEventType::NotifyWarn(msg.into()));
+                return;
+            }
+        };
+
+        let result = self.load_bookmarks().and_then(|mut bookmarks| {
+            if bookmarks.contains(&bookmark) {
+                return Ok(false);
+            }
+            bookmarks.push(bookmark.clone());
+            self.save_bookmarks(&bookmarks)?;
+            Ok(true)
+        });
+        match result {
+            Ok(true) => {
+                let msg = format!("Bookmarked: {}", bookmark.uri());
+                self.tx.send(AppEventType::NotifySuccess(msg));
+            }
+            Ok(false) => {
+                let msg = format!("Already bookmarked: {}", bookmark.uri());
+                self.tx.send(AppEventType::NotifyInfo(msg));
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+    }
+
+    pub fn open_bookmarks(&mut self) {
+        if matches!(self.page_stack.current_page(), Page::Bookmarks(_)) {
+            return;
+        }
+        match self.load_bookmarks() {
+            Ok(bookmarks) => {
+                let page = Page::of_bookmarks(bookmarks, Rc::clone(&self.ctx), self.tx.clone());
+                self.page_stack.push(page);
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+    }
+
+    pub fn open_bookmark(&mut self, bookmark: Bookmark) {
+        let object_key = bookmark.object_key();
+        if bookmark.is_object() {
+            self.open_object_detail(object_key);
+        } else {
+            self.go_to_object_key(object_key, None);
+        }
+    }
+
+    pub fn open_recent_locations(&mut self) {
+        if matches!(self.page_stack.current_page(), Page::RecentLocations(_)) {
+            return;
+        }
+        let locations = self.session.recent_locations.clone();
+        let page = Page::of_recent_locations(locations, Rc::clone(&self.ctx), self.tx.clone());
+        self.page_stack.push(page);
+    }
+
+    pub fn open_connections(&mut self) {
+        if matches!(self.page_stack.current_page(), Page::Connections(_)) {
+            return;
+        }
+        let names: Vec<String> = self.ctx.config.connections.keys().cloned().collect();
+        if names.is_empty() {
+            let msg = "No connections are defined in the config file";
+            self.tx.send(AppEventType::NotifyWarn(msg.into()));
+            return;
+        }
+        let page = Page::of_connections(names, Rc::clone(&self.ctx), self.tx.clone());
+        self.page_stack.push(page);
+    }
+
+    pub fn save_session(&self) {
+        let mut session = Session {
+            connection: self.session.connection.clone(),
+            recent_locations: self.session.recent_locations.clone(),
+            ..Default::default()
+        };
+        for page in self.page_stack.iter() {
+            match page {
+                Page::BucketList(page) => {
+                    session.bucket_list = Some(page.view_session());
+                }
+                Page::ObjectList(page) => {
+                    let object_key = page.current_dir_object_key();
+                    let view = page.view_session();
+                    session
+                        .object_lists
+                        .push(ObjectListSession::new(object_key, view));
+                }
+                Page::ObjectDetail(page) => {
+                    // only the object in the last prefix can be restored
+                    let object_key = page.current_object_key();
+                    let in_last_prefix = session
+                        .object_lists
+                        .last()
+                        .is_some_and(|s| object_key.parent() == Some(s.object_key()));
+                    if in_last_prefix && session.object.is_none() {
+                        session.object = Some(object_key.joined_object_path(true));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let result = self
+            .ctx
+            .config
+            .session_file_path()
+            .map_err(|e| AppError::msg(e.to_string()))
+            .and_then(|path| save_session(path, &session));
+        if let Err(e) = result {
+            self.handle_error(&e);
+        }
+    }
+
+    pub fn delete_bookmark(&self, bookmark: Bookmark) {
+        let result = self.load_bookmarks().and_then(|mut bookmarks| {
+            bookmarks.retain(|b| b != &bookmark);
+            self.save_bookmarks(&bookmarks)
+        });
+        if let Err(e) = result {
+            self.tx.send(AppEventType::NotifyError(e));
+        }
+    }
+
+    fn load_bookmarks(&self) -> Result<Vec<Bookmark>> {
+        let path = self
+            .ctx
+            .config
+            .bookmarks_file_path()
+            .map_err(|e| AppError::msg(e.to_string()))?;
+        load_bookmarks(path)
+    }
+
+    fn save_bookmarks(&self, bookmarks: &[Bookmark]) -> Result<()> {
+        let path = self
+            .ctx
+            .config
+            .bookmarks_file_path()
+            .map_err(|e| AppError::msg(e.to_string()))?;
+        save_bookmarks(path, bookmarks)
+    }
+
+    pub fn start_load_prefix_analytics(&mut self, object_key: ObjectKey) {
+        self.tx.send(AppEventType::LoadPrefixAnalytics(object_key));
+        self.is_loading = true;
+    }
+
+    pub fn load_prefix_analytics(&self, object_key: ObjectKey) {
+        let bucket = object_key.bucket_name.clone();
+        let prefix = object_key.joined_object_path(false);
+
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            let objects = client.list_all_object_summaries(&bucket, &prefix).await;
+            let result = CompleteLoadPrefixAnalyticsResult::new(objects, object_key);
+            tx.send(AppEventType::CompleteLoadPrefixAnalytics(result));
+        });
+    }
+
+    pub fn complete_load_prefix_analytics(
+        &mut self,
+        result: Result<CompleteLoadPrefixAnalyticsResult>,
+    ) {
+        match result {
+            Ok(CompleteLoadPrefixAnalyticsResult {
+                object_key,
+                objects,
+            }) => {
+                let page = Page::of_prefix_analytics(
+                    object_key,
+                    objects,
+                    Rc::clone(&self.ctx),
+                    self.tx.clone(),
+                );
+                self.page_stack.push(page);
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.is_loading = false;
+    }
+
+    pub fn back_to_bucket_list(&mut self) {
+        if self.app_objects.get_bucket_items().len() == 1 {
+            return;
+        }
+        self.page_stack.clear();
+    }
+
+    pub fn load_objects(&self, current_object_key: ObjectKey) {
+        let bucket = current_object_key.bucket_name.clone();
+        let prefix = current_object_key.joined_object_path(false);
+
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            let items = client.load_objects(&bucket, &prefix).await;
+            let result = CompleteLoadObjectsResult::new(items, current_object_key);
+            tx.send(AppEventType::CompleteLoadObjects(result));
+        });
+    }
+
+    pub fn complete_load_objects(&mut self, result: Result<CompleteLoadObjectsResult>) {
+        match result {
+            Ok(CompleteLoadObjectsResult { items, object_key }) => {
+                self.app_objects
+                    .set_object_items(object_key.clone(), items.clone());
+
+                let object_list_page = self.build_object_list_page(items, object_key);
+                self.push_object_list_page(object_list_page);
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.is_loading = false;
+    }
+
+    pub fn reload_objects(&self) {
+        let object_list_page = self.page_stack.current_page().as_object_list();
+        let object_key = object_list_page.current_dir_object_key().clone();
+        let bucket = object_key.bucket_name.clone();
+        let prefix = object_key.joined_object_path(false);
+
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            let items = client.load_objects(&bucket, &prefix).await;
+            let result = CompleteReloadObjectsResult::new(items, object_key);
+            tx.send(AppEventType::CompleteReloadObjects(result));
+        });
+    }
+
+    pub fn complete_reload_objects(&mut self, result: Result<CompleteReloadObjectsResult>) {
+        self.page_stack.pop();
+        self.complete_load_objects(result.map(|r| r.into()));
+    }
+
+    pub fn load_object_detail(&self) {
+        let object_list_page = self.page_stack.current_page().as_object_list();
+
+        if let ObjectItem::File { name, .. } = object_list_page.current_selected_item() {
+            let name = name.clone();
+
+            let map_key = object_list_page.current_selected_object_key().clone();
+            let bucket = map_key.bucket_name.clone();
+            let key = map_key.joined_object_path(true);
+
+            let client = self.client.clone();
+            let tx = self.tx.clone();
+            spawn(async move {
+                let detail = client.load_object_detail(&bucket, &key, &name).await;
+                let result = CompleteLoadObjectDetailResult::new(detail, map_key);
+                tx.send(AppEventType::CompleteLoadObjectDetail(result));
+            });
+        }
+    }
+
+    pub fn complete_load_object_detail(&mut self, result: Result<CompleteLoadObjectDetailResult>) {
+        match result {
+            Ok(CompleteLoadObjectDetailResult { detail, map_key }) => {
+                self.app_objects
+                    .set_object_detail(map_key.clone(), *detail.clone());
+
+                let object_page = self.page_stack.current_page().as_object_list();
+
+                let object_detail_page = Page::of_object_detail(
+                    *detail.clone(),
+                    object_page.object_list(),
+                    map_key,
+                    object_page.list_state(),
+                    Rc::clone(&self.ctx),
+                    self.tx.clone(),
+                );
+                self.page_stack.push(object_detail_page);
+                self.open_pending_preview();
+            }
+            Err(e) => {
+                self.pending_preview = false;
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.is_loading = false;
+    }
+
+    pub fn open_object_versions_tab(&mut self) {
+        let object_detail_page = self.page_stack.current_page().as_object_detail();
+
+        let current_object_key = object_detail_page.current_object_key().clone();
+        let versions = self.app_objects.get_object_versions(&current_object_key);
+
+        if let Some(versions) = versions {
+            // object versions has been already loaded
+            let result =
+                CompleteLoadObjectVersionsResult::new(Ok(versions.clone()), current_object_key);
+            self.tx
+                .send(AppEventType::CompleteLoadObjectVersions(result));
+        } else {
+            self.tx.send(AppEventType::LoadObjectVersions);
+            self.is_loading = true;
+        }
+    }
+
+    pub fn load_object_versions(&self) {
+        let object_detail_page = self.page_stack.current_page().as_object_detail();
+
+        let map_key = object_detail_page.current_object_key().clone();
+        let bucket = map_key.bucket_name.clone();
+        let key = map_key.joined_object_path(true);
+
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            let versions = client.load_object_versions(&bucket, &key).await;
+            let result = CompleteLoadObjectVersionsResult::new(versions, map_key);
+            tx.send(AppEventType::CompleteLoadObjectVersions(result));
+        });
+    }
+
+    pub fn complete_load_object_versions(
+        &mut self,
+        result: Result<CompleteLoadObjectVersionsResult>,
+    ) {
+        match result {
+            Ok(CompleteLoadObjectVersionsResult { versions, map_key }) => {
+                self.app_objects
+                    .set_object_versions(map_key, versions.clone());
+
+                let object_detail_page = self.page_stack.current_page_mut().as_mut_object_detail();
+                object_detail_page.set_versions(versions);
+                object_detail_page.select_versions_tab();
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.is_loading = false;
+    }
+
+    pub fn start_load_all_download_objects(&mut self, key: ObjectKey, download_as: bool) {
+        self.tx
+            .send(AppEventType::LoadAllDownloadObjectList(key, download_as));
+        self.is_loading = true;
+    }
+
+    pub fn load_all_download_objects(&self, key: ObjectKey, download_as: bool) {
+        let bucket = key.bucket_name.clone();
+        let prefix = key.joined_object_path(false);
+
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            let objects = client.list_all_download_objects(&bucket, &prefix).await;
+            let result = CompleteLoadAllDownloadObjectListResult::new(objects, download_as);
+            tx.send(AppEventType::CompleteLoadAllDownloadObjectList(result));
+        });
+    }
+
+    pub fn complete_load_all_download_objects(
+        &mut self,
+        result: Result<CompleteLoadAllDownloadObjectListResult>,
+    ) {
+        match result {
+            Ok(CompleteLoadAllDownloadObjectListResult { objs, download_as }) => {
+                match self.page_stack.current_page_mut() {
+                    Page::BucketList(page) => {
+                        page.open_download_confirm_dialog(objs, download_as);
+                    }
+                    Page::ObjectList(page) => {
+                        page.open_download_confirm_dialog(objs, download_as);
+                    }
+                    page => panic!("Invalid page: {:?}", page),
+                }
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.is_loading = false;
+    }
+
+    pub fn open_help(&mut self) {
+        let helps = if self.prefix_tree_focused() {
+            let items = self.prefix_tree_help_items();
+            build_help_spans(items, &self.mapper, self.ctx.theme.help_key_fg)
+        } else {
+            self.page_stack.current_page().helps(&self.mapper)
+        };
+        if helps.is_empty() {
+            return;
+        }
+        let help_page = Page::of_help(helps, Rc::clone(&self.ctx), self.tx.clone());
+        self.page_stack.push(help_page);
+    }
+
+    pub fn open_command_palette(&mut self) {
+        let page = self.page_stack.current_page();
+        if matches!(page, Page::CommandPalette(_)) {
+            return;
+        }
+        let help_items = if self.prefix_tree_focused() {
+            self.prefix_tree_help_items()
+        } else {
+            page.help_items()
+        };
+        let items = build_command_palette_items(help_items, &self.mapper);
+        if items.is_empty() {
+            return;
+        }
+        let page = Page::of_command_palette(items, Rc::clone(&self.ctx), self.tx.clone());
+        self.page_stack.push(page);
+    }
+
+    pub fn close_current_page(&mut self) {
+        self.page_stack.pop();
+    }
+
+    pub fn open_preview(
+        &mut self,
+        object_key: ObjectKey,
+        file_detail: FileDetail,
+        version_id: Option<String>,
+    ) {
+        self.tx.send(AppEventType::PreviewObject(
+            object_key,
             file_detail,
             version_id,
         ));
         self.is_loading = true;
     }
 
-    pub fn start_download_object(
-        &mut self,
-        object_key: ObjectKey,
-        object_name: String,
-        size_byte: usize,
-        version_id: Option<String>,
-    ) {
-        self.tx.send(AppEventType::DownloadObject(
-            object_key,
-            object_name,
-            size_byte,
-            version_id,
-        ));
-        self.is_loading = true;
+    pub fn start_download_object(
+        &mut self,
+        object_key: ObjectKey,
+        object_name: String,
+        size_byte: usize,
+        version_id: Option<String>,
+    ) {
+        self.tx.send(AppEventType::DownloadObject(
+            object_key,
+            object_name,
+            size_byte,
+            version_id,
+        ));
+        self.is_loading = true;
+    }
+
+    pub fn download_object(
+        &self,
+        object_key: ObjectKey,
+        object_name: String,
+        size_byte: usize,
+        version_id: Option<String>,
+    ) {
+        let bucket = object_key.bucket_name.clone();
+        let key = object_key.joined_object_path(true);
+
+        let path = self.ctx.config.download_file_path(&object_name);
+        let writer = create_binary_file(&path);
+
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        let loading = self.handle_loading_size(size_byte, tx.clone());
+
+        spawn(async move {
+            match writer {
+                Ok(mut writer) => {
+                    let result = client
+                        .download_object(&bucket, &key, version_id, &mut writer, loading)
+                        .await;
+                    let result = CompleteDownloadObjectResult::new(result, path);
+                    tx.send(AppEventType::CompleteDownloadObject(result));
+                }
+                Err(e) => {
+                    tx.send(AppEventType::CompleteDownloadObject(Err(e)));
+                }
+            }
+        });
+    }
+
+    pub fn start_download_object_as(
+        &mut self,
+        object_key: ObjectKey,
+        size_byte: usize,
+        input: String,
+        version_id: Option<String>,
+    ) {
+        self.tx.send(AppEventType::DownloadObjectAs(
+            object_key, size_byte, input, version_id,
+        ));
+        self.is_loading = true;
+    }
+
+    pub fn download_object_as(
+        &self,
+        object_key: ObjectKey,
+        size_byte: usize,
+        input: String,
+        version_id: Option<String>,
+    ) {
+        let bucket = object_key.bucket_name.clone();
+        let key = object_key.joined_object_path(true);
+
+        let path = self.ctx.config.download_file_path(&input);
+        let writer = create_binary_file(&path);
+
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        let loading = self.handle_loading_size(size_byte, tx.clone());
+
+        spawn(async move {
+            match writer {
+                Ok(mut writer) => {
+                    let result = client
+                        .download_object(&bucket, &key, version_id, &mut writer, loading)
+                        .await;
+                    let result = CompleteDownloadObjectResult::new(result, path);
+                    tx.send(AppEventType::CompleteDownloadObject(result));
+                }
+                Err(e) => {
+                    tx.send(AppEventType::CompleteDownloadObject(Err(e)));
+                }
+            }
+        });
+    }
+
+    pub fn complete_download_object(&mut self, result: Result<CompleteDownloadObjectResult>) {
+        match result {
+            Ok(CompleteDownloadObjectResult { path }) => {
+                let msg = format!(
+                    "Download completed successfully: {}",
+                    path.to_string_lossy()
+                );
+                self.tx.send(AppEventType::NotifySuccess(msg));
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.is_loading = false;
+    }
+
+    pub fn download_objects(
+        &mut self,
+        bucket: String,
+        key: ObjectKey,
+        dir: String,
+        objs: Vec<DownloadObjectInfo>,
+    ) {
+        self.is_loading = true;
+
+        let current_selected_dir_key = key.joined_object_path(false);
+        let mut obj_paths = Vec::with_capacity(objs.len());
+        for obj in objs {
+            let relative_path =
+                PathBuf::from(&dir).join(obj.key.strip_prefix(&current_selected_dir_key).unwrap());
+            let absolute_path = self.ctx.config.download_file_path(relative_path);
+            obj_paths.push((obj, absolute_path));
+        }
+        let download_dir = self.ctx.config.download_file_path(&dir);
+
+        let total_count = obj_paths.len();
+        let total_size: usize = obj_paths.iter().map(|(obj, _)| obj.size_byte).sum();
+        let decimal_places = if total_size > 1_000_000_000 { 1 } else { 0 };
+        let format_opt =
+            humansize::FormatSizeOptions::from(humansize::DECIMAL).decimal_places(decimal_places);
+        let total_size_s = humansize::format_size_i(total_size, format_opt);
+
+        let max_concurrent_requests = self.ctx.config.max_concurrent_requests;
+
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+
+        spawn(async move {
+            let mut iter = futures::stream::iter(obj_paths)
+                .map(|(obj, path)| {
+                    let bucket = bucket.clone();
+                    let client = client.clone();
+                    async move {
+                        download_object_to_file(client.as_ref(), &bucket, &obj.key, &path).await?;
+                        Ok(obj.size_byte)
+                    }
+                })
+                .buffered(max_concurrent_requests);
+
+            let mut cur_count = 0;
+            let mut cur_size = 0;
+            while let Some(result) = iter.next().await {
+                match result {
+                    Ok(size) => {
+                        cur_count += 1;
+                        cur_size += size;
+
+                        let cur_size_s = humansize::format_size_i(cur_size, format_opt);
+                        let msg = format!(
+                            "{}/{} objects downloaded ({} out of {} total)",
+                            cur_count, total_count, cur_size_s, total_size_s
+                        );
+                        tx.send(AppEventType::NotifyInfo(msg));
+                    }
+                    Err(e) => {
+                        tx.send(AppEventType::CompleteDownloadObjects(Err(e)));
+                        return;
+                    }
+                }
+            }
+
+            let result = CompleteDownloadObjectsResult::new(download_dir);
+            tx.send(AppEventType::CompleteDownloadObjects(result));
+        });
+    }
+
+    pub fn complete_download_objects(&mut self, result: Result<CompleteDownloadObjectsResult>) {
+        match result {
+            Ok(CompleteDownloadObjectsResult { download_dir }) => {
+                let msg = format!(
+                    "Download completed successfully: {}",
+                    download_dir.to_string_lossy()
+                );
+                self.tx.send(AppEventType::NotifySuccess(msg));
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.is_loading = false;
+    }
+
+    pub fn preview_object(
+        &self,
+        object_key: ObjectKey,
+        file_detail: FileDetail,
+        version_id: Option<String>,
+    ) {
+        let size_byte = file_detail.size_byte;
+
+        let bucket = object_key.bucket_name.clone();
+        let key = object_key.joined_object_path(true);
+
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        let loading = self.handle_loading_size(size_byte, tx.clone());
+
+        spawn(async move {
+            let mut bytes = Vec::with_capacity(size_byte);
+            let result = {
+                let mut writer = BufWriter::new(&mut bytes);
+                client
+                    .download_object(&bucket, &key, version_id.clone(), &mut writer, loading)
+                    .await
+            };
+            let obj = result.map(|_| RawObject { bytes });
+            let result = CompletePreviewObjectResult::new(obj, object_key, file_detail, version_id);
+            tx.send(AppEventType::CompletePreviewObject(result));
+        });
+    }
+
+    pub fn complete_preview_object(&mut self, result: Result<CompletePreviewObjectResult>) {
+        match result {
+            Ok(CompletePreviewObjectResult {
+                object_key,
+                obj,
+                file_detail,
+                file_version_id,
+            }) => {
+                let object_preview_page = Page::of_object_preview(
+                    object_key,
+                    file_detail,
+                    file_version_id,
+                    obj,
+                    Rc::clone(&self.ctx),
+                    self.tx.clone(),
+                );
+                self.page_stack.push(object_preview_page);
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        };
+        self.clear_notification();
+        self.is_loading = false;
+    }
+
+    pub fn start_save_object(&mut self, name: String, obj: Arc<RawObject>) {
+        self.tx.send(AppEventType::SaveObject(name, obj));
+        self.is_loading = true;
+    }
+
+    pub fn save_object(&self, name: String, obj: Arc<RawObject>) {
+        let path = self.ctx.config.download_file_path(&name);
+        let writer = create_binary_file(&path);
+
+        let tx = self.tx.clone();
+        spawn(async move {
+            match writer {
+                Ok(mut writer) => {
+                    let result = writer.write_all(&obj.bytes).map_err(AppError::error);
+                    let result = CompleteSaveObjectResult::new(result, path);
+                    tx.send(AppEventType::CompleteSaveObject(result));
+                }
+                Err(e) => {
+                    tx.send(AppEventType::CompleteSaveObject(Err(e)));
+                }
+            }
+        });
+    }
+
+    pub fn complete_save_object(&mut self, result: Result<CompleteSaveObjectResult>) {
+        match result {
+            Ok(CompleteSaveObjectResult { path }) => {
+                let msg = format!(
+                    "Download completed successfully: {}",
+                    path.to_string_lossy()
+                );
+                self.tx.send(AppEventType::NotifySuccess(msg));
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.is_loading = false;
+
+        if let Page::ObjectPreview(page) = self.page_stack.current_page() {
+            if page.is_image_preview() {
+                self.tx.send(AppEventType::PreviewRerenderImage);
+            }
+        }
+    }
+
+    pub fn start_edit_object(&mut self, object_key: ObjectKey) {
+        self.tx.send(AppEventType::EditObject(object_key));
+        self.is_loading = true;
+    }
+
+    pub fn edit_object(&self, object_key: ObjectKey) {
+        let bucket = object_key.bucket_name.clone();
+        let key = object_key.joined_object_path(true);
+        let name = object_key.object_path.last().cloned().unwrap_or_default();
+        let path = self.ctx.config.temp_file_path(&name);
+
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            let result =
+                client
+                    .load_object_for_edit(&bucket, &key)
+                    .await
+                    .and_then(|(obj, attrs)| {
+                        let mut writer = create_binary_file(&path)?;
+                        writer
+                            .write_all(&obj.bytes)
+                            .and_then(|_| writer.flush())
+                            .map_err(AppError::error)?;
+                        Ok((obj, attrs))
+                    });
+            let result = CompleteLoadEditObjectResult::new(result, object_key, path);
+            tx.send(AppEventType::CompleteLoadEditObject(result));
+        });
+    }
+
+    // The caller is responsible for opening the returned file in the editor
+    pub fn complete_load_edit_object(
+        &mut self,
+        result: Result<CompleteLoadEditObjectResult>,
+    ) -> Option<CompleteLoadEditObjectResult> {
+        match result {
+            Ok(result) => Some(result),
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+                self.is_loading = false;
+                None
+            }
+        }
+    }
+
+    pub fn upload_edited_object(
+        &mut self,
+        result: CompleteLoadEditObjectResult,
+        edit_result: Result<()>,
+    ) {
+        let CompleteLoadEditObjectResult {
+            object_key,
+            path,
+            original,
+            attrs,
+        } = result;
+
+        let bytes = edit_result.and_then(|_| {
+            std::fs::read(&path).map_err(|e| AppError::new("Failed to read edited file", e))
+        });
+        let bytes = match bytes {
+            Ok(bytes) if bytes != original.bytes => bytes,
+            Ok(_) => {
+                let _ = std::fs::remove_file(&path);
+                self.tx
+                    .send(AppEventType::NotifyInfo("Object was not modified".into()));
+                self.is_loading = false;
+                return;
+            }
+            Err(e) => {
+                let _ = std::fs::remove_file(&path);
+                self.tx.send(AppEventType::NotifyError(e));
+                self.is_loading = false;
+                return;
+            }
+        };
+
+        let bucket = object_key.bucket_name.clone();
+        let key = object_key.joined_object_path(true);
+
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            let result = client
+                .upload_edited_object(&bucket, &key, bytes, &attrs)
+                .await;
+            let result = match result {
+                Ok(_) => {
+                    let _ = std::fs::remove_file(&path);
+                    Ok(())
+                }
+                // keep the edited file so that the changes are not lost
+                Err(e) => Err(AppError {
+                    msg: format!("{} (edited file: {})", e.msg, path.to_string_lossy()),
+                    ..e
+                }),
+            };
+            let result = CompleteUploadEditedObjectResult::new(result, object_key);
+            tx.send(AppEventType::CompleteUploadEditedObject(result));
+        });
+    }
+
+    pub fn complete_upload_edited_object(
+        &mut self,
+        result: Result<CompleteUploadEditedObjectResult>,
+    ) {
+        match result {
+            Ok(CompleteUploadEditedObjectResult { object_key }) => {
+                // the size and the date in the parent listing are changed as well
+                if let Some(parent_key) = object_key.parent() {
+                    self.app_objects.clear_object_items_under(&parent_key);
+                }
+                let msg = format!(
+                    "Upload completed successfully: {}",
+                    object_key.joined_object_path(true)
+                );
+                self.tx.send(AppEventType::NotifySuccess(msg));
+
+                if let Page::ObjectDetail(page) = self.page_stack.current_page() {
+                    if page.current_object_key() == &object_key {
+                        self.tx.send(AppEventType::ReloadEditedObject(object_key));
+                        return;
+                    }
+                }
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.is_loading = false;
+    }
+
+    pub fn reload_edited_object(&self, object_key: ObjectKey) {
+        let parent_key = object_key.parent().unwrap_or_default();
+        let bucket = object_key.bucket_name.clone();
+        let prefix = parent_key.joined_object_path(false);
+        let key = object_key.joined_object_path(true);
+        let name = object_key.object_path.last().cloned().unwrap_or_default();
+
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            let items = client.load_objects(&bucket, &prefix).await;
+            let detail = client.load_object_detail(&bucket, &key, &name).await;
+            let result = CompleteReloadEditedObjectResult::new(items, detail, object_key);
+            tx.send(AppEventType::CompleteReloadEditedObject(result));
+        });
+    }
+
+    // Replaces the detail page and the object list page below it with the reloaded ones
+    pub fn complete_reload_edited_object(
+        &mut self,
+        result: Result<CompleteReloadEditedObjectResult>,
+    ) {
+        match result {
+            Ok(CompleteReloadEditedObjectResult {
+                items,
+                detail,
+                object_key,
+            }) => {
+                let parent_key = object_key.parent().unwrap_or_default();
+                self.app_objects
+                    .set_object_items(parent_key.clone(), items.clone());
+                self.app_objects
+                    .set_object_detail(object_key.clone(), *detail.clone());
+
+                self.page_stack.pop();
+                let session = self
+                    .page_stack
+                    .current_page()
+                    .as_object_list()
+                    .view_session();
+                self.page_stack.pop();
+
+                let mut object_list_page = self.build_object_list_page(items, parent_key);
+                object_list_page
+                    .as_mut_object_list()
+                    .restore_view_session(&session);
+                let object_page = object_list_page.as_object_list();
+                let object_detail_page = Page::of_object_detail(
+                    *detail,
+                    object_page.object_list(),
+                    object_key,
+                    object_page.list_state(),
+                    Rc::clone(&self.ctx),
+                    self.tx.clone(),
+                );
+                self.page_stack.push(object_list_page);
+                self.page_stack.push(object_detail_page);
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.is_loading = false;
+    }
+
+    pub fn start_open_object(&mut self, object_key: ObjectKey, version_id: Option<String>) {
+        self.tx
+            .send(AppEventType::OpenObject(object_key, version_id));
+        self.is_loading = true;
+    }
+
+    pub fn open_object(&self, object_key: ObjectKey, version_id: Option<String>) {
+        let bucket = object_key.bucket_name.clone();
+        let key = object_key.joined_object_path(true);
+        let name = object_key.object_path.last().cloned().unwrap_or_default();
+        let path = self.ctx.config.temp_file_path(&name);
+        let writer = create_binary_file(&path);
+
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            let result = match writer {
+                Ok(mut writer) => {
+                    client
+                        .download_object(&bucket, &key, version_id, &mut writer, |_| {})
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+            let result = CompleteDownloadOpenObjectResult::new(result, path);
+            tx.send(AppEventType::CompleteDownloadOpenObject(result));
+        });
+    }
+
+    // Returns the command and the file path if the object should be opened with the configured command.
+    // The caller is responsible for running it, since the command may need the terminal.
+    pub fn complete_download_open_object(
+        &mut self,
+        result: Result<CompleteDownloadOpenObjectResult>,
+    ) -> Option<(String, PathBuf)> {
+        match result {
+            Ok(CompleteDownloadOpenObjectResult { path }) => {
+                let command = self
+                    .ctx
+                    .config
+                    .open_command(&path.to_string_lossy())
+                    .map(String::from);
+                if let Some(command) = command {
+                    return Some((command, path));
+                }
+                let result = open::that_detached(&path)
+                    .map_err(|e| AppError::new("Failed to open object", e));
+                self.complete_open_object(result);
+                None
+            }
+            Err(e) => {
+                self.complete_open_object(Err(e));
+                None
+            }
+        }
+    }
+
+    pub fn complete_open_object(&mut self, result: Result<()>) {
+        if let Err(e) = result {
+            self.tx.send(AppEventType::NotifyError(e));
+        }
+        self.is_loading = false;
+    }
+
+    pub fn start_pipe_object(
+        &mut self,
+        object_key: ObjectKey,
+        version_id: Option<String>,
+        command: String,
+    ) {
+        self.tx
+            .send(AppEventType::PipeObject(object_key, version_id, command));
+        self.is_loading = true;
+    }
+
+    pub fn pipe_object(&self, object_key: ObjectKey, version_id: Option<String>, command: String) {
+        let bucket = object_key.bucket_name.clone();
+        let key = object_key.joined_object_path(true);
+
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        let handle = Handle::current();
+        // writing to stdin blocks while the command is not reading,
+        // so the download is driven on a blocking thread instead of a runtime worker
+        spawn_blocking(move || {
+            let output = PipedCommand::spawn(&command).and_then(|mut piped| {
+                let result = {
+                    let mut writer = piped.stdin();
+                    handle.block_on(client.download_object(
+                        &bucket,
+                        &key,
+                        version_id,
+                        &mut writer,
+                        |_| {},
+                    ))
+                };
+                // wait for the command to exit even if the download fails
+                let output = piped.wait();
+                result.and(output)
+            });
+            let result = CompletePipeObjectResult::new(output, command);
+            tx.send(AppEventType::CompletePipeObject(result));
+        });
+    }
+
+    pub fn complete_pipe_object(&mut self, result: Result<CompletePipeObjectResult>) {
+        match result {
+            Ok(CompletePipeObjectResult { command, output }) => {
+                let page =
+                    Page::of_command_output(command, output, Rc::clone(&self.ctx), self.tx.clone());
+                self.page_stack.push(page);
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.is_loading = false;
+    }
+
+    pub fn start_custom_command(&mut self, index: usize, target: CustomCommandTarget) {
+        self.tx.send(AppEventType::CustomCommand(index, target));
+        self.is_loading = true;
+    }
+
+    pub fn custom_command(&mut self, index: usize, target: CustomCommandTarget) {
+        let Some(config) = self.ctx.config.custom_commands.get(index) else {
+            self.is_loading = false;
+            return;
+        };
+        let template = config.command.clone();
+
+        let local_path = if needs_local_path(&template) {
+            if !target.is_file {
+                let msg = format!("{{local_path}} is not available for {}", target.s3_uri);
+                self.tx.send(AppEventType::NotifyWarn(msg));
+                self.is_loading = false;
+                return;
+            }
+            let name = target.key.rsplit('/').next().unwrap_or_default();
+            Some(self.ctx.config.temp_file_path(name))
+        } else {
+            None
+        };
+
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            let result = match &local_path {
+                Some(path) => match create_binary_file(path) {
+                    Ok(mut writer) => {
+                        let version_id = target.version_id.clone();
+                        client
+                            .download_object(
+                                &target.bucket,
+                                &target.key,
+                                version_id,
+                                &mut writer,
+                                |_| {},
+                            )
+                            .await
+                    }
+                    Err(e) => Err(e),
+                },
+                None => Ok(()),
+            };
+            let command = expand_custom_command(&template, &target, local_path.as_deref());
+            let result = CompletePrepareCustomCommandResult::new(result, index, command);
+            tx.send(AppEventType::CompletePrepareCustomCommand(result));
+        });
+    }
+
+    // Returns the command if it should be run in the terminal.
+    // The caller is responsible for running it, since the command needs the terminal.
+    pub fn complete_prepare_custom_command(
+        &mut self,
+        result: Result<CompletePrepareCustomCommandResult>,
+    ) -> Option<String> {
+        match result {
+            Ok(CompletePrepareCustomCommandResult { index, command }) => {
+                let config = self.ctx.config.custom_commands.get(index)?;
+                match config.mode {
+                    CustomCommandMode::Terminal => Some(command),
+                    CustomCommandMode::Background => {
+                        let name = config.name.clone();
+                        let tx = self.tx.clone();
+                        spawn_blocking(move || {
+                            let output = PipedCommand::spawn(&command).and_then(|c| c.wait());
+                            let result = CompleteCustomCommandResult::new(output, name);
+                            tx.send(AppEventType::CompleteCustomCommand(result));
+                        });
+                        // the app can be used while the command is running
+                        self.is_loading = false;
+                        None
+                    }
+                }
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+                self.is_loading = false;
+                None
+            }
+        }
+    }
+
+    pub fn complete_custom_command(&mut self, result: Result<CompleteCustomCommandResult>) {
+        match result {
+            Ok(CompleteCustomCommandResult { name, output }) => {
+                let msg = match (output.status.success(), output.last_line()) {
+                    (true, Some(line)) => format!("{} completed: {}", name, line),
+                    (true, None) => format!("{} completed", name),
+                    (false, Some(line)) => format!("{} failed ({}): {}", name, output.status, line),
+                    (false, None) => format!("{} failed ({})", name, output.status),
+                };
+                if output.status.success() {
+                    self.tx.send(AppEventType::NotifySuccess(msg));
+                } else {
+                    self.tx.send(AppEventType::NotifyWarn(msg));
+                }
+                let page =
+                    Page::of_command_output(name, output, Rc::clone(&self.ctx), self.tx.clone());
+                self.page_stack.push(page);
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+    }
+
+    pub fn complete_custom_command_in_terminal(&mut self, result: Result<()>) {
+        if let Err(e) = result {
+            self.tx.send(AppEventType::NotifyError(e));
+        }
+        self.is_loading = false;
+    }
+
+    pub fn start_load_sync_plan(&mut self, object_key: ObjectKey, options: SyncOptions) {
+        self.tx
+            .send(AppEventType::LoadSyncPlan(object_key, options));
+        self.is_loading = true;
+    }
+
+    pub fn load_sync_plan(&self, object_key: ObjectKey, options: SyncOptions) {
+        let bucket = object_key.bucket_name.clone();
+        let prefix = object_key.joined_object_path(false);
+
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            if options.direction == SyncDirection::Upload && !options.local_dir.is_dir() {
+                let msg = format!(
+                    "Directory does not exist: {}",
+                    options.local_dir.to_string_lossy()
+                );
+                let result = CompleteLoadSyncPlanResult::new(Err(AppError::msg(msg)));
+                tx.send(AppEventType::CompleteLoadSyncPlan(result));
+                return;
+            }
+
+            let plan = match client.list_all_object_summaries(&bucket, &prefix).await {
+                Ok(objects) => {
+                    // listing and hashing local files may take a while
+                    spawn_blocking(move || {
+                        let local_files = list_local_files(&options.local_dir)?;
+                        let dir = options.local_dir.clone();
+                        let local_md5 = |path: &str| md5_hex(dir.join(path));
+                        Ok(build_sync_plan(
+                            bucket,
+                            prefix,
+                            &options,
+                            local_files,
+                            objects,
+                            local_md5,
+                        ))
+                    })
+                    .await
+                    .map_err(|e| AppError::new("Failed to compare files", e))
+                    .and_then(|plan| plan)
+                }
+                Err(e) => Err(e),
+            };
+            let result = CompleteLoadSyncPlanResult::new(plan);
+            tx.send(AppEventType::CompleteLoadSyncPlan(result));
+        });
+    }
+
+    pub fn complete_load_sync_plan(&mut self, result: Result<CompleteLoadSyncPlanResult>) {
+        match result {
+            Ok(CompleteLoadSyncPlanResult { plan }) => {
+                let page = Page::of_sync_plan(plan, Rc::clone(&self.ctx), self.tx.clone());
+                self.page_stack.push(page);
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.is_loading = false;
+    }
+
+    pub fn start_execute_sync(&mut self, plan: SyncPlan) {
+        self.tx.send(AppEventType::ExecuteSync(plan));
+        self.is_loading = true;
+    }
+
+    pub fn execute_sync(&self, plan: SyncPlan) {
+        let bucket = plan.bucket.clone();
+        let direction = plan.direction;
+        let operations = plan.operations();
+        let total_count = operations.len();
+
+        let max_concurrent_requests = self.ctx.config.max_concurrent_requests;
+
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+
+        spawn(async move {
+            let mut iter = futures::stream::iter(operations)
+                .map(|op| {
+                    let bucket = bucket.clone();
+                    let client = client.clone();
+                    async move {
+                        match (direction, op.action) {
+                            (SyncDirection::Upload, SyncAction::Deleted) => {
+                                client.delete_object(&bucket, &op.key).await?;
+                            }
+                            (SyncDirection::Upload, _) => {
+                                client
+                                    .upload_object(&bucket, &op.key, &op.local_path)
+                                    .await?;
+                            }
+                            (SyncDirection::Download, SyncAction::Deleted) => {
+                                std::fs::remove_file(&op.local_path)
+                                    .map_err(|e| AppError::new("Failed to delete file", e))?;
+                            }
+                            (SyncDirection::Download, _) => {
+                                let f = download_object_to_file(
+                                    client.as_ref(),
+                                    &bucket,
+                                    &op.key,
+                                    &op.local_path,
+                                )
+                                .await?;
+                                // keep the timestamp of the object so that the next sync does not detect it as changed
+                                if let Some(last_modified) = op.last_modified {
+                                    f.set_modified(last_modified.into())
+                                        .map_err(|e| AppError::new("Failed to set timestamp", e))?;
+                                }
+                            }
+                        }
+                        Ok(op.action)
+                    }
+                })
+                .buffered(max_concurrent_requests);
+
+            let mut transferred = 0;
+            let mut deleted = 0;
+            while let Some(result) = iter.next().await {
+                match result {
+                    Ok(SyncAction::Deleted) => deleted += 1,
+                    Ok(_) => transferred += 1,
+                    Err(e) => {
+                        tx.send(AppEventType::CompleteExecuteSync(Err(e)));
+                        return;
+                    }
+                }
+                let msg = format!(
+                    "{}/{} operations completed",
+                    transferred + deleted,
+                    total_count
+                );
+                tx.send(AppEventType::NotifyInfo(msg));
+            }
+
+            let result = CompleteExecuteSyncResult::new(direction, transferred, deleted);
+            tx.send(AppEventType::CompleteExecuteSync(result));
+        });
+    }
+
+    pub fn complete_execute_sync(&mut self, result: Result<CompleteExecuteSyncResult>) {
+        self.is_loading = false;
+        match result {
+            Ok(CompleteExecuteSyncResult {
+                direction,
+                transferred,
+                deleted,
+            }) => {
+                if let Page::SyncPlan(_) = self.page_stack.current_page() {
+                    self.page_stack.pop();
+                }
+                let msg = format!(
+                    "Sync completed successfully: {} transferred, {} deleted",
+                    transferred, deleted
+                );
+                self.tx.send(AppEventType::NotifySuccess(msg));
+
+                if direction == SyncDirection::Upload {
+                    if let Page::ObjectList(_) = self.page_stack.current_page() {
+                        self.object_list_refresh();
+                    }
+                }
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+    }
+
+    fn handle_loading_size(&self, total_size: usize, tx: Sender) -> Box<dyn Fn(usize) + Send> {
+        if total_size < 10_000_000 {
+            return Box::new(|_| {});
+        }
+        let decimal_places = if total_size > 1_000_000_000 { 1 } else { 0 };
+        let opt =
+            humansize::FormatSizeOptions::from(humansize::DECIMAL).decimal_places(decimal_places);
+        let total_s = humansize::format_size_i(total_size, opt);
+        let f = move |current| {
+            let percent = (current * 100) / total_size;
+            let cur_s = humansize::format_size_i(current, opt);
+            let msg = format!("{:3}% downloaded ({} out of {})", percent, cur_s, total_s);
+            tx.send(AppEventType::NotifyInfo(msg));
+        };
+        Box::new(f)
     }
 
-    pub fn download_object(
-        &self,
-        object_key: ObjectKey,
-        object_name: String,
-        size_byte: usize,
-        version_id: Option<String>,
-    ) {
-        let bucket = object_key.bucket_name.clone();
-        let key = object_key.joined_object_path(true);
-
-        let path = self.ctx.config.download_file_path(&object_name);
-        let writer = create_binary_file(&path);
+    pub fn start_load_prefix_diff(&mut self, left: ObjectKey, right: ObjectKey) {
+        self.tx.send(AppEventType::LoadPrefixDiff(left, right));
+        self.is_loading = true;
+    }
 
+    pub fn load_prefix_diff(&self, left: ObjectKey, right: ObjectKey) {
         let client = self.client.clone();
         let tx = self.tx.clone();
-        let loading = self.handle_loading_size(size_byte, tx.clone());
-
         spawn(async move {
-            match writer {
-                Ok(mut writer) => {
-                    let result = client
-                        .download_object(&bucket, &key, version_id, &mut writer, loading)
-                        .await;
-                    let result = CompleteDownloadObjectResult::new(result, path);
-                    tx.send(AppEventType::CompleteDownloadObject(result));
-                }
-                Err(e) => {
-                    tx.send(AppEventType::CompleteDownloadObject(Err(e)));
-                }
-            }
+            let left_bucket = left.bucket_name.clone();
+            let left_prefix = left.joined_object_path(false);
+            let right_bucket = right.bucket_name.clone();
+            let right_prefix = right.joined_object_path(false);
+            let diff = futures::future::try_join(
+                client.list_all_object_summaries(&left_bucket, &left_prefix),
+                client.list_all_object_summaries(&right_bucket, &right_prefix),
+            )
+            .await
+            .map(|(left_objects, right_objects)| {
+                build_prefix_diff(left, right, left_objects, right_objects)
+            });
+            let result = CompleteLoadPrefixDiffResult::new(diff);
+            tx.send(AppEventType::CompleteLoadPrefixDiff(result));
         });
     }
 
-    pub fn start_download_object_as(
+    pub fn complete_load_prefix_diff(&mut self, result: Result<CompleteLoadPrefixDiffResult>) {
+        match result {
+            Ok(CompleteLoadPrefixDiffResult { diff }) => {
+                let page = Page::of_prefix_diff(diff, Rc::clone(&self.ctx), self.tx.clone());
+                self.page_stack.push(page);
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.is_loading = false;
+    }
+
+    pub fn open_commander(&mut self) {
+        let object_list_page = self.page_stack.current_page().as_object_list();
+        let object_key = object_list_page.current_dir_object_key().clone();
+        let selected = object_list_page
+            .non_empty()
+            .then(|| object_list_page.current_selected_item().name().to_string());
+        let items = self
+            .app_objects
+            .get_object_items(&object_key)
+            .unwrap_or_else(|| object_list_page.object_list());
+
+        // both panes start from the current directory
+        let mut left = self.build_commander_pane(items.clone(), object_key.clone(), true);
+        if let Some(name) = selected {
+            left.select_item_by_name(&name);
+        }
+        let right = self.build_commander_pane(items, object_key, true);
+        let connection = self.session.connection.clone();
+        let page = Page::of_commander(
+            left,
+            right,
+            connection,
+            Rc::clone(&self.ctx),
+            self.tx.clone(),
+        );
+        self.page_stack.push(page);
+    }
+
+    // the client of the connection, the one of the tab if the connection is the same as the tab's
+    fn connection_client(&self, connection: Option<&str>) -> Option<Arc<C>> {
+        if connection == self.session.connection.as_deref() {
+            return Some(self.client.clone());
+        }
+        connection
+            .and_then(|name| self.connection_clients.get(name))
+            .cloned()
+    }
+
+    // the objects loaded with the other connections are not cached, since the cache belongs to the tab
+    fn commander_pane_uses_cache(&self, pane: CommanderPane) -> bool {
+        match self.page_stack.current_page() {
+            Page::Commander(page) => {
+                page.pane_connection(pane) == self.session.connection.as_deref()
+            }
+            _ => false,
+        }
+    }
+
+    pub fn commander_load_objects(
         &mut self,
+        pane: CommanderPane,
         object_key: ObjectKey,
-        size_byte: usize,
-        input: String,
-        version_id: Option<String>,
+        name: Option<String>,
     ) {
-        self.tx.send(AppEventType::DownloadObjectAs(
-            object_key, size_byte, input, version_id,
-        ));
+        let Page::Commander(page) = self.page_stack.current_page() else {
+            return;
+        };
+        let Some(client) = self.connection_client(page.pane_connection(pane)) else {
+            return;
+        };
+        let use_cache = self.commander_pane_uses_cache(pane);
+        if let Some(items) = use_cache
+            .then(|| self.app_objects.get_object_items(&object_key))
+            .flatten()
+        {
+            // object list has been already loaded
+            self.set_commander_pane(pane, items, object_key, name);
+            return;
+        }
+
+        let bucket = object_key.bucket_name.clone();
+        let prefix = object_key.joined_object_path(false);
+
+        let tx = self.tx.clone();
+        spawn(async move {
+            let items = client.load_objects(&bucket, &prefix).await;
+            let result = CompleteLoadObjectsResult::new(items, object_key);
+            tx.send(AppEventType::CompleteCommanderLoadObjects(
+                pane, name, result,
+            ));
+        });
         self.is_loading = true;
     }
 
-    pub fn download_object_as(
-        &self,
-        object_key: ObjectKey,
-        size_byte: usize,
-        input: String,
-        version_id: Option<String>,
+    pub fn complete_commander_load_objects(
+        &mut self,
+        pane: CommanderPane,
+        name: Option<String>,
+        result: Result<CompleteLoadObjectsResult>,
     ) {
-        let bucket = object_key.bucket_name.clone();
-        let key = object_key.joined_object_path(true);
+        match result {
+            Ok(CompleteLoadObjectsResult { items, object_key }) => {
+                if self.commander_pane_uses_cache(pane) {
+                    self.app_objects
+                        .set_object_items(object_key.clone(), items.clone());
+                }
+                self.set_commander_pane(pane, items, object_key, name);
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.is_loading = false;
+    }
 
-        let path = self.ctx.config.download_file_path(&input);
-        let writer = create_binary_file(&path);
+    pub fn commander_refresh(&mut self, pane: CommanderPane) {
+        if let Page::Commander(page) = self.page_stack.current_page() {
+            let (object_key, name) = page.pane_location(pane);
+            if self.commander_pane_uses_cache(pane) {
+                self.app_objects.clear_object_items_under(&object_key);
+            }
+            self.commander_load_objects(pane, object_key, name);
+        }
+    }
 
-        let client = self.client.clone();
-        let tx = self.tx.clone();
-        let loading = self.handle_loading_size(size_byte, tx.clone());
+    pub async fn commander_switch_connection(&mut self, pane: CommanderPane, name: String) {
+        let client = match self.connection_client(Some(&name)) {
+            Some(client) => client,
+            None => {
+                let Some(connection) = self.ctx.config.connections.get(&name) else {
+                    let e = AppError::msg(format!("Connection not found: {}", name));
+                    self.tx.send(AppEventType::NotifyError(e));
+                    self.is_loading = false;
+                    return;
+                };
+                let default_region = self.ctx.config.default_region.clone();
+                let client = Arc::new(C::connect(connection, default_region).await);
+                self.connection_clients.insert(name.clone(), client.clone());
+                client
+            }
+        };
 
+        let tx = self.tx.clone();
         spawn(async move {
-            match writer {
-                Ok(mut writer) => {
-                    let result = client
-                        .download_object(&bucket, &key, version_id, &mut writer, loading)
-                        .await;
-                    let result = CompleteDownloadObjectResult::new(result, path);
-                    tx.send(AppEventType::CompleteDownloadObject(result));
-                }
-                Err(e) => {
-                    tx.send(AppEventType::CompleteDownloadObject(Err(e)));
-                }
-            }
+            let buckets = client.load_all_buckets().await;
+            let result = CompleteSwitchConnectionResult::new(name, buckets);
+            tx.send(AppEventType::CompleteCommanderSwitchConnection(
+                pane, result,
+            ));
         });
     }
 
-    pub fn complete_download_object(&mut self, result: Result<CompleteDownloadObjectResult>) {
+    pub fn complete_commander_switch_connection(
+        &mut self,
+        pane: CommanderPane,
+        result: Result<CompleteSwitchConnectionResult>,
+    ) {
+        self.is_loading = false;
+        let Page::Commander(page) = self.page_stack.current_page_mut() else {
+            return;
+        };
         match result {
-            Ok(CompleteDownloadObjectResult { path }) => {
-                let msg = format!(
-                    "Download completed successfully: {}",
-                    path.to_string_lossy()
-                );
+            Ok(CompleteSwitchConnectionResult { name, buckets }) => {
+                // keep the location if the bucket also exists in the new connection
+                let current_key = page.pane(pane).current_dir_object_key().clone();
+                let object_key = if buckets.iter().any(|b| b.name == current_key.bucket_name) {
+                    current_key
+                } else if let Some(bucket) = buckets.first() {
+                    ObjectKey::bucket(&bucket.name)
+                } else {
+                    return;
+                };
+                page.set_pane_connection(pane, Some(name.clone()));
+
+                let msg = format!("Switched connection of the pane to {}", name);
                 self.tx.send(AppEventType::NotifySuccess(msg));
+
+                self.commander_load_objects(pane, object_key, None);
             }
             Err(e) => {
+                // keep using the current connection
                 self.tx.send(AppEventType::NotifyError(e));
             }
         }
-        self.is_loading = false;
     }
 
-    pub fn download_objects(
+    fn set_commander_pane(
         &mut self,
-        bucket: String,
-        key: ObjectKey,
-        dir: String,
-        objs: Vec<DownloadObjectInfo>,
+        pane: CommanderPane,
+        items: Vec<ObjectItem>,
+        object_key: ObjectKey,
+        name: Option<String>,
     ) {
-        self.is_loading = true;
-
-        let current_selected_dir_key = key.joined_object_path(false);
-        let mut obj_paths = Vec::with_capacity(objs.len());
-        for obj in objs {
-            let relative_path =
-                PathBuf::from(&dir).join(obj.key.strip_prefix(&current_selected_dir_key).unwrap());
-            let absolute_path = self.ctx.config.download_file_path(relative_path);
-            obj_paths.push((obj, absolute_path));
+        let use_cache = self.commander_pane_uses_cache(pane);
+        let mut object_list_page = self.build_commander_pane(items, object_key, use_cache);
+        if let Some(name) = name {
+            object_list_page.select_item_by_name(&name);
         }
-        let download_dir = self.ctx.config.download_file_path(&dir);
+        if let Page::Commander(page) = self.page_stack.current_page_mut() {
+            page.set_pane(pane, object_list_page);
+        }
+    }
 
-        let total_count = obj_paths.len();
-        let total_size: usize = obj_paths.iter().map(|(obj, _)| obj.size_byte).sum();
-        let decimal_places = if total_size > 1_000_000_000 { 1 } else { 0 };
-        let format_opt =
-            humansize::FormatSizeOptions::from(humansize::DECIMAL).decimal_places(decimal_places);
-        let total_size_s = humansize::format_size_i(total_size, format_opt);
+    pub fn start_transfer_objects(&mut self, transfer: Transfer) {
+        self.tx.send(AppEventType::TransferObjects(transfer));
+        self.is_loading = true;
+    }
 
+    pub fn transfer_objects(&self, transfer: Transfer) {
         let max_concurrent_requests = self.ctx.config.max_concurrent_requests;
 
-        let client = self.client.clone();
+        let source_client = self.connection_client(transfer.source_connection.as_deref());
+        let destination_client = self.connection_client(transfer.destination_connection.as_deref());
+        let (Some(client), Some(destination_client)) = (source_client, destination_client) else {
+            let e = AppError::msg("The connection of the pane is not available");
+            self.tx.send(AppEventType::CompleteTransferObjects(Err(e)));
+            return;
+        };
+        // the objects are copied between the connections through the local temporary files
+        let same_connection = transfer.is_same_connection();
+        let temp_file_path = self.ctx.config.temp_file_path("transfer");
         let tx = self.tx.clone();
 
         spawn(async move {
-            let mut iter = futures::stream::iter(obj_paths)
-                .map(|(obj, path)| {
-                    let bucket = bucket.clone();
+            let source_bucket = transfer.source.bucket_name.clone();
+            // the file is also listed, since its size is needed to copy large objects in parts
+            let prefix = transfer.source.joined_object_path(!transfer.source_is_dir);
+            let source_objects = match client
+                .list_all_download_objects(&source_bucket, &prefix)
+                .await
+            {
+                Ok(objs) if transfer.source_is_dir => objs,
+                Ok(objs) => objs.into_iter().filter(|obj| obj.key == prefix).collect(),
+                Err(e) => {
+                    tx.send(AppEventType::CompleteTransferObjects(Err(e)));
+                    return;
+                }
+            };
+            let objects = transfer.objects(source_objects);
+            let total_count = objects.len();
+
+            let destination_bucket = transfer.destination.bucket_name.clone();
+            let remove_source = transfer.mode == TransferMode::Move;
+
+            let mut iter = futures::stream::iter(objects.into_iter().enumerate())
+                .map(|(i, obj)| {
+                    let source_bucket = source_bucket.clone();
+                    let destination_bucket = destination_bucket.clone();
                     let client = client.clone();
+                    let destination_client = destination_client.clone();
+                    let mut path = temp_file_path.clone().into_os_string();
+                    path.push(format!("-{}", i));
                     async move {
-                        let mut writer = create_binary_file(path)?;
-                        client
-                            .download_object(&bucket, &obj.key, None, &mut writer, |_| {})
+                        if same_connection {
+                            client
+                                .copy_object(
+                                    &source_bucket,
+                                    &obj.source_key,
+                                    &destination_bucket,
+                                    &obj.destination_key,
+                                    obj.size_byte,
+                                )
+                                .await?;
+                        } else {
+                            copy_object_between_connections(
+                                client.as_ref(),
+                                destination_client.as_ref(),
+                                &source_bucket,
+                                &obj.source_key,
+                                &destination_bucket,
+                                &obj.destination_key,
+                                Path::new(&path),
+                            )
                             .await?;
-                        Ok(obj.size_byte)
+                        }
+                        if remove_source {
+                            client
+                                .delete_object(&source_bucket, &obj.source_key)
+                                .await?;
+                        }
+                        Ok(())
                     }
                 })
                 .buffered(max_concurrent_requests);
 
-            let mut cur_count = 0;
-            let mut cur_size = 0;
+            let mut count = 0;
             while let Some(result) = iter.next().await {
-                match result {
-                    Ok(size) => {
-                        cur_count += 1;
-                        cur_size += size;
-
-                        let cur_size_s = humansize::format_size_i(cur_size, format_opt);
-                        let msg = format!(
-                            "{}/{} objects downloaded ({} out of {} total)",
-                            cur_count, total_count, cur_size_s, total_size_s
-                        );
-                        tx.send(AppEventType::NotifyInfo(msg));
-                    }
-                    Err(e) => {
-                        tx.send(AppEventType::CompleteDownloadObjects(Err(e)));
-                        return;
-                    }
+                if let Err(e) = result {
+                    tx.send(AppEventType::CompleteTransferObjects(Err(e)));
+                    return;
                 }
+                count += 1;
+                let msg = format!("{}/{} objects completed", count, total_count);
+                tx.send(AppEventType::NotifyInfo(msg));
             }
 
-            let result = CompleteDownloadObjectsResult::new(download_dir);
-            tx.send(AppEventType::CompleteDownloadObjects(result));
+            let result = CompleteTransferObjectsResult::new(transfer, count);
+            tx.send(AppEventType::CompleteTransferObjects(result));
         });
     }
 
-    pub fn complete_download_objects(&mut self, result: Result<CompleteDownloadObjectsResult>) {
+    pub fn complete_transfer_objects(&mut self, result: Result<CompleteTransferObjectsResult>) {
+        self.is_loading = false;
         match result {
-            Ok(CompleteDownloadObjectsResult { download_dir }) => {
+            Ok(CompleteTransferObjectsResult { transfer, count }) => {
+                let verb = match transfer.mode {
+                    TransferMode::Copy => "Copied",
+                    TransferMode::Move => "Moved",
+                };
                 let msg = format!(
-                    "Download completed successfully: {}",
-                    download_dir.to_string_lossy()
+                    "{} {} objects to {} successfully",
+                    verb,
+                    count,
+                    transfer.destination.prefix_uri()
                 );
                 self.tx.send(AppEventType::NotifySuccess(msg));
             }
@@ -652,123 +2696,350 @@ impl<C: Client> App<C> {
                 self.tx.send(AppEventType::NotifyError(e));
             }
         }
-        self.is_loading = false;
+        // reload both panes even if failed, since some objects may have been transferred
+        self.commander_refresh(CommanderPane::Left);
+        self.commander_refresh(CommanderPane::Right);
+    }
+
+    pub fn prefix_tree_available(&self) -> bool {
+        matches!(
+            self.page_stack.current_page(),
+            Page::BucketList(_) | Page::ObjectList(_)
+        )
+    }
+
+    fn prefix_tree_shown(&self) -> bool {
+        self.prefix_tree.is_visible() && self.prefix_tree_available()
+    }
+
+    pub fn prefix_tree_focused(&self) -> bool {
+        self.prefix_tree.is_focused() && self.prefix_tree_available()
+    }
+
+    pub fn toggle_prefix_tree(&mut self) {
+        self.prefix_tree.toggle();
+    }
+
+    // rebuild the tree from the loaded objects, following the directory of the current page
+    fn update_prefix_tree(&mut self) {
+        let bucket_names = self.app_objects.get_bucket_names();
+        let current = self.current_dir_object_key().cloned();
+        let app_objects = &self.app_objects;
+        self.prefix_tree
+            .update(bucket_names, current.as_ref(), |key| {
+                app_objects.get_dir_names(key)
+            });
+    }
+
+    pub fn handle_prefix_tree_events(&mut self, user_events: Vec<UserEvent>) {
+        self.update_prefix_tree();
+        handle_user_events! { user_events =>
+            UserEvent::PrefixTreeDown => {
+                self.prefix_tree.select_next();
+            }
+            UserEvent::PrefixTreeUp => {
+                self.prefix_tree.select_prev();
+            }
+            UserEvent::PrefixTreeGoToTop => {
+                self.prefix_tree.select_first();
+            }
+            UserEvent::PrefixTreeGoToBottom => {
+                self.prefix_tree.select_last();
+            }
+            UserEvent::PrefixTreePageDown => {
+                self.prefix_tree.select_next_page();
+            }
+            UserEvent::PrefixTreePageUp => {
+                self.prefix_tree.select_prev_page();
+            }
+            UserEvent::PrefixTreeExpand => {
+                if let Some(object_key) = self.prefix_tree.expand_selected() {
+                    self.prefix_tree_load_objects(object_key);
+                }
+            }
+            UserEvent::PrefixTreeCollapse => {
+                self.prefix_tree.collapse_selected();
+            }
+            UserEvent::PrefixTreeSelect => {
+                self.prefix_tree_select();
+            }
+            UserEvent::PrefixTreeFocusList => {
+                self.prefix_tree.set_focused(false);
+            }
+            UserEvent::Help => {
+                self.open_help();
+            }
+        }
+    }
+
+    fn prefix_tree_select(&mut self) {
+        let Some(row) = self.prefix_tree.selected_row() else {
+            return;
+        };
+        let object_key = row.key.clone();
+        self.prefix_tree.set_focused(false);
+        if self.current_dir_object_key() != Some(&object_key) {
+            self.go_to_object_key(object_key, None);
+        }
     }
 
-    pub fn preview_object(
-        &self,
-        object_key: ObjectKey,
-        file_detail: FileDetail,
-        version_id: Option<String>,
-    ) {
-        let size_byte = file_detail.size_byte;
-
+    fn prefix_tree_load_objects(&mut self, object_key: ObjectKey) {
         let bucket = object_key.bucket_name.clone();
-        let key = object_key.joined_object_path(true);
+        let prefix = object_key.joined_object_path(false);
 
         let client = self.client.clone();
         let tx = self.tx.clone();
-        let loading = self.handle_loading_size(size_byte, tx.clone());
-
         spawn(async move {
-            let mut bytes = Vec::with_capacity(size_byte);
-            let result = {
-                let mut writer = BufWriter::new(&mut bytes);
-                client
-                    .download_object(&bucket, &key, version_id.clone(), &mut writer, loading)
-                    .await
-            };
-            let obj = result.map(|_| RawObject { bytes });
-            let result = CompletePreviewObjectResult::new(obj, file_detail, version_id);
-            tx.send(AppEventType::CompletePreviewObject(result));
+            let items = client.load_objects(&bucket, &prefix).await;
+            let result = CompleteLoadObjectsResult::new(items, object_key);
+            tx.send(AppEventType::CompletePrefixTreeLoadObjects(result));
         });
+        self.is_loading = true;
     }
 
-    pub fn complete_preview_object(&mut self, result: Result<CompletePreviewObjectResult>) {
+    pub fn complete_prefix_tree_load_objects(&mut self, result: Result<CompleteLoadObjectsResult>) {
         match result {
-            Ok(CompletePreviewObjectResult {
-                obj,
-                file_detail,
-                file_version_id,
-            }) => {
-                let object_preview_page = Page::of_object_preview(
-                    file_detail,
-                    file_version_id,
-                    obj,
-                    Rc::clone(&self.ctx),
-                    self.tx.clone(),
-                );
-                self.page_stack.push(object_preview_page);
+            Ok(CompleteLoadObjectsResult { items, object_key }) => {
+                self.app_objects.set_object_items(object_key.clone(), items);
+                self.prefix_tree.expand(object_key);
             }
             Err(e) => {
                 self.tx.send(AppEventType::NotifyError(e));
             }
-        };
-        self.clear_notification();
+        }
         self.is_loading = false;
     }
 
-    pub fn start_save_object(&mut self, name: String, obj: Arc<RawObject>) {
-        self.tx.send(AppEventType::SaveObject(name, obj));
-        self.is_loading = true;
+    fn prefix_tree_help_items(&self) -> Vec<BuildHelpsItem> {
+        #[rustfmt::skip]
+        let helps = vec![
+            BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+            BuildHelpsItem::new(UserEvent::PrefixTreeDown, "Select next prefix"),
+            BuildHelpsItem::new(UserEvent::PrefixTreeUp, "Select previous prefix"),
+            BuildHelpsItem::new(UserEvent::PrefixTreeGoToTop, "Go to top"),
+            BuildHelpsItem::new(UserEvent::PrefixTreeGoToBottom, "Go to bottom"),
+            BuildHelpsItem::new(UserEvent::PrefixTreePageDown, "Scroll page forward"),
+            BuildHelpsItem::new(UserEvent::PrefixTreePageUp, "Scroll page backward"),
+            BuildHelpsItem::new(UserEvent::PrefixTreeExpand, "Expand prefix"),
+            BuildHelpsItem::new(UserEvent::PrefixTreeCollapse, "Collapse prefix"),
+            BuildHelpsItem::new(UserEvent::PrefixTreeSelect, "Open prefix"),
+            BuildHelpsItem::new(UserEvent::PrefixTreeFocusList, "Focus list"),
+            BuildHelpsItem::new(UserEvent::TogglePrefixTree, "Hide prefix tree"),
+        ];
+        helps
     }
 
-    pub fn save_object(&self, name: String, obj: Arc<RawObject>) {
-        let path = self.ctx.config.download_file_path(&name);
-        let writer = create_binary_file(&path);
+    fn prefix_tree_short_helps(&self) -> Vec<SpansWithPriority> {
+        #[rustfmt::skip]
+        let helps = vec![
+            BuildShortHelpsItem::single(UserEvent::Quit, "Quit", 0),
+            BuildShortHelpsItem::group(vec![UserEvent::PrefixTreeDown, UserEvent::PrefixTreeUp], "Select", 3),
+            BuildShortHelpsItem::group(vec![UserEvent::PrefixTreeExpand, UserEvent::PrefixTreeCollapse], "Expand/Collapse", 4),
+            BuildShortHelpsItem::single(UserEvent::PrefixTreeSelect, "Open", 1),
+            BuildShortHelpsItem::single(UserEvent::PrefixTreeFocusList, "List", 2),
+            BuildShortHelpsItem::single(UserEvent::TogglePrefixTree, "Hide", 5),
+            BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+        ];
+        build_short_help_spans(helps, &self.mapper)
+    }
+
+    // the new tab starts with the same connection at the current directory
+    pub fn open_tab(&mut self) {
+        let location = self.current_dir_object_key().map(|key| Location {
+            object_key: key.clone(),
+            is_dir: true,
+        });
+        let app_objects = self.app_objects.clone();
+
+        let tab = self.take_active_tab();
+        self.tabs[self.active_tab] = Some(tab);
+        self.active_tab += 1;
+        self.tabs.insert(self.active_tab, None);
+        self.active_tab_id = TabId(self.next_tab_id);
+        self.next_tab_id += 1;
+        self.app_objects = app_objects;
+
+        self.initial_location = location.map(|location| (location, false));
+        let buckets = self.app_objects.get_bucket_items();
+        self.complete_initialize(Ok(CompleteInitializeResult { buckets }));
+    }
+
+    pub fn close_tab(&mut self) {
+        if self.tabs.len() == 1 {
+            let msg = "Cannot close the last tab".to_string();
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        }
+        self.tabs.remove(self.active_tab);
+        let index = self.active_tab.min(self.tabs.len() - 1);
+        self.restore_tab(index);
+    }
+
+    pub fn switch_to_next_tab(&mut self) {
+        self.switch_tab((self.active_tab + 1) % self.tabs.len());
+    }
+
+    pub fn switch_to_prev_tab(&mut self) {
+        let len = self.tabs.len();
+        self.switch_tab((self.active_tab + len - 1) % len);
+    }
+
+    fn switch_tab(&mut self, index: usize) {
+        if index == self.active_tab || index >= self.tabs.len() {
+            return;
+        }
+        let tab = self.take_active_tab();
+        self.tabs[self.active_tab] = Some(tab);
+        self.restore_tab(index);
+    }
+
+    // move the state of the active tab out, leaving an empty page stack
+    fn take_active_tab(&mut self) -> Tab<C> {
+        let page_stack = PageStack::new(Rc::clone(&self.ctx), self.tx.clone());
+        Tab {
+            id: self.active_tab_id,
+            page_stack: std::mem::replace(&mut self.page_stack, page_stack),
+            client: self.client.clone(),
+            app_objects: std::mem::take(&mut self.app_objects),
+            connection: self.session.connection.clone(),
+        }
+    }
+
+    fn restore_tab(&mut self, index: usize) {
+        let Some(tab) = self.tabs[index].take() else {
+            return;
+        };
+        self.page_stack = tab.page_stack;
+        self.client = tab.client;
+        self.app_objects = tab.app_objects;
+        self.session.connection = tab.connection;
+        self.active_tab = index;
+        self.active_tab_id = tab.id;
+    }
+
+    // the state of the tab, whether it is active or not, to apply the result of a background task
+    fn tab_state_mut(&mut self, tab_id: TabId) -> Option<(&mut PageStack, &mut AppObjects)> {
+        if tab_id == self.active_tab_id {
+            return Some((&mut self.page_stack, &mut self.app_objects));
+        }
+        self.tabs
+            .iter_mut()
+            .flatten()
+            .find(|tab| tab.id == tab_id)
+            .map(|tab| (&mut tab.page_stack, &mut tab.app_objects))
+    }
+
+    fn tab_client(&self, tab_id: TabId) -> Option<Arc<C>> {
+        if tab_id == self.active_tab_id {
+            return Some(self.client.clone());
+        }
+        self.tabs
+            .iter()
+            .flatten()
+            .find(|tab| tab.id == tab_id)
+            .map(|tab| tab.client.clone())
+    }
+
+    fn tab_titles(&self) -> Vec<String> {
+        let tabs: Vec<(&PageStack, Option<&String>)> = self
+            .tabs
+            .iter()
+            .map(|tab| match tab {
+                Some(tab) => (&tab.page_stack, tab.connection.as_ref()),
+                None => (&self.page_stack, self.session.connection.as_ref()),
+            })
+            .collect();
+        // the connections are shown only if the tabs use different ones
+        let show_connection = tabs.windows(2).any(|w| w[0].1 != w[1].1);
+        tabs.into_iter()
+            .map(|(page_stack, connection)| {
+                let title = match current_dir_object_key(page_stack) {
+                    Some(key) => match key.object_path.last() {
+                        Some(name) => format!("{}/", name),
+                        None => key.bucket_name.clone(),
+                    },
+                    None => "Buckets".to_string(),
+                };
+                match connection {
+                    Some(connection) if show_connection => format!("{} ({})", title, connection),
+                    _ => title,
+                }
+            })
+            .collect()
+    }
+
+    pub fn start_load_text_diff(&mut self, left: DiffTarget, right: DiffTarget) {
+        let max_size_byte = self.ctx.config.preview.max_diff_size_byte;
+        if left.size_byte > max_size_byte || right.size_byte > max_size_byte {
+            let msg = format!(
+                "Objects larger than {} cannot be compared",
+                format_size_byte(max_size_byte)
+            );
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        }
+        self.tx.send(AppEventType::LoadTextDiff(left, right));
+        self.is_loading = true;
+    }
 
+    pub fn load_text_diff(&self, left: DiffTarget, right: DiffTarget) {
+        let client = self.client.clone();
         let tx = self.tx.clone();
         spawn(async move {
-            match writer {
-                Ok(mut writer) => {
-                    let result = writer.write_all(&obj.bytes).map_err(AppError::error);
-                    let result = CompleteSaveObjectResult::new(result, path);
-                    tx.send(AppEventType::CompleteSaveObject(result));
-                }
-                Err(e) => {
-                    tx.send(AppEventType::CompleteSaveObject(Err(e)));
+            let download = |target: &DiffTarget| {
+                let client = client.clone();
+                let bucket = target.object_key.bucket_name.clone();
+                let key = target.object_key.joined_object_path(true);
+                let version_id = target.version_id.clone();
+                async move {
+                    let mut bytes = Vec::new();
+                    {
+                        let mut writer = BufWriter::new(&mut bytes);
+                        client
+                            .download_object(&bucket, &key, version_id, &mut writer, |_| {})
+                            .await?;
+                    }
+                    Ok(RawObject { bytes })
                 }
-            }
+            };
+            let objects = futures::future::try_join(download(&left), download(&right))
+                .await
+                .and_then(|(l, r)| {
+                    // a NUL byte is a good hint that the object is not a text
+                    if l.bytes.contains(&0) || r.bytes.contains(&0) {
+                        Err(AppError::msg("Binary objects cannot be compared"))
+                    } else {
+                        Ok((l, r))
+                    }
+                });
+            let result = CompleteLoadTextDiffResult::new(objects, left, right);
+            tx.send(AppEventType::CompleteLoadTextDiff(result));
         });
     }
 
-    pub fn complete_save_object(&mut self, result: Result<CompleteSaveObjectResult>) {
+    pub fn complete_load_text_diff(&mut self, result: Result<CompleteLoadTextDiffResult>) {
         match result {
-            Ok(CompleteSaveObjectResult { path }) => {
-                let msg = format!(
-                    "Download completed successfully: {}",
-                    path.to_string_lossy()
+            Ok(CompleteLoadTextDiffResult {
+                left,
+                right,
+                left_object,
+                right_object,
+            }) => {
+                let page = Page::of_text_diff(
+                    left,
+                    right,
+                    left_object,
+                    right_object,
+                    Rc::clone(&self.ctx),
+                    self.tx.clone(),
                 );
-                self.tx.send(AppEventType::NotifySuccess(msg));
+                self.page_stack.push(page);
             }
             Err(e) => {
                 self.tx.send(AppEventType::NotifyError(e));
             }
         }
         self.is_loading = false;
-
-        if let Page::ObjectPreview(page) = self.page_stack.current_page() {
-            if page.is_image_preview() {
-                self.tx.send(AppEventType::PreviewRerenderImage);
-            }
-        }
-    }
-
-    fn handle_loading_size(&self, total_size: usize, tx: Sender) -> Box<dyn Fn(usize) + Send> {
-        if total_size < 10_000_000 {
-            return Box::new(|_| {});
-        }
-        let decimal_places = if total_size > 1_000_000_000 { 1 } else { 0 };
-        let opt =
-            humansize::FormatSizeOptions::from(humansize::DECIMAL).decimal_places(decimal_places);
-        let total_s = humansize::format_size_i(total_size, opt);
-        let f = move |current| {
-            let percent = (current * 100) / total_size;
-            let cur_s = humansize::format_size_i(current, opt);
-            let msg = format!("{:3}% downloaded ({} out of {})", percent, cur_s, total_s);
-            tx.send(AppEventType::NotifyInfo(msg));
-        };
-        Box::new(f)
     }
 
     pub fn bucket_list_open_management_console(&self) {
@@ -813,6 +3084,168 @@ impl<C: Client> App<C> {
         }
     }
 
+    pub fn generate_presigned_url(
+        &self,
+        object_key: ObjectKey,
+        version_id: Option<String>,
+        method: PresignedUrlMethod,
+        expires_in: Duration,
+        content_disposition: Option<String>,
+    ) {
+        let bucket = object_key.bucket_name.clone();
+        let key = object_key.joined_object_path(true);
+        let expires_at = chrono::Local::now()
+            + chrono::Duration::from_std(expires_in).unwrap_or(chrono::Duration::zero());
+
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+
+        spawn(async move {
+            let url = client
+                .generate_presigned_url(
+                    &bucket,
+                    &key,
+                    version_id,
+                    method,
+                    expires_in,
+                    content_disposition,
+                )
+                .await;
+            let result = CompleteGeneratePresignedUrlResult::new(url, expires_at);
+            tx.send(AppEventType::CompleteGeneratePresignedUrl(result));
+        });
+    }
+
+    pub fn complete_generate_presigned_url(
+        &self,
+        result: Result<CompleteGeneratePresignedUrlResult>,
+    ) {
+        let result = result.and_then(|CompleteGeneratePresignedUrlResult { url, expires_at }| {
+            copy_to_clipboard(url).map(|_| expires_at)
+        });
+        match result {
+            Ok(expires_at) => {
+                let date_format = &self.ctx.config.ui.object_detail.date_format;
+                let msg = format!(
+                    "Copied presigned URL to clipboard successfully (expires at {})",
+                    format_datetime(&expires_at, date_format)
+                );
+                self.tx.send(AppEventType::NotifySuccess(msg));
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+    }
+
+    pub fn resolve_key_input(&mut self, key_event: KeyEvent) -> Vec<KeyInput> {
+        let current_page = self.page_stack.current_page();
+        let text_input = current_page.is_text_input_active();
+        let sections = if self.prefix_tree_focused() {
+            &["prefix_tree"]
+        } else {
+            current_page.key_sections()
+        };
+        let inputs = self
+            .mapper
+            .resolve(&mut self.key_input, key_event, sections, text_input);
+        if self.key_input.is_pending() {
+            // waiting for the next key
+            let generation = self.key_input.generation();
+            let timeout = Duration::from_millis(self.ctx.config.ui.key_sequence_timeout_millis);
+            let tx = self.tx.clone();
+            spawn(async move {
+                tokio::time::sleep(timeout).await;
+                tx.send(AppEventType::KeyInputTimeout(generation));
+            });
+        }
+        inputs
+    }
+
+    pub fn resolve_key_input_timeout(&mut self, generation: u64) -> Option<KeyInput> {
+        self.mapper.resolve_timeout(&mut self.key_input, generation)
+    }
+
+    pub fn mouse_enabled(&self) -> bool {
+        self.ctx.config.ui.mouse
+    }
+
+    pub fn resolve_mouse_input(&mut self, mouse_event: MouseEvent) -> Option<MouseInput> {
+        let input = self.mouse_input.resolve(mouse_event);
+        if input.is_some() {
+            // cancel the key sequence being typed
+            self.key_input.clear();
+        }
+        input
+    }
+
+    // Returns the event to be handled as if its key was pressed
+    pub fn handle_mouse_input(&mut self, input: MouseInput) -> Option<UserEvent> {
+        let position = input.position();
+        if self.header_area.contains(position) {
+            if let MouseInput::Click(_) = input {
+                self.go_to_breadcrumb_at(position);
+            }
+            return None;
+        }
+        if self.tab_bar_area.contains(position) {
+            if let MouseInput::Click(_) = input {
+                let tab_bar = TabBar::new(self.tab_titles(), self.active_tab);
+                if let Some(index) = tab_bar.tab_index_at(self.tab_bar_area, position) {
+                    self.switch_tab(index);
+                }
+            }
+            return None;
+        }
+        if self.prefix_tree_shown() && self.prefix_tree_area.contains(position) {
+            self.update_prefix_tree();
+            self.prefix_tree.set_focused(true);
+            return self.prefix_tree.handle_mouse_input(input);
+        }
+        if self.content_area.contains(position) {
+            if let MouseInput::Click(_) | MouseInput::DoubleClick(_) = input {
+                self.prefix_tree.set_focused(false);
+            }
+            let area = self.content_area;
+            return self
+                .page_stack
+                .current_page_mut()
+                .handle_mouse_input(input, area);
+        }
+        None
+    }
+
+    fn go_to_breadcrumb_at(&mut self, position: Position) {
+        let header = Header::new(self.breadcrumb());
+        let Some(index) = header.segment_index_at(self.header_area, position) else {
+            return;
+        };
+        let Some(current_key) = self.current_dir_object_key() else {
+            return;
+        };
+        let object_key = ObjectKey {
+            bucket_name: current_key.bucket_name.clone(),
+            object_path: current_key.object_path[..index].to_vec(),
+        };
+        // select the directory that was opened from the ancestor
+        let name = current_key.object_path.get(index).cloned();
+        if let Page::Commander(page) = self.page_stack.current_page() {
+            // move the active pane instead of leaving the commander
+            if *current_key != object_key {
+                let pane = page.active();
+                self.commander_load_objects(pane, object_key, name);
+            }
+            return;
+        }
+        if matches!(
+            self.page_stack.current_page(),
+            Page::ObjectList(page) if *page.current_dir_object_key() == object_key
+        ) {
+            return;
+        }
+        self.go_to_object_key(object_key, name);
+    }
+
     pub fn loading(&self) -> bool {
         self.is_loading
     }
@@ -863,15 +3296,20 @@ impl<C: Client> App<C> {
     pub fn render(&mut self, f: &mut Frame) {
         let chunks = Layout::vertical([
             Constraint::Length(self.header_height()),
+            Constraint::Length(self.tab_bar_height()),
             Constraint::Min(0),
             Constraint::Length(2),
         ])
         .split(f.area());
 
+        self.header_area = chunks[0];
+        self.tab_bar_area = chunks[1];
+
         self.render_background(f, f.area());
         self.render_header(f, chunks[0]);
-        self.render_content(f, chunks[1]);
-        self.render_footer(f, chunks[2]);
+        self.render_tab_bar(f, chunks[1]);
+        self.render_content(f, chunks[2]);
+        self.render_footer(f, chunks[3]);
         self.render_loading_dialog(f);
     }
 
@@ -882,8 +3320,17 @@ impl<C: Client> App<C> {
         }
     }
 
+    // the tab bar is shown only if multiple tabs are opened
+    fn tab_bar_height(&self) -> u16 {
+        if self.tabs.len() > 1 && self.header_height() > 0 {
+            1
+        } else {
+            0
+        }
+    }
+
     fn render_background(&self, f: &mut Frame, area: Rect) {
-        let block = Block::default().bg(self.ctx.theme.bg);
+        let block = Block::default().bg(self.ctx.theme.bg).fg(self.ctx.theme.fg);
         f.render_widget(block, area);
     }
 
@@ -894,19 +3341,63 @@ impl<C: Client> App<C> {
         }
     }
 
+    fn render_tab_bar(&self, f: &mut Frame, area: Rect) {
+        if !area.is_empty() {
+            let tab_bar = TabBar::new(self.tab_titles(), self.active_tab).theme(&self.ctx.theme);
+            f.render_widget(tab_bar, area);
+        }
+    }
+
     fn render_content(&mut self, f: &mut Frame, area: Rect) {
+        let tree_focused = self.prefix_tree_focused();
+        if let Page::ObjectList(page) = self.page_stack.current_page_mut() {
+            page.set_focused(!tree_focused);
+        }
+        let area = if self.prefix_tree_shown() {
+            let tree_width = (area.width / 4).clamp(20, 40).min(area.width);
+            let [tree_area, page_area] =
+                Layout::horizontal([Constraint::Length(tree_width), Constraint::Min(0)])
+                    .areas(area);
+            self.render_prefix_tree(f, tree_area);
+            page_area
+        } else {
+            self.prefix_tree_area = Rect::default();
+            area
+        };
+        self.content_area = area;
+
+        if matches!(
+            self.page_stack.current_page(),
+            Page::CommandPalette(_) | Page::Connections(_)
+        ) {
+            // the palette and the connection picker are shown over the page they were opened from
+            if let Some(page) = self.page_stack.iter_mut().rev().nth(1) {
+                page.render(f, area);
+            }
+        }
         self.page_stack.current_page_mut().render(f, area);
     }
 
+    fn render_prefix_tree(&mut self, f: &mut Frame, area: Rect) {
+        self.update_prefix_tree();
+        self.prefix_tree_area = area;
+        let tree = PrefixTree::new(&self.ctx.theme);
+        f.render_stateful_widget(tree, area, &mut self.prefix_tree);
+    }
+
     fn render_footer(&self, f: &mut Frame, area: Rect) {
         let status_type = match self.current_notification() {
             Notification::Info(msg) => StatusType::Info(msg.into()),
             Notification::Success(msg) => StatusType::Success(msg.into()),
             Notification::Warn(msg) => StatusType::Warn(msg.into()),
             Notification::Error(msg) => StatusType::Error(msg.into()),
-            Notification::None => {
-                StatusType::Help(self.page_stack.current_page().short_helps(&self.mapper))
-            }
+            Notification::None => match self.key_input.pending_str() {
+                Some(keys) => StatusType::PendingKeys(keys),
+                None if self.prefix_tree_focused() => {
+                    StatusType::Help(self.prefix_tree_short_helps())
+                }
+                None => StatusType::Help(self.page_stack.current_page().short_helps(&self.mapper)),
+            },
         };
         let status = Status::new(status_type).theme(&self.ctx.theme);
         f.render_widget(status, area);
@@ -920,14 +3411,136 @@ impl<C: Client> App<C> {
     }
 
     fn breadcrumb(&self) -> Vec<String> {
-        self.page_stack
-            .iter()
-            .filter_map(|page| match page {
-                Page::ObjectList(page) => Some(page),
-                _ => None,
-            })
-            .next_back()
-            .map(|page| page.current_dir_object_key().paths())
+        self.current_dir_object_key()
+            .map(|key| key.paths())
             .unwrap_or_default()
     }
+
+    fn current_dir_object_key(&self) -> Option<&ObjectKey> {
+        current_dir_object_key(&self.page_stack)
+    }
+}
+
+fn current_dir_object_key(page_stack: &PageStack) -> Option<&ObjectKey> {
+    page_stack
+        .iter()
+        .filter_map(|page| match page {
+            Page::ObjectList(page) => Some(page.current_dir_object_key()),
+            Page::Commander(page) => Some(page.active_pane().current_dir_object_key()),
+            _ => None,
+        })
+        .next_back()
+}
+
+// download the object into the file and upload it with the client of the other connection
+async fn copy_object_between_connections<C: Client>(
+    source_client: &C,
+    destination_client: &C,
+    source_bucket: &str,
+    source_key: &str,
+    destination_bucket: &str,
+    destination_key: &str,
+    path: &Path,
+) -> Result<()> {
+    let result = async {
+        download_object_to_file(source_client, source_bucket, source_key, path).await?;
+        destination_client
+            .upload_object(destination_bucket, destination_key, path)
+            .await
+    }
+    .await;
+    let _ = std::fs::remove_file(path);
+    result
+}
+
+// the returned file has been flushed, so that the caller can update its attributes
+async fn download_object_to_file<C: Client>(
+    client: &C,
+    bucket: &str,
+    key: &str,
+    path: &Path,
+) -> Result<File> {
+    let mut writer = create_binary_file(path)?;
+    client
+        .download_object(bucket, key, None, &mut writer, |_| {})
+        .await?;
+    writer
+        .into_inner()
+        .map_err(|e| AppError::new("Failed to write file", e))
+}
+
+fn set_split_preview_in_pages(
+    page_stack: &mut PageStack,
+    object_key: &ObjectKey,
+    content: std::result::Result<SplitPreviewContent, String>,
+) {
+    for page in page_stack.iter_mut() {
+        if let Page::ObjectList(page) = page {
+            page.set_split_preview(object_key, content.clone());
+        }
+    }
+}
+
+fn update_prefix_size_in_pages(
+    page_stack: &mut PageStack,
+    object_key: &ObjectKey,
+    size: PrefixSize,
+) {
+    let (Some(parent), Some(name)) = (object_key.parent(), object_key.object_path.last()) else {
+        return;
+    };
+    for page in page_stack.iter_mut() {
+        if let Page::ObjectList(page) = page {
+            if page.current_dir_object_key() == &parent {
+                page.set_prefix_size(name.clone(), size.clone());
+            }
+        }
+    }
+}
+
+fn remove_prefix_size_in_pages(page_stack: &mut PageStack, object_key: &ObjectKey) {
+    let (Some(parent), Some(name)) = (object_key.parent(), object_key.object_path.last()) else {
+        return;
+    };
+    for page in page_stack.iter_mut() {
+        if let Page::ObjectList(page) = page {
+            if page.current_dir_object_key() == &parent {
+                page.remove_prefix_size(name);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{client, config::ConnectionConfig};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_complete_custom_command() {
+        let connection = ConnectionConfig {
+            region: Some("us-east-1".into()),
+            ..Default::default()
+        };
+        let client = client::new(&connection, "us-east-1".into()).await;
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let tx = Sender::new(tx);
+        let mut app = App::new(
+            UserEventMapper::default(),
+            client,
+            AppContext::default(),
+            tx,
+        );
+
+        let output = PipedCommand::spawn("echo hello").unwrap().wait();
+        let result = CompleteCustomCommandResult::new(output, "greet".into());
+        app.complete_custom_command(result);
+
+        // the output is shown in the result view
+        assert!(matches!(
+            app.page_stack.current_page(),
+            Page::CommandOutput(_)
+        ));
+    }
 }
@@ -0,0 +1,124 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::Result,
+    file::{load_toml, save_toml},
+    object::ObjectKey,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub bucket: String,
+    // empty for a bucket, ends with `/` for a prefix
+    #[serde(default)]
+    pub key: String,
+}
+
+impl Bookmark {
+    pub fn dir(object_key: &ObjectKey) -> Self {
+        Self {
+            bucket: object_key.bucket_name.clone(),
+            key: object_key.joined_object_path(false),
+        }
+    }
+
+    pub fn object(object_key: &ObjectKey) -> Self {
+        Self {
+            bucket: object_key.bucket_name.clone(),
+            key: object_key.joined_object_path(true),
+        }
+    }
+
+    pub fn is_object(&self) -> bool {
+        !self.key.is_empty() && !self.key.ends_with('/')
+    }
+
+    pub fn object_key(&self) -> ObjectKey {
+        ObjectKey {
+            bucket_name: self.bucket.clone(),
+            object_path: self
+                .key
+                .split('/')
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect(),
+        }
+    }
+
+    pub fn uri(&self) -> String {
+        format!("s3://{}/{}", self.bucket, self.key)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BookmarksFile {
+    #[serde(default)]
+    bookmarks: Vec<Bookmark>,
+}
+
+pub fn load_bookmarks(path: impl AsRef<Path>) -> Result<Vec<Bookmark>> {
+    let file: BookmarksFile = load_toml(path, "bookmarks")?;
+    Ok(file.bookmarks)
+}
+
+pub fn save_bookmarks(path: impl AsRef<Path>, bookmarks: &[Bookmark]) -> Result<()> {
+    let file = BookmarksFile {
+        bookmarks: bookmarks.to_vec(),
+    };
+    save_toml(path, &file, "bookmarks")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::file::TestDir;
+
+    use super::*;
+
+    #[test]
+    fn test_bookmark_object_key() {
+        let key = ObjectKey {
+            bucket_name: "bucket".into(),
+            object_path: vec!["a".into(), "b".into()],
+        };
+
+        let dir = Bookmark::dir(&key);
+        assert_eq!(dir.uri(), "s3://bucket/a/b/");
+        assert!(!dir.is_object());
+        assert_eq!(dir.object_key(), key);
+
+        let object = Bookmark::object(&key);
+        assert_eq!(object.uri(), "s3://bucket/a/b");
+        assert!(object.is_object());
+        assert_eq!(object.object_key(), key);
+
+        let bucket = Bookmark::dir(&ObjectKey::bucket("bucket"));
+        assert_eq!(bucket.uri(), "s3://bucket/");
+        assert!(!bucket.is_object());
+        assert_eq!(bucket.object_key(), ObjectKey::bucket("bucket"));
+    }
+
+    #[test]
+    fn test_save_and_load_bookmarks() {
+        let dir = TestDir::new("bookmark");
+        let path = dir.join("bookmarks.toml");
+
+        assert!(load_bookmarks(&path).unwrap().is_empty());
+
+        let bookmarks = vec![
+            Bookmark::dir(&ObjectKey::bucket("bucket-1")),
+            Bookmark::object(&ObjectKey {
+                bucket_name: "bucket-2".into(),
+                object_path: vec!["dir".into(), "file.txt".into()],
+            }),
+        ];
+        save_bookmarks(&path, &bookmarks).unwrap();
+        assert_eq!(load_bookmarks(&path).unwrap(), bookmarks);
+        assert!(!dir.join("bookmarks.toml.tmp").exists());
+
+        // overwritten as a whole
+        save_bookmarks(&path, &bookmarks[..1]).unwrap();
+        assert_eq!(load_bookmarks(&path).unwrap(), bookmarks[..1]);
+    }
+}
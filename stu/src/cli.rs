@@ -0,0 +1,761 @@
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+};
+
+use clap::Subcommand;
+use serde::Serialize;
+
+use crate::{
+    client::Client,
+    config::Config,
+    error::{AppError, Result},
+    format::{format_datetime, format_object_count, format_size_byte},
+    location::{parse_location, Location},
+    object::{BucketItem, FileDetail, FileVersion, ObjectItem, ObjectSummary, PrefixSize},
+};
+
+pub const EXIT_SUCCESS: i32 = 0;
+pub const EXIT_FAILURE: i32 = 1;
+pub const EXIT_INVALID_ARGS: i32 = 2;
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// List buckets, or objects under the prefix
+    Ls {
+        /// S3 URI of the prefix (s3://<bucket>/<prefix>/), buckets are listed if omitted
+        #[arg(value_name = "S3_URI")]
+        uri: Option<String>,
+        /// List all objects under the prefix recursively
+        #[arg(long)]
+        recursive: bool,
+        /// Output in JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the content of the object to stdout
+    Cat {
+        /// S3 URI of the object (s3://<bucket>/<key>)
+        #[arg(value_name = "S3_URI")]
+        uri: String,
+        /// Version ID of the object
+        #[arg(long, value_name = "ID")]
+        version_id: Option<String>,
+    },
+    /// Download the object to a local file
+    Get {
+        /// S3 URI of the object (s3://<bucket>/<key>)
+        #[arg(value_name = "S3_URI")]
+        uri: String,
+        /// Destination file or directory [default: the object name in the current directory]
+        #[arg(value_name = "PATH")]
+        path: Option<PathBuf>,
+        /// Version ID of the object
+        #[arg(long, value_name = "ID")]
+        version_id: Option<String>,
+    },
+    /// Show the detail of the object
+    Stat {
+        /// S3 URI of the object (s3://<bucket>/<key>)
+        #[arg(value_name = "S3_URI")]
+        uri: String,
+        /// Output in JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// List the versions of the object
+    Versions {
+        /// S3 URI of the object (s3://<bucket>/<key>)
+        #[arg(value_name = "S3_URI")]
+        uri: String,
+        /// Output in JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show the total size and the number of objects under the prefix
+    Du {
+        /// S3 URI of the prefix (s3://<bucket>/<prefix>/)
+        #[arg(value_name = "S3_URI")]
+        uri: String,
+        /// Output in JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+// returns the exit code
+pub async fn run(command: Command, client: impl Client, config: &Config) -> i32 {
+    let result = match command {
+        Command::Ls {
+            uri: None, json, ..
+        } => ls_buckets(&client, json).await,
+        Command::Ls {
+            uri: Some(uri),
+            recursive,
+            json,
+        } => ls_objects(&client, config, &uri, recursive, json).await,
+        Command::Cat { uri, version_id } => cat(&client, &uri, version_id).await,
+        Command::Get {
+            uri,
+            path,
+            version_id,
+        } => get(&client, &uri, path, version_id).await,
+        Command::Stat { uri, json } => stat(&client, config, &uri, json).await,
+        Command::Versions { uri, json } => versions(&client, config, &uri, json).await,
+        Command::Du { uri, json } => du(&client, &uri, json).await,
+    };
+    match result {
+        Ok(()) => EXIT_SUCCESS,
+        Err(CliError::InvalidArgs(msg)) => {
+            eprintln!("error: {}", msg);
+            EXIT_INVALID_ARGS
+        }
+        Err(CliError::App(e)) if is_broken_pipe(&e) => {
+            // the reader has been closed (e.g. `stu cat ... | head`)
+            EXIT_SUCCESS
+        }
+        Err(CliError::App(e)) => {
+            match e.cause {
+                Some(cause) => eprintln!("error: {}: {}", e.msg, cause),
+                None => eprintln!("error: {}", e.msg),
+            }
+            EXIT_FAILURE
+        }
+    }
+}
+
+enum CliError {
+    InvalidArgs(String),
+    App(AppError),
+}
+
+impl From<AppError> for CliError {
+    fn from(e: AppError) -> Self {
+        CliError::App(e)
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(e: io::Error) -> Self {
+        CliError::App(AppError::error(e))
+    }
+}
+
+fn is_broken_pipe(e: &AppError) -> bool {
+    e.cause
+        .as_ref()
+        .and_then(|cause| cause.downcast_ref::<io::Error>())
+        .is_some_and(|e| e.kind() == io::ErrorKind::BrokenPipe)
+}
+
+fn parse_prefix(uri: &str) -> std::result::Result<Location, CliError> {
+    let location = parse_location(uri)
+        .ok_or_else(|| CliError::InvalidArgs(format!("Invalid S3 URI: {}", uri)))?;
+    // `s3://bucket/dir` is treated as `s3://bucket/dir/`
+    Ok(Location {
+        is_dir: true,
+        ..location
+    })
+}
+
+fn parse_object(uri: &str) -> std::result::Result<Location, CliError> {
+    match parse_location(uri) {
+        Some(location) if !location.is_dir => Ok(location),
+        Some(_) => Err(CliError::InvalidArgs(format!("Not an object: {}", uri))),
+        None => Err(CliError::InvalidArgs(format!("Invalid S3 URI: {}", uri))),
+    }
+}
+
+async fn load_object_detail(
+    client: &impl Client,
+    location: &Location,
+) -> std::result::Result<FileDetail, CliError> {
+    let bucket = &location.object_key.bucket_name;
+    let key = location.object_key.joined_object_path(true);
+    let name = location
+        .object_key
+        .object_path
+        .last()
+        .cloned()
+        .unwrap_or_default();
+    let detail = client.load_object_detail(bucket, &key, &name).await?;
+    Ok(detail)
+}
+
+async fn ls_buckets(client: &impl Client, json: bool) -> std::result::Result<(), CliError> {
+    let buckets = client.load_all_buckets().await?;
+    let mut out = io::stdout().lock();
+    if json {
+        write_json(&mut out, &buckets_json(&buckets))?;
+    } else {
+        write_buckets(&mut out, &buckets)?;
+    }
+    Ok(())
+}
+
+async fn ls_objects(
+    client: &impl Client,
+    config: &Config,
+    uri: &str,
+    recursive: bool,
+    json: bool,
+) -> std::result::Result<(), CliError> {
+    let location = parse_prefix(uri)?;
+    let bucket = &location.object_key.bucket_name;
+    let prefix = location.object_key.joined_object_path(false);
+    let date_format = &config.ui.object_list.date_format;
+
+    let mut out = io::stdout().lock();
+    if recursive {
+        let summaries = client.list_all_object_summaries(bucket, &prefix).await?;
+        if json {
+            write_json(&mut out, &summaries_json(&summaries))?;
+        } else {
+            write_summaries(&mut out, &summaries, date_format)?;
+        }
+    } else {
+        let items = client.load_objects(bucket, &prefix).await?;
+        if json {
+            write_json(&mut out, &objects_json(&items))?;
+        } else {
+            write_objects(&mut out, &items, date_format)?;
+        }
+    }
+    Ok(())
+}
+
+async fn cat(
+    client: &impl Client,
+    uri: &str,
+    version_id: Option<String>,
+) -> std::result::Result<(), CliError> {
+    let location = parse_object(uri)?;
+    let bucket = &location.object_key.bucket_name;
+    let key = location.object_key.joined_object_path(true);
+
+    let mut writer = BufWriter::new(io::stdout());
+    client
+        .download_object(bucket, &key, version_id, &mut writer, |_| {})
+        .await?;
+    Ok(())
+}
+
+async fn get(
+    client: &impl Client,
+    uri: &str,
+    path: Option<PathBuf>,
+    version_id: Option<String>,
+) -> std::result::Result<(), CliError> {
+    let location = parse_object(uri)?;
+    let detail = load_object_detail(client, &location).await?;
+
+    let path = match path {
+        Some(path) if path.is_dir() => path.join(&detail.name),
+        Some(path) => path,
+        None => PathBuf::from(&detail.name),
+    };
+    let file = File::create(&path).map_err(|e| AppError::new("Failed to create file", e))?;
+    let mut writer = BufWriter::new(file);
+
+    let total = detail.size_byte;
+    let progress = stderr_progress();
+    client
+        .download_object(
+            &location.object_key.bucket_name,
+            &detail.key,
+            version_id,
+            &mut writer,
+            |current| {
+                if progress {
+                    let percent = (current * 100).checked_div(total).unwrap_or(100);
+                    let cur_s = format_size_byte(current);
+                    let total_s = format_size_byte(total);
+                    eprint!("\r{:3}% downloaded ({} out of {})", percent, cur_s, total_s);
+                }
+            },
+        )
+        .await?;
+    if progress {
+        eprintln!();
+    }
+    eprintln!("Downloaded {} to {}", detail.s3_uri, path.display());
+    Ok(())
+}
+
+async fn stat(
+    client: &impl Client,
+    config: &Config,
+    uri: &str,
+    json: bool,
+) -> std::result::Result<(), CliError> {
+    let location = parse_object(uri)?;
+    let detail = load_object_detail(client, &location).await?;
+
+    let mut out = io::stdout().lock();
+    if json {
+        write_json(&mut out, &detail_json(&detail))?;
+    } else {
+        write_detail(&mut out, &detail, &config.ui.object_detail.date_format)?;
+    }
+    Ok(())
+}
+
+async fn versions(
+    client: &impl Client,
+    config: &Config,
+    uri: &str,
+    json: bool,
+) -> std::result::Result<(), CliError> {
+    let location = parse_object(uri)?;
+    let bucket = &location.object_key.bucket_name;
+    let key = location.object_key.joined_object_path(true);
+    let versions = client.load_object_versions(bucket, &key).await?;
+
+    let mut out = io::stdout().lock();
+    if json {
+        write_json(&mut out, &versions_json(&versions))?;
+    } else {
+        write_versions(&mut out, &versions, &config.ui.object_detail.date_format)?;
+    }
+    Ok(())
+}
+
+async fn du(client: &impl Client, uri: &str, json: bool) -> std::result::Result<(), CliError> {
+    let location = parse_prefix(uri)?;
+    let bucket = &location.object_key.bucket_name;
+    let prefix = location.object_key.joined_object_path(false);
+
+    let progress = stderr_progress();
+    let size = client
+        .calculate_prefix_size(bucket, &prefix, |size| {
+            if progress {
+                let count = format_object_count(size.object_count);
+                eprint!("\r{}, {}", count, format_size_byte(size.size_byte));
+            }
+        })
+        .await?;
+    if progress {
+        eprintln!();
+    }
+
+    let uri = location.object_key.prefix_uri();
+    let mut out = io::stdout().lock();
+    if json {
+        write_json(&mut out, &prefix_size_json(&uri, &size))?;
+    } else {
+        write_prefix_size(&mut out, &uri, &size)?;
+    }
+    Ok(())
+}
+
+// show progress only if stderr is not redirected
+fn stderr_progress() -> bool {
+    console::Term::stderr().is_term()
+}
+
+fn write_json<W: Write, T: Serialize>(out: &mut W, value: &T) -> Result<()> {
+    serde_json::to_writer_pretty(&mut *out, value)
+        .map_err(|e| AppError::new("Failed to serialize output", e))?;
+    writeln!(out).map_err(AppError::error)
+}
+
+#[derive(Debug, Serialize)]
+struct BucketJson<'a> {
+    name: &'a str,
+    region: &'a str,
+    s3_uri: &'a str,
+}
+
+fn buckets_json(buckets: &[BucketItem]) -> Vec<BucketJson<'_>> {
+    buckets
+        .iter()
+        .map(|b| BucketJson {
+            name: &b.name,
+            region: &b.region,
+            s3_uri: &b.s3_uri,
+        })
+        .collect()
+}
+
+fn write_buckets<W: Write>(out: &mut W, buckets: &[BucketItem]) -> io::Result<()> {
+    for bucket in buckets {
+        writeln!(out, "{}", bucket.name)?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum ObjectJson<'a> {
+    Dir {
+        name: &'a str,
+        key: &'a str,
+        s3_uri: &'a str,
+    },
+    File {
+        name: &'a str,
+        key: &'a str,
+        s3_uri: &'a str,
+        size_byte: usize,
+        last_modified: String,
+    },
+}
+
+fn objects_json(items: &[ObjectItem]) -> Vec<ObjectJson<'_>> {
+    items
+        .iter()
+        .map(|item| match item {
+            ObjectItem::Dir {
+                name, key, s3_uri, ..
+            } => ObjectJson::Dir { name, key, s3_uri },
+            ObjectItem::File {
+                name,
+                key,
+                s3_uri,
+                size_byte,
+                last_modified,
+                ..
+            } => ObjectJson::File {
+                name,
+                key,
+                s3_uri,
+                size_byte: *size_byte,
+                last_modified: last_modified.to_rfc3339(),
+            },
+        })
+        .collect()
+}
+
+fn write_objects<W: Write>(out: &mut W, items: &[ObjectItem], date_format: &str) -> io::Result<()> {
+    let date_w = items
+        .iter()
+        .map(|item| match item {
+            ObjectItem::Dir { .. } => 0,
+            ObjectItem::File { last_modified, .. } => {
+                format_datetime(last_modified, date_format).len()
+            }
+        })
+        .max()
+        .unwrap_or_default();
+    for item in items {
+        match item {
+            ObjectItem::Dir { name, .. } => {
+                writeln!(out, "{:date_w$}  {:>10}  {}/", "", "PRE", name)?;
+            }
+            ObjectItem::File {
+                name,
+                size_byte,
+                last_modified,
+                ..
+            } => {
+                let date = format_datetime(last_modified, date_format);
+                let size = format_size_byte(*size_byte);
+                writeln!(out, "{:date_w$}  {:>10}  {}", date, size, name)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+struct ObjectSummaryJson<'a> {
+    key: &'a str,
+    size_byte: usize,
+    last_modified: String,
+    storage_class: &'a str,
+    e_tag: &'a str,
+}
+
+fn summaries_json(summaries: &[ObjectSummary]) -> Vec<ObjectSummaryJson<'_>> {
+    summaries
+        .iter()
+        .map(|s| ObjectSummaryJson {
+            key: &s.key,
+            size_byte: s.size_byte,
+            last_modified: s.last_modified.to_rfc3339(),
+            storage_class: &s.storage_class,
+            e_tag: &s.e_tag,
+        })
+        .collect()
+}
+
+fn write_summaries<W: Write>(
+    out: &mut W,
+    summaries: &[ObjectSummary],
+    date_format: &str,
+) -> io::Result<()> {
+    for s in summaries {
+        let date = format_datetime(&s.last_modified, date_format);
+        let size = format_size_byte(s.size_byte);
+        writeln!(out, "{}  {:>10}  {}", date, size, s.key)?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+struct FileDetailJson<'a> {
+    name: &'a str,
+    key: &'a str,
+    size_byte: usize,
+    last_modified: String,
+    e_tag: &'a str,
+    content_type: &'a str,
+    storage_class: &'a str,
+    s3_uri: &'a str,
+    arn: &'a str,
+    object_url: &'a str,
+}
+
+fn detail_json(detail: &FileDetail) -> FileDetailJson<'_> {
+    FileDetailJson {
+        name: &detail.name,
+        key: &detail.key,
+        size_byte: detail.size_byte,
+        last_modified: detail.last_modified.to_rfc3339(),
+        e_tag: &detail.e_tag,
+        content_type: &detail.content_type,
+        storage_class: &detail.storage_class,
+        s3_uri: &detail.s3_uri,
+        arn: &detail.arn,
+        object_url: &detail.object_url,
+    }
+}
+
+fn write_detail<W: Write>(out: &mut W, detail: &FileDetail, date_format: &str) -> io::Result<()> {
+    let size = format!(
+        "{} ({} bytes)",
+        format_size_byte(detail.size_byte),
+        detail.size_byte
+    );
+    let rows = [
+        ("Name", detail.name.clone()),
+        ("Key", detail.key.clone()),
+        ("Size", size),
+        (
+            "Last Modified",
+            format_datetime(&detail.last_modified, date_format),
+        ),
+        ("ETag", detail.e_tag.clone()),
+        ("Content-Type", detail.content_type.clone()),
+        ("Storage class", detail.storage_class.clone()),
+        ("S3 URI", detail.s3_uri.clone()),
+        ("ARN", detail.arn.clone()),
+        ("Object URL", detail.object_url.clone()),
+    ];
+    for (label, value) in rows {
+        writeln!(out, "{:<14} {}", format!("{}:", label), value)?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+struct FileVersionJson<'a> {
+    version_id: &'a str,
+    size_byte: usize,
+    last_modified: String,
+    e_tag: &'a str,
+    is_latest: bool,
+}
+
+fn versions_json(versions: &[FileVersion]) -> Vec<FileVersionJson<'_>> {
+    versions
+        .iter()
+        .map(|v| FileVersionJson {
+            version_id: &v.version_id,
+            size_byte: v.size_byte,
+            last_modified: v.last_modified.to_rfc3339(),
+            e_tag: &v.e_tag,
+            is_latest: v.is_latest,
+        })
+        .collect()
+}
+
+fn write_versions<W: Write>(
+    out: &mut W,
+    versions: &[FileVersion],
+    date_format: &str,
+) -> io::Result<()> {
+    for v in versions {
+        let date = format_datetime(&v.last_modified, date_format);
+        let size = format_size_byte(v.size_byte);
+        let latest = if v.is_latest { " (latest)" } else { "" };
+        writeln!(out, "{}  {}  {:>10}{}", v.version_id, date, size, latest)?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+struct StorageClassSizeJson {
+    size_byte: usize,
+    object_count: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct PrefixSizeJson<'a> {
+    s3_uri: &'a str,
+    size_byte: usize,
+    object_count: usize,
+    storage_classes: BTreeMap<&'a str, StorageClassSizeJson>,
+}
+
+fn prefix_size_json<'a>(uri: &'a str, size: &'a PrefixSize) -> PrefixSizeJson<'a> {
+    let storage_classes = size
+        .storage_classes
+        .iter()
+        .map(|(class, (size_byte, object_count))| {
+            let size = StorageClassSizeJson {
+                size_byte: *size_byte,
+                object_count: *object_count,
+            };
+            (class.as_str(), size)
+        })
+        .collect();
+    PrefixSizeJson {
+        s3_uri: uri,
+        size_byte: size.size_byte,
+        object_count: size.object_count,
+        storage_classes,
+    }
+}
+
+fn write_prefix_size<W: Write>(out: &mut W, uri: &str, size: &PrefixSize) -> io::Result<()> {
+    writeln!(
+        out,
+        "{}  {}  {}",
+        format_size_byte(size.size_byte),
+        format_object_count(size.object_count),
+        uri
+    )?;
+    for (class, (size_byte, object_count)) in &size.storage_classes {
+        writeln!(
+            out,
+            "  {}: {}  {}",
+            class,
+            format_size_byte(*size_byte),
+            format_object_count(*object_count)
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Local, NaiveDateTime};
+
+    fn parse_datetime(s: &str) -> DateTime<Local> {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_local_timezone(Local)
+            .unwrap()
+    }
+
+    fn object_items() -> Vec<ObjectItem> {
+        vec![
+            ObjectItem::Dir {
+                name: "dir".into(),
+                key: "a/dir/".into(),
+                s3_uri: "s3://bucket/a/dir/".into(),
+                object_url: "https://bucket.s3.amazonaws.com/a/dir/".into(),
+            },
+            ObjectItem::File {
+                name: "file.txt".into(),
+                size_byte: 2048,
+                last_modified: parse_datetime("2024-01-02 13:04:05"),
+                key: "a/file.txt".into(),
+                s3_uri: "s3://bucket/a/file.txt".into(),
+                arn: "arn:aws:s3:::bucket/a/file.txt".into(),
+                object_url: "https://bucket.s3.amazonaws.com/a/file.txt".into(),
+                e_tag: "\"abc\"".into(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_write_objects() {
+        let mut out = Vec::new();
+        write_objects(&mut out, &object_items(), "%Y-%m-%d %H:%M:%S").unwrap();
+
+        let expected = [
+            "                            PRE  dir/",
+            "2024-01-02 13:04:05       2 KiB  file.txt",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_objects_json() {
+        let items = object_items();
+        let value = serde_json::to_value(objects_json(&items)).unwrap();
+
+        let last_modified = parse_datetime("2024-01-02 13:04:05").to_rfc3339();
+        let expected = serde_json::json!([
+            {
+                "type": "dir",
+                "name": "dir",
+                "key": "a/dir/",
+                "s3_uri": "s3://bucket/a/dir/",
+            },
+            {
+                "type": "file",
+                "name": "file.txt",
+                "key": "a/file.txt",
+                "s3_uri": "s3://bucket/a/file.txt",
+                "size_byte": 2048,
+                "last_modified": last_modified,
+            },
+        ]);
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn test_prefix_size_output() {
+        let mut size = PrefixSize::default();
+        size.add("STANDARD", 1024);
+        size.add("STANDARD", 2048);
+        size.add("GLACIER", 4096);
+
+        let mut out = Vec::new();
+        write_prefix_size(&mut out, "s3://bucket/a/", &size).unwrap();
+        let expected = [
+            "7 KiB  3 objects  s3://bucket/a/",
+            "  GLACIER: 4 KiB  1 object",
+            "  STANDARD: 3 KiB  2 objects",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+
+        let value = serde_json::to_value(prefix_size_json("s3://bucket/a/", &size)).unwrap();
+        let expected = serde_json::json!({
+            "s3_uri": "s3://bucket/a/",
+            "size_byte": 7168,
+            "object_count": 3,
+            "storage_classes": {
+                "GLACIER": { "size_byte": 4096, "object_count": 1 },
+                "STANDARD": { "size_byte": 3072, "object_count": 2 },
+            },
+        });
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn test_parse_uri() {
+        let location = parse_prefix("s3://bucket/a").ok().unwrap();
+        assert_eq!(location, Location::prefix("bucket", "a/"));
+
+        assert!(parse_object("s3://bucket/a.txt").is_ok());
+        assert!(matches!(
+            parse_object("s3://bucket/a/"),
+            Err(CliError::InvalidArgs(_))
+        ));
+        assert!(matches!(
+            parse_prefix("s3://"),
+            Err(CliError::InvalidArgs(_))
+        ));
+    }
+}
@@ -1,24 +1,56 @@
 use std::{
+    collections::HashMap,
     fmt::Debug,
     future::Future,
     io::{BufWriter, Write},
+    path::Path,
+    sync::Mutex,
+    time::Duration,
 };
 
-use aws_config::{default_provider::region, meta::region::RegionProviderChain, BehaviorVersion};
+use aws_config::{
+    default_provider::region, environment::EnvironmentVariableCredentialsProvider,
+    meta::region::RegionProviderChain, BehaviorVersion,
+};
 use aws_sdk_s3::{
-    config::Region,
+    config::{Credentials, Region},
     error::SdkError,
     operation::list_objects_v2::{ListObjectsV2Error, ListObjectsV2Output},
+    presigning::PresigningConfig,
+    primitives::{ByteStream, Length},
+    types::{CompletedMultipartUpload, CompletedPart, StorageClass},
 };
 use chrono::TimeZone;
+use futures::StreamExt;
 
 use crate::{
+    config::{ConnectionConfig, CredentialsSource, PathStyle},
     error::{AppError, Result},
-    object::{BucketItem, DownloadObjectInfo, FileDetail, FileVersion, ObjectItem},
+    object::{
+        BucketItem, DownloadObjectInfo, EditObjectAttributes, FileDetail, FileVersion, ObjectItem,
+        ObjectSummary, PrefixSize, PresignedUrlMethod, RawObject,
+    },
 };
 
 const DELIMITER: &str = "/";
 
+const BUCKET_REGION_HEADER: &str = "x-amz-bucket-region";
+const RESOLVE_BUCKET_REGION_CONCURRENCY: usize = 10;
+
+// storage class is omitted from the response for STANDARD objects by some S3 compatible services
+const DEFAULT_STORAGE_CLASS: &str = "STANDARD";
+
+const CREDENTIALS_PROVIDER_NAME: &str = "stu-connection";
+
+const PRECONDITION_FAILED_STATUS: u16 = 412;
+
+// objects larger than this can not be copied with a single CopyObject request
+const MAX_SINGLE_COPY_SIZE: usize = 5 * 1024 * 1024 * 1024;
+// files larger than this can not be uploaded with a single PutObject request
+const MAX_SINGLE_UPLOAD_SIZE: usize = 5 * 1024 * 1024 * 1024;
+const MULTIPART_PART_SIZE: usize = 512 * 1024 * 1024;
+const MAX_MULTIPART_PARTS: usize = 10_000;
+
 pub enum AddressingStyle {
     Auto,
     Path,          // https://s3.region.amazonaws.com/bucket/key
@@ -35,8 +67,19 @@ impl AddressingStyle {
     }
 }
 
+impl From<PathStyle> for AddressingStyle {
+    fn from(style: PathStyle) -> Self {
+        match style {
+            PathStyle::Auto => AddressingStyle::Auto,
+            PathStyle::Always => AddressingStyle::Path,
+            PathStyle::Never => AddressingStyle::VirtualHosted,
+        }
+    }
+}
+
 #[rustfmt::skip]
 pub trait Client: Send + Sync + 'static + Debug {
+    fn connect(connection: &ConnectionConfig, default_region_fallback: String) -> impl Future<Output = Self> + Send where Self: Sized;
     fn region(&self) -> &str;
     fn load_all_buckets(&self) -> impl Future<Output = Result<Vec<BucketItem>>> + Send;
     fn load_bucket(&self, name: &str) -> impl Future<Output = Result<Vec<BucketItem>>> + Send;
@@ -44,32 +87,33 @@ pub trait Client: Send + Sync + 'static + Debug {
     fn load_object_detail(&self, bucket: &str, key: &str, name: &str) -> impl Future<Output = Result<FileDetail>> + Send;
     fn load_object_versions(&self, bucket: &str, key: &str) -> impl Future<Output = Result<Vec<FileVersion>>> + Send;
     fn download_object<W: std::io::Write + Send, F: Fn(usize) + Send>(&self, bucket: &str, key: &str, version_id: Option<String>, writer: &mut BufWriter<W>, f: F) -> impl Future<Output = Result<()>> + Send;
+    fn load_object_head(&self, bucket: &str, key: &str, max_bytes: usize) -> impl Future<Output = Result<Vec<u8>>> + Send;
     fn list_all_download_objects(&self, bucket: &str, prefix: &str) -> impl Future<Output = Result<Vec<DownloadObjectInfo>>> + Send;
+    fn list_all_object_summaries(&self, bucket: &str, prefix: &str) -> impl Future<Output = Result<Vec<ObjectSummary>>> + Send;
+    fn calculate_prefix_size<F: Fn(&PrefixSize) + Send>(&self, bucket: &str, prefix: &str, f: F) -> impl Future<Output = Result<PrefixSize>> + Send;
+    fn load_object_for_edit(&self, bucket: &str, key: &str) -> impl Future<Output = Result<(RawObject, EditObjectAttributes)>> + Send;
+    fn upload_edited_object(&self, bucket: &str, key: &str, bytes: Vec<u8>, attrs: &EditObjectAttributes) -> impl Future<Output = Result<()>> + Send;
+    fn upload_object(&self, bucket: &str, key: &str, path: &Path) -> impl Future<Output = Result<()>> + Send;
+    fn delete_object(&self, bucket: &str, key: &str) -> impl Future<Output = Result<()>> + Send;
+    fn copy_object(&self, src_bucket: &str, src_key: &str, dst_bucket: &str, dst_key: &str, size_byte: usize) -> impl Future<Output = Result<()>> + Send;
+    fn generate_presigned_url(&self, bucket: &str, key: &str, version_id: Option<String>, method: PresignedUrlMethod, expires_in: Duration, content_disposition: Option<String>) -> impl Future<Output = Result<String>> + Send;
     fn open_management_console_buckets(&self) -> Result<()>;
     fn open_management_console_list(&self, bucket: &str, prefix: &str) -> Result<()>;
     fn open_management_console_object(&self, bucket: &str, prefix: &str) -> Result<()>;
 }
 
-pub async fn new(
-    region: Option<String>,
-    endpoint_url: Option<String>,
-    profile: Option<String>,
-    default_region_fallback: String,
-    addressing_style: AddressingStyle,
-) -> impl Client {
-    AwsSdkClient::new(
-        region,
-        endpoint_url,
-        profile,
-        default_region_fallback,
-        addressing_style,
-    )
-    .await
+pub async fn new(connection: &ConnectionConfig, default_region_fallback: String) -> impl Client {
+    AwsSdkClient::connect(connection, default_region_fallback).await
 }
 
 struct AwsSdkClient {
     client: aws_sdk_s3::Client,
     region: String,
+    config: aws_sdk_s3::Config,
+    // bucket regions are resolved only when connecting to AWS S3 (not to S3 compatible endpoints)
+    resolve_bucket_region: bool,
+    bucket_regions: Mutex<HashMap<String, String>>,
+    regional_clients: Mutex<HashMap<String, aws_sdk_s3::Client>>,
 }
 
 impl Debug for AwsSdkClient {
@@ -83,6 +127,7 @@ impl AwsSdkClient {
         region: Option<String>,
         endpoint_url: Option<String>,
         profile: Option<String>,
+        credentials: CredentialsSource,
         default_region_fallback: String,
         addressing_style: AddressingStyle,
     ) -> AwsSdkClient {
@@ -102,50 +147,370 @@ impl AwsSdkClient {
         if let Some(profile) = &profile {
             config_loader = config_loader.profile_name(profile);
         }
+        match credentials {
+            CredentialsSource::Default => {}
+            CredentialsSource::Environment => {
+                config_loader = config_loader
+                    .credentials_provider(EnvironmentVariableCredentialsProvider::new());
+            }
+            CredentialsSource::Static {
+                access_key_id,
+                secret_access_key,
+                session_token,
+            } => {
+                let credentials = Credentials::new(
+                    access_key_id,
+                    secret_access_key,
+                    session_token,
+                    None,
+                    CREDENTIALS_PROVIDER_NAME,
+                );
+                config_loader = config_loader.credentials_provider(credentials);
+            }
+        }
         let sdk_config = config_loader.load().await;
 
         let config_builder = aws_sdk_s3::config::Builder::from(&sdk_config)
             .force_path_style(addressing_style.to_force_path_style(sdk_config.endpoint_url()));
         let config = config_builder.build();
 
-        let client = aws_sdk_s3::Client::from_conf(config);
+        let client = aws_sdk_s3::Client::from_conf(config.clone());
         let region = sdk_config.region().unwrap().to_string();
+        let resolve_bucket_region = sdk_config.endpoint_url().is_none();
+
+        AwsSdkClient {
+            client,
+            region,
+            config,
+            resolve_bucket_region,
+            bucket_regions: Mutex::new(HashMap::new()),
+            regional_clients: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn cached_bucket_region(&self, bucket: &str) -> Option<String> {
+        self.bucket_regions.lock().unwrap().get(bucket).cloned()
+    }
 
-        AwsSdkClient { client, region }
+    fn cache_bucket_region(&self, bucket: &str, region: &str) {
+        self.bucket_regions
+            .lock()
+            .unwrap()
+            .insert(bucket.to_string(), region.to_string());
+    }
+
+    async fn bucket_region(&self, bucket: &str) -> String {
+        if !self.resolve_bucket_region {
+            return self.region.clone();
+        }
+        if let Some(region) = self.cached_bucket_region(bucket) {
+            return region;
+        }
+        match self.fetch_bucket_region(bucket).await {
+            Some(region) => {
+                self.cache_bucket_region(bucket, &region);
+                region
+            }
+            // not cached, since the request may have failed only temporarily
+            None => self.region.clone(),
+        }
+    }
+
+    async fn fetch_bucket_region(&self, bucket: &str) -> Option<String> {
+        let location_result = self
+            .client
+            .get_bucket_location()
+            .bucket(bucket)
+            .send()
+            .await;
+        if let Ok(output) = location_result {
+            let location = output.location_constraint().map_or("", |l| l.as_str());
+            return Some(location_constraint_to_region(location));
+        }
+
+        // GetBucketLocation may be denied, so fall back to HeadBucket.
+        // If the bucket is in another region, the region is returned in the header of the error response.
+        let head_result = self.client.head_bucket().bucket(bucket).send().await;
+        match head_result {
+            Ok(output) => output.bucket_region().map(String::from),
+            Err(SdkError::ServiceError(e)) => e
+                .raw()
+                .headers()
+                .get(BUCKET_REGION_HEADER)
+                .map(String::from),
+            Err(_) => None,
+        }
+    }
+
+    async fn client_for_bucket(&self, bucket: &str) -> (aws_sdk_s3::Client, String) {
+        let region = self.bucket_region(bucket).await;
+        if region == self.region {
+            return (self.client.clone(), region);
+        }
+
+        let mut regional_clients = self.regional_clients.lock().unwrap();
+        let client = regional_clients
+            .entry(region.clone())
+            .or_insert_with(|| {
+                let config = self
+                    .config
+                    .to_builder()
+                    .region(Region::new(region.clone()))
+                    .build();
+                aws_sdk_s3::Client::from_conf(config)
+            })
+            .clone();
+        (client, region)
+    }
+
+    // copy the object in parts with UploadPartCopy, keeping the attributes that CopyObject copies
+    async fn multipart_copy_object(
+        &self,
+        src_bucket: &str,
+        src_key: &str,
+        dst_bucket: &str,
+        dst_key: &str,
+        size_byte: usize,
+    ) -> Result<()> {
+        let (src_client, _) = self.client_for_bucket(src_bucket).await;
+        let result = src_client
+            .head_object()
+            .bucket(src_bucket)
+            .key(src_key)
+            .send()
+            .await;
+        let head = result.map_err(|e| AppError::new("Failed to load object", e))?;
+
+        let (client, _) = self.client_for_bucket(dst_bucket).await;
+        let result = client
+            .create_multipart_upload()
+            .bucket(dst_bucket)
+            .key(dst_key)
+            .set_content_type(head.content_type)
+            .set_content_encoding(head.content_encoding)
+            .set_content_disposition(head.content_disposition)
+            .set_content_language(head.content_language)
+            .set_cache_control(head.cache_control)
+            .set_metadata(head.metadata)
+            .send()
+            .await;
+        let output = result.map_err(|e| AppError::new("Failed to copy object", e))?;
+        let upload_id = output.upload_id.unwrap_or_default();
+
+        let copy_source = build_copy_source(src_bucket, src_key);
+        let part_size = multipart_part_size(size_byte);
+        let mut parts = Vec::new();
+        for (i, start) in (0..size_byte).step_by(part_size).enumerate() {
+            let end = (start + part_size).min(size_byte) - 1;
+            let part_number = i as i32 + 1;
+            let result = client
+                .upload_part_copy()
+                .bucket(dst_bucket)
+                .key(dst_key)
+                .upload_id(&upload_id)
+                .part_number(part_number)
+                .copy_source(&copy_source)
+                .copy_source_range(format!("bytes={}-{}", start, end))
+                .send()
+                .await;
+            match result {
+                Ok(output) => {
+                    let e_tag = output.copy_part_result.and_then(|r| r.e_tag);
+                    let part = CompletedPart::builder()
+                        .part_number(part_number)
+                        .set_e_tag(e_tag)
+                        .build();
+                    parts.push(part);
+                }
+                Err(e) => {
+                    self.abort_multipart_upload(&client, dst_bucket, dst_key, &upload_id)
+                        .await;
+                    return Err(AppError::new("Failed to copy object", e));
+                }
+            }
+        }
+
+        let upload = CompletedMultipartUpload::builder()
+            .set_parts(Some(parts))
+            .build();
+        let result = client
+            .complete_multipart_upload()
+            .bucket(dst_bucket)
+            .key(dst_key)
+            .upload_id(&upload_id)
+            .multipart_upload(upload)
+            .send()
+            .await;
+        if let Err(e) = result {
+            self.abort_multipart_upload(&client, dst_bucket, dst_key, &upload_id)
+                .await;
+            return Err(AppError::new("Failed to copy object", e));
+        }
+        Ok(())
+    }
+
+    async fn multipart_upload_object(
+        &self,
+        bucket: &str,
+        key: &str,
+        path: &Path,
+        size_byte: usize,
+    ) -> Result<()> {
+        let (client, _) = self.client_for_bucket(bucket).await;
+        let result = client
+            .create_multipart_upload()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await;
+        let output = result.map_err(|e| AppError::new("Failed to upload object", e))?;
+        let upload_id = output.upload_id.unwrap_or_default();
+
+        let part_size = multipart_part_size(size_byte);
+        let mut parts = Vec::new();
+        for (i, start) in (0..size_byte).step_by(part_size).enumerate() {
+            let length = part_size.min(size_byte - start);
+            let part_number = i as i32 + 1;
+            // each part is streamed from the file instead of being read into memory
+            let result = ByteStream::read_from()
+                .path(path)
+                .offset(start as u64)
+                .length(Length::Exact(length as u64))
+                .build()
+                .await;
+            let body = match result {
+                Ok(body) => body,
+                Err(e) => {
+                    self.abort_multipart_upload(&client, bucket, key, &upload_id)
+                        .await;
+                    return Err(AppError::new("Failed to read file", e));
+                }
+            };
+            let result = client
+                .upload_part()
+                .bucket(bucket)
+                .key(key)
+                .upload_id(&upload_id)
+                .part_number(part_number)
+                .body(body)
+                .send()
+                .await;
+            match result {
+                Ok(output) => {
+                    let part = CompletedPart::builder()
+                        .part_number(part_number)
+                        .set_e_tag(output.e_tag)
+                        .build();
+                    parts.push(part);
+                }
+                Err(e) => {
+                    self.abort_multipart_upload(&client, bucket, key, &upload_id)
+                        .await;
+                    return Err(AppError::new("Failed to upload object", e));
+                }
+            }
+        }
+
+        let upload = CompletedMultipartUpload::builder()
+            .set_parts(Some(parts))
+            .build();
+        let result = client
+            .complete_multipart_upload()
+            .bucket(bucket)
+            .key(key)
+            .upload_id(&upload_id)
+            .multipart_upload(upload)
+            .send()
+            .await;
+        if let Err(e) = result {
+            self.abort_multipart_upload(&client, bucket, key, &upload_id)
+                .await;
+            return Err(AppError::new("Failed to upload object", e));
+        }
+        Ok(())
+    }
+
+    // the uploaded parts are kept (and charged) until the upload is aborted
+    async fn abort_multipart_upload(
+        &self,
+        client: &aws_sdk_s3::Client,
+        bucket: &str,
+        key: &str,
+        upload_id: &str,
+    ) {
+        let _ = client
+            .abort_multipart_upload()
+            .bucket(bucket)
+            .key(key)
+            .upload_id(upload_id)
+            .send()
+            .await;
+    }
+
+    fn console_region(&self, bucket: &str) -> String {
+        self.cached_bucket_region(bucket)
+            .unwrap_or_else(|| self.region.clone())
     }
 }
 
 impl Client for AwsSdkClient {
+    async fn connect(connection: &ConnectionConfig, default_region_fallback: String) -> Self {
+        AwsSdkClient::new(
+            connection.region.clone(),
+            connection.endpoint_url.clone(),
+            connection.profile.clone(),
+            connection.credentials.clone(),
+            default_region_fallback,
+            connection.path_style.into(),
+        )
+        .await
+    }
+
     fn region(&self) -> &str {
         &self.region
     }
 
     async fn load_all_buckets(&self) -> Result<Vec<BucketItem>> {
-        let list_buckets_result = self
-            .client
-            .list_buckets()
-            .bucket_region(&self.region)
-            .send()
-            .await;
-        let list_buckets_output =
-            list_buckets_result.map_err(|e| AppError::new("Failed to load buckets", e))?;
+        let mut names_and_regions: Vec<(String, Option<String>)> = Vec::new();
 
-        let buckets: Vec<BucketItem> = list_buckets_output
-            .buckets()
-            .iter()
-            .map(|bucket| {
-                let bucket_name = bucket.name().unwrap().to_string();
-                let s3_uri = build_bucket_s3_uri(&bucket_name);
-                let arn = build_bucket_arn(&bucket_name);
-                let object_url = build_bucket_url(&self.region, &bucket_name);
-                BucketItem {
-                    name: bucket_name,
-                    s3_uri,
-                    arn,
-                    object_url,
-                }
+        let mut token: Option<String> = None;
+        loop {
+            let list_buckets_result = self
+                .client
+                .list_buckets()
+                .set_continuation_token(token)
+                .send()
+                .await;
+            let list_buckets_output =
+                list_buckets_result.map_err(|e| AppError::new("Failed to load buckets", e))?;
+
+            let bs = list_buckets_output.buckets().iter().map(|bucket| {
+                let name = bucket.name().unwrap().to_string();
+                let region = bucket.bucket_region().map(String::from);
+                (name, region)
+            });
+            names_and_regions.extend(bs);
+
+            token = list_buckets_output.continuation_token().map(String::from);
+            if token.is_none() {
+                break;
+            }
+        }
+
+        for (name, region) in &names_and_regions {
+            if let Some(region) = region {
+                self.cache_bucket_region(name, region);
+            }
+        }
+
+        let buckets: Vec<BucketItem> = futures::stream::iter(names_and_regions)
+            .map(|(name, _)| async move {
+                let region = self.bucket_region(&name).await;
+                build_bucket_item(name, region)
             })
-            .collect();
+            .buffered(RESOLVE_BUCKET_REGION_CONCURRENCY)
+            .collect()
+            .await;
 
         if buckets.is_empty() {
             Err(AppError::msg("No buckets found"))
@@ -155,27 +520,20 @@ impl Client for AwsSdkClient {
     }
 
     async fn load_bucket(&self, name: &str) -> Result<Vec<BucketItem>> {
-        let s3_uri = build_bucket_s3_uri(name);
-        let arn = build_bucket_arn(name);
-        let object_url = build_bucket_url(&self.region, name);
-
-        let bucket = BucketItem {
-            name: name.to_string(),
-            s3_uri,
-            arn,
-            object_url,
-        };
+        let region = self.bucket_region(name).await;
+        let bucket = build_bucket_item(name.to_string(), region);
         Ok(vec![bucket])
     }
 
     async fn load_objects(&self, bucket: &str, prefix: &str) -> Result<Vec<ObjectItem>> {
+        let (client, region) = self.client_for_bucket(bucket).await;
+
         let mut dirs_vec: Vec<Vec<ObjectItem>> = Vec::new();
         let mut files_vec: Vec<Vec<ObjectItem>> = Vec::new();
 
         let mut token: Option<String> = None;
         loop {
-            let result = self
-                .client
+            let result = client
                 .list_objects_v2()
                 .bucket(bucket)
                 .prefix(prefix)
@@ -192,10 +550,10 @@ impl Client for AwsSdkClient {
 
             let output = result.map_err(|e| AppError::new("Failed to load objects", e))?;
 
-            let dirs = objects_output_to_dirs(&self.region, bucket, &output);
+            let dirs = objects_output_to_dirs(&region, bucket, &output);
             dirs_vec.push(dirs);
 
-            let files = objects_output_to_files(&self.region, bucket, &output);
+            let files = objects_output_to_files(&region, bucket, &output);
             files_vec.push(files);
 
             token = output.next_continuation_token().map(String::from);
@@ -210,13 +568,8 @@ impl Client for AwsSdkClient {
     }
 
     async fn load_object_detail(&self, bucket: &str, key: &str, name: &str) -> Result<FileDetail> {
-        let result = self
-            .client
-            .head_object()
-            .bucket(bucket)
-            .key(key)
-            .send()
-            .await;
+        let (client, region) = self.client_for_bucket(bucket).await;
+        let result = client.head_object().bucket(bucket).key(key).send().await;
         let output = result.map_err(|e| AppError::new("Failed to load object detail", e))?;
 
         let name = name.to_owned();
@@ -231,7 +584,7 @@ impl Client for AwsSdkClient {
         let key = key.to_owned();
         let s3_uri = build_object_s3_uri(bucket, &key);
         let arn = build_object_arn(bucket, &key);
-        let object_url = build_object_url(&self.region, bucket, &key);
+        let object_url = build_object_url(&region, bucket, &key);
         Ok(FileDetail {
             name,
             size_byte,
@@ -247,8 +600,8 @@ impl Client for AwsSdkClient {
     }
 
     async fn load_object_versions(&self, bucket: &str, key: &str) -> Result<Vec<FileVersion>> {
-        let result = self
-            .client
+        let (client, _) = self.client_for_bucket(bucket).await;
+        let result = client
             .list_object_versions()
             .bucket(bucket)
             .prefix(key)
@@ -285,7 +638,8 @@ impl Client for AwsSdkClient {
         writer: &mut BufWriter<W>,
         f: F,
     ) -> Result<()> {
-        let mut request = self.client.get_object().bucket(bucket).key(key);
+        let (client, _) = self.client_for_bucket(bucket).await;
+        let mut request = client.get_object().bucket(bucket).key(key);
         if let Some(version_id) = version_id {
             request = request.version_id(version_id);
         }
@@ -317,17 +671,39 @@ impl Client for AwsSdkClient {
         Ok(())
     }
 
+    async fn load_object_head(&self, bucket: &str, key: &str, max_bytes: usize) -> Result<Vec<u8>> {
+        let (client, _) = self.client_for_bucket(bucket).await;
+        let range = format!("bytes=0-{}", max_bytes.saturating_sub(1));
+        let result = client
+            .get_object()
+            .bucket(bucket)
+            .key(key)
+            .range(range)
+            .send()
+            .await;
+        let output = result.map_err(|e| AppError::new("Failed to download object", e))?;
+
+        let bytes = output
+            .body
+            .collect()
+            .await
+            .map_err(|e| AppError::new("Failed to collect body", e))?
+            .to_vec();
+        Ok(bytes)
+    }
+
     async fn list_all_download_objects(
         &self,
         bucket: &str,
         prefix: &str,
     ) -> Result<Vec<DownloadObjectInfo>> {
+        let (client, _) = self.client_for_bucket(bucket).await;
+
         let mut objs: Vec<DownloadObjectInfo> = Vec::new();
 
         let mut token: Option<String> = None;
         loop {
-            let result = self
-                .client
+            let result = client
                 .list_objects_v2()
                 .bucket(bucket)
                 .prefix(prefix)
@@ -356,6 +732,259 @@ impl Client for AwsSdkClient {
         Ok(objs)
     }
 
+    async fn list_all_object_summaries(
+        &self,
+        bucket: &str,
+        prefix: &str,
+    ) -> Result<Vec<ObjectSummary>> {
+        let (client, _) = self.client_for_bucket(bucket).await;
+
+        let mut objs: Vec<ObjectSummary> = Vec::new();
+
+        let mut token: Option<String> = None;
+        loop {
+            let result = client
+                .list_objects_v2()
+                .bucket(bucket)
+                .prefix(prefix)
+                .set_continuation_token(token)
+                .send()
+                .await;
+            let output = result.map_err(|e| AppError::new("Failed to list objects", e))?;
+
+            let os = output
+                .contents()
+                .iter()
+                .map(|file| {
+                    let key = file.key().unwrap().to_owned();
+                    let size_byte = file.size().unwrap_or_default() as usize;
+                    let last_modified = convert_datetime(file.last_modified().unwrap());
+                    let storage_class = file
+                        .storage_class()
+                        .map(|c| c.as_str())
+                        .unwrap_or(DEFAULT_STORAGE_CLASS)
+                        .to_owned();
+                    let e_tag = file.e_tag().unwrap_or_default().to_owned();
+                    ObjectSummary {
+                        key,
+                        size_byte,
+                        last_modified,
+                        storage_class,
+                        e_tag,
+                    }
+                })
+                .filter(|o| !o.key.ends_with('/')); // skip dummy empty object
+            objs.extend(os);
+
+            token = output.next_continuation_token().map(String::from);
+            if token.is_none() {
+                break;
+            }
+        }
+
+        Ok(objs)
+    }
+
+    async fn calculate_prefix_size<F: Fn(&PrefixSize) + Send>(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        f: F,
+    ) -> Result<PrefixSize> {
+        let (client, _) = self.client_for_bucket(bucket).await;
+
+        let mut size = PrefixSize::default();
+
+        let mut token: Option<String> = None;
+        loop {
+            let result = client
+                .list_objects_v2()
+                .bucket(bucket)
+                .prefix(prefix)
+                .set_continuation_token(token)
+                .send()
+                .await;
+            let output = result.map_err(|e| AppError::new("Failed to calculate prefix size", e))?;
+
+            output
+                .contents()
+                .iter()
+                .filter(|file| !file.key().unwrap_or_default().ends_with('/')) // skip dummy empty object
+                .for_each(|file| {
+                    let storage_class = file
+                        .storage_class()
+                        .map(|c| c.as_str())
+                        .unwrap_or(DEFAULT_STORAGE_CLASS);
+                    size.add(storage_class, file.size().unwrap_or_default() as usize);
+                });
+
+            token = output.next_continuation_token().map(String::from);
+            if token.is_none() {
+                break;
+            }
+            f(&size);
+        }
+
+        size.completed = true;
+        Ok(size)
+    }
+
+    async fn load_object_for_edit(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> Result<(RawObject, EditObjectAttributes)> {
+        let (client, _) = self.client_for_bucket(bucket).await;
+        let result = client.get_object().bucket(bucket).key(key).send().await;
+        let output = result.map_err(|e| AppError::new("Failed to download object", e))?;
+
+        let attrs = EditObjectAttributes {
+            e_tag: output.e_tag().unwrap_or_default().to_string(),
+            content_type: output.content_type().map(String::from),
+            content_encoding: output.content_encoding().map(String::from),
+            content_disposition: output.content_disposition().map(String::from),
+            content_language: output.content_language().map(String::from),
+            cache_control: output.cache_control().map(String::from),
+            storage_class: output.storage_class().map(|s| s.as_str().to_string()),
+            metadata: output.metadata().cloned().unwrap_or_default(),
+        };
+
+        let bytes = output
+            .body
+            .collect()
+            .await
+            .map_err(|e| AppError::new("Failed to collect body", e))?
+            .to_vec();
+
+        Ok((RawObject { bytes }, attrs))
+    }
+
+    async fn upload_edited_object(
+        &self,
+        bucket: &str,
+        key: &str,
+        bytes: Vec<u8>,
+        attrs: &EditObjectAttributes,
+    ) -> Result<()> {
+        let (client, _) = self.client_for_bucket(bucket).await;
+        let result = client
+            .put_object()
+            .bucket(bucket)
+            .key(key)
+            .body(ByteStream::from(bytes))
+            .if_match(&attrs.e_tag)
+            .set_content_type(attrs.content_type.clone())
+            .set_content_encoding(attrs.content_encoding.clone())
+            .set_content_disposition(attrs.content_disposition.clone())
+            .set_content_language(attrs.content_language.clone())
+            .set_cache_control(attrs.cache_control.clone())
+            .set_storage_class(attrs.storage_class.as_deref().map(StorageClass::from))
+            .set_metadata(Some(attrs.metadata.clone()))
+            .send()
+            .await;
+
+        if let Err(SdkError::ServiceError(ref e)) = result {
+            if e.raw().status().as_u16() == PRECONDITION_FAILED_STATUS {
+                return Err(AppError::msg(
+                    "Object has been modified since it was downloaded",
+                ));
+            }
+        }
+
+        result.map_err(|e| AppError::new("Failed to upload object", e))?;
+        Ok(())
+    }
+
+    async fn upload_object(&self, bucket: &str, key: &str, path: &Path) -> Result<()> {
+        let size_byte = std::fs::metadata(path)
+            .map_err(|e| AppError::new("Failed to read file", e))?
+            .len() as usize;
+        if size_byte > MAX_SINGLE_UPLOAD_SIZE {
+            return self
+                .multipart_upload_object(bucket, key, path, size_byte)
+                .await;
+        }
+        let (client, _) = self.client_for_bucket(bucket).await;
+        let body = ByteStream::from_path(path)
+            .await
+            .map_err(|e| AppError::new("Failed to read file", e))?;
+        let result = client
+            .put_object()
+            .bucket(bucket)
+            .key(key)
+            .body(body)
+            .send()
+            .await;
+        result.map_err(|e| AppError::new("Failed to upload object", e))?;
+        Ok(())
+    }
+
+    async fn delete_object(&self, bucket: &str, key: &str) -> Result<()> {
+        let (client, _) = self.client_for_bucket(bucket).await;
+        let result = client.delete_object().bucket(bucket).key(key).send().await;
+        result.map_err(|e| AppError::new("Failed to delete object", e))?;
+        Ok(())
+    }
+
+    async fn copy_object(
+        &self,
+        src_bucket: &str,
+        src_key: &str,
+        dst_bucket: &str,
+        dst_key: &str,
+        size_byte: usize,
+    ) -> Result<()> {
+        if size_byte > MAX_SINGLE_COPY_SIZE {
+            return self
+                .multipart_copy_object(src_bucket, src_key, dst_bucket, dst_key, size_byte)
+                .await;
+        }
+        let (client, _) = self.client_for_bucket(dst_bucket).await;
+        let result = client
+            .copy_object()
+            .copy_source(build_copy_source(src_bucket, src_key))
+            .bucket(dst_bucket)
+            .key(dst_key)
+            .send()
+            .await;
+        result.map_err(|e| AppError::new("Failed to copy object", e))?;
+        Ok(())
+    }
+
+    async fn generate_presigned_url(
+        &self,
+        bucket: &str,
+        key: &str,
+        version_id: Option<String>,
+        method: PresignedUrlMethod,
+        expires_in: Duration,
+        content_disposition: Option<String>,
+    ) -> Result<String> {
+        let (client, _) = self.client_for_bucket(bucket).await;
+        let presigning_config = PresigningConfig::expires_in(expires_in)
+            .map_err(|e| AppError::new("Invalid expiration for presigned URL", e))?;
+
+        let presigned_request = match method {
+            PresignedUrlMethod::Get => client
+                .get_object()
+                .bucket(bucket)
+                .key(key)
+                .set_version_id(version_id)
+                .set_response_content_disposition(content_disposition)
+                .presigned(presigning_config)
+                .await
+                .map_err(|e| AppError::new("Failed to generate presigned URL", e))?,
+            PresignedUrlMethod::Put => client
+                .put_object()
+                .bucket(bucket)
+                .key(key)
+                .presigned(presigning_config)
+                .await
+                .map_err(|e| AppError::new("Failed to generate presigned URL", e))?,
+        };
+        Ok(presigned_request.uri().to_string())
+    }
+
     fn open_management_console_buckets(&self) -> Result<()> {
         let path = format!(
             "https://s3.console.aws.amazon.com/s3/buckets?region={}",
@@ -367,7 +996,9 @@ impl Client for AwsSdkClient {
     fn open_management_console_list(&self, bucket: &str, prefix: &str) -> Result<()> {
         let path = format!(
             "https://s3.console.aws.amazon.com/s3/buckets/{}?region={}&prefix={}",
-            bucket, self.region, prefix
+            bucket,
+            self.console_region(bucket),
+            prefix
         );
         open::that(path).map_err(AppError::error)
     }
@@ -375,7 +1006,9 @@ impl Client for AwsSdkClient {
     fn open_management_console_object(&self, bucket: &str, prefix: &str) -> Result<()> {
         let path = format!(
             "https://s3.console.aws.amazon.com/s3/object/{}?region={}&prefix={}",
-            bucket, self.region, prefix
+            bucket,
+            self.console_region(bucket),
+            prefix
         );
         open::that(path).map_err(AppError::error)
     }
@@ -445,6 +1078,28 @@ fn objects_output_to_files(
         .collect()
 }
 
+fn build_bucket_item(name: String, region: String) -> BucketItem {
+    let s3_uri = build_bucket_s3_uri(&name);
+    let arn = build_bucket_arn(&name);
+    let object_url = build_bucket_url(&region, &name);
+    BucketItem {
+        name,
+        region,
+        s3_uri,
+        arn,
+        object_url,
+    }
+}
+
+// https://docs.aws.amazon.com/AmazonS3/latest/API/API_GetBucketLocation.html
+fn location_constraint_to_region(location: &str) -> String {
+    match location {
+        "" => "us-east-1".to_string(),
+        "EU" => "eu-west-1".to_string(),
+        _ => location.to_string(),
+    }
+}
+
 fn parse_path(path: &str, dir: bool) -> Vec<String> {
     let ss: Vec<String> = path.split(DELIMITER).map(String::from).collect();
     if dir {
@@ -483,3 +1138,55 @@ fn build_object_arn(bucket: &str, key: &str) -> String {
 fn build_object_url(region: &str, bucket: &str, key: &str) -> String {
     format!("https://{}.s3.{}.amazonaws.com/{}", bucket, region, key)
 }
+
+// the parts must be fewer than MAX_MULTIPART_PARTS
+fn multipart_part_size(size_byte: usize) -> usize {
+    MULTIPART_PART_SIZE.max(size_byte.div_ceil(MAX_MULTIPART_PARTS))
+}
+
+// the key of the copy source must be URL-encoded
+fn build_copy_source(bucket: &str, key: &str) -> String {
+    let encoded_key: String = key
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect();
+    format!("{}/{}", bucket, encoded_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_s3::config::retry::RetryConfig;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_bucket_region_fallback_not_cached() {
+        // nothing listens on the port, so the region can not be resolved
+        let config = aws_sdk_s3::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new("us-east-1"))
+            .endpoint_url("http://127.0.0.1:1")
+            .credentials_provider(Credentials::new("id", "secret", None, None, "test"))
+            .retry_config(RetryConfig::disabled())
+            .build();
+        let client = AwsSdkClient {
+            client: aws_sdk_s3::Client::from_conf(config.clone()),
+            region: "us-east-1".into(),
+            config,
+            resolve_bucket_region: true,
+            bucket_regions: Mutex::new(HashMap::new()),
+            regional_clients: Mutex::new(HashMap::new()),
+        };
+
+        assert_eq!(client.bucket_region("bucket").await, "us-east-1");
+        assert_eq!(client.cached_bucket_region("bucket"), None);
+
+        client.cache_bucket_region("bucket", "ap-northeast-1");
+        assert_eq!(client.bucket_region("bucket").await, "ap-northeast-1");
+    }
+}
@@ -1,4 +1,11 @@
-use ratatui::style::Color;
+use std::{fmt, str::FromStr};
+
+use ratatui::style::{Color, Modifier};
+use serde::{de, Deserialize, Deserializer};
+
+use crate::config::ThemeConfig;
+
+const NO_COLOR_ENV_VAR: &str = "NO_COLOR";
 
 #[derive(Debug, Clone)]
 pub struct ColorTheme {
@@ -12,6 +19,8 @@ pub struct ColorTheme {
     pub list_selected_fg: Color,
     pub list_selected_inactive_bg: Color,
     pub list_selected_inactive_fg: Color,
+    // also applied to the selected item of dialogs, which is otherwise distinguished only by the color
+    pub list_selected_modifier: Modifier,
     pub list_filter_match: Color,
 
     pub detail_selected: Color,
@@ -31,6 +40,29 @@ pub struct ColorTheme {
 
 impl Default for ColorTheme {
     fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl ColorTheme {
+    pub fn load(config: &ThemeConfig, no_color: bool) -> ColorTheme {
+        if no_color {
+            return ColorTheme::no_color();
+        }
+        let mut theme = ColorTheme::preset(config.preset);
+        theme.apply(config);
+        theme
+    }
+
+    fn preset(preset: ThemePreset) -> ColorTheme {
+        match preset {
+            ThemePreset::Dark => ColorTheme::dark(),
+            ThemePreset::Light => ColorTheme::light(),
+            ThemePreset::HighContrast => ColorTheme::high_contrast(),
+        }
+    }
+
+    fn dark() -> Self {
         Self {
             bg: Color::Reset,
             fg: Color::Reset,
@@ -42,6 +74,7 @@ impl Default for ColorTheme {
             list_selected_fg: Color::Black,
             list_selected_inactive_bg: Color::DarkGray,
             list_selected_inactive_fg: Color::Black,
+            list_selected_modifier: Modifier::empty(),
             list_filter_match: Color::Red,
 
             detail_selected: Color::Cyan,
@@ -59,4 +92,288 @@ impl Default for ColorTheme {
             status_error: Color::Red,
         }
     }
+
+    fn light() -> Self {
+        Self {
+            bg: Color::Reset,
+            fg: Color::Reset,
+
+            divider: Color::Indexed(248),
+            link: Color::Indexed(26),
+
+            list_selected_bg: Color::Indexed(25),
+            list_selected_fg: Color::Indexed(231),
+            list_selected_inactive_bg: Color::Indexed(250),
+            list_selected_inactive_fg: Color::Indexed(16),
+            list_selected_modifier: Modifier::empty(),
+            list_filter_match: Color::Indexed(160),
+
+            detail_selected: Color::Indexed(25),
+
+            dialog_selected: Color::Indexed(25),
+
+            preview_line_number: Color::Indexed(245),
+
+            help_key_fg: Color::Indexed(130),
+
+            status_help: Color::Indexed(243),
+            status_info: Color::Indexed(26),
+            status_success: Color::Indexed(28),
+            status_warn: Color::Indexed(130),
+            status_error: Color::Indexed(160),
+        }
+    }
+
+    fn high_contrast() -> Self {
+        Self {
+            bg: Color::Black,
+            fg: Color::White,
+
+            divider: Color::White,
+            link: Color::LightCyan,
+
+            list_selected_bg: Color::LightYellow,
+            list_selected_fg: Color::Black,
+            list_selected_inactive_bg: Color::White,
+            list_selected_inactive_fg: Color::Black,
+            list_selected_modifier: Modifier::BOLD,
+            list_filter_match: Color::LightRed,
+
+            detail_selected: Color::LightYellow,
+
+            dialog_selected: Color::LightYellow,
+
+            preview_line_number: Color::Gray,
+
+            help_key_fg: Color::LightYellow,
+
+            status_help: Color::Gray,
+            status_info: Color::LightCyan,
+            status_success: Color::LightGreen,
+            status_warn: Color::LightYellow,
+            status_error: Color::LightRed,
+        }
+    }
+
+    // https://no-color.org/
+    // the selected items are shown by reversing the terminal's default colors
+    fn no_color() -> Self {
+        Self {
+            bg: Color::Reset,
+            fg: Color::Reset,
+
+            divider: Color::Reset,
+            link: Color::Reset,
+
+            list_selected_bg: Color::Reset,
+            list_selected_fg: Color::Reset,
+            list_selected_inactive_bg: Color::Reset,
+            list_selected_inactive_fg: Color::Reset,
+            list_selected_modifier: Modifier::REVERSED,
+            list_filter_match: Color::Reset,
+
+            detail_selected: Color::Reset,
+
+            dialog_selected: Color::Reset,
+
+            preview_line_number: Color::Reset,
+
+            help_key_fg: Color::Reset,
+
+            status_help: Color::Reset,
+            status_info: Color::Reset,
+            status_success: Color::Reset,
+            status_warn: Color::Reset,
+            status_error: Color::Reset,
+        }
+    }
+
+    fn apply(&mut self, config: &ThemeConfig) {
+        macro_rules! apply_colors {
+            ($($field:ident),* $(,)?) => {
+                $(
+                    if let Some(ThemeColor(color)) = config.$field {
+                        self.$field = color;
+                    }
+                )*
+            };
+        }
+        apply_colors!(
+            bg,
+            fg,
+            divider,
+            link,
+            list_selected_bg,
+            list_selected_fg,
+            list_selected_inactive_bg,
+            list_selected_inactive_fg,
+            list_filter_match,
+            detail_selected,
+            dialog_selected,
+            preview_line_number,
+            help_key_fg,
+            status_help,
+            status_info,
+            status_success,
+            status_warn,
+            status_error,
+        );
+        if let Some(ThemeModifier(modifier)) = config.list_selected_modifier {
+            self.list_selected_modifier = modifier;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemePreset {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+}
+
+impl ThemePreset {
+    // the syntax highlighting theme used if `preview.highlight_theme` is not specified
+    pub fn highlight_theme(&self) -> &'static str {
+        match self {
+            ThemePreset::Dark => "base16-ocean.dark",
+            ThemePreset::Light => "InspiredGitHub",
+            ThemePreset::HighContrast => "base16-eighties.dark",
+        }
+    }
+}
+
+// A color in the config file.
+// Accepts a name ("red", "dark-gray", "reset", ...), an index of the 256 colors (0-255, as a number or a string) or a hex code ("#rrggbb").
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThemeColor(pub Color);
+
+impl<'de> Deserialize<'de> for ThemeColor {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ThemeColorVisitor;
+
+        impl de::Visitor<'_> for ThemeColorVisitor {
+            type Value = ThemeColor;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a color name, an index (0-255) or a hex code (#rrggbb)")
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                u8::try_from(v)
+                    .map(|i| ThemeColor(Color::Indexed(i)))
+                    .map_err(|_| E::invalid_value(de::Unexpected::Signed(v), &self))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Color::from_str(v)
+                    .map(ThemeColor)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+        }
+
+        deserializer.deserialize_any(ThemeColorVisitor)
+    }
+}
+
+// A text modifier in the config file.
+// Accepts a name ("bold", "reversed", ...) or an array of names, which are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThemeModifier(pub Modifier);
+
+impl<'de> Deserialize<'de> for ThemeModifier {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ThemeModifierVisitor;
+
+        impl<'de> de::Visitor<'de> for ThemeModifierVisitor {
+            type Value = ThemeModifier;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a modifier name (bold, dim, italic, underlined, reversed, ...) or an array of them")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                // the names of the flags are upper snake case (e.g. CROSSED_OUT)
+                Modifier::from_name(&v.to_uppercase().replace('-', "_"))
+                    .map(ThemeModifier)
+                    .ok_or_else(|| E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+
+            fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut modifier = Modifier::empty();
+                while let Some(ThemeModifier(m)) = seq.next_element()? {
+                    modifier |= m;
+                }
+                Ok(ThemeModifier(modifier))
+            }
+        }
+
+        deserializer.deserialize_any(ThemeModifierVisitor)
+    }
+}
+
+pub fn no_color_requested() -> bool {
+    std::env::var_os(NO_COLOR_ENV_VAR).is_some_and(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_theme() {
+        let config: ThemeConfig = toml::from_str(
+            r##"
+            preset = "light"
+            divider = "dark-gray"
+            link = 33
+            list_selected_bg = "#112233"
+            list_selected_modifier = ["bold", "crossed-out"]
+            status_error = "196"
+            "##,
+        )
+        .unwrap();
+        let theme = ColorTheme::load(&config, false);
+
+        assert_eq!(theme.divider, Color::DarkGray);
+        assert_eq!(theme.link, Color::Indexed(33));
+        assert_eq!(theme.list_selected_bg, Color::Rgb(0x11, 0x22, 0x33));
+        assert_eq!(theme.status_error, Color::Indexed(196));
+        assert_eq!(
+            theme.list_selected_modifier,
+            Modifier::BOLD | Modifier::CROSSED_OUT
+        );
+        // not overridden
+        assert_eq!(theme.list_selected_fg, Color::Indexed(231));
+
+        let theme = ColorTheme::load(&config, true);
+        assert_eq!(theme.list_selected_bg, Color::Reset);
+        assert_eq!(theme.list_selected_modifier, Modifier::REVERSED);
+    }
+
+    #[test]
+    fn test_load_theme_invalid_color() {
+        assert!(toml::from_str::<ThemeConfig>(r#"divider = "foo""#).is_err());
+        assert!(toml::from_str::<ThemeConfig>("divider = 256").is_err());
+        assert!(toml::from_str::<ThemeConfig>(r#"preset = "foo""#).is_err());
+        assert!(toml::from_str::<ThemeConfig>(r#"list_selected_modifier = "foo""#).is_err());
+    }
+
+    #[test]
+    fn test_load_theme_modifier() {
+        let config: ThemeConfig = toml::from_str(r#"list_selected_modifier = "reversed""#).unwrap();
+        let theme = ColorTheme::load(&config, false);
+        assert_eq!(theme.list_selected_modifier, Modifier::REVERSED);
+
+        let config: ThemeConfig = toml::from_str("list_selected_modifier = []").unwrap();
+        let theme = ColorTheme::load(&config, false);
+        assert_eq!(theme.list_selected_modifier, Modifier::empty());
+    }
 }
@@ -0,0 +1,322 @@
+use std::{
+    io::{self, BufRead, BufWriter, Read, Write},
+    path::Path,
+    process::{Child, ChildStdin, Command, ExitStatus, Stdio},
+    thread::{self, JoinHandle},
+};
+
+use crate::error::{AppError, Result};
+
+#[derive(Debug)]
+pub struct CommandOutput {
+    pub status: ExitStatus,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
+impl CommandOutput {
+    // the last line of the output, stderr if the command failed, to be shown in a notification
+    pub fn last_line(&self) -> Option<String> {
+        let output = if self.status.success() {
+            &self.stdout
+        } else {
+            &self.stderr
+        };
+        String::from_utf8_lossy(output)
+            .lines()
+            .rev()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(String::from)
+    }
+}
+
+// A shell command which reads the object body from stdin.
+// stdout and stderr are read in separate threads so that the command is not blocked by full pipes.
+pub struct PipedCommand {
+    child: Child,
+    stdout: JoinHandle<io::Result<Vec<u8>>>,
+    stderr: JoinHandle<io::Result<Vec<u8>>>,
+}
+
+impl PipedCommand {
+    pub fn spawn(command: &str) -> Result<PipedCommand> {
+        let mut child = shell_command(command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| AppError::new("Failed to run command", e))?;
+
+        let stdout = read_to_end_in_thread(child.stdout.take().unwrap());
+        let stderr = read_to_end_in_thread(child.stderr.take().unwrap());
+
+        Ok(PipedCommand {
+            child,
+            stdout,
+            stderr,
+        })
+    }
+
+    pub fn stdin(&mut self) -> BufWriter<PipeWriter> {
+        BufWriter::new(PipeWriter {
+            stdin: self.child.stdin.take(),
+        })
+    }
+
+    pub fn wait(mut self) -> Result<CommandOutput> {
+        // close stdin so that the command can detect EOF
+        drop(self.child.stdin.take());
+
+        let status = self
+            .child
+            .wait()
+            .map_err(|e| AppError::new("Failed to wait for command", e))?;
+        let stdout = join_output(self.stdout)?;
+        let stderr = join_output(self.stderr)?;
+        Ok(CommandOutput {
+            status,
+            stdout,
+            stderr,
+        })
+    }
+}
+
+// Ignores the rest of the input if the command exits without reading all of it (e.g. `head`)
+pub struct PipeWriter {
+    stdin: Option<ChildStdin>,
+}
+
+impl Write for PipeWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let Some(stdin) = self.stdin.as_mut() else {
+            return Ok(buf.len());
+        };
+        match stdin.write(buf) {
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => {
+                self.stdin = None;
+                Ok(buf.len())
+            }
+            ret => ret,
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let Some(stdin) = self.stdin.as_mut() else {
+            return Ok(());
+        };
+        match stdin.flush() {
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => {
+                self.stdin = None;
+                Ok(())
+            }
+            ret => ret,
+        }
+    }
+}
+
+pub fn open_with_command<P: AsRef<Path>>(command: &str, path: P) -> Result<()> {
+    let path = path.as_ref().to_string_lossy();
+    let command = format!("{} {}", command, shell_quote(&path));
+    let status = shell_command(&command)
+        .status()
+        .map_err(|e| AppError::new("Failed to run command", e))?;
+    if !status.success() {
+        return Err(AppError::msg(format!("Command exited with {}", status)));
+    }
+    Ok(())
+}
+
+// Runs the command in the terminal and waits for the user to confirm the output before returning.
+pub fn run_in_terminal(command: &str) -> Result<()> {
+    let status = shell_command(command)
+        .status()
+        .map_err(|e| AppError::new("Failed to run command", e))?;
+
+    print!("\n[Press Enter to return]");
+    let _ = io::stdout().flush();
+    let _ = io::stdin().lock().read_line(&mut String::new());
+
+    if !status.success() {
+        return Err(AppError::msg(format!("Command exited with {}", status)));
+    }
+    Ok(())
+}
+
+// The values of the placeholders in the custom command template.
+#[derive(Debug, Clone, Default)]
+pub struct CustomCommandTarget {
+    pub bucket: String,
+    pub key: String,
+    pub s3_uri: String,
+    pub object_url: String,
+    pub version_id: Option<String>,
+    // whether the key points to an object which can be downloaded to {local_path}
+    pub is_file: bool,
+}
+
+pub fn needs_local_path(template: &str) -> bool {
+    template.contains("{local_path}")
+}
+
+pub fn expand_custom_command(
+    template: &str,
+    target: &CustomCommandTarget,
+    local_path: Option<&Path>,
+) -> String {
+    let local_path = local_path
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let placeholders = [
+        ("{bucket}", target.bucket.as_str()),
+        ("{key}", target.key.as_str()),
+        ("{s3_uri}", target.s3_uri.as_str()),
+        ("{object_url}", target.object_url.as_str()),
+        (
+            "{version_id}",
+            target.version_id.as_deref().unwrap_or_default(),
+        ),
+        ("{local_path}", local_path.as_str()),
+    ];
+
+    // replace in a single pass so that the values containing placeholder-like strings are kept as they are
+    let mut ret = String::with_capacity(template.len());
+    let mut rest = template;
+    'outer: while let Some(pos) = rest.find('{') {
+        ret.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        for (placeholder, value) in placeholders {
+            if let Some(r) = rest.strip_prefix(placeholder) {
+                ret.push_str(&shell_quote(value));
+                rest = r;
+                continue 'outer;
+            }
+        }
+        ret.push('{');
+        rest = &rest[1..];
+    }
+    ret.push_str(rest);
+    ret
+}
+
+fn read_to_end_in_thread<R: Read + Send + 'static>(mut r: R) -> JoinHandle<io::Result<Vec<u8>>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        r.read_to_end(&mut buf)?;
+        Ok(buf)
+    })
+}
+
+fn join_output(handle: JoinHandle<io::Result<Vec<u8>>>) -> Result<Vec<u8>> {
+    handle
+        .join()
+        .map_err(|_| AppError::msg("Failed to read command output"))?
+        .map_err(|e| AppError::new("Failed to read command output", e))
+}
+
+#[cfg(not(windows))]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+#[cfg(not(windows))]
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+#[cfg(windows)]
+fn shell_quote(s: &str) -> String {
+    format!("\"{}\"", s)
+}
+
+#[cfg(all(test, not(windows)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_piped_command() {
+        let mut command = PipedCommand::spawn("tr a-z A-Z; echo err >&2").unwrap();
+        {
+            let mut stdin = command.stdin();
+            stdin.write_all(b"hello\n").unwrap();
+            stdin.flush().unwrap();
+        }
+        let output = command.wait().unwrap();
+
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"HELLO\n");
+        assert_eq!(output.stderr, b"err\n");
+    }
+
+    #[test]
+    fn test_piped_command_exits_early() {
+        let mut command = PipedCommand::spawn("head -c 1").unwrap();
+        {
+            let mut stdin = command.stdin();
+            for _ in 0..1024 {
+                stdin.write_all(&[b'a'; 1024]).unwrap();
+            }
+            stdin.flush().unwrap();
+        }
+        let output = command.wait().unwrap();
+
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"a");
+    }
+
+    #[test]
+    fn test_command_output_last_line() {
+        let command = PipedCommand::spawn("echo first; echo last; echo; echo err >&2").unwrap();
+        let output = command.wait().unwrap();
+        assert_eq!(output.last_line(), Some("last".into()));
+
+        let command = PipedCommand::spawn("echo out; echo error >&2; exit 1").unwrap();
+        let output = command.wait().unwrap();
+        assert_eq!(output.last_line(), Some("error".into()));
+
+        let command = PipedCommand::spawn("true").unwrap();
+        let output = command.wait().unwrap();
+        assert_eq!(output.last_line(), None);
+    }
+
+    #[test]
+    fn test_expand_custom_command() {
+        let target = CustomCommandTarget {
+            bucket: "bucket-1".into(),
+            key: "dir/file {key}.txt".into(),
+            s3_uri: "s3://bucket-1/dir/file {key}.txt".into(),
+            object_url: "https://bucket-1.s3.us-east-1.amazonaws.com/dir/file {key}.txt".into(),
+            version_id: None,
+            is_file: true,
+        };
+
+        let actual =
+            expand_custom_command("aws s3 cp {s3_uri} s3://backup/{bucket}/", &target, None);
+        assert_eq!(
+            actual,
+            "aws s3 cp 's3://bucket-1/dir/file {key}.txt' s3://backup/'bucket-1'/"
+        );
+
+        let actual = expand_custom_command(
+            "viewer {local_path} --version={version_id} {unknown}",
+            &target,
+            Some(Path::new("/tmp/file.txt")),
+        );
+        assert_eq!(actual, "viewer '/tmp/file.txt' --version='' {unknown}");
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("/tmp/a b.txt"), "'/tmp/a b.txt'");
+        assert_eq!(shell_quote("/tmp/it's.txt"), r"'/tmp/it'\''s.txt'");
+    }
+}
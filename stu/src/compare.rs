@@ -0,0 +1,190 @@
+use std::collections::BTreeMap;
+
+use crate::object::{ObjectKey, ObjectSummary};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    OnlyLeft,
+    OnlyRight,
+    Different,
+    Identical,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffSide {
+    pub size_byte: usize,
+    pub e_tag: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixDiffItem {
+    pub kind: DiffKind,
+    pub relative_path: String,
+    pub left: Option<DiffSide>,
+    pub right: Option<DiffSide>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PrefixDiff {
+    pub left: ObjectKey,
+    pub right: ObjectKey,
+    pub items: Vec<PrefixDiffItem>,
+}
+
+impl PrefixDiff {
+    pub fn count(&self, kind: DiffKind) -> usize {
+        self.items.iter().filter(|i| i.kind == kind).count()
+    }
+
+    pub fn left_object_key(&self, item: &PrefixDiffItem) -> Option<ObjectKey> {
+        item.left
+            .as_ref()
+            .map(|_| child_key(&self.left, &item.relative_path))
+    }
+
+    pub fn right_object_key(&self, item: &PrefixDiffItem) -> Option<ObjectKey> {
+        item.right
+            .as_ref()
+            .map(|_| child_key(&self.right, &item.relative_path))
+    }
+}
+
+fn child_key(dir_key: &ObjectKey, relative_path: &str) -> ObjectKey {
+    relative_path
+        .split('/')
+        .fold(dir_key.clone(), |key, name| key.child(name))
+}
+
+pub fn build_prefix_diff(
+    left: ObjectKey,
+    right: ObjectKey,
+    left_objects: Vec<ObjectSummary>,
+    right_objects: Vec<ObjectSummary>,
+) -> PrefixDiff {
+    let left_map = to_relative_map(&left, left_objects);
+    let mut right_map = to_relative_map(&right, right_objects);
+
+    let mut items: Vec<PrefixDiffItem> = Vec::new();
+    for (relative_path, l) in left_map {
+        let (kind, r) = match right_map.remove(&relative_path) {
+            None => (DiffKind::OnlyLeft, None),
+            Some(r) if is_same(&l, &r) => (DiffKind::Identical, Some(r)),
+            Some(r) => (DiffKind::Different, Some(r)),
+        };
+        items.push(PrefixDiffItem {
+            kind,
+            relative_path,
+            left: Some(l),
+            right: r,
+        });
+    }
+    for (relative_path, r) in right_map {
+        items.push(PrefixDiffItem {
+            kind: DiffKind::OnlyRight,
+            relative_path,
+            left: None,
+            right: Some(r),
+        });
+    }
+    items.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+
+    PrefixDiff { left, right, items }
+}
+
+fn to_relative_map(dir_key: &ObjectKey, objects: Vec<ObjectSummary>) -> BTreeMap<String, DiffSide> {
+    let prefix = dir_key.joined_object_path(false);
+    objects
+        .into_iter()
+        // skip directory markers
+        .filter(|o| !o.key.ends_with('/'))
+        .filter_map(|o| {
+            let relative_path = o.key.strip_prefix(&prefix)?.to_string();
+            let side = DiffSide {
+                size_byte: o.size_byte,
+                e_tag: o.e_tag,
+            };
+            Some((relative_path, side))
+        })
+        .collect()
+}
+
+fn is_same(left: &DiffSide, right: &DiffSide) -> bool {
+    left.size_byte == right.size_byte && left.e_tag == right.e_tag
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Local, NaiveDateTime};
+
+    use super::*;
+
+    #[test]
+    fn test_build_prefix_diff() {
+        let left = ObjectKey::from_prefix_uri("s3://a/x/").unwrap();
+        let right = ObjectKey::from_prefix_uri("s3://b/y/z/").unwrap();
+        let left_objects = vec![
+            object("x/", 0, "dir"),
+            object("x/same.txt", 10, "e1"),
+            object("x/size.txt", 10, "e2"),
+            object("x/etag.txt", 10, "e3"),
+            object("x/left.txt", 10, "e4"),
+            object("x/d/same.txt", 10, "e5"),
+        ];
+        let right_objects = vec![
+            object("y/z/same.txt", 10, "e1"),
+            object("y/z/size.txt", 20, "e2"),
+            object("y/z/etag.txt", 10, "e6"),
+            object("y/z/right.txt", 10, "e7"),
+            object("y/z/d/same.txt", 10, "e5"),
+        ];
+
+        let diff = build_prefix_diff(left, right, left_objects, right_objects);
+
+        let actual: Vec<(DiffKind, &str)> = diff
+            .items
+            .iter()
+            .map(|i| (i.kind, i.relative_path.as_str()))
+            .collect();
+        let expected = vec![
+            (DiffKind::Identical, "d/same.txt"),
+            (DiffKind::Different, "etag.txt"),
+            (DiffKind::OnlyLeft, "left.txt"),
+            (DiffKind::OnlyRight, "right.txt"),
+            (DiffKind::Identical, "same.txt"),
+            (DiffKind::Different, "size.txt"),
+        ];
+        assert_eq!(actual, expected);
+
+        assert_eq!(diff.count(DiffKind::Identical), 2);
+        assert_eq!(diff.count(DiffKind::Different), 2);
+
+        let item = &diff.items[0];
+        assert_eq!(
+            diff.left_object_key(item).unwrap().joined_object_path(true),
+            "x/d/same.txt"
+        );
+        assert_eq!(
+            diff.right_object_key(item)
+                .unwrap()
+                .joined_object_path(true),
+            "y/z/d/same.txt"
+        );
+        assert!(diff.right_object_key(&diff.items[2]).is_none());
+    }
+
+    fn object(key: &str, size_byte: usize, e_tag: &str) -> ObjectSummary {
+        ObjectSummary {
+            key: key.into(),
+            size_byte,
+            last_modified: NaiveDateTime::parse_from_str(
+                "2024-01-02 13:01:02",
+                "%Y-%m-%d %H:%M:%S",
+            )
+            .unwrap()
+            .and_local_timezone(Local)
+            .unwrap(),
+            storage_class: "STANDARD".into(),
+            e_tag: format!("\"{}\"", e_tag),
+        }
+    }
+}
@@ -4,20 +4,31 @@ use std::{
 };
 
 use anyhow::Context;
+use chrono::Local;
+use clap::ValueEnum;
+use indexmap::IndexMap;
 use serde::Deserialize;
 use smart_default::SmartDefault;
 use umbra::optional;
 
+use crate::{
+    color::{ThemeColor, ThemeModifier, ThemePreset},
+    util::extension_from_file_name,
+};
+
 const STU_ROOT_DIR_ENV_VAR: &str = "STU_ROOT_DIR";
 
 const APP_BASE_DIR: &str = ".stu";
 const CONFIG_FILE_NAME: &str = "config.toml";
 const KEYBINDINGS_FILE_NAME: &str = "keybindings.toml";
+const BOOKMARKS_FILE_NAME: &str = "bookmarks.toml";
+const SESSION_FILE_NAME: &str = "session.toml";
 const ERROR_LOG_FILE_NAME: &str = "error.log";
 const DEBUG_LOG_FILE_NAME: &str = "debug.log";
 const DOWNLOAD_DIR: &str = "download";
 const PREVIEW_THEME_DIR: &str = "preview_theme";
 const PREVIEW_SYNTAX_DIR: &str = "preview_syntax";
+const TEMP_DIR_NAME: &str = "stu";
 
 #[optional(derives = [Deserialize])]
 #[derive(Debug, Clone, SmartDefault)]
@@ -32,11 +43,22 @@ pub struct Config {
     pub ui: UiConfig,
     #[nested]
     pub preview: PreviewConfig,
+    #[nested]
+    pub session: SessionConfig,
+    pub theme: ThemeConfig,
+    pub connections: IndexMap<String, ConnectionConfig>,
+    pub open_commands: IndexMap<String, String>,
+    pub custom_commands: Vec<CustomCommandConfig>,
 }
 
 #[optional(derives = [Deserialize])]
 #[derive(Debug, Clone, SmartDefault)]
 pub struct UiConfig {
+    // how long to wait for the next key of a key sequence or a count
+    #[default = 1000]
+    pub key_sequence_timeout_millis: u64,
+    #[default = false]
+    pub mouse: bool,
     #[nested]
     pub object_list: UiObjectListConfig,
     #[nested]
@@ -52,6 +74,15 @@ pub struct UiObjectListConfig {
     pub date_format: String,
     #[default = 19] // // "2021-01-01 12:34:56".len()
     pub date_width: usize,
+    // show the preview of the selected item next to the list
+    #[default = false]
+    pub split_preview: bool,
+    // the width of the list in the split layout, in percent
+    #[default = 50]
+    pub split_ratio: u16,
+    // how long to wait after the selection is changed before loading the preview
+    #[default = 200]
+    pub split_preview_delay_millis: u64,
 }
 
 #[optional(derives = [Deserialize])]
@@ -83,6 +114,120 @@ pub struct PreviewConfig {
         "utf-16le".into(),
     ])]
     pub encodings: Vec<String>,
+    // objects larger than this are not downloaded for the text diff
+    #[default(10 * 1024 * 1024)]
+    pub max_diff_size_byte: usize,
+}
+
+#[optional(derives = [Deserialize])]
+#[derive(Debug, Clone, SmartDefault)]
+pub struct SessionConfig {
+    #[default = false]
+    pub restore: bool,
+    #[default = 20]
+    pub max_recent_locations: usize,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    pub preset: ThemePreset,
+    // override the colors of the preset
+    pub bg: Option<ThemeColor>,
+    pub fg: Option<ThemeColor>,
+    pub divider: Option<ThemeColor>,
+    pub link: Option<ThemeColor>,
+    pub list_selected_bg: Option<ThemeColor>,
+    pub list_selected_fg: Option<ThemeColor>,
+    pub list_selected_inactive_bg: Option<ThemeColor>,
+    pub list_selected_inactive_fg: Option<ThemeColor>,
+    pub list_selected_modifier: Option<ThemeModifier>,
+    pub list_filter_match: Option<ThemeColor>,
+    pub detail_selected: Option<ThemeColor>,
+    pub dialog_selected: Option<ThemeColor>,
+    pub preview_line_number: Option<ThemeColor>,
+    pub help_key_fg: Option<ThemeColor>,
+    pub status_help: Option<ThemeColor>,
+    pub status_info: Option<ThemeColor>,
+    pub status_success: Option<ThemeColor>,
+    pub status_warn: Option<ThemeColor>,
+    pub status_error: Option<ThemeColor>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ConnectionConfig {
+    pub profile: Option<String>,
+    pub region: Option<String>,
+    pub endpoint_url: Option<String>,
+    pub path_style: PathStyle,
+    pub credentials: CredentialsSource,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum PathStyle {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Clone, Default, Deserialize)]
+#[serde(tag = "source", rename_all = "lowercase")]
+pub enum CredentialsSource {
+    // the default credential provider chain (respects `profile`)
+    #[default]
+    Default,
+    // AWS_ACCESS_KEY_ID, AWS_SECRET_ACCESS_KEY, AWS_SESSION_TOKEN only
+    Environment,
+    Static {
+        access_key_id: String,
+        secret_access_key: String,
+        session_token: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomCommandConfig {
+    pub name: String,
+    pub key: String,
+    pub page: CustomCommandPage,
+    pub command: String,
+    #[serde(default)]
+    pub mode: CustomCommandMode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomCommandPage {
+    BucketList,
+    ObjectList,
+    ObjectDetail,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CustomCommandMode {
+    // run without the terminal and show the output in the result view
+    #[default]
+    Background,
+    // suspend the app and run in the terminal (for interactive commands)
+    Terminal,
+}
+
+impl std::fmt::Debug for CredentialsSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // do not leak secrets into debug logs and app dumps
+        match self {
+            Self::Default => write!(f, "Default"),
+            Self::Environment => write!(f, "Environment"),
+            Self::Static { access_key_id, .. } => f
+                .debug_struct("Static")
+                .field("access_key_id", access_key_id)
+                .finish_non_exhaustive(),
+        }
+    }
 }
 
 fn default_download_dir() -> String {
@@ -105,7 +250,16 @@ impl Config {
         if path.exists() {
             let content = std::fs::read_to_string(path)?;
             let config: OptionalConfig = toml::from_str(&content)?;
-            Ok(config.into())
+            let highlight_theme_specified = config
+                .preview
+                .as_ref()
+                .is_some_and(|p| p.highlight_theme.is_some());
+            let mut config: Config = config.into();
+            if !highlight_theme_specified {
+                // follow the color theme preset
+                config.preview.highlight_theme = config.theme.preset.highlight_theme().into();
+            }
+            Ok(config)
         } else {
             Ok(Config::default())
         }
@@ -116,11 +270,42 @@ impl Config {
         dir.join(name)
     }
 
+    pub fn temp_file_path<P: AsRef<Path>>(&self, name: P) -> PathBuf {
+        let dir = std::env::temp_dir().join(TEMP_DIR_NAME);
+        let prefix = Local::now().format("%Y%m%d%H%M%S%3f");
+        dir.join(format!("{}-{}", prefix, name.as_ref().to_string_lossy()))
+    }
+
+    pub fn open_command(&self, name: &str) -> Option<&str> {
+        let extension = extension_from_file_name(name).to_lowercase();
+        self.open_commands.get(&extension).map(String::as_str)
+    }
+
+    pub fn custom_commands_for(
+        &self,
+        page: CustomCommandPage,
+    ) -> impl Iterator<Item = (usize, &CustomCommandConfig)> {
+        self.custom_commands
+            .iter()
+            .enumerate()
+            .filter(move |(_, c)| c.page == page)
+    }
+
     pub fn keybindings_file_path(&self) -> anyhow::Result<PathBuf> {
         let dir = Config::get_app_base_dir()?;
         Ok(dir.join(KEYBINDINGS_FILE_NAME))
     }
 
+    pub fn bookmarks_file_path(&self) -> anyhow::Result<PathBuf> {
+        let dir = Config::get_app_base_dir()?;
+        Ok(dir.join(BOOKMARKS_FILE_NAME))
+    }
+
+    pub fn session_file_path(&self) -> anyhow::Result<PathBuf> {
+        let dir = Config::get_app_base_dir()?;
+        Ok(dir.join(SESSION_FILE_NAME))
+    }
+
     pub fn error_log_path(&self) -> anyhow::Result<PathBuf> {
         let dir = Config::get_app_base_dir()?;
         Ok(dir.join(ERROR_LOG_FILE_NAME))
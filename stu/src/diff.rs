@@ -0,0 +1,349 @@
+use crate::{format::format_version, object::ObjectKey};
+
+// Myers' algorithm needs O(D^2) memory, so give up finding the shortest edit beyond this distance
+const MAX_EDIT_DISTANCE: usize = 2000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffTarget {
+    pub object_key: ObjectKey,
+    pub version_id: Option<String>,
+    pub size_byte: usize,
+}
+
+impl DiffTarget {
+    pub fn new(object_key: ObjectKey, version_id: Option<String>, size_byte: usize) -> Self {
+        Self {
+            object_key,
+            version_id,
+            size_byte,
+        }
+    }
+
+    pub fn file_name(&self) -> &str {
+        self.object_key
+            .object_path
+            .last()
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+
+    pub fn label(&self) -> String {
+        let path = self.object_key.joined_object_path(true);
+        match &self.version_id {
+            Some(version_id) => format!("{} ({})", path, format_version(version_id)),
+            None => path,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLineKind {
+    Equal,
+    Delete,
+    Insert,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    // 0-based line index of the old text
+    pub old: Option<usize>,
+    // 0-based line index of the new text
+    pub new: Option<usize>,
+}
+
+impl DiffLine {
+    fn equal(old: usize, new: usize) -> Self {
+        Self {
+            kind: DiffLineKind::Equal,
+            old: Some(old),
+            new: Some(new),
+        }
+    }
+
+    fn delete(old: usize) -> Self {
+        Self {
+            kind: DiffLineKind::Delete,
+            old: Some(old),
+            new: None,
+        }
+    }
+
+    fn insert(new: usize) -> Self {
+        Self {
+            kind: DiffLineKind::Insert,
+            old: None,
+            new: Some(new),
+        }
+    }
+
+    pub fn is_change(&self) -> bool {
+        self.kind != DiffLineKind::Equal
+    }
+}
+
+pub fn diff_lines(old: &[&str], new: &[&str]) -> Vec<DiffLine> {
+    // the common prefix and suffix do not need to be passed to the algorithm
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut lines: Vec<DiffLine> = (0..prefix).map(|i| DiffLine::equal(i, i)).collect();
+
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+    match shortest_edit(old_mid, new_mid) {
+        Some(edits) => {
+            let (mut i, mut j) = (prefix, prefix);
+            for kind in edits {
+                match kind {
+                    DiffLineKind::Equal => {
+                        lines.push(DiffLine::equal(i, j));
+                        i += 1;
+                        j += 1;
+                    }
+                    DiffLineKind::Delete => {
+                        lines.push(DiffLine::delete(i));
+                        i += 1;
+                    }
+                    DiffLineKind::Insert => {
+                        lines.push(DiffLine::insert(j));
+                        j += 1;
+                    }
+                }
+            }
+        }
+        None => {
+            let deletes = (prefix..prefix + old_mid.len()).map(DiffLine::delete);
+            let inserts = (prefix..prefix + new_mid.len()).map(DiffLine::insert);
+            lines.extend(deletes.chain(inserts));
+        }
+    }
+
+    let old_start = old.len() - suffix;
+    let new_start = new.len() - suffix;
+    lines.extend((0..suffix).map(|i| DiffLine::equal(old_start + i, new_start + i)));
+
+    lines
+}
+
+// returns None if the edit distance exceeds MAX_EDIT_DISTANCE
+fn shortest_edit(a: &[&str], b: &[&str]) -> Option<Vec<DiffLineKind>> {
+    let n = a.len() as isize;
+    let m = b.len() as isize;
+    let max = (n + m) as usize;
+    let offset = max as isize + 1;
+
+    let mut v = vec![0_isize; 2 * max + 3];
+    // trace[d] holds v[-d..=d] before the d-th step
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    'outer: for d in 0..=(max as isize) {
+        if d as usize > MAX_EDIT_DISTANCE {
+            return None;
+        }
+        trace.push(v[(offset - d) as usize..=(offset + d) as usize].to_vec());
+
+        for k in (-d..=d).step_by(2) {
+            let down =
+                k == -d || (k != d && v[(offset + k - 1) as usize] < v[(offset + k + 1) as usize]);
+            let mut x = if down {
+                v[(offset + k + 1) as usize]
+            } else {
+                v[(offset + k - 1) as usize] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[(offset + k) as usize] = x;
+            if x >= n && y >= m {
+                break 'outer;
+            }
+        }
+    }
+
+    let mut edits = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        if d == 0 {
+            while x > 0 && y > 0 {
+                edits.push(DiffLineKind::Equal);
+                x -= 1;
+                y -= 1;
+            }
+            break;
+        }
+
+        let get = |k: isize| v[(k + d) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && get(k - 1) < get(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = get(prev_k);
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            edits.push(DiffLineKind::Equal);
+            x -= 1;
+            y -= 1;
+        }
+        if x == prev_x {
+            edits.push(DiffLineKind::Insert);
+        } else {
+            edits.push(DiffLineKind::Delete);
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    edits.reverse();
+
+    Some(edits)
+}
+
+// pairs the deleted and inserted lines of each change to display them side by side
+pub fn side_by_side(lines: &[DiffLine]) -> Vec<(Option<DiffLine>, Option<DiffLine>)> {
+    let mut rows = Vec::new();
+    let mut deletes: Vec<DiffLine> = Vec::new();
+    let mut inserts: Vec<DiffLine> = Vec::new();
+
+    let flush = |rows: &mut Vec<_>, deletes: &mut Vec<DiffLine>, inserts: &mut Vec<DiffLine>| {
+        let n = deletes.len().max(inserts.len());
+        for i in 0..n {
+            rows.push((deletes.get(i).copied(), inserts.get(i).copied()));
+        }
+        deletes.clear();
+        inserts.clear();
+    };
+
+    for line in lines {
+        match line.kind {
+            DiffLineKind::Equal => {
+                flush(&mut rows, &mut deletes, &mut inserts);
+                rows.push((Some(*line), Some(*line)));
+            }
+            DiffLineKind::Delete => {
+                if !inserts.is_empty() {
+                    flush(&mut rows, &mut deletes, &mut inserts);
+                }
+                deletes.push(*line);
+            }
+            DiffLineKind::Insert => {
+                inserts.push(*line);
+            }
+        }
+    }
+    flush(&mut rows, &mut deletes, &mut inserts);
+
+    rows
+}
+
+// returns the indices of the rows where each hunk (consecutive changed rows) starts
+pub fn hunk_starts<I>(changes: I) -> Vec<usize>
+where
+    I: IntoIterator<Item = bool>,
+{
+    let mut starts = Vec::new();
+    let mut prev = false;
+    for (i, change) in changes.into_iter().enumerate() {
+        if change && !prev {
+            starts.push(i);
+        }
+        prev = change;
+    }
+    starts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_lines() {
+        let old = vec!["a", "b", "c", "d", "e"];
+        let new = vec!["a", "c", "x", "d", "e", "f"];
+
+        let actual: Vec<(DiffLineKind, Option<usize>, Option<usize>)> = diff_lines(&old, &new)
+            .into_iter()
+            .map(|l| (l.kind, l.old, l.new))
+            .collect();
+        let expected = vec![
+            (DiffLineKind::Equal, Some(0), Some(0)),
+            (DiffLineKind::Delete, Some(1), None),
+            (DiffLineKind::Equal, Some(2), Some(1)),
+            (DiffLineKind::Insert, None, Some(2)),
+            (DiffLineKind::Equal, Some(3), Some(3)),
+            (DiffLineKind::Equal, Some(4), Some(4)),
+            (DiffLineKind::Insert, None, Some(5)),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_diff_lines_empty() {
+        assert!(diff_lines(&[], &[]).is_empty());
+
+        let actual: Vec<DiffLineKind> = diff_lines(&["a", "b"], &[])
+            .into_iter()
+            .map(|l| l.kind)
+            .collect();
+        assert_eq!(actual, vec![DiffLineKind::Delete, DiffLineKind::Delete]);
+
+        let actual: Vec<DiffLineKind> = diff_lines(&[], &["a"])
+            .into_iter()
+            .map(|l| l.kind)
+            .collect();
+        assert_eq!(actual, vec![DiffLineKind::Insert]);
+    }
+
+    #[test]
+    fn test_diff_lines_reconstruct() {
+        let old: Vec<String> = (0..50).map(|i| format!("line {}", i % 7)).collect();
+        let new: Vec<String> = (0..60).map(|i| format!("line {}", i % 5)).collect();
+        let old: Vec<&str> = old.iter().map(String::as_str).collect();
+        let new: Vec<&str> = new.iter().map(String::as_str).collect();
+
+        let lines = diff_lines(&old, &new);
+
+        let actual_old: Vec<&str> = lines.iter().filter_map(|l| l.old).map(|i| old[i]).collect();
+        let actual_new: Vec<&str> = lines.iter().filter_map(|l| l.new).map(|i| new[i]).collect();
+        assert_eq!(actual_old, old);
+        assert_eq!(actual_new, new);
+        for l in lines.iter().filter(|l| l.kind == DiffLineKind::Equal) {
+            assert_eq!(old[l.old.unwrap()], new[l.new.unwrap()]);
+        }
+    }
+
+    #[test]
+    fn test_side_by_side() {
+        let old = vec!["a", "b", "c", "d"];
+        let new = vec!["a", "x", "y", "d"];
+        let lines = diff_lines(&old, &new);
+
+        let actual: Vec<(Option<usize>, Option<usize>)> = side_by_side(&lines)
+            .into_iter()
+            .map(|(l, r)| (l.and_then(|l| l.old), r.and_then(|r| r.new)))
+            .collect();
+        let expected = vec![
+            (Some(0), Some(0)),
+            (Some(1), Some(1)),
+            (Some(2), Some(2)),
+            (Some(3), Some(3)),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_hunk_starts() {
+        let changes = [false, true, true, false, false, true, false, true];
+        assert_eq!(hunk_starts(changes), vec![1, 5, 7]);
+    }
+}
@@ -2,24 +2,42 @@ use std::{
     fmt::{self, Debug, Formatter},
     path::PathBuf,
     sync::Arc,
+    time::Duration,
 };
 
+use chrono::{DateTime, Local};
 use futures::{FutureExt, StreamExt};
-use ratatui::crossterm::event::KeyEvent;
-use tokio::{select, spawn, sync::mpsc};
+use ratatui::crossterm::event::{KeyEvent, MouseEvent};
+use tokio::{
+    select, spawn,
+    sync::{mpsc, oneshot},
+};
 
 use crate::{
+    app::TabId,
+    bookmark::Bookmark,
+    command::{CommandOutput, CustomCommandTarget},
+    compare::PrefixDiff,
+    diff::DiffTarget,
     error::{AppError, Result},
+    keys::UserEvent,
+    location::Location,
     object::{
-        BucketItem, DownloadObjectInfo, FileDetail, FileVersion, ObjectItem, ObjectKey, RawObject,
+        BucketItem, DownloadObjectInfo, EditObjectAttributes, FileDetail, FileVersion, ObjectItem,
+        ObjectKey, ObjectSummary, PrefixSize, PresignedUrlMethod, RawObject, SplitPreviewContent,
     },
+    pages::commander::CommanderPane,
+    sync::{SyncDirection, SyncOptions, SyncPlan},
+    transfer::Transfer,
 };
 
 #[derive(Debug)]
 pub enum AppEventType {
     Key(KeyEvent),
+    Mouse(MouseEvent),
+    KeyInputTimeout(u64),
     Resize,
-    Initialize(Option<String>),
+    Initialize(Option<Location>, bool),
     CompleteInitialize(Result<CompleteInitializeResult>),
     ReloadBuckets,
     CompleteReloadBuckets(Result<CompleteReloadBucketsResult>),
@@ -46,11 +64,82 @@ pub enum AppEventType {
     StartSaveObject(String, Arc<RawObject>),
     SaveObject(String, Arc<RawObject>),
     CompleteSaveObject(Result<CompleteSaveObjectResult>),
+    StartEditObject(ObjectKey),
+    EditObject(ObjectKey),
+    CompleteLoadEditObject(Result<CompleteLoadEditObjectResult>),
+    CompleteUploadEditedObject(Result<CompleteUploadEditedObjectResult>),
+    ReloadEditedObject(ObjectKey),
+    CompleteReloadEditedObject(Result<CompleteReloadEditedObjectResult>),
+    StartOpenObject(ObjectKey, Option<String>),
+    OpenObject(ObjectKey, Option<String>),
+    CompleteDownloadOpenObject(Result<CompleteDownloadOpenObjectResult>),
+    StartPipeObject(ObjectKey, Option<String>, String),
+    PipeObject(ObjectKey, Option<String>, String),
+    CompletePipeObject(Result<CompletePipeObjectResult>),
+    StartCustomCommand(usize, CustomCommandTarget),
+    CustomCommand(usize, CustomCommandTarget),
+    CompletePrepareCustomCommand(Result<CompletePrepareCustomCommandResult>),
+    CompleteCustomCommand(Result<CompleteCustomCommandResult>),
+    StartLoadSyncPlan(ObjectKey, SyncOptions),
+    LoadSyncPlan(ObjectKey, SyncOptions),
+    CompleteLoadSyncPlan(Result<CompleteLoadSyncPlanResult>),
+    StartExecuteSync(SyncPlan),
+    ExecuteSync(SyncPlan),
+    CompleteExecuteSync(Result<CompleteExecuteSyncResult>),
+    StartLoadPrefixDiff(ObjectKey, ObjectKey),
+    LoadPrefixDiff(ObjectKey, ObjectKey),
+    CompleteLoadPrefixDiff(Result<CompleteLoadPrefixDiffResult>),
+    CommanderLoadObjects(CommanderPane, ObjectKey, Option<String>),
+    CommanderRefresh(CommanderPane),
+    CommanderSwitchConnection(CommanderPane, String),
+    CompleteCommanderSwitchConnection(CommanderPane, Result<CompleteSwitchConnectionResult>),
+    CompleteCommanderLoadObjects(
+        CommanderPane,
+        Option<String>,
+        Result<CompleteLoadObjectsResult>,
+    ),
+    CompletePrefixTreeLoadObjects(Result<CompleteLoadObjectsResult>),
+    StartTransferObjects(Transfer),
+    TransferObjects(Transfer),
+    CompleteTransferObjects(Result<CompleteTransferObjectsResult>),
+    StartLoadTextDiff(DiffTarget, DiffTarget),
+    LoadTextDiff(DiffTarget, DiffTarget),
+    CompleteLoadTextDiff(Result<CompleteLoadTextDiffResult>),
+    GeneratePresignedUrl(
+        ObjectKey,
+        Option<String>,
+        PresignedUrlMethod,
+        Duration,
+        Option<String>,
+    ),
+    CompleteGeneratePresignedUrl(Result<CompleteGeneratePresignedUrlResult>),
+    StartLoadSplitPreview(ObjectKey, bool),
+    LoadSplitPreview(TabId, ObjectKey, bool),
+    CompleteLoadSplitPreview(TabId, ObjectKey, Result<CompleteLoadSplitPreviewResult>),
+    CalculatePrefixSize(Vec<ObjectKey>),
+    ProgressCalculatePrefixSize(TabId, ObjectKey, PrefixSize),
+    CompleteCalculatePrefixSize(TabId, ObjectKey, Result<CompleteCalculatePrefixSizeResult>),
+    StartLoadPrefixAnalytics(ObjectKey),
+    LoadPrefixAnalytics(ObjectKey),
+    CompleteLoadPrefixAnalytics(Result<CompleteLoadPrefixAnalyticsResult>),
+    GoToObjectKey(ObjectKey, Option<String>),
+    OpenObjectDetail(ObjectKey),
+    CompleteGoToObjectKey(Result<CompleteGoToObjectKeyResult>),
+    OpenBookmark(Bookmark),
+    DeleteBookmark(Bookmark),
+    GoToLocation(Location, bool),
+    LoadGoToCandidates(String),
+    CompleteLoadGoToCandidates(Result<CompleteLoadGoToCandidatesResult>),
+    StartSwitchConnection(String),
+    SwitchConnection(String),
+    CompleteSwitchConnection(Result<CompleteSwitchConnectionResult>),
     BucketListMoveDown(ObjectKey),
     BucketListRefresh,
     ObjectListMoveDown,
     ObjectListMoveUp,
     ObjectListRefresh,
+    ObjectListToggleSplitPreview,
+    OpenCommander,
     BackToBucketList,
     OpenObjectVersionsTab,
     OpenPreview(ObjectKey, FileDetail, Option<String>),
@@ -60,6 +149,8 @@ pub enum AppEventType {
     ObjectDetailOpenManagementConsole(ObjectKey),
     CloseCurrentPage,
     OpenHelp,
+    OpenConnections,
+    ExecuteUserEvent(UserEvent),
     CopyToClipboard(String, String),
     NotifyInfo(String),
     NotifySuccess(String),
@@ -99,6 +190,121 @@ impl CompleteReloadBucketsResult {
     }
 }
 
+#[derive(Debug)]
+pub struct CompleteLoadSplitPreviewResult {
+    pub content: SplitPreviewContent,
+}
+
+impl CompleteLoadSplitPreviewResult {
+    pub fn new(content: Result<SplitPreviewContent>) -> Result<CompleteLoadSplitPreviewResult> {
+        let content = content?;
+        Ok(CompleteLoadSplitPreviewResult { content })
+    }
+}
+
+#[derive(Debug)]
+pub struct CompleteCalculatePrefixSizeResult {
+    pub size: PrefixSize,
+}
+
+impl CompleteCalculatePrefixSizeResult {
+    pub fn new(size: Result<PrefixSize>) -> Result<CompleteCalculatePrefixSizeResult> {
+        let size = size?;
+        Ok(CompleteCalculatePrefixSizeResult { size })
+    }
+}
+
+#[derive(Debug)]
+pub struct CompleteLoadPrefixAnalyticsResult {
+    pub object_key: ObjectKey,
+    pub objects: Vec<ObjectSummary>,
+}
+
+impl CompleteLoadPrefixAnalyticsResult {
+    pub fn new(
+        objects: Result<Vec<ObjectSummary>>,
+        object_key: ObjectKey,
+    ) -> Result<CompleteLoadPrefixAnalyticsResult> {
+        let objects = objects?;
+        Ok(CompleteLoadPrefixAnalyticsResult {
+            object_key,
+            objects,
+        })
+    }
+}
+
+// the object items of each loaded prefix
+type LoadedObjectItems = Vec<(ObjectKey, Vec<ObjectItem>)>;
+
+#[derive(Debug)]
+pub struct CompleteGoToObjectKeyResult {
+    pub object_key: ObjectKey,
+    pub name: Option<String>,
+    pub open_detail: bool,
+    // open the page on top of the current page, instead of rebuilding the page stack
+    pub on_top: bool,
+    pub loaded: LoadedObjectItems,
+    pub detail: Option<FileDetail>,
+}
+
+impl CompleteGoToObjectKeyResult {
+    pub fn new(
+        loaded: Result<(LoadedObjectItems, Option<FileDetail>)>,
+        object_key: ObjectKey,
+        name: Option<String>,
+        open_detail: bool,
+        on_top: bool,
+    ) -> Result<CompleteGoToObjectKeyResult> {
+        let (loaded, detail) = loaded?;
+        Ok(CompleteGoToObjectKeyResult {
+            object_key,
+            name,
+            open_detail,
+            on_top,
+            loaded,
+            detail,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct CompleteLoadGoToCandidatesResult {
+    pub input: String,
+    pub object_key: ObjectKey,
+    pub items: Vec<ObjectItem>,
+}
+
+impl CompleteLoadGoToCandidatesResult {
+    pub fn new(
+        items: Result<Vec<ObjectItem>>,
+        input: String,
+        object_key: ObjectKey,
+    ) -> Result<CompleteLoadGoToCandidatesResult> {
+        let items = items?;
+        Ok(CompleteLoadGoToCandidatesResult {
+            input,
+            object_key,
+            items,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct CompleteSwitchConnectionResult {
+    pub name: String,
+    pub buckets: Vec<BucketItem>,
+}
+
+impl CompleteSwitchConnectionResult {
+    pub fn new(
+        name: String,
+        buckets: Result<Vec<BucketItem>>,
+    ) -> Result<CompleteSwitchConnectionResult> {
+        let buckets = buckets?;
+        Ok(CompleteSwitchConnectionResult { name, buckets })
+    }
+}
+
 #[derive(Debug)]
 pub struct CompleteLoadObjectsResult {
     pub items: Vec<ObjectItem>,
@@ -213,6 +419,7 @@ impl CompleteDownloadObjectsResult {
 
 #[derive(Debug)]
 pub struct CompletePreviewObjectResult {
+    pub object_key: ObjectKey,
     pub obj: RawObject,
     pub file_detail: FileDetail,
     pub file_version_id: Option<String>,
@@ -221,11 +428,13 @@ pub struct CompletePreviewObjectResult {
 impl CompletePreviewObjectResult {
     pub fn new(
         obj: Result<RawObject>,
+        object_key: ObjectKey,
         file_detail: FileDetail,
         file_version_id: Option<String>,
     ) -> Result<CompletePreviewObjectResult> {
         let obj = obj?;
         Ok(CompletePreviewObjectResult {
+            object_key,
             obj,
             file_detail,
             file_version_id,
@@ -245,6 +454,220 @@ impl CompleteSaveObjectResult {
     }
 }
 
+#[derive(Debug)]
+pub struct CompleteDownloadOpenObjectResult {
+    pub path: PathBuf,
+}
+
+impl CompleteDownloadOpenObjectResult {
+    pub fn new(result: Result<()>, path: PathBuf) -> Result<CompleteDownloadOpenObjectResult> {
+        result?;
+        Ok(CompleteDownloadOpenObjectResult { path })
+    }
+}
+
+#[derive(Debug)]
+pub struct CompletePipeObjectResult {
+    pub command: String,
+    pub output: CommandOutput,
+}
+
+impl CompletePipeObjectResult {
+    pub fn new(output: Result<CommandOutput>, command: String) -> Result<CompletePipeObjectResult> {
+        let output = output?;
+        Ok(CompletePipeObjectResult { command, output })
+    }
+}
+
+#[derive(Debug)]
+pub struct CompletePrepareCustomCommandResult {
+    pub index: usize,
+    pub command: String,
+}
+
+impl CompletePrepareCustomCommandResult {
+    pub fn new(
+        result: Result<()>,
+        index: usize,
+        command: String,
+    ) -> Result<CompletePrepareCustomCommandResult> {
+        result?;
+        Ok(CompletePrepareCustomCommandResult { index, command })
+    }
+}
+
+#[derive(Debug)]
+pub struct CompleteCustomCommandResult {
+    pub name: String,
+    pub output: CommandOutput,
+}
+
+impl CompleteCustomCommandResult {
+    pub fn new(output: Result<CommandOutput>, name: String) -> Result<CompleteCustomCommandResult> {
+        let output = output?;
+        Ok(CompleteCustomCommandResult { name, output })
+    }
+}
+
+#[derive(Debug)]
+pub struct CompleteLoadSyncPlanResult {
+    pub plan: SyncPlan,
+}
+
+impl CompleteLoadSyncPlanResult {
+    pub fn new(plan: Result<SyncPlan>) -> Result<CompleteLoadSyncPlanResult> {
+        let plan = plan?;
+        Ok(CompleteLoadSyncPlanResult { plan })
+    }
+}
+
+#[derive(Debug)]
+pub struct CompleteExecuteSyncResult {
+    pub direction: SyncDirection,
+    pub transferred: usize,
+    pub deleted: usize,
+}
+
+impl CompleteExecuteSyncResult {
+    pub fn new(
+        direction: SyncDirection,
+        transferred: usize,
+        deleted: usize,
+    ) -> Result<CompleteExecuteSyncResult> {
+        Ok(CompleteExecuteSyncResult {
+            direction,
+            transferred,
+            deleted,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct CompleteLoadPrefixDiffResult {
+    pub diff: PrefixDiff,
+}
+
+impl CompleteLoadPrefixDiffResult {
+    pub fn new(diff: Result<PrefixDiff>) -> Result<CompleteLoadPrefixDiffResult> {
+        let diff = diff?;
+        Ok(CompleteLoadPrefixDiffResult { diff })
+    }
+}
+
+#[derive(Debug)]
+pub struct CompleteTransferObjectsResult {
+    pub transfer: Transfer,
+    pub count: usize,
+}
+
+impl CompleteTransferObjectsResult {
+    pub fn new(transfer: Transfer, count: usize) -> Result<CompleteTransferObjectsResult> {
+        Ok(CompleteTransferObjectsResult { transfer, count })
+    }
+}
+
+#[derive(Debug)]
+pub struct CompleteLoadTextDiffResult {
+    pub left: DiffTarget,
+    pub right: DiffTarget,
+    pub left_object: RawObject,
+    pub right_object: RawObject,
+}
+
+impl CompleteLoadTextDiffResult {
+    pub fn new(
+        objects: Result<(RawObject, RawObject)>,
+        left: DiffTarget,
+        right: DiffTarget,
+    ) -> Result<CompleteLoadTextDiffResult> {
+        let (left_object, right_object) = objects?;
+        Ok(CompleteLoadTextDiffResult {
+            left,
+            right,
+            left_object,
+            right_object,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct CompleteLoadEditObjectResult {
+    pub object_key: ObjectKey,
+    pub path: PathBuf,
+    pub original: RawObject,
+    pub attrs: EditObjectAttributes,
+}
+
+impl CompleteLoadEditObjectResult {
+    pub fn new(
+        result: Result<(RawObject, EditObjectAttributes)>,
+        object_key: ObjectKey,
+        path: PathBuf,
+    ) -> Result<CompleteLoadEditObjectResult> {
+        let (original, attrs) = result?;
+        Ok(CompleteLoadEditObjectResult {
+            object_key,
+            path,
+            original,
+            attrs,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct CompleteUploadEditedObjectResult {
+    pub object_key: ObjectKey,
+}
+
+impl CompleteUploadEditedObjectResult {
+    pub fn new(
+        result: Result<()>,
+        object_key: ObjectKey,
+    ) -> Result<CompleteUploadEditedObjectResult> {
+        result?;
+        Ok(CompleteUploadEditedObjectResult { object_key })
+    }
+}
+
+#[derive(Debug)]
+pub struct CompleteReloadEditedObjectResult {
+    pub items: Vec<ObjectItem>,
+    pub detail: Box<FileDetail>,
+    pub object_key: ObjectKey,
+}
+
+impl CompleteReloadEditedObjectResult {
+    pub fn new(
+        items: Result<Vec<ObjectItem>>,
+        detail: Result<FileDetail>,
+        object_key: ObjectKey,
+    ) -> Result<CompleteReloadEditedObjectResult> {
+        let items = items?;
+        let detail = Box::new(detail?);
+        Ok(CompleteReloadEditedObjectResult {
+            items,
+            detail,
+            object_key,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct CompleteGeneratePresignedUrlResult {
+    pub url: String,
+    pub expires_at: DateTime<Local>,
+}
+
+impl CompleteGeneratePresignedUrlResult {
+    pub fn new(
+        url: Result<String>,
+        expires_at: DateTime<Local>,
+    ) -> Result<CompleteGeneratePresignedUrlResult> {
+        let url = url?;
+        Ok(CompleteGeneratePresignedUrlResult { url, expires_at })
+    }
+}
+
 #[derive(Clone)]
 pub struct Sender {
     tx: mpsc::UnboundedSender<AppEventType>,
@@ -268,43 +691,85 @@ impl Sender {
 
 pub struct Receiver {
     rx: mpsc::UnboundedReceiver<AppEventType>,
+    input_tx: mpsc::UnboundedSender<InputControl>,
 }
 
 impl Receiver {
-    pub fn new(rx: mpsc::UnboundedReceiver<AppEventType>) -> Self {
-        Self { rx }
+    pub fn new(
+        rx: mpsc::UnboundedReceiver<AppEventType>,
+        input_tx: mpsc::UnboundedSender<InputControl>,
+    ) -> Self {
+        Self { rx, input_tx }
     }
 
     pub async fn recv(&mut self) -> AppEventType {
         self.rx.recv().await.unwrap()
     }
+
+    // Stop reading terminal events so that an external process can use the terminal.
+    pub async fn pause_input(&self) {
+        let (done_tx, done_rx) = oneshot::channel();
+        if self.input_tx.send(InputControl::Pause(done_tx)).is_ok() {
+            let _ = done_rx.await;
+        }
+    }
+
+    pub fn resume_input(&self) {
+        let _ = self.input_tx.send(InputControl::Resume);
+    }
+}
+
+pub enum InputControl {
+    Pause(oneshot::Sender<()>),
+    Resume,
 }
 
 pub fn new() -> (Sender, Receiver) {
     let (tx, rx) = mpsc::unbounded_channel();
+    let (input_tx, mut input_rx) = mpsc::unbounded_channel();
     let tx = Sender::new(tx);
-    let rx = Receiver::new(rx);
+    let rx = Receiver::new(rx, input_tx);
 
-    let mut reader = ratatui::crossterm::event::EventStream::new();
     let event_tx = tx.clone();
     spawn(async move {
         loop {
-            let event = reader.next().fuse();
-            select! {
-                _ = event_tx.tx.closed() => {
-                    break;
-                }
-                Some(Ok(e)) = event => {
-                    match e {
-                        ratatui::crossterm::event::Event::Key(key) => {
-                            event_tx.send(AppEventType::Key(key));
-                        }
-                        ratatui::crossterm::event::Event::Resize(_, _) => {
-                            event_tx.send(AppEventType::Resize);
+            // the reader must be dropped while paused, otherwise it keeps consuming the input
+            let mut reader = ratatui::crossterm::event::EventStream::new();
+            let paused = loop {
+                let event = reader.next().fuse();
+                select! {
+                    _ = event_tx.tx.closed() => {
+                        break None;
+                    }
+                    Some(InputControl::Pause(done)) = input_rx.recv() => {
+                        break Some(done);
+                    }
+                    Some(Ok(e)) = event => {
+                        match e {
+                            ratatui::crossterm::event::Event::Key(key) => {
+                                event_tx.send(AppEventType::Key(key));
+                            }
+                            ratatui::crossterm::event::Event::Mouse(mouse) => {
+                                event_tx.send(AppEventType::Mouse(mouse));
+                            }
+                            ratatui::crossterm::event::Event::Resize(_, _) => {
+                                event_tx.send(AppEventType::Resize);
+                            }
+                            _ => {}
                         }
-                        _ => {}
                     }
                 }
+            };
+            drop(reader);
+
+            let Some(done) = paused else {
+                break;
+            };
+            let _ = done.send(());
+            while let Some(control) = input_rx.recv().await {
+                if let InputControl::Resume = control {
+                    break;
+                }
             }
         }
     });
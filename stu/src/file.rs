@@ -1,13 +1,18 @@
 use arboard::Clipboard;
 use chrono::Local;
+use serde::{de::DeserializeOwned, Serialize};
 use std::{
+    env,
     fs::{File, OpenOptions},
     io::{BufWriter, Write},
     path::Path,
+    process::Command,
 };
 
 use crate::error::{AppError, Result};
 
+const DEFAULT_EDITOR: &str = "vi";
+
 pub fn create_binary_file<P: AsRef<Path>>(path: P) -> Result<BufWriter<File>> {
     create_dirs(&path)?;
     let f = File::create(&path).map_err(|e| AppError::new("Failed to create file", e))?;
@@ -33,6 +38,39 @@ pub fn save_error_log<P: AsRef<Path>>(path: P, e: &AppError) -> Result<()> {
     .map_err(|e| AppError::new("Failed to write file", e))
 }
 
+// Returns the default value if the file does not exist.
+// `name` is used in the error messages (e.g. "bookmarks").
+pub fn load_toml<T: DeserializeOwned + Default, P: AsRef<Path>>(path: P, name: &str) -> Result<T> {
+    let path = path.as_ref();
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| AppError::new(format!("Failed to read {}", name), e))?;
+    toml::from_str(&content).map_err(|e| AppError::new(format!("Failed to parse {}", name), e))
+}
+
+// Writes to a temporary file and renames it, so that the file is not left truncated if interrupted
+pub fn save_toml<T: Serialize, P: AsRef<Path>>(path: P, value: &T, name: &str) -> Result<()> {
+    let path = path.as_ref();
+    let content = toml::to_string(value)
+        .map_err(|e| AppError::new(format!("Failed to serialize {}", name), e))?;
+    create_dirs(path)?;
+
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let write = |tmp_path: &Path| -> std::io::Result<()> {
+        let mut f = File::create(tmp_path)?;
+        f.write_all(content.as_bytes())?;
+        f.sync_all()?;
+        std::fs::rename(tmp_path, path)
+    };
+    write(Path::new(&tmp_path)).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp_path);
+        AppError::new(format!("Failed to write {}", name), e)
+    })
+}
+
 pub fn open_or_create_append_file<P: AsRef<Path>>(path: P) -> std::io::Result<File> {
     OpenOptions::new().create(true).append(true).open(path)
 }
@@ -50,3 +88,46 @@ pub fn copy_to_clipboard(value: String) -> Result<()> {
         .and_then(|mut c| c.set_text(value))
         .map_err(|e| AppError::new("Failed to copy to clipboard", e))
 }
+
+pub fn open_editor<P: AsRef<Path>>(path: P) -> Result<()> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| DEFAULT_EDITOR.into());
+    // the editor may be specified with arguments (e.g. `code --wait`)
+    let mut args = editor.split_whitespace();
+    let program = args.next().unwrap_or(DEFAULT_EDITOR);
+
+    let status = Command::new(program)
+        .args(args)
+        .arg(path.as_ref())
+        .status()
+        .map_err(|e| AppError::new(format!("Failed to launch editor: {}", program), e))?;
+    if !status.success() {
+        return Err(AppError::msg(format!("Editor exited with {}", status)));
+    }
+    Ok(())
+}
+
+// A directory under the temp dir, removed when dropped
+#[cfg(test)]
+pub struct TestDir(std::path::PathBuf);
+
+#[cfg(test)]
+impl TestDir {
+    pub fn new(name: &str) -> Self {
+        let dir = env::temp_dir().join(format!("stu-{}-test-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        Self(dir)
+    }
+
+    pub fn join(&self, path: impl AsRef<Path>) -> std::path::PathBuf {
+        self.0.join(path)
+    }
+}
+
+#[cfg(test)]
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
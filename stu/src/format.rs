@@ -4,6 +4,14 @@ pub fn format_size_byte(size_byte: usize) -> String {
     humansize::format_size_i(size_byte, humansize::BINARY)
 }
 
+pub fn format_object_count(count: usize) -> String {
+    if count == 1 {
+        "1 object".into()
+    } else {
+        format!("{} objects", count)
+    }
+}
+
 #[cfg(not(feature = "imggen"))]
 pub fn format_version(version: &str) -> &str {
     version
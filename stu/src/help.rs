@@ -3,7 +3,10 @@ use ratatui::{
     text::Span,
 };
 
-use crate::keys::{key_event_to_string, UserEvent, UserEventMapper};
+use crate::{
+    config::{Config, CustomCommandPage},
+    keys::{key_sequence_to_string, UserEvent, UserEventMapper},
+};
 
 pub struct BuildShortHelpsItem {
     event: BuildShortHelpsItemEvent,
@@ -44,7 +47,7 @@ pub fn build_short_help_spans(
             BuildShortHelpsItemEvent::Single(event) => mapper.find_first_key(event).map(|key| {
                 let spans = vec![
                     "<".into(),
-                    key_event_to_string(key, true).into(),
+                    key_sequence_to_string(&key, true).into(),
                     ">".into(),
                     ": ".into(),
                     item.description.into(),
@@ -64,7 +67,7 @@ pub fn build_short_help_spans(
                 } else {
                     let keys_str = keys
                         .into_iter()
-                        .map(|k| key_event_to_string(k, true))
+                        .map(|k| key_sequence_to_string(&k, true))
                         .collect::<Vec<String>>()
                         .join("/");
                     let spans = vec![
@@ -113,7 +116,7 @@ pub fn prune_spans_to_fit_width(
     let mut spans_with_priority_with_index: Vec<(usize, &SpansWithPriority)> =
         spans_with_priorities.iter().enumerate().collect();
 
-    spans_with_priority_with_index.sort_by(|(_, sp1), (_, sp2)| sp2.priority.cmp(&sp1.priority));
+    spans_with_priority_with_index.sort_by_key(|(_, sp)| std::cmp::Reverse(sp.priority));
 
     let mut prune: Vec<usize> = Vec::new();
     for (i, sp) in &spans_with_priority_with_index {
@@ -155,6 +158,48 @@ impl BuildHelpsItem {
     }
 }
 
+pub fn build_custom_command_helps(config: &Config, page: CustomCommandPage) -> Vec<BuildHelpsItem> {
+    config
+        .custom_commands_for(page)
+        .map(|(i, c)| BuildHelpsItem::new(UserEvent::custom_command(page, i), c.name.as_str()))
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandPaletteItem {
+    pub event: UserEvent,
+    pub description: String,
+    pub keys: String,
+}
+
+pub fn build_command_palette_items(
+    helps: Vec<BuildHelpsItem>,
+    mapper: &UserEventMapper,
+) -> Vec<CommandPaletteItem> {
+    let mut items: Vec<CommandPaletteItem> = Vec::new();
+    for item in helps {
+        if items.iter().any(|i| i.event == item.event) {
+            continue;
+        }
+        let keys = mapper.find_keys(item.event);
+        if keys.is_empty() {
+            // same as the help, actions without any keys are not listed
+            continue;
+        }
+        let keys = keys
+            .iter()
+            .map(|key| format!("<{}>", key_sequence_to_string(key, false)))
+            .collect::<Vec<String>>()
+            .join(" ");
+        items.push(CommandPaletteItem {
+            event: item.event,
+            description: item.description,
+            keys,
+        });
+    }
+    items
+}
+
 pub fn build_help_spans(
     helps: Vec<BuildHelpsItem>,
     mapper: &UserEventMapper,
@@ -172,7 +217,7 @@ pub fn build_help_spans(
                     .map(|key| {
                         vec![
                             "<".into(),
-                            key_event_to_string(key, false).fg(key_fg).bold(),
+                            key_sequence_to_string(&key, false).fg(key_fg).bold(),
                             ">".into(),
                         ]
                     })
@@ -1,15 +1,27 @@
+use std::collections::HashMap;
+
 use indexmap::IndexMap;
 use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
-use crate::config::Config;
+use crate::config::{Config, CustomCommandConfig, CustomCommandPage};
 
 const DEFAULT_KEYBINDINGS: &str = include_str!("../assets/keybindings.toml");
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum UserEvent {
     Quit,
     Help,
     DumpApp,
+    AddBookmark,
+    OpenBookmarks,
+    OpenRecentLocations,
+    OpenConnections,
+    OpenCommandPalette,
+    TogglePrefixTree,
+    NewTab,
+    CloseTab,
+    NextTab,
+    PrevTab,
     BucketListDown,
     BucketListUp,
     BucketListGoToTop,
@@ -25,6 +37,9 @@ pub enum UserEvent {
     BucketListRefresh,
     BucketListResetFilter,
     BucketListManagementConsole,
+    BucketListConnection,
+    BucketListGoTo,
+    BucketListCustomCommand(usize),
     ObjectListDown,
     ObjectListUp,
     ObjectListGoToTop,
@@ -42,6 +57,17 @@ pub enum UserEvent {
     ObjectListRefresh,
     ObjectListResetFilter,
     ObjectListManagementConsole,
+    ObjectListCalculateSize,
+    ObjectListCalculateSizeAll,
+    ObjectListAnalytics,
+    ObjectListSync,
+    ObjectListCompare,
+    ObjectListGoTo,
+    ObjectListToggleSplitPreview,
+    ObjectListCommander,
+    ObjectListMark,
+    ObjectListDiff,
+    ObjectListCustomCommand(usize),
     ObjectDetailDown,
     ObjectDetailUp,
     ObjectDetailRight,
@@ -54,6 +80,13 @@ pub enum UserEvent {
     ObjectDetailPreview,
     ObjectDetailCopyDetails,
     ObjectDetailManagementConsole,
+    ObjectDetailPresignedUrl,
+    ObjectDetailEdit,
+    ObjectDetailOpen,
+    ObjectDetailPipe,
+    ObjectDetailMarkVersion,
+    ObjectDetailDiff,
+    ObjectDetailCustomCommand(usize),
     ObjectPreviewDown,
     ObjectPreviewUp,
     ObjectPreviewRight,
@@ -68,9 +101,113 @@ pub enum UserEvent {
     ObjectPreviewEncoding,
     ObjectPreviewToggleWrap,
     ObjectPreviewToggleNumber,
+    ObjectPreviewEdit,
+    ObjectPreviewOpen,
+    ObjectPreviewPipe,
+    ObjectPreviewSearch,
+    ObjectPreviewSearchBackward,
+    ObjectPreviewSearchNext,
+    ObjectPreviewSearchPrev,
+    ObjectPreviewGoToLine,
+    PrefixAnalyticsDown,
+    PrefixAnalyticsUp,
+    PrefixAnalyticsGoToTop,
+    PrefixAnalyticsGoToBottom,
+    PrefixAnalyticsPageDown,
+    PrefixAnalyticsPageUp,
+    PrefixAnalyticsSelect,
+    PrefixAnalyticsBack,
+    CommandOutputDown,
+    CommandOutputUp,
+    CommandOutputRight,
+    CommandOutputLeft,
+    CommandOutputGoToTop,
+    CommandOutputGoToBottom,
+    CommandOutputPageDown,
+    CommandOutputPageUp,
+    CommandOutputToggleWrap,
+    CommandOutputToggleNumber,
+    CommandOutputBack,
+    SyncPlanDown,
+    SyncPlanUp,
+    SyncPlanGoToTop,
+    SyncPlanGoToBottom,
+    SyncPlanPageDown,
+    SyncPlanPageUp,
+    SyncPlanExecute,
+    SyncPlanBack,
+    PrefixDiffDown,
+    PrefixDiffUp,
+    PrefixDiffGoToTop,
+    PrefixDiffGoToBottom,
+    PrefixDiffPageDown,
+    PrefixDiffPageUp,
+    PrefixDiffToggleFilter,
+    PrefixDiffOpenLeft,
+    PrefixDiffOpenRight,
+    PrefixDiffTextDiff,
+    PrefixDiffBack,
+    TextDiffDown,
+    TextDiffUp,
+    TextDiffRight,
+    TextDiffLeft,
+    TextDiffGoToTop,
+    TextDiffGoToBottom,
+    TextDiffPageDown,
+    TextDiffPageUp,
+    TextDiffNextHunk,
+    TextDiffPrevHunk,
+    TextDiffToggleMode,
+    TextDiffBack,
+    CommanderSwitchPane,
+    CommanderCopy,
+    CommanderMove,
+    CommanderGoTo,
+    CommanderClose,
+    PrefixTreeDown,
+    PrefixTreeUp,
+    PrefixTreeGoToTop,
+    PrefixTreeGoToBottom,
+    PrefixTreePageDown,
+    PrefixTreePageUp,
+    PrefixTreeExpand,
+    PrefixTreeCollapse,
+    PrefixTreeSelect,
+    PrefixTreeFocusList,
+    BookmarksDown,
+    BookmarksUp,
+    BookmarksGoToTop,
+    BookmarksGoToBottom,
+    BookmarksPageDown,
+    BookmarksPageUp,
+    BookmarksSelect,
+    BookmarksFilter,
+    BookmarksResetFilter,
+    BookmarksDelete,
+    BookmarksBack,
+    RecentLocationsDown,
+    RecentLocationsUp,
+    RecentLocationsGoToTop,
+    RecentLocationsGoToBottom,
+    RecentLocationsPageDown,
+    RecentLocationsPageUp,
+    RecentLocationsSelect,
+    RecentLocationsFilter,
+    RecentLocationsResetFilter,
+    RecentLocationsBack,
+    HelpDown,
+    HelpUp,
     HelpClose,
+    CommandPaletteDown,
+    CommandPaletteUp,
+    CommandPaletteExecute,
+    CommandPaletteClose,
     InputDialogClose,
     InputDialogApply,
+    GoToDialogComplete,
+    GoToDialogPreview,
+    SearchDialogToggleRegex,
+    SearchDialogToggleIgnoreCase,
     SelectDialogDown,
     SelectDialogUp,
     SelectDialogRight,
@@ -79,34 +216,316 @@ pub enum UserEvent {
     SelectDialogSelect,
 }
 
+impl UserEvent {
+    // events that are repeated by a count prefix (e.g. `5j`)
+    pub fn is_movement(&self) -> bool {
+        matches!(
+            self,
+            UserEvent::BucketListDown
+                | UserEvent::BucketListUp
+                | UserEvent::BucketListPageDown
+                | UserEvent::BucketListPageUp
+                | UserEvent::ObjectListDown
+                | UserEvent::ObjectListUp
+                | UserEvent::ObjectListPageDown
+                | UserEvent::ObjectListPageUp
+                | UserEvent::ObjectDetailDown
+                | UserEvent::ObjectDetailUp
+                | UserEvent::ObjectPreviewDown
+                | UserEvent::ObjectPreviewUp
+                | UserEvent::ObjectPreviewRight
+                | UserEvent::ObjectPreviewLeft
+                | UserEvent::ObjectPreviewPageDown
+                | UserEvent::ObjectPreviewPageUp
+                | UserEvent::PrefixAnalyticsDown
+                | UserEvent::PrefixAnalyticsUp
+                | UserEvent::PrefixAnalyticsPageDown
+                | UserEvent::PrefixAnalyticsPageUp
+                | UserEvent::CommandOutputDown
+                | UserEvent::CommandOutputUp
+                | UserEvent::CommandOutputRight
+                | UserEvent::CommandOutputLeft
+                | UserEvent::CommandOutputPageDown
+                | UserEvent::CommandOutputPageUp
+                | UserEvent::SyncPlanDown
+                | UserEvent::SyncPlanUp
+                | UserEvent::SyncPlanPageDown
+                | UserEvent::SyncPlanPageUp
+                | UserEvent::PrefixDiffDown
+                | UserEvent::PrefixDiffUp
+                | UserEvent::PrefixDiffPageDown
+                | UserEvent::PrefixDiffPageUp
+                | UserEvent::TextDiffDown
+                | UserEvent::TextDiffUp
+                | UserEvent::TextDiffRight
+                | UserEvent::TextDiffLeft
+                | UserEvent::TextDiffPageDown
+                | UserEvent::TextDiffPageUp
+                | UserEvent::TextDiffNextHunk
+                | UserEvent::TextDiffPrevHunk
+                | UserEvent::HelpDown
+                | UserEvent::HelpUp
+                | UserEvent::PrefixTreeDown
+                | UserEvent::PrefixTreeUp
+                | UserEvent::PrefixTreePageDown
+                | UserEvent::PrefixTreePageUp
+                | UserEvent::BookmarksDown
+                | UserEvent::BookmarksUp
+                | UserEvent::BookmarksPageDown
+                | UserEvent::BookmarksPageUp
+                | UserEvent::RecentLocationsDown
+                | UserEvent::RecentLocationsUp
+                | UserEvent::RecentLocationsPageDown
+                | UserEvent::RecentLocationsPageUp
+        )
+    }
+
+    pub fn custom_command(page: CustomCommandPage, index: usize) -> UserEvent {
+        match page {
+            CustomCommandPage::BucketList => UserEvent::BucketListCustomCommand(index),
+            CustomCommandPage::ObjectList => UserEvent::ObjectListCustomCommand(index),
+            CustomCommandPage::ObjectDetail => UserEvent::ObjectDetailCustomCommand(index),
+        }
+    }
+}
+
+// keys pressed in order, e.g. `g g`
+pub type KeySequence = Vec<KeyEvent>;
+
+const MAX_COUNT: usize = 9999;
+
+// sections whose bindings are available on every page
+const SHARED_SECTIONS: [&str; 5] = [
+    "common",
+    "input_dialog",
+    "go_to_dialog",
+    "search_dialog",
+    "select_dialog",
+];
+
 #[derive(Debug, Default)]
 pub struct UserEventMapper {
-    map: IndexMap<KeyEvent, Vec<UserEvent>>,
+    map: IndexMap<KeySequence, Vec<UserEvent>>,
+    // the keybindings section each event is defined in
+    sections: HashMap<UserEvent, &'static str>,
+}
+
+// Keys and count typed so far that have not been resolved to events yet
+#[derive(Debug, Default)]
+pub struct KeyInputState {
+    keys: KeySequence,
+    count: Option<usize>,
+    // incremented on every update, to ignore the timeouts of the previous inputs
+    generation: u64,
+}
+
+impl KeyInputState {
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn is_pending(&self) -> bool {
+        !self.keys.is_empty() || self.count.is_some()
+    }
+
+    // the text shown in the status bar while waiting for the next key
+    pub fn pending_str(&self) -> Option<String> {
+        if !self.is_pending() {
+            return None;
+        }
+        let count = self.count.map(|c| c.to_string()).unwrap_or_default();
+        let keys = key_sequence_to_string(&self.keys, true);
+        Some(format!("{}{}", count, keys))
+    }
+
+    pub fn clear(&mut self) {
+        self.keys.clear();
+        self.count = None;
+        self.generation += 1;
+    }
+}
+
+// Events resolved from the key input
+#[derive(Debug)]
+pub struct KeyInput {
+    pub events: Vec<UserEvent>,
+    // the last key, which is passed as is to the text inputs
+    pub key_event: KeyEvent,
+    pub count: usize,
 }
 
 impl UserEventMapper {
     pub fn load(config: &Config) -> anyhow::Result<UserEventMapper> {
         let path = config.keybindings_file_path()?;
         let custom_bindings_str = std::fs::read_to_string(path).unwrap_or_default();
-        build_user_event_mapper(DEFAULT_KEYBINDINGS, &custom_bindings_str)
-            .map_err(|e| anyhow::anyhow!(e))
+        let mut mapper = build_user_event_mapper(DEFAULT_KEYBINDINGS, &custom_bindings_str)
+            .map_err(|e| anyhow::anyhow!(e))?;
+        mapper
+            .set_custom_commands(&config.custom_commands)
+            .map_err(|e| anyhow::anyhow!(e))?;
+        Ok(mapper)
     }
 
-    pub fn find_events(&self, e: KeyEvent) -> Vec<UserEvent> {
-        self.map.get(&e).cloned().unwrap_or_default()
+    fn set_custom_commands(&mut self, commands: &[CustomCommandConfig]) -> Result<(), String> {
+        for (i, command) in commands.iter().enumerate() {
+            let keys = parse_key_sequence(&command.key)
+                .map_err(|e| format!("invalid key for custom command '{}': {}", command.name, e))?;
+            let user_event = UserEvent::custom_command(command.page, i);
+            // custom commands take precedence over the default bindings of the same page
+            self.map.entry(keys).or_default().insert(0, user_event);
+            self.sections
+                .insert(user_event, custom_command_section(command.page));
+        }
+        Ok(())
     }
 
-    pub fn find_keys(&self, e: UserEvent) -> Vec<KeyEvent> {
+    pub fn find_events(&self, keys: &[KeyEvent]) -> Vec<UserEvent> {
+        self.map.get(keys).cloned().unwrap_or_default()
+    }
+
+    pub fn find_keys(&self, e: UserEvent) -> Vec<KeySequence> {
         self.map
             .iter()
-            .filter_map(|(k, v)| if v.contains(&e) { Some(*k) } else { None })
+            .filter_map(|(k, v)| {
+                if v.contains(&e) {
+                    Some(k.clone())
+                } else {
+                    None
+                }
+            })
             .collect()
     }
 
-    pub fn find_first_key(&self, e: UserEvent) -> Option<KeyEvent> {
+    pub fn find_first_key(&self, e: UserEvent) -> Option<KeySequence> {
+        self.map.iter().find_map(|(k, v)| {
+            if v.contains(&e) {
+                Some(k.clone())
+            } else {
+                None
+            }
+        })
+    }
+
+    // whether the keys are the beginning of a longer sequence bound in the sections
+    fn is_sequence_prefix(&self, keys: &[KeyEvent], sections: &[&str]) -> bool {
+        self.map.iter().any(|(k, events)| {
+            k.len() > keys.len() && k.starts_with(keys) && self.in_sections(events, sections)
+        })
+    }
+
+    fn in_sections(&self, events: &[UserEvent], sections: &[&str]) -> bool {
+        events.iter().any(|e| {
+            self.sections
+                .get(e)
+                .is_some_and(|s| SHARED_SECTIONS.contains(s) || sections.contains(s))
+        })
+    }
+
+    // Returns the inputs resolved by the key, which is empty if waiting for the next key.
+    // `sections` are the keybindings sections of the current page, only their sequences wait for the next key.
+    // If `text_input` is true, the key is resolved alone so that it can be typed into the input.
+    pub fn resolve(
+        &self,
+        state: &mut KeyInputState,
+        key_event: KeyEvent,
+        sections: &[&str],
+        text_input: bool,
+    ) -> Vec<KeyInput> {
+        if text_input {
+            state.clear();
+            return vec![KeyInput {
+                events: self.find_events(&[key_event]),
+                key_event,
+                count: 1,
+            }];
+        }
+
+        if state.is_pending() && key_event.code == KeyCode::Esc {
+            // cancel the pending input
+            state.clear();
+            return vec![KeyInput {
+                events: Vec::new(),
+                key_event,
+                count: 1,
+            }];
+        }
+
+        if state.keys.is_empty() && !self.is_bound(&[key_event], sections) {
+            if let Some(d) = count_digit(key_event) {
+                if d > 0 || state.count.is_some() {
+                    let count = state.count.unwrap_or(0) * 10 + d;
+                    state.count = Some(count.min(MAX_COUNT));
+                    state.generation += 1;
+                    return Vec::new();
+                }
+            }
+        }
+
+        state.keys.push(key_event);
+        if self.is_sequence_prefix(&state.keys, sections) {
+            state.generation += 1;
+            return Vec::new();
+        }
+
+        let events = self.find_events(&state.keys);
+        if events.is_empty() && state.keys.len() > 1 {
+            // the sequence is broken, so the keys typed so far are resolved first
+            // and then the last key is handled as a new input
+            state.keys.pop();
+            let mut inputs: Vec<KeyInput> = self.take_pending(state).into_iter().collect();
+            inputs.extend(self.resolve(state, key_event, sections, text_input));
+            return inputs;
+        }
+        let count = state.count.unwrap_or(1);
+        state.clear();
+        vec![KeyInput {
+            events,
+            key_event,
+            count,
+        }]
+    }
+
+    // Resolves the pending keys if no key is pressed until the timeout (e.g. `g` is bound with `g g`).
+    // Returns None if the state has been updated after the timeout was set.
+    pub fn resolve_timeout(&self, state: &mut KeyInputState, generation: u64) -> Option<KeyInput> {
+        if state.generation != generation || !state.is_pending() {
+            return None;
+        }
+        self.take_pending(state)
+    }
+
+    // Resolves the pending keys as they are and clears the state
+    fn take_pending(&self, state: &mut KeyInputState) -> Option<KeyInput> {
+        let input = state.keys.last().map(|key_event| KeyInput {
+            events: self.find_events(&state.keys),
+            key_event: *key_event,
+            count: state.count.unwrap_or(1),
+        });
+        state.clear();
+        input
+    }
+
+    fn is_bound(&self, keys: &[KeyEvent], sections: &[&str]) -> bool {
         self.map
-            .iter()
-            .find_map(|(k, v)| if v.contains(&e) { Some(*k) } else { None })
+            .get(keys)
+            .is_some_and(|events| self.in_sections(events, sections))
+            || self.is_sequence_prefix(keys, sections)
+    }
+}
+
+// custom commands are scoped to the page they run on, like its own bindings
+fn custom_command_section(page: CustomCommandPage) -> &'static str {
+    match page {
+        CustomCommandPage::BucketList => "bucket_list",
+        CustomCommandPage::ObjectList => "object_list",
+        CustomCommandPage::ObjectDetail => "object_detail",
+    }
+}
+
+fn count_digit(key_event: KeyEvent) -> Option<usize> {
+    match key_event.code {
+        KeyCode::Char(c) if key_event.modifiers.is_empty() => c.to_digit(10).map(|d| d as usize),
+        _ => None,
     }
 }
 
@@ -116,11 +535,21 @@ fn build_user_event_mapper(
     custom_bindings_str: &str
 ) -> Result<UserEventMapper, String> {
     let bindings = deserialize_and_merge_bindings(default_bindings_str, custom_bindings_str)?;
-    let mut map = IndexMap::new();
+    let mut map = UserEventMapper::default();
 
     set_event_to_map(&mut map, &bindings, "common", "quit", UserEvent::Quit)?;
     set_event_to_map(&mut map, &bindings, "common", "help", UserEvent::Help)?;
     set_event_to_map(&mut map, &bindings, "common", "dump", UserEvent::DumpApp)?;
+    set_event_to_map(&mut map, &bindings, "common", "add_bookmark", UserEvent::AddBookmark)?;
+    set_event_to_map(&mut map, &bindings, "common", "bookmarks", UserEvent::OpenBookmarks)?;
+    set_event_to_map(&mut map, &bindings, "common", "recent_locations", UserEvent::OpenRecentLocations)?;
+    set_event_to_map(&mut map, &bindings, "common", "connection", UserEvent::OpenConnections)?;
+    set_event_to_map(&mut map, &bindings, "common", "command_palette", UserEvent::OpenCommandPalette)?;
+    set_event_to_map(&mut map, &bindings, "common", "prefix_tree", UserEvent::TogglePrefixTree)?;
+    set_event_to_map(&mut map, &bindings, "common", "new_tab", UserEvent::NewTab)?;
+    set_event_to_map(&mut map, &bindings, "common", "close_tab", UserEvent::CloseTab)?;
+    set_event_to_map(&mut map, &bindings, "common", "next_tab", UserEvent::NextTab)?;
+    set_event_to_map(&mut map, &bindings, "common", "prev_tab", UserEvent::PrevTab)?;
 
     set_event_to_map(&mut map, &bindings, "bucket_list", "down", UserEvent::BucketListDown)?;
     set_event_to_map(&mut map, &bindings, "bucket_list", "up", UserEvent::BucketListUp)?;
@@ -137,6 +566,8 @@ fn build_user_event_mapper(
     set_event_to_map(&mut map, &bindings, "bucket_list", "refresh", UserEvent::BucketListRefresh)?;
     set_event_to_map(&mut map, &bindings, "bucket_list", "reset_filter", UserEvent::BucketListResetFilter)?;
     set_event_to_map(&mut map, &bindings, "bucket_list", "management_console", UserEvent::BucketListManagementConsole)?;
+    set_event_to_map(&mut map, &bindings, "bucket_list", "connection", UserEvent::BucketListConnection)?;
+    set_event_to_map(&mut map, &bindings, "bucket_list", "go_to", UserEvent::BucketListGoTo)?;
 
     set_event_to_map(&mut map, &bindings, "object_list", "down", UserEvent::ObjectListDown)?;
     set_event_to_map(&mut map, &bindings, "object_list", "up", UserEvent::ObjectListUp)?;
@@ -155,6 +586,16 @@ fn build_user_event_mapper(
     set_event_to_map(&mut map, &bindings, "object_list", "refresh", UserEvent::ObjectListRefresh)?;
     set_event_to_map(&mut map, &bindings, "object_list", "reset_filter", UserEvent::ObjectListResetFilter)?;
     set_event_to_map(&mut map, &bindings, "object_list", "management_console", UserEvent::ObjectListManagementConsole)?;
+    set_event_to_map(&mut map, &bindings, "object_list", "calculate_size", UserEvent::ObjectListCalculateSize)?;
+    set_event_to_map(&mut map, &bindings, "object_list", "calculate_size_all", UserEvent::ObjectListCalculateSizeAll)?;
+    set_event_to_map(&mut map, &bindings, "object_list", "analytics", UserEvent::ObjectListAnalytics)?;
+    set_event_to_map(&mut map, &bindings, "object_list", "sync", UserEvent::ObjectListSync)?;
+    set_event_to_map(&mut map, &bindings, "object_list", "compare", UserEvent::ObjectListCompare)?;
+    set_event_to_map(&mut map, &bindings, "object_list", "go_to", UserEvent::ObjectListGoTo)?;
+    set_event_to_map(&mut map, &bindings, "object_list", "toggle_split_preview", UserEvent::ObjectListToggleSplitPreview)?;
+    set_event_to_map(&mut map, &bindings, "object_list", "commander", UserEvent::ObjectListCommander)?;
+    set_event_to_map(&mut map, &bindings, "object_list", "mark", UserEvent::ObjectListMark)?;
+    set_event_to_map(&mut map, &bindings, "object_list", "diff", UserEvent::ObjectListDiff)?;
     
     set_event_to_map(&mut map, &bindings, "object_detail", "down", UserEvent::ObjectDetailDown)?;
     set_event_to_map(&mut map, &bindings, "object_detail", "up", UserEvent::ObjectDetailUp)?;
@@ -168,6 +609,12 @@ fn build_user_event_mapper(
     set_event_to_map(&mut map, &bindings, "object_detail", "preview", UserEvent::ObjectDetailPreview)?;
     set_event_to_map(&mut map, &bindings, "object_detail", "copy_details", UserEvent::ObjectDetailCopyDetails)?;
     set_event_to_map(&mut map, &bindings, "object_detail", "management_console", UserEvent::ObjectDetailManagementConsole)?;
+    set_event_to_map(&mut map, &bindings, "object_detail", "presigned_url", UserEvent::ObjectDetailPresignedUrl)?;
+    set_event_to_map(&mut map, &bindings, "object_detail", "edit", UserEvent::ObjectDetailEdit)?;
+    set_event_to_map(&mut map, &bindings, "object_detail", "open", UserEvent::ObjectDetailOpen)?;
+    set_event_to_map(&mut map, &bindings, "object_detail", "pipe", UserEvent::ObjectDetailPipe)?;
+    set_event_to_map(&mut map, &bindings, "object_detail", "mark_version", UserEvent::ObjectDetailMarkVersion)?;
+    set_event_to_map(&mut map, &bindings, "object_detail", "diff", UserEvent::ObjectDetailDiff)?;
 
     set_event_to_map(&mut map, &bindings, "object_preview", "down", UserEvent::ObjectPreviewDown)?;
     set_event_to_map(&mut map, &bindings, "object_preview", "up", UserEvent::ObjectPreviewUp)?;
@@ -183,12 +630,127 @@ fn build_user_event_mapper(
     set_event_to_map(&mut map, &bindings, "object_preview", "encoding", UserEvent::ObjectPreviewEncoding)?;
     set_event_to_map(&mut map, &bindings, "object_preview", "toggle_wrap", UserEvent::ObjectPreviewToggleWrap)?;
     set_event_to_map(&mut map, &bindings, "object_preview", "toggle_number", UserEvent::ObjectPreviewToggleNumber)?;
+    set_event_to_map(&mut map, &bindings, "object_preview", "edit", UserEvent::ObjectPreviewEdit)?;
+    set_event_to_map(&mut map, &bindings, "object_preview", "open", UserEvent::ObjectPreviewOpen)?;
+    set_event_to_map(&mut map, &bindings, "object_preview", "pipe", UserEvent::ObjectPreviewPipe)?;
+    set_event_to_map(&mut map, &bindings, "object_preview", "search", UserEvent::ObjectPreviewSearch)?;
+    set_event_to_map(&mut map, &bindings, "object_preview", "search_backward", UserEvent::ObjectPreviewSearchBackward)?;
+    set_event_to_map(&mut map, &bindings, "object_preview", "search_next", UserEvent::ObjectPreviewSearchNext)?;
+    set_event_to_map(&mut map, &bindings, "object_preview", "search_prev", UserEvent::ObjectPreviewSearchPrev)?;
+    set_event_to_map(&mut map, &bindings, "object_preview", "go_to_line", UserEvent::ObjectPreviewGoToLine)?;
+
+    set_event_to_map(&mut map, &bindings, "prefix_analytics", "down", UserEvent::PrefixAnalyticsDown)?;
+    set_event_to_map(&mut map, &bindings, "prefix_analytics", "up", UserEvent::PrefixAnalyticsUp)?;
+    set_event_to_map(&mut map, &bindings, "prefix_analytics", "go_to_top", UserEvent::PrefixAnalyticsGoToTop)?;
+    set_event_to_map(&mut map, &bindings, "prefix_analytics", "go_to_bottom", UserEvent::PrefixAnalyticsGoToBottom)?;
+    set_event_to_map(&mut map, &bindings, "prefix_analytics", "page_down", UserEvent::PrefixAnalyticsPageDown)?;
+    set_event_to_map(&mut map, &bindings, "prefix_analytics", "page_up", UserEvent::PrefixAnalyticsPageUp)?;
+    set_event_to_map(&mut map, &bindings, "prefix_analytics", "select", UserEvent::PrefixAnalyticsSelect)?;
+    set_event_to_map(&mut map, &bindings, "prefix_analytics", "back", UserEvent::PrefixAnalyticsBack)?;
+
+    set_event_to_map(&mut map, &bindings, "command_output", "down", UserEvent::CommandOutputDown)?;
+    set_event_to_map(&mut map, &bindings, "command_output", "up", UserEvent::CommandOutputUp)?;
+    set_event_to_map(&mut map, &bindings, "command_output", "right", UserEvent::CommandOutputRight)?;
+    set_event_to_map(&mut map, &bindings, "command_output", "left", UserEvent::CommandOutputLeft)?;
+    set_event_to_map(&mut map, &bindings, "command_output", "go_to_top", UserEvent::CommandOutputGoToTop)?;
+    set_event_to_map(&mut map, &bindings, "command_output", "go_to_bottom", UserEvent::CommandOutputGoToBottom)?;
+    set_event_to_map(&mut map, &bindings, "command_output", "page_down", UserEvent::CommandOutputPageDown)?;
+    set_event_to_map(&mut map, &bindings, "command_output", "page_up", UserEvent::CommandOutputPageUp)?;
+    set_event_to_map(&mut map, &bindings, "command_output", "toggle_wrap", UserEvent::CommandOutputToggleWrap)?;
+    set_event_to_map(&mut map, &bindings, "command_output", "toggle_number", UserEvent::CommandOutputToggleNumber)?;
+    set_event_to_map(&mut map, &bindings, "command_output", "back", UserEvent::CommandOutputBack)?;
+
+    set_event_to_map(&mut map, &bindings, "sync_plan", "down", UserEvent::SyncPlanDown)?;
+    set_event_to_map(&mut map, &bindings, "sync_plan", "up", UserEvent::SyncPlanUp)?;
+    set_event_to_map(&mut map, &bindings, "sync_plan", "go_to_top", UserEvent::SyncPlanGoToTop)?;
+    set_event_to_map(&mut map, &bindings, "sync_plan", "go_to_bottom", UserEvent::SyncPlanGoToBottom)?;
+    set_event_to_map(&mut map, &bindings, "sync_plan", "page_down", UserEvent::SyncPlanPageDown)?;
+    set_event_to_map(&mut map, &bindings, "sync_plan", "page_up", UserEvent::SyncPlanPageUp)?;
+    set_event_to_map(&mut map, &bindings, "sync_plan", "execute", UserEvent::SyncPlanExecute)?;
+    set_event_to_map(&mut map, &bindings, "sync_plan", "back", UserEvent::SyncPlanBack)?;
+
+    set_event_to_map(&mut map, &bindings, "prefix_diff", "down", UserEvent::PrefixDiffDown)?;
+    set_event_to_map(&mut map, &bindings, "prefix_diff", "up", UserEvent::PrefixDiffUp)?;
+    set_event_to_map(&mut map, &bindings, "prefix_diff", "go_to_top", UserEvent::PrefixDiffGoToTop)?;
+    set_event_to_map(&mut map, &bindings, "prefix_diff", "go_to_bottom", UserEvent::PrefixDiffGoToBottom)?;
+    set_event_to_map(&mut map, &bindings, "prefix_diff", "page_down", UserEvent::PrefixDiffPageDown)?;
+    set_event_to_map(&mut map, &bindings, "prefix_diff", "page_up", UserEvent::PrefixDiffPageUp)?;
+    set_event_to_map(&mut map, &bindings, "prefix_diff", "toggle_filter", UserEvent::PrefixDiffToggleFilter)?;
+    set_event_to_map(&mut map, &bindings, "prefix_diff", "open_left", UserEvent::PrefixDiffOpenLeft)?;
+    set_event_to_map(&mut map, &bindings, "prefix_diff", "open_right", UserEvent::PrefixDiffOpenRight)?;
+    set_event_to_map(&mut map, &bindings, "prefix_diff", "text_diff", UserEvent::PrefixDiffTextDiff)?;
+    set_event_to_map(&mut map, &bindings, "prefix_diff", "back", UserEvent::PrefixDiffBack)?;
 
+    set_event_to_map(&mut map, &bindings, "text_diff", "down", UserEvent::TextDiffDown)?;
+    set_event_to_map(&mut map, &bindings, "text_diff", "up", UserEvent::TextDiffUp)?;
+    set_event_to_map(&mut map, &bindings, "text_diff", "right", UserEvent::TextDiffRight)?;
+    set_event_to_map(&mut map, &bindings, "text_diff", "left", UserEvent::TextDiffLeft)?;
+    set_event_to_map(&mut map, &bindings, "text_diff", "go_to_top", UserEvent::TextDiffGoToTop)?;
+    set_event_to_map(&mut map, &bindings, "text_diff", "go_to_bottom", UserEvent::TextDiffGoToBottom)?;
+    set_event_to_map(&mut map, &bindings, "text_diff", "page_down", UserEvent::TextDiffPageDown)?;
+    set_event_to_map(&mut map, &bindings, "text_diff", "page_up", UserEvent::TextDiffPageUp)?;
+    set_event_to_map(&mut map, &bindings, "text_diff", "next_hunk", UserEvent::TextDiffNextHunk)?;
+    set_event_to_map(&mut map, &bindings, "text_diff", "prev_hunk", UserEvent::TextDiffPrevHunk)?;
+    set_event_to_map(&mut map, &bindings, "text_diff", "toggle_mode", UserEvent::TextDiffToggleMode)?;
+    set_event_to_map(&mut map, &bindings, "text_diff", "back", UserEvent::TextDiffBack)?;
+
+    set_event_to_map(&mut map, &bindings, "commander", "switch_pane", UserEvent::CommanderSwitchPane)?;
+    set_event_to_map(&mut map, &bindings, "commander", "copy", UserEvent::CommanderCopy)?;
+    set_event_to_map(&mut map, &bindings, "commander", "move", UserEvent::CommanderMove)?;
+    set_event_to_map(&mut map, &bindings, "commander", "go_to", UserEvent::CommanderGoTo)?;
+    set_event_to_map(&mut map, &bindings, "commander", "close", UserEvent::CommanderClose)?;
+
+    set_event_to_map(&mut map, &bindings, "prefix_tree", "down", UserEvent::PrefixTreeDown)?;
+    set_event_to_map(&mut map, &bindings, "prefix_tree", "up", UserEvent::PrefixTreeUp)?;
+    set_event_to_map(&mut map, &bindings, "prefix_tree", "go_to_top", UserEvent::PrefixTreeGoToTop)?;
+    set_event_to_map(&mut map, &bindings, "prefix_tree", "go_to_bottom", UserEvent::PrefixTreeGoToBottom)?;
+    set_event_to_map(&mut map, &bindings, "prefix_tree", "page_down", UserEvent::PrefixTreePageDown)?;
+    set_event_to_map(&mut map, &bindings, "prefix_tree", "page_up", UserEvent::PrefixTreePageUp)?;
+    set_event_to_map(&mut map, &bindings, "prefix_tree", "expand", UserEvent::PrefixTreeExpand)?;
+    set_event_to_map(&mut map, &bindings, "prefix_tree", "collapse", UserEvent::PrefixTreeCollapse)?;
+    set_event_to_map(&mut map, &bindings, "prefix_tree", "select", UserEvent::PrefixTreeSelect)?;
+    set_event_to_map(&mut map, &bindings, "prefix_tree", "focus_list", UserEvent::PrefixTreeFocusList)?;
+
+    set_event_to_map(&mut map, &bindings, "bookmarks", "down", UserEvent::BookmarksDown)?;
+    set_event_to_map(&mut map, &bindings, "bookmarks", "up", UserEvent::BookmarksUp)?;
+    set_event_to_map(&mut map, &bindings, "bookmarks", "go_to_top", UserEvent::BookmarksGoToTop)?;
+    set_event_to_map(&mut map, &bindings, "bookmarks", "go_to_bottom", UserEvent::BookmarksGoToBottom)?;
+    set_event_to_map(&mut map, &bindings, "bookmarks", "page_down", UserEvent::BookmarksPageDown)?;
+    set_event_to_map(&mut map, &bindings, "bookmarks", "page_up", UserEvent::BookmarksPageUp)?;
+    set_event_to_map(&mut map, &bindings, "bookmarks", "select", UserEvent::BookmarksSelect)?;
+    set_event_to_map(&mut map, &bindings, "bookmarks", "filter", UserEvent::BookmarksFilter)?;
+    set_event_to_map(&mut map, &bindings, "bookmarks", "reset_filter", UserEvent::BookmarksResetFilter)?;
+    set_event_to_map(&mut map, &bindings, "bookmarks", "delete", UserEvent::BookmarksDelete)?;
+    set_event_to_map(&mut map, &bindings, "bookmarks", "back", UserEvent::BookmarksBack)?;
+
+    set_event_to_map(&mut map, &bindings, "recent_locations", "down", UserEvent::RecentLocationsDown)?;
+    set_event_to_map(&mut map, &bindings, "recent_locations", "up", UserEvent::RecentLocationsUp)?;
+    set_event_to_map(&mut map, &bindings, "recent_locations", "go_to_top", UserEvent::RecentLocationsGoToTop)?;
+    set_event_to_map(&mut map, &bindings, "recent_locations", "go_to_bottom", UserEvent::RecentLocationsGoToBottom)?;
+    set_event_to_map(&mut map, &bindings, "recent_locations", "page_down", UserEvent::RecentLocationsPageDown)?;
+    set_event_to_map(&mut map, &bindings, "recent_locations", "page_up", UserEvent::RecentLocationsPageUp)?;
+    set_event_to_map(&mut map, &bindings, "recent_locations", "select", UserEvent::RecentLocationsSelect)?;
+    set_event_to_map(&mut map, &bindings, "recent_locations", "filter", UserEvent::RecentLocationsFilter)?;
+    set_event_to_map(&mut map, &bindings, "recent_locations", "reset_filter", UserEvent::RecentLocationsResetFilter)?;
+    set_event_to_map(&mut map, &bindings, "recent_locations", "back", UserEvent::RecentLocationsBack)?;
+
+    set_event_to_map(&mut map, &bindings, "help", "down", UserEvent::HelpDown)?;
+    set_event_to_map(&mut map, &bindings, "help", "up", UserEvent::HelpUp)?;
     set_event_to_map(&mut map, &bindings, "help", "close", UserEvent::HelpClose)?;
 
+    set_event_to_map(&mut map, &bindings, "command_palette", "down", UserEvent::CommandPaletteDown)?;
+    set_event_to_map(&mut map, &bindings, "command_palette", "up", UserEvent::CommandPaletteUp)?;
+    set_event_to_map(&mut map, &bindings, "command_palette", "execute", UserEvent::CommandPaletteExecute)?;
+    set_event_to_map(&mut map, &bindings, "command_palette", "close", UserEvent::CommandPaletteClose)?;
+
     set_event_to_map(&mut map, &bindings, "input_dialog", "close", UserEvent::InputDialogClose)?;
     set_event_to_map(&mut map, &bindings, "input_dialog", "apply", UserEvent::InputDialogApply)?;
 
+    set_event_to_map(&mut map, &bindings, "go_to_dialog", "complete", UserEvent::GoToDialogComplete)?;
+    set_event_to_map(&mut map, &bindings, "go_to_dialog", "preview", UserEvent::GoToDialogPreview)?;
+    set_event_to_map(&mut map, &bindings, "search_dialog", "toggle_regex", UserEvent::SearchDialogToggleRegex)?;
+    set_event_to_map(&mut map, &bindings, "search_dialog", "toggle_ignore_case", UserEvent::SearchDialogToggleIgnoreCase)?;
+
     set_event_to_map(&mut map, &bindings, "select_dialog", "down", UserEvent::SelectDialogDown)?;
     set_event_to_map(&mut map, &bindings, "select_dialog", "up", UserEvent::SelectDialogUp)?;
     set_event_to_map(&mut map, &bindings, "select_dialog", "right", UserEvent::SelectDialogRight)?;
@@ -196,13 +758,13 @@ fn build_user_event_mapper(
     set_event_to_map(&mut map, &bindings, "select_dialog", "close", UserEvent::SelectDialogClose)?;
     set_event_to_map(&mut map, &bindings, "select_dialog", "select", UserEvent::SelectDialogSelect)?;
 
-    Ok(UserEventMapper { map })
+    Ok(map)
 }
 
 fn set_event_to_map(
-    map: &mut IndexMap<KeyEvent, Vec<UserEvent>>,
+    mapper: &mut UserEventMapper,
     bindings: &KeyMap,
-    section: &str,
+    section: &'static str,
     event: &str,
     user_event: UserEvent,
 ) -> Result<(), String> {
@@ -212,9 +774,10 @@ fn set_event_to_map(
         .ok_or_else(|| {
             format!("No keybindings found for section '{section}' and event '{event}'")
         })?;
-    for key_event in parse_key_events(keys)? {
-        map.entry(key_event).or_default().push(user_event);
+    for key_sequence in parse_key_sequences(keys)? {
+        mapper.map.entry(key_sequence).or_default().push(user_event);
     }
+    mapper.sections.insert(user_event, section);
     Ok(())
 }
 
@@ -242,12 +805,24 @@ fn deserialize_and_merge_bindings(
     Ok(bindings)
 }
 
-fn parse_key_events(raws: &[String]) -> Result<Vec<KeyEvent>, String> {
+fn parse_key_sequences(raws: &[String]) -> Result<Vec<KeySequence>, String> {
     raws.iter()
-        .map(|raw| parse_key_event(raw))
+        .map(|raw| parse_key_sequence(raw))
         .collect::<Result<Vec<_>, String>>()
 }
 
+// keys of a sequence are separated by spaces (e.g. "g g", "ctrl-w j")
+fn parse_key_sequence(raw: &str) -> Result<KeySequence, String> {
+    let keys = raw
+        .split_whitespace()
+        .map(parse_key_event)
+        .collect::<Result<Vec<_>, String>>()?;
+    if keys.is_empty() {
+        return Err(format!("Unable to parse {raw}"));
+    }
+    Ok(keys)
+}
+
 fn parse_key_event(raw: &str) -> Result<KeyEvent, String> {
     let raw_lower = raw.to_ascii_lowercase().replace(' ', "");
     let (remaining, modifiers) = extract_modifiers(&raw_lower);
@@ -327,6 +902,13 @@ fn parse_key_code_with_modifiers(
     Ok(KeyEvent::new(c, modifiers))
 }
 
+pub fn key_sequence_to_string(keys: &[KeyEvent], short: bool) -> String {
+    keys.iter()
+        .map(|key| key_event_to_string(*key, short))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 pub fn key_event_to_string(key: KeyEvent, short: bool) -> String {
     if let KeyCode::Char(c) = key.code {
         if key.modifiers == KeyModifiers::SHIFT {
@@ -425,3 +1007,162 @@ pub fn key_event_to_string(key: KeyEvent, short: bool) -> String {
 
     key
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty())
+    }
+
+    fn mapper(custom_bindings_str: &str) -> UserEventMapper {
+        build_user_event_mapper(DEFAULT_KEYBINDINGS, custom_bindings_str).unwrap()
+    }
+
+    #[test]
+    fn test_parse_key_sequence() {
+        assert_eq!(parse_key_sequence("g g"), Ok(vec![key('g'), key('g')]));
+        assert_eq!(
+            parse_key_sequence(" ctrl-w  j "),
+            Ok(vec![
+                KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL),
+                key('j')
+            ])
+        );
+        assert!(parse_key_sequence("").is_err());
+        assert!(parse_key_sequence("g foo").is_err());
+    }
+
+    #[test]
+    fn test_resolve_key_sequence() {
+        let mapper = mapper(
+            r#"
+            [object_list]
+            go_to_top = ["g g"]
+            "#,
+        );
+        let mut state = KeyInputState::default();
+        let sections = &["object_list"];
+
+        assert!(mapper
+            .resolve(&mut state, key('g'), sections, false)
+            .is_empty());
+        assert_eq!(state.pending_str(), Some("g".into()));
+        let inputs = mapper.resolve(&mut state, key('g'), sections, false);
+        assert_eq!(inputs.len(), 1);
+        assert!(inputs[0].events.contains(&UserEvent::ObjectListGoToTop));
+        assert_eq!(state.pending_str(), None);
+
+        // a broken sequence resolves the first key, then the last key
+        assert!(mapper
+            .resolve(&mut state, key('g'), sections, false)
+            .is_empty());
+        let inputs = mapper.resolve(&mut state, key('j'), sections, false);
+        assert_eq!(inputs.len(), 2);
+        assert_eq!(inputs[0].key_event, key('g'));
+        assert!(!inputs[0].events.contains(&UserEvent::ObjectListGoToTop));
+        assert!(inputs[1].events.contains(&UserEvent::ObjectListDown));
+        assert_eq!(state.pending_str(), None);
+
+        // resolved on timeout
+        assert!(mapper
+            .resolve(&mut state, key('g'), sections, false)
+            .is_empty());
+        let generation = state.generation();
+        let input = mapper.resolve_timeout(&mut state, generation).unwrap();
+        assert_eq!(input.key_event, key('g'));
+        assert!(mapper.resolve_timeout(&mut state, generation).is_none());
+
+        // the sequence of the other page does not delay `g` in the bucket list
+        let inputs = mapper.resolve(&mut state, key('g'), &["bucket_list"], false);
+        assert_eq!(inputs.len(), 1);
+        assert!(inputs[0].events.contains(&UserEvent::BucketListGoToTop));
+        assert_eq!(state.pending_str(), None);
+
+        // typed into the text input as is
+        let inputs = mapper.resolve(&mut state, key('g'), sections, true);
+        assert_eq!(inputs[0].key_event, key('g'));
+    }
+
+    #[test]
+    fn test_resolve_broken_key_sequence() {
+        let mapper = mapper(
+            r#"
+            [bucket_list]
+            go_to_top = ["g", "g g"]
+            go_to_bottom = ["z z"]
+            "#,
+        );
+        let mut state = KeyInputState::default();
+        let sections = &["bucket_list"];
+
+        // the first key keeps its own binding and the count
+        assert!(mapper
+            .resolve(&mut state, key('3'), sections, false)
+            .is_empty());
+        assert!(mapper
+            .resolve(&mut state, key('g'), sections, false)
+            .is_empty());
+        let inputs = mapper.resolve(&mut state, key('j'), sections, false);
+        assert_eq!(inputs.len(), 2);
+        assert!(inputs[0].events.contains(&UserEvent::BucketListGoToTop));
+        assert_eq!(inputs[0].count, 3);
+        assert!(inputs[1].events.contains(&UserEvent::BucketListDown));
+        assert_eq!(inputs[1].count, 1);
+
+        // the last key can start a new sequence
+        assert!(mapper
+            .resolve(&mut state, key('g'), sections, false)
+            .is_empty());
+        let inputs = mapper.resolve(&mut state, key('z'), sections, false);
+        assert_eq!(inputs.len(), 1);
+        assert!(inputs[0].events.contains(&UserEvent::BucketListGoToTop));
+        assert_eq!(state.pending_str(), Some("z".into()));
+        let inputs = mapper.resolve(&mut state, key('z'), sections, false);
+        assert!(inputs[0].events.contains(&UserEvent::BucketListGoToBottom));
+    }
+
+    #[test]
+    fn test_resolve_count() {
+        let mapper = mapper("");
+        let mut state = KeyInputState::default();
+        let sections = &["object_list"];
+
+        assert!(mapper
+            .resolve(&mut state, key('1'), sections, false)
+            .is_empty());
+        assert!(mapper
+            .resolve(&mut state, key('0'), sections, false)
+            .is_empty());
+        assert_eq!(state.pending_str(), Some("10".into()));
+        let input = mapper
+            .resolve(&mut state, key('j'), sections, false)
+            .remove(0);
+        assert!(input.events.contains(&UserEvent::ObjectListDown));
+        assert_eq!(input.count, 10);
+
+        // `0` does not start a count
+        let input = mapper
+            .resolve(&mut state, key('0'), sections, false)
+            .remove(0);
+        assert!(input.events.is_empty());
+        assert_eq!(input.count, 1);
+
+        // cancel with esc
+        assert!(mapper
+            .resolve(&mut state, key('5'), sections, false)
+            .is_empty());
+        let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::empty());
+        let input = mapper.resolve(&mut state, esc, sections, false).remove(0);
+        assert!(input.events.is_empty());
+        assert_eq!(state.pending_str(), None);
+
+        // digits are typed into the text input
+        let input = mapper
+            .resolve(&mut state, key('5'), sections, true)
+            .remove(0);
+        assert_eq!(input.key_event, key('5'));
+        assert_eq!(input.count, 1);
+    }
+}
@@ -0,0 +1,373 @@
+use crate::object::{ObjectItem, ObjectKey};
+
+const S3_URI_SCHEME: &str = "s3://";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub object_key: ObjectKey,
+    pub is_dir: bool,
+}
+
+impl Location {
+    pub fn bucket(bucket_name: &str) -> Location {
+        Location {
+            object_key: ObjectKey::bucket(bucket_name),
+            is_dir: true,
+        }
+    }
+
+    pub fn prefix(bucket_name: &str, prefix: &str) -> Location {
+        Location {
+            object_key: ObjectKey {
+                bucket_name: bucket_name.into(),
+                object_path: split_path(prefix),
+            },
+            is_dir: true,
+        }
+    }
+
+    pub fn object(bucket_name: &str, key: &str) -> Option<Location> {
+        if key.is_empty() || key.ends_with('/') {
+            return None;
+        }
+        let location = Location {
+            object_key: ObjectKey {
+                bucket_name: bucket_name.into(),
+                object_path: split_path(key),
+            },
+            is_dir: false,
+        };
+        Some(location)
+    }
+}
+
+fn split_path(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+// accepts the following formats:
+// - s3://bucket/prefix/, s3://bucket/key
+// - bucket/prefix/, bucket/key
+// - https://bucket.s3.region.amazonaws.com/key (virtual-hosted style object url)
+// - https://s3.region.amazonaws.com/bucket/key (path style object url)
+// - arn:aws:s3:::bucket/key
+pub fn parse_location(input: &str) -> Option<Location> {
+    let input = input.trim();
+    let path = if let Some(rest) = strip_arn_prefix(input) {
+        rest.to_string()
+    } else if let Some(rest) = input
+        .strip_prefix("https://")
+        .or_else(|| input.strip_prefix("http://"))
+    {
+        path_from_url(rest)?
+    } else {
+        input
+            .strip_prefix(S3_URI_SCHEME)
+            .unwrap_or(input)
+            .to_string()
+    };
+
+    let (bucket_name, key) = path.split_once('/').unwrap_or((&path, ""));
+    if bucket_name.is_empty() {
+        return None;
+    }
+    let object_key = ObjectKey {
+        bucket_name: bucket_name.into(),
+        object_path: split_path(key),
+    };
+    let is_dir = key.is_empty() || key.ends_with('/');
+    Some(Location { object_key, is_dir })
+}
+
+fn strip_arn_prefix(input: &str) -> Option<&str> {
+    // arn:<partition>:s3:::<bucket>/<key>
+    let rest = input.strip_prefix("arn:")?;
+    let (_partition, rest) = rest.split_once(':')?;
+    rest.strip_prefix("s3:::")
+}
+
+fn path_from_url(url: &str) -> Option<String> {
+    // the query (e.g. versionId of the object url) and the fragment are not part of the key
+    let url = url.split(['?', '#']).next().unwrap_or_default();
+    let (host, path) = url.split_once('/').unwrap_or((url, ""));
+    let host = host.split(':').next().unwrap_or_default();
+    let path = percent_decode(path)?;
+    // bucket.s3.region.amazonaws.com, bucket.s3.amazonaws.com, bucket.s3-region.amazonaws.com
+    match host.find(".s3.").or_else(|| host.find(".s3-")) {
+        Some(i) if i > 0 => Some(format!("{}/{}", &host[..i], path)),
+        _ => {
+            // path style (or the endpoint of S3-compatible storage)
+            if path.is_empty() {
+                None
+            } else {
+                Some(path)
+            }
+        }
+    }
+}
+
+fn percent_decode(s: &str) -> Option<String> {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match hex {
+            Some(b) => {
+                decoded.push(b);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompletionSource {
+    Buckets,
+    Dir(ObjectKey),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletionQuery {
+    pub source: CompletionSource,
+    // the input before the name to be completed
+    base: String,
+    partial: String,
+}
+
+// returns None if the input cannot be completed (object urls and arns)
+pub fn completion_query(input: &str) -> Option<CompletionQuery> {
+    let (scheme, path) = match input.strip_prefix(S3_URI_SCHEME) {
+        Some(path) => (S3_URI_SCHEME, path),
+        None if input.contains("://") || input.starts_with("arn:") => return None,
+        None => ("", input),
+    };
+
+    let query = match path.rsplit_once('/') {
+        None => CompletionQuery {
+            source: CompletionSource::Buckets,
+            base: scheme.into(),
+            partial: path.into(),
+        },
+        Some((dir, partial)) => {
+            let mut names = dir.split('/');
+            let bucket_name = names.next().unwrap_or_default();
+            if bucket_name.is_empty() {
+                return None;
+            }
+            let object_key = ObjectKey {
+                bucket_name: bucket_name.into(),
+                object_path: names.filter(|s| !s.is_empty()).map(String::from).collect(),
+            };
+            CompletionQuery {
+                source: CompletionSource::Dir(object_key),
+                base: format!("{}{}/", scheme, dir),
+                partial: partial.into(),
+            }
+        }
+    };
+    Some(query)
+}
+
+impl CompletionQuery {
+    // names of directories must end with `/`
+    // returns the completed input and the candidates
+    pub fn complete(&self, names: &[String]) -> (String, Vec<String>) {
+        let mut candidates: Vec<String> = names
+            .iter()
+            .filter(|name| name.starts_with(&self.partial))
+            .cloned()
+            .collect();
+        candidates.sort();
+
+        let completed = match candidates.as_slice() {
+            [] => self.partial.clone(),
+            [name] => {
+                if self.source == CompletionSource::Buckets {
+                    format!("{}/", name)
+                } else {
+                    name.clone()
+                }
+            }
+            [first, rest @ ..] => rest
+                .iter()
+                .fold(first.clone(), |prefix, name| common_prefix(&prefix, name)),
+        };
+        if candidates.len() == 1 {
+            candidates.clear();
+        }
+        (format!("{}{}", self.base, completed), candidates)
+    }
+}
+
+// names to be passed to CompletionQuery::complete
+pub fn candidate_names(items: &[ObjectItem]) -> Vec<String> {
+    items
+        .iter()
+        .map(|item| match item {
+            ObjectItem::Dir { name, .. } => format!("{}/", name),
+            ObjectItem::File { name, .. } => name.clone(),
+        })
+        .collect()
+}
+
+fn common_prefix(a: &str, b: &str) -> String {
+    a.chars()
+        .zip(b.chars())
+        .take_while(|(x, y)| x == y)
+        .map(|(x, _)| x)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("s3://bucket", "bucket", &[], true)]
+    #[case("s3://bucket/", "bucket", &[], true)]
+    #[case("s3://bucket/a/b/", "bucket", &["a", "b"], true)]
+    #[case("s3://bucket/a/b.txt", "bucket", &["a", "b.txt"], false)]
+    #[case("bucket/a/b.txt", "bucket", &["a", "b.txt"], false)]
+    #[case(" bucket/a/ ", "bucket", &["a"], true)]
+    #[case(
+        "https://bucket.s3.ap-northeast-1.amazonaws.com/a/b.txt",
+        "bucket",
+        &["a", "b.txt"],
+        false
+    )]
+    #[case("https://bucket.s3.amazonaws.com/a/", "bucket", &["a"], true)]
+    #[case(
+        "https://s3.ap-northeast-1.amazonaws.com/bucket/a/b.txt",
+        "bucket",
+        &["a", "b.txt"],
+        false
+    )]
+    #[case("http://localhost:4566/bucket/a/b.txt", "bucket", &["a", "b.txt"], false)]
+    #[case("arn:aws:s3:::bucket", "bucket", &[], true)]
+    #[case("arn:aws:s3:::bucket/a/b.txt", "bucket", &["a", "b.txt"], false)]
+    #[case(
+        "https://bucket.s3.amazonaws.com/a/b.txt?versionId=abc#top",
+        "bucket",
+        &["a", "b.txt"],
+        false
+    )]
+    #[case("https://bucket.s3.amazonaws.com/a/?list", "bucket", &["a"], true)]
+    #[case(
+        "https://bucket.s3.amazonaws.com/a%20b/%E3%81%82%2B.txt",
+        "bucket",
+        &["a b", "あ+.txt"],
+        false
+    )]
+    #[case(
+        "https://s3.amazonaws.com/bucket/100%25.txt?x-id=GetObject",
+        "bucket",
+        &["100%.txt"],
+        false
+    )]
+    fn test_parse_location(
+        #[case] input: &str,
+        #[case] bucket_name: &str,
+        #[case] object_path: &[&str],
+        #[case] is_dir: bool,
+    ) {
+        let expected = Location {
+            object_key: ObjectKey {
+                bucket_name: bucket_name.into(),
+                object_path: object_path.iter().map(|s| s.to_string()).collect(),
+            },
+            is_dir,
+        };
+        assert_eq!(parse_location(input), Some(expected));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("s3://")]
+    #[case("/a/b")]
+    #[case("https://s3.amazonaws.com/")]
+    #[case("https://s3.amazonaws.com/?bucket")]
+    #[case("https://bucket.s3.amazonaws.com/%FF")]
+    fn test_parse_location_invalid(#[case] input: &str) {
+        assert_eq!(parse_location(input), None);
+    }
+
+    #[test]
+    fn test_location_constructors() {
+        let prefix = Location::prefix("bucket", "a/b");
+        assert_eq!(parse_location("s3://bucket/a/b/"), Some(prefix));
+
+        let object = Location::object("bucket", "a/b.txt");
+        assert_eq!(parse_location("s3://bucket/a/b.txt"), object);
+
+        assert_eq!(Location::object("bucket", "a/"), None);
+        assert_eq!(Location::object("bucket", ""), None);
+        assert_eq!(
+            Location::bucket("bucket"),
+            parse_location("s3://bucket").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_completion_query() {
+        let query = completion_query("s3://buc").unwrap();
+        assert_eq!(query.source, CompletionSource::Buckets);
+
+        let query = completion_query("bucket/a/b").unwrap();
+        let expected = ObjectKey {
+            bucket_name: "bucket".into(),
+            object_path: vec!["a".into()],
+        };
+        assert_eq!(query.source, CompletionSource::Dir(expected));
+
+        assert!(completion_query("https://bucket.s3.amazonaws.com/a").is_none());
+        assert!(completion_query("arn:aws:s3:::bucket/a").is_none());
+    }
+
+    #[test]
+    fn test_complete() {
+        let names: Vec<String> = ["foo/", "foobar/", "foobaz.txt", "qux.txt"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let query = completion_query("s3://bucket/dir/fo").unwrap();
+        let (input, candidates) = query.complete(&names);
+        assert_eq!(input, "s3://bucket/dir/foo");
+        assert_eq!(candidates, vec!["foo/", "foobar/", "foobaz.txt"]);
+
+        let query = completion_query("s3://bucket/dir/foobar").unwrap();
+        let (input, candidates) = query.complete(&names);
+        assert_eq!(input, "s3://bucket/dir/foobar/");
+        assert!(candidates.is_empty());
+
+        let query = completion_query("s3://bucket/dir/x").unwrap();
+        let (input, candidates) = query.complete(&names);
+        assert_eq!(input, "s3://bucket/dir/x");
+        assert!(candidates.is_empty());
+
+        let buckets = vec!["bucket-1".to_string(), "bucket-2".to_string()];
+        let query = completion_query("b").unwrap();
+        let (input, candidates) = query.complete(&buckets);
+        assert_eq!(input, "bucket-");
+        assert_eq!(candidates, vec!["bucket-1", "bucket-2"]);
+
+        let query = completion_query("s3://bucket-1").unwrap();
+        let (input, candidates) = query.complete(&buckets);
+        assert_eq!(input, "s3://bucket-1/");
+        assert!(candidates.is_empty());
+    }
+}
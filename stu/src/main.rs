@@ -1,8 +1,13 @@
 mod app;
+mod bookmark;
+mod cli;
 mod client;
 mod color;
+mod command;
+mod compare;
 mod config;
 mod constant;
+mod diff;
 mod environment;
 mod error;
 mod event;
@@ -10,104 +15,253 @@ mod file;
 mod format;
 mod help;
 mod keys;
+mod location;
 mod macros;
+mod mouse;
 mod object;
 mod pages;
 mod run;
+mod session;
+mod sync;
+mod transfer;
 mod util;
 mod widget;
 
-use clap::{arg, Parser, ValueEnum};
+use anyhow::{bail, Context};
+use clap::{error::ErrorKind, CommandFactory, Parser};
 use event::AppEventType;
 use file::open_or_create_append_file;
+use ratatui::crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+};
 use std::sync::Mutex;
 use tracing_subscriber::fmt::time::ChronoLocal;
 
 use crate::{
     app::{App, AppContext},
+    cli::Command,
     color::ColorTheme,
-    config::Config,
+    config::{Config, ConnectionConfig, PathStyle},
     environment::Environment,
     keys::UserEventMapper,
+    location::{parse_location, Location},
+    session::{load_session, Session},
 };
 
-#[derive(Debug, Clone, Copy, ValueEnum)]
-enum PathStyle {
-    Auto,
-    Always,
-    Never,
-}
-
-impl From<PathStyle> for client::AddressingStyle {
-    fn from(style: PathStyle) -> Self {
-        match style {
-            PathStyle::Auto => client::AddressingStyle::Auto,
-            PathStyle::Always => client::AddressingStyle::Path,
-            PathStyle::Never => client::AddressingStyle::VirtualHosted,
-        }
-    }
-}
-
 /// STU - S3 Terminal UI
 #[derive(Parser)]
-#[command(version)]
+#[command(
+    version,
+    override_usage = "stu [OPTIONS] [S3_URI]\n       stu [OPTIONS] <COMMAND>"
+)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// S3 URI to open (s3://<bucket>/<prefix>/ or s3://<bucket>/<key>)
+    #[arg(value_name = "S3_URI", conflicts_with_all = ["bucket", "prefix", "key"])]
+    uri: Option<String>,
+
     /// AWS region
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     region: Option<String>,
 
     /// AWS endpoint url
-    #[arg(short, long, value_name = "URL")]
+    #[arg(short, long, value_name = "URL", global = true)]
     endpoint_url: Option<String>,
 
     /// AWS profile name
-    #[arg(short, long, value_name = "NAME")]
+    #[arg(short, long, value_name = "NAME", global = true)]
     profile: Option<String>,
 
     /// Target bucket name
     #[arg(short, long, value_name = "NAME")]
     bucket: Option<String>,
 
-    /// Path style type for object paths
-    #[arg(long, value_name = "TYPE", default_value = "auto")]
-    path_style: PathStyle,
+    /// Prefix to open in the target bucket
+    #[arg(
+        long,
+        value_name = "PREFIX",
+        requires = "bucket",
+        conflicts_with = "key"
+    )]
+    prefix: Option<String>,
 
-    /// Enable debug logs
+    /// Object key to open in the target bucket
+    #[arg(long, value_name = "KEY", requires = "bucket")]
+    key: Option<String>,
+
+    /// Open the object preview instead of the object detail
     #[arg(long)]
+    preview: bool,
+
+    /// Path style type for object paths [default: auto]
+    #[arg(long, value_name = "TYPE", global = true)]
+    path_style: Option<PathStyle>,
+
+    /// Connection name defined in the config file
+    #[arg(short, long, value_name = "NAME", global = true)]
+    connection: Option<String>,
+
+    /// Enable debug logs
+    #[arg(long, global = true)]
     debug: bool,
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    let args = Args::parse();
-    let config = Config::load()?;
+    let mut args = Args::parse();
+    if let Some(command) = args.command.take() {
+        if args.has_tui_options() {
+            Args::command()
+                .error(
+                    ErrorKind::ArgumentConflict,
+                    "[S3_URI], --bucket, --prefix, --key and --preview cannot be used with a subcommand",
+                )
+                .exit();
+        }
+        let code = run_command(command, &args).await?;
+        std::process::exit(code);
+    }
+
+    let location = build_location(&args)?;
+    let mut config = Config::load()?;
+    let no_color = color::no_color_requested();
+    if no_color {
+        // syntax highlighting also outputs colors
+        config.preview.highlight = false;
+    }
     let mapper = UserEventMapper::load(&config)?;
     let env = Environment::new(&config);
-    let theme = ColorTheme::default();
+    let theme = ColorTheme::load(&config.theme, no_color);
     let ctx = AppContext::new(config, env, theme);
 
     initialize_debug_log(&args, &ctx.config)?;
 
-    let client = client::new(
-        args.region,
-        args.endpoint_url,
-        args.profile,
-        ctx.config.default_region.clone(),
-        args.path_style.into(),
-    )
-    .await;
+    // a broken session file does not prevent startup, the recent locations are also kept in it
+    let session_path = ctx.config.session_file_path()?;
+    let (session, mut session_warning) = match load_session(session_path) {
+        Ok(session) => (session, None),
+        Err(e) => (
+            Session::default(),
+            Some(format!("{}, started a new session", e.msg)),
+        ),
+    };
+    // restore the last session only if no location is specified on the command line
+    let mut restore = ctx.config.session.restore
+        && location.is_none()
+        && (args.connection.is_none() || args.connection == session.connection);
+    if let Some(name) = session.connection.as_ref().filter(|_| restore) {
+        if !ctx.config.connections.contains_key(name) {
+            // the connection may have been removed from the config file
+            let msg = format!("Connection of the last session not found: {}", name);
+            session_warning = Some(msg);
+            restore = false;
+        }
+    }
+    let (connection_name, location) = if restore {
+        let name = args.connection.clone().or(session.connection.clone());
+        (name, session.location())
+    } else {
+        (args.connection.clone(), location)
+    };
+
+    let connection = build_connection(&args, connection_name.as_deref(), &ctx.config)?;
+    let client = client::new(&connection, ctx.config.default_region.clone()).await;
 
     let (tx, rx) = event::new();
     let mut app = App::new(mapper, client, ctx, tx.clone());
-    tx.send(AppEventType::Initialize(args.bucket));
+    app.set_session(session, connection_name, restore);
+    tx.send(AppEventType::Initialize(location, args.preview));
+    if let Some(msg) = session_warning {
+        tx.send(AppEventType::NotifyWarn(msg));
+    }
 
+    let mouse = app.mouse_enabled();
     let mut terminal = ratatui::try_init()?;
+    if mouse {
+        execute!(std::io::stdout(), EnableMouseCapture)?;
+    }
     let ret = run::run(&mut app, &mut terminal, rx).await;
+    if mouse {
+        execute!(std::io::stdout(), DisableMouseCapture)?;
+    }
     ratatui::try_restore()?;
 
     ret
 }
 
+impl Args {
+    fn has_tui_options(&self) -> bool {
+        self.uri.is_some()
+            || self.bucket.is_some()
+            || self.prefix.is_some()
+            || self.key.is_some()
+            || self.preview
+    }
+}
+
+async fn run_command(command: Command, args: &Args) -> anyhow::Result<i32> {
+    let config = Config::load()?;
+    initialize_debug_log(args, &config)?;
+
+    let connection = build_connection(args, args.connection.as_deref(), &config)?;
+    let client = client::new(&connection, config.default_region.clone()).await;
+
+    Ok(cli::run(command, client, &config).await)
+}
+
+fn build_connection(
+    args: &Args,
+    name: Option<&str>,
+    config: &Config,
+) -> anyhow::Result<ConnectionConfig> {
+    let mut connection = match name {
+        Some(name) => config
+            .connections
+            .get(name)
+            .cloned()
+            .with_context(|| format!("Connection not found in config: {}", name))?,
+        None => ConnectionConfig::default(),
+    };
+    // command line options take precedence over the connection settings
+    if args.region.is_some() {
+        connection.region.clone_from(&args.region);
+    }
+    if args.endpoint_url.is_some() {
+        connection.endpoint_url.clone_from(&args.endpoint_url);
+    }
+    if args.profile.is_some() {
+        connection.profile.clone_from(&args.profile);
+    }
+    if let Some(path_style) = args.path_style {
+        connection.path_style = path_style;
+    }
+    Ok(connection)
+}
+
+fn build_location(args: &Args) -> anyhow::Result<Option<Location>> {
+    let location = if let Some(uri) = &args.uri {
+        let location = parse_location(uri).with_context(|| format!("Invalid S3 URI: {}", uri))?;
+        Some(location)
+    } else if let Some(bucket) = &args.bucket {
+        let location = match (&args.prefix, &args.key) {
+            (Some(prefix), _) => Location::prefix(bucket, prefix),
+            (_, Some(key)) => Location::object(bucket, key)
+                .with_context(|| format!("Invalid object key: {}", key))?,
+            (None, None) => Location::bucket(bucket),
+        };
+        Some(location)
+    } else {
+        None
+    };
+    if args.preview && !location.as_ref().is_some_and(|l| !l.is_dir) {
+        bail!("--preview requires an object key");
+    }
+    Ok(location)
+}
+
 fn initialize_debug_log(args: &Args, config: &Config) -> anyhow::Result<()> {
     if args.debug {
         let path = config.debug_log_path()?;
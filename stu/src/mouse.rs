@@ -0,0 +1,145 @@
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    crossterm::event::{MouseButton, MouseEvent, MouseEventKind},
+    layout::Position,
+};
+
+use crate::{keys::UserEvent, widget::ScrollListState};
+
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseInput {
+    Click(Position),
+    DoubleClick(Position),
+    ScrollDown(Position),
+    ScrollUp(Position),
+}
+
+impl MouseInput {
+    pub fn position(&self) -> Position {
+        match self {
+            MouseInput::Click(position)
+            | MouseInput::DoubleClick(position)
+            | MouseInput::ScrollDown(position)
+            | MouseInput::ScrollUp(position) => *position,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct MouseInputState {
+    last_click: Option<(Instant, Position)>,
+}
+
+impl MouseInputState {
+    pub fn resolve(&mut self, event: MouseEvent) -> Option<MouseInput> {
+        self.resolve_at(event, Instant::now())
+    }
+
+    fn resolve_at(&mut self, event: MouseEvent, now: Instant) -> Option<MouseInput> {
+        let position = Position::new(event.column, event.row);
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                // terminals do not report double clicks, so detect them from the last click
+                let double_click = self.last_click.is_some_and(|(at, pos)| {
+                    pos == position && now.duration_since(at) <= DOUBLE_CLICK_INTERVAL
+                });
+                if double_click {
+                    self.last_click = None;
+                    Some(MouseInput::DoubleClick(position))
+                } else {
+                    self.last_click = Some((now, position));
+                    Some(MouseInput::Click(position))
+                }
+            }
+            MouseEventKind::ScrollDown => Some(MouseInput::ScrollDown(position)),
+            MouseEventKind::ScrollUp => Some(MouseInput::ScrollUp(position)),
+            _ => None,
+        }
+    }
+}
+
+// a click selects the row, and a double click on the row returns the select event
+pub fn handle_list_mouse_input(
+    input: MouseInput,
+    list_state: &mut ScrollListState,
+    select: Option<UserEvent>,
+    down: UserEvent,
+    up: UserEvent,
+) -> Option<UserEvent> {
+    match input {
+        MouseInput::Click(position) => {
+            if let Some(i) = list_state.index_at(position) {
+                list_state.select_index(i);
+            }
+            None
+        }
+        MouseInput::DoubleClick(position) => {
+            list_state.index_at(position)?;
+            select
+        }
+        MouseInput::ScrollDown(_) => Some(down),
+        MouseInput::ScrollUp(_) => Some(up),
+    }
+}
+
+// `index` is the index of the dialog item at the position of the input
+pub fn handle_dialog_mouse_input(
+    input: MouseInput,
+    index: Option<usize>,
+    mut select_index: impl FnMut(usize),
+) -> Option<UserEvent> {
+    match input {
+        MouseInput::Click(_) => {
+            if let Some(i) = index {
+                select_index(i);
+            }
+            None
+        }
+        MouseInput::DoubleClick(_) => {
+            select_index(index?);
+            Some(UserEvent::SelectDialogSelect)
+        }
+        MouseInput::ScrollDown(_) => Some(UserEvent::SelectDialogDown),
+        MouseInput::ScrollUp(_) => Some(UserEvent::SelectDialogUp),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyModifiers;
+
+    use super::*;
+
+    #[test]
+    fn test_resolve_double_click() {
+        let mut state = MouseInputState::default();
+        let now = Instant::now();
+
+        let click = |column, row| MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+
+        let actual = state.resolve_at(click(1, 2), now);
+        assert_eq!(actual, Some(MouseInput::Click(Position::new(1, 2))));
+        let actual = state.resolve_at(click(1, 2), now + Duration::from_millis(100));
+        assert_eq!(actual, Some(MouseInput::DoubleClick(Position::new(1, 2))));
+
+        // the third click starts a new double click
+        let actual = state.resolve_at(click(1, 2), now + Duration::from_millis(200));
+        assert_eq!(actual, Some(MouseInput::Click(Position::new(1, 2))));
+
+        // too slow
+        let actual = state.resolve_at(click(1, 2), now + Duration::from_millis(800));
+        assert_eq!(actual, Some(MouseInput::Click(Position::new(1, 2))));
+
+        // another position
+        let actual = state.resolve_at(click(3, 2), now + Duration::from_millis(900));
+        assert_eq!(actual, Some(MouseInput::Click(Position::new(3, 2))));
+    }
+}
@@ -1,13 +1,15 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fmt::{self, Debug, Formatter},
 };
 
 use chrono::{DateTime, Local};
+use itsuki::zero_indexed_enum;
 
 #[derive(Clone, Debug)]
 pub struct BucketItem {
     pub name: String,
+    pub region: String,
     pub s3_uri: String,
     pub arn: String,
     pub object_url: String,
@@ -76,7 +78,6 @@ pub struct FileVersion {
     pub size_byte: usize,
     pub last_modified: DateTime<Local>,
     pub e_tag: String,
-    #[allow(dead_code)]
     pub is_latest: bool,
 }
 
@@ -99,12 +100,83 @@ pub struct DownloadObjectInfo {
     pub size_byte: usize,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone)]
+pub struct ObjectSummary {
+    pub key: String,
+    pub size_byte: usize,
+    pub last_modified: DateTime<Local>,
+    pub storage_class: String,
+    pub e_tag: String,
+}
+
+// attributes to be kept when the edited object is uploaded
+#[derive(Debug, Clone, Default)]
+pub struct EditObjectAttributes {
+    pub e_tag: String,
+    pub content_type: Option<String>,
+    pub content_encoding: Option<String>,
+    pub content_disposition: Option<String>,
+    pub content_language: Option<String>,
+    pub cache_control: Option<String>,
+    pub storage_class: Option<String>,
+    pub metadata: HashMap<String, String>,
+}
+
+// the content shown next to the object list in the split layout
+#[derive(Debug, Clone)]
+pub enum SplitPreviewContent {
+    Dir(Vec<ObjectItem>),
+    // detail and the first bytes of the object
+    File(Box<FileDetail>, Vec<u8>),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrefixSize {
+    pub size_byte: usize,
+    pub object_count: usize,
+    // storage class => (size_byte, object_count)
+    pub storage_classes: BTreeMap<String, (usize, usize)>,
+    pub completed: bool,
+}
+
+impl PrefixSize {
+    pub fn add(&mut self, storage_class: &str, size_byte: usize) {
+        self.size_byte += size_byte;
+        self.object_count += 1;
+        let entry = self
+            .storage_classes
+            .entry(storage_class.to_string())
+            .or_default();
+        entry.0 += size_byte;
+        entry.1 += 1;
+    }
+}
+
+#[derive(Default)]
+#[zero_indexed_enum]
+pub enum PresignedUrlMethod {
+    #[default]
+    Get,
+    Put,
+}
+
+impl PresignedUrlMethod {
+    pub fn str(&self) -> &'static str {
+        match self {
+            Self::Get => "GET",
+            Self::Put => "PUT",
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
 pub struct AppObjects {
     bucket_items: Vec<BucketItem>,
     object_items_map: HashMap<ObjectKey, Vec<ObjectItem>>,
     detail_map: HashMap<ObjectKey, FileDetail>,
     versions_map: HashMap<ObjectKey, Vec<FileVersion>>,
+    prefix_size_map: HashMap<ObjectKey, PrefixSize>,
+    head_map: HashMap<ObjectKey, Vec<u8>>,
 }
 
 impl AppObjects {
@@ -116,6 +188,37 @@ impl AppObjects {
         self.object_items_map.get(key).map(|items| items.to_vec())
     }
 
+    // the listed buckets, followed by the buckets whose objects are loaded without listing
+    pub fn get_bucket_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .bucket_items
+            .iter()
+            .map(|item| item.name.clone())
+            .collect();
+        let mut others: Vec<String> = self
+            .object_items_map
+            .keys()
+            .map(|key| key.bucket_name.clone())
+            .filter(|name| !names.contains(name))
+            .collect();
+        others.sort();
+        others.dedup();
+        names.extend(others);
+        names
+    }
+
+    pub fn get_dir_names(&self, key: &ObjectKey) -> Option<Vec<String>> {
+        self.object_items_map.get(key).map(|items| {
+            items
+                .iter()
+                .filter_map(|item| match item {
+                    ObjectItem::Dir { name, .. } => Some(name.clone()),
+                    ObjectItem::File { .. } => None,
+                })
+                .collect()
+        })
+    }
+
     pub fn set_bucket_items(&mut self, items: Vec<BucketItem>) {
         self.bucket_items = items;
     }
@@ -140,10 +243,32 @@ impl AppObjects {
         self.versions_map.insert(key, versions);
     }
 
+    pub fn get_object_head(&self, key: &ObjectKey) -> Option<&Vec<u8>> {
+        self.head_map.get(key)
+    }
+
+    pub fn set_object_head(&mut self, key: ObjectKey, head: Vec<u8>) {
+        self.head_map.insert(key, head);
+    }
+
+    pub fn get_prefix_size(&self, key: &ObjectKey) -> Option<&PrefixSize> {
+        self.prefix_size_map.get(key)
+    }
+
+    pub fn set_prefix_size(&mut self, key: ObjectKey, size: PrefixSize) {
+        self.prefix_size_map.insert(key, size);
+    }
+
+    pub fn remove_prefix_size(&mut self, key: &ObjectKey) {
+        self.prefix_size_map.remove(key);
+    }
+
     pub fn clear_object_items_under(&mut self, key: &ObjectKey) {
         self.object_items_map.retain(|k, _| !k.has_prefix(key));
         self.detail_map.retain(|k, _| !k.has_prefix(key));
         self.versions_map.retain(|k, _| !k.has_prefix(key));
+        self.prefix_size_map.retain(|k, _| !k.has_prefix(key));
+        self.head_map.retain(|k, _| !k.has_prefix(key));
     }
 
     pub fn clear_all(&mut self) {
@@ -151,6 +276,8 @@ impl AppObjects {
         self.object_items_map.clear();
         self.detail_map.clear();
         self.versions_map.clear();
+        self.prefix_size_map.clear();
+        self.head_map.clear();
     }
 }
 
@@ -183,7 +310,55 @@ impl ObjectKey {
         joined
     }
 
-    fn has_prefix(&self, prefix: &ObjectKey) -> bool {
+    pub fn child(&self, name: impl Into<String>) -> ObjectKey {
+        let mut object_path = self.object_path.clone();
+        object_path.push(name.into());
+        ObjectKey {
+            bucket_name: self.bucket_name.clone(),
+            object_path,
+        }
+    }
+
+    // keys from the bucket root to self
+    pub fn hierarchy(&self) -> Vec<ObjectKey> {
+        (0..=self.object_path.len())
+            .map(|n| ObjectKey {
+                bucket_name: self.bucket_name.clone(),
+                object_path: self.object_path[..n].to_vec(),
+            })
+            .collect()
+    }
+
+    // accepts `s3://bucket/prefix/` (or `bucket/prefix/`) as a directory key
+    pub fn from_prefix_uri(uri: &str) -> Option<ObjectKey> {
+        let path = uri.trim();
+        let path = path.strip_prefix("s3://").unwrap_or(path);
+        let mut names = path.split('/').filter(|s| !s.is_empty());
+        let bucket_name = names.next()?.to_string();
+        let object_path = names.map(|s| s.to_string()).collect();
+        Some(ObjectKey {
+            bucket_name,
+            object_path,
+        })
+    }
+
+    pub fn prefix_uri(&self) -> String {
+        format!(
+            "s3://{}/{}",
+            self.bucket_name,
+            self.joined_object_path(false)
+        )
+    }
+
+    pub fn parent(&self) -> Option<ObjectKey> {
+        let (_, object_path) = self.object_path.split_last()?;
+        Some(ObjectKey {
+            bucket_name: self.bucket_name.clone(),
+            object_path: object_path.to_vec(),
+        })
+    }
+
+    pub fn has_prefix(&self, prefix: &ObjectKey) -> bool {
         if self.bucket_name != prefix.bucket_name {
             return false;
         }
@@ -273,6 +448,79 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_get_bucket_names() {
+        let mut app_objects = AppObjects::default();
+        app_objects.set_bucket_items(vec![bucket_item("foo"), bucket_item("bar")]);
+        app_objects.set_object_items(object_key("foo", &[]), Vec::new());
+        app_objects.set_object_items(object_key("qux", &["a"]), Vec::new());
+        app_objects.set_object_items(object_key("baz", &[]), Vec::new());
+        app_objects.set_object_items(object_key("qux", &[]), Vec::new());
+
+        assert_eq!(
+            app_objects.get_bucket_names(),
+            vec!["foo", "bar", "baz", "qux"]
+        );
+    }
+
+    #[test]
+    fn test_object_key_hierarchy() {
+        let key = object_key("foo", &["a", "b"]);
+        let expected = vec![
+            object_key("foo", &[]),
+            object_key("foo", &["a"]),
+            object_key("foo", &["a", "b"]),
+        ];
+        assert_eq!(key.hierarchy(), expected);
+    }
+
+    #[test]
+    fn test_prefix_size_add() {
+        let mut size = PrefixSize::default();
+        size.add("STANDARD", 100);
+        size.add("GLACIER", 20);
+        size.add("STANDARD", 3);
+
+        assert_eq!(size.size_byte, 123);
+        assert_eq!(size.object_count, 3);
+        assert_eq!(
+            size.storage_classes.into_iter().collect::<Vec<_>>(),
+            vec![("GLACIER".into(), (20, 1)), ("STANDARD".into(), (103, 2))]
+        );
+    }
+
+    #[rstest]
+    #[case("s3://bucket/", "bucket", &[])]
+    #[case("s3://bucket/a/b/", "bucket", &["a", "b"])]
+    #[case("s3://bucket/a/b", "bucket", &["a", "b"])]
+    #[case("bucket/a", "bucket", &["a"])]
+    fn test_object_key_from_prefix_uri(
+        #[case] uri: &str,
+        #[case] bucket: &str,
+        #[case] object_path: &[&str],
+    ) {
+        let actual = ObjectKey::from_prefix_uri(uri).unwrap();
+        assert_eq!(actual.bucket_name, bucket);
+        assert_eq!(actual.object_path, object_path);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("s3://")]
+    fn test_object_key_from_prefix_uri_invalid(#[case] uri: &str) {
+        assert!(ObjectKey::from_prefix_uri(uri).is_none());
+    }
+
+    fn bucket_item(name: &str) -> BucketItem {
+        BucketItem {
+            name: name.to_string(),
+            region: String::new(),
+            s3_uri: String::new(),
+            arn: String::new(),
+            object_url: String::new(),
+        }
+    }
+
     fn object_key(bucket_name: &str, object_path: &[&str]) -> ObjectKey {
         ObjectKey {
             bucket_name: bucket_name.to_string(),
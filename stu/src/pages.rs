@@ -1,8 +1,18 @@
 pub mod page;
 
+pub mod bookmarks;
 pub mod bucket_list;
+pub mod command_output;
+pub mod command_palette;
+pub mod commander;
+pub mod connections;
 pub mod help;
 pub mod initializing;
 pub mod object_detail;
 pub mod object_list;
 pub mod object_preview;
+pub mod prefix_analytics;
+pub mod prefix_diff;
+pub mod recent_locations;
+pub mod sync_plan;
+pub mod text_diff;
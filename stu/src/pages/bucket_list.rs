@@ -13,19 +13,24 @@ use ratatui::{
 use crate::{
     app::AppContext,
     color::ColorTheme,
+    command::CustomCommandTarget,
+    config::CustomCommandPage,
     event::{AppEventType, Sender},
     format::format_size_byte,
     handle_user_events, handle_user_events_with_default,
     help::{
-        build_help_spans, build_short_help_spans, BuildHelpsItem, BuildShortHelpsItem, Spans,
-        SpansWithPriority,
+        build_custom_command_helps, build_help_spans, build_short_help_spans, BuildHelpsItem,
+        BuildShortHelpsItem, Spans, SpansWithPriority,
     },
     keys::{UserEvent, UserEventMapper},
+    location::parse_location,
+    mouse::{handle_dialog_mouse_input, handle_list_mouse_input, MouseInput},
     object::{BucketItem, DownloadObjectInfo, ObjectKey},
+    session::ListViewSession,
     widget::{
         BucketListSortDialog, BucketListSortDialogState, BucketListSortType, ConfirmDialog,
-        ConfirmDialogState, CopyDetailDialog, CopyDetailDialogState, InputDialog, InputDialogState,
-        ScrollList, ScrollListState,
+        ConfirmDialogState, CopyDetailDialog, CopyDetailDialogState, GoToDialog, GoToDialogState,
+        InputDialog, InputDialogState, ScrollList, ScrollListState,
     },
 };
 
@@ -54,6 +59,7 @@ enum ViewState {
     CopyDetailDialog(Box<CopyDetailDialogState>),
     DownloadConfirmDialog(Vec<DownloadObjectInfo>, ConfirmDialogState, bool),
     SaveDialog(InputDialogState, Option<Vec<DownloadObjectInfo>>),
+    GoToDialog(GoToDialogState),
 }
 
 impl BucketListPage {
@@ -121,6 +127,15 @@ impl BucketListPage {
                     UserEvent::BucketListDownloadObjectAs => {
                         self.start_download_as();
                     }
+                    UserEvent::BucketListConnection => {
+                        self.tx.send(AppEventType::OpenConnections);
+                    }
+                    UserEvent::BucketListGoTo => {
+                        self.open_go_to_dialog();
+                    }
+                    UserEvent::BucketListCustomCommand(index) if self.non_empty() => {
+                        self.run_custom_command(*index);
+                    }
                     UserEvent::Help => {
                         self.tx.send(AppEventType::OpenHelp);
                     }
@@ -215,6 +230,64 @@ impl BucketListPage {
                     }
                 }
             }
+            ViewState::GoToDialog(ref mut state) => {
+                handle_user_events_with_default! { user_events =>
+                    UserEvent::InputDialogClose => {
+                        self.close_go_to_dialog();
+                    }
+                    UserEvent::InputDialogApply => {
+                        let input = state.input().to_string();
+                        self.go_to(input, false);
+                    }
+                    UserEvent::GoToDialogPreview => {
+                        let input = state.input().to_string();
+                        self.go_to(input, true);
+                    }
+                    UserEvent::GoToDialogComplete => {
+                        let input = state.input().to_string();
+                        self.tx.send(AppEventType::LoadGoToCandidates(input));
+                    }
+                    UserEvent::Help => {
+                        self.tx.send(AppEventType::OpenHelp);
+                    }
+                    => {
+                        state.handle_key_event(key_event);
+                    }
+                }
+            }
+        }
+    }
+
+    // keys are typed into the input as is, without key sequences and counts
+    pub fn is_text_input_active(&self) -> bool {
+        matches!(
+            self.view_state,
+            ViewState::FilterDialog | ViewState::SaveDialog(..) | ViewState::GoToDialog(_)
+        )
+    }
+
+    pub fn handle_mouse(&mut self, input: MouseInput, area: Rect) -> Option<UserEvent> {
+        let position = input.position();
+        match self.view_state {
+            ViewState::Default if self.non_empty() => handle_list_mouse_input(
+                input,
+                &mut self.list_state,
+                Some(UserEvent::BucketListSelect),
+                UserEvent::BucketListDown,
+                UserEvent::BucketListUp,
+            ),
+            ViewState::SortDialog => {
+                let index = self.sort_dialog_state.item_index_at(area, position);
+                handle_dialog_mouse_input(input, index, |i| {
+                    self.sort_dialog_state.select_index(i);
+                    self.sort_view_indices();
+                })
+            }
+            ViewState::CopyDetailDialog(ref mut state) => {
+                let index = state.item_index_at(area, position);
+                handle_dialog_mouse_input(input, index, |i| state.select_index(i))
+            }
+            _ => None,
         }
     }
 
@@ -273,11 +346,23 @@ impl BucketListPage {
             let (cursor_x, cursor_y) = state.cursor();
             f.set_cursor_position((cursor_x, cursor_y));
         }
+
+        if let ViewState::GoToDialog(state) = &mut self.view_state {
+            let go_to_dialog = GoToDialog::default().theme(&self.ctx.theme);
+            f.render_stateful_widget(go_to_dialog, area, state);
+
+            let (cursor_x, cursor_y) = state.cursor();
+            f.set_cursor_position((cursor_x, cursor_y));
+        }
     }
 
     pub fn helps(&self, mapper: &UserEventMapper) -> Vec<Spans> {
+        build_help_spans(self.help_items(), mapper, self.ctx.theme.help_key_fg)
+    }
+
+    pub fn help_items(&self) -> Vec<BuildHelpsItem> {
         #[rustfmt::skip]
-        let helps = match self.view_state {
+        let mut helps = match self.view_state {
             ViewState::Default => {
                 if self.filter_input_state.is_empty() {
                     vec![
@@ -296,6 +381,8 @@ impl BucketListPage {
                         BuildHelpsItem::new(UserEvent::BucketListDownloadObjectAs, "Download object as"),
                         BuildHelpsItem::new(UserEvent::BucketListRefresh, "Refresh bucket list"),
                         BuildHelpsItem::new(UserEvent::BucketListManagementConsole, "Open management console in browser"),
+                        BuildHelpsItem::new(UserEvent::BucketListConnection, "Switch connection"),
+                        BuildHelpsItem::new(UserEvent::BucketListGoTo, "Go to location"),
                     ]
                 } else {
                     vec![
@@ -315,6 +402,8 @@ impl BucketListPage {
                         BuildHelpsItem::new(UserEvent::BucketListDownloadObjectAs, "Download object as"),
                         BuildHelpsItem::new(UserEvent::BucketListRefresh, "Refresh bucket list"),
                         BuildHelpsItem::new(UserEvent::BucketListManagementConsole, "Open management console in browser"),
+                        BuildHelpsItem::new(UserEvent::BucketListConnection, "Switch connection"),
+                        BuildHelpsItem::new(UserEvent::BucketListGoTo, "Go to location"),
                     ]
                 }
             },
@@ -359,8 +448,23 @@ impl BucketListPage {
                     BuildHelpsItem::new(UserEvent::InputDialogApply, "Download object"),
                 ]
             }
+            ViewState::GoToDialog(_) => {
+                vec![
+                    BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+                    BuildHelpsItem::new(UserEvent::InputDialogClose, "Close go to dialog"),
+                    BuildHelpsItem::new(UserEvent::GoToDialogComplete, "Complete bucket or prefix"),
+                    BuildHelpsItem::new(UserEvent::InputDialogApply, "Go to location"),
+                    BuildHelpsItem::new(UserEvent::GoToDialogPreview, "Go to location and preview object"),
+                ]
+            }
         };
-        build_help_spans(helps, mapper, self.ctx.theme.help_key_fg)
+        if matches!(self.view_state, ViewState::Default) {
+            helps.extend(build_custom_command_helps(
+                &self.ctx.config,
+                CustomCommandPage::BucketList,
+            ));
+        }
+        helps
     }
 
     pub fn short_helps(&self, mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
@@ -431,6 +535,15 @@ impl BucketListPage {
                     BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
                 ]
             }
+            ViewState::GoToDialog(_) => {
+                vec![
+                    BuildShortHelpsItem::single(UserEvent::InputDialogClose, "Close", 2),
+                    BuildShortHelpsItem::single(UserEvent::GoToDialogComplete, "Complete", 3),
+                    BuildShortHelpsItem::single(UserEvent::InputDialogApply, "Go", 1),
+                    BuildShortHelpsItem::single(UserEvent::GoToDialogPreview, "Preview", 4),
+                    BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+                ]
+            }
         };
         build_short_help_spans(helps, mapper)
     }
@@ -601,10 +714,48 @@ impl BucketListPage {
         }
     }
 
-    fn non_empty(&self) -> bool {
+    fn run_custom_command(&self, index: usize) {
+        let item = self.current_selected_item();
+        let target = CustomCommandTarget {
+            bucket: item.name.clone(),
+            s3_uri: item.s3_uri.clone(),
+            object_url: item.object_url.clone(),
+            ..Default::default()
+        };
+        self.tx
+            .send(AppEventType::StartCustomCommand(index, target));
+    }
+
+    pub fn non_empty(&self) -> bool {
         !self.view_indices.is_empty()
     }
 
+    pub fn view_session(&self) -> ListViewSession {
+        ListViewSession {
+            selected: self
+                .non_empty()
+                .then(|| self.current_selected_item().name.clone()),
+            filter: self.filter_input_state.input().into(),
+            sort: self.sort_dialog_state.selected().val(),
+        }
+    }
+
+    pub fn restore_view_session(&mut self, session: &ListViewSession) {
+        self.filter_input_state.set_input(session.filter.clone());
+        let sort = BucketListSortType::try_from(session.sort).unwrap_or_default();
+        self.sort_dialog_state.set_selected(sort);
+        self.filter_view_indices();
+
+        let position = session.selected.as_ref().and_then(|name| {
+            self.view_indices
+                .iter()
+                .position(|&i| &self.bucket_items[i].name == name)
+        });
+        if let Some(i) = position {
+            self.list_state.select_index(i);
+        }
+    }
+
     fn start_download(&self) {
         let key = self.current_selected_object_key();
         self.tx
@@ -663,6 +814,48 @@ impl BucketListPage {
     fn close_save_dialog(&mut self) {
         self.view_state = ViewState::Default;
     }
+
+    fn open_go_to_dialog(&mut self) {
+        let mut state = GoToDialogState::default();
+        state.set_completion("s3://".into(), Vec::new());
+        self.view_state = ViewState::GoToDialog(state);
+    }
+
+    fn close_go_to_dialog(&mut self) {
+        self.view_state = ViewState::Default;
+    }
+
+    fn go_to(&mut self, input: String, preview: bool) {
+        if input.trim().is_empty() {
+            return;
+        }
+        let Some(location) = parse_location(&input) else {
+            let msg = format!("Invalid location: {}", input.trim());
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        };
+        if preview && location.is_dir {
+            let msg = "Only objects can be previewed";
+            self.tx.send(AppEventType::NotifyWarn(msg.into()));
+            return;
+        }
+        self.tx.send(AppEventType::GoToLocation(location, preview));
+        self.close_go_to_dialog();
+    }
+
+    pub fn set_go_to_completion(
+        &mut self,
+        input: &str,
+        completed: String,
+        candidates: Vec<String>,
+    ) {
+        if let ViewState::GoToDialog(state) = &mut self.view_state {
+            // ignore if the input has been changed while loading
+            if state.input() == input {
+                state.set_completion(completed, candidates);
+            }
+        }
+    }
 }
 
 fn build_list_items<'a>(
@@ -675,6 +868,11 @@ fn build_list_items<'a>(
     area: Rect,
 ) -> Vec<ListItem<'a>> {
     let show_item_count = (area.height as usize) - 2 /* border */;
+    let region_w = view_indices
+        .iter()
+        .map(|&original_idx| current_items[original_idx].region.len())
+        .max()
+        .unwrap_or_default();
     view_indices
         .iter()
         .map(|&original_idx| &current_items[original_idx])
@@ -683,24 +881,42 @@ fn build_list_items<'a>(
         .enumerate()
         .map(|(idx, item)| {
             let selected = idx + offset == selected;
-            build_list_item(&item.name, selected, filter, area.width, theme)
+            build_list_item(
+                &item.name,
+                &item.region,
+                region_w,
+                selected,
+                filter,
+                area.width,
+                theme,
+            )
         })
         .collect()
 }
 
 fn build_list_item<'a>(
     name: &'a str,
+    region: &'a str,
+    region_w: usize,
     selected: bool,
     filter: &'a str,
     width: u16,
     theme: &'a ColorTheme,
 ) -> ListItem<'a> {
-    let name_w = (width as usize) - 4 /* border + pad */;
+    let name_w =
+        (width as usize).saturating_sub(region_w + 5 /* spaces */ + 4 /* border + pad */);
     let pad_name =
         console::pad_str(name, name_w, console::Alignment::Left, Some(ELLIPSIS)).to_string();
+    let pad_region = console::pad_str(region, region_w, console::Alignment::Left, None).to_string();
 
     let line = if filter.is_empty() {
-        Line::from(vec![" ".into(), pad_name.into(), " ".into()])
+        Line::from(vec![
+            " ".into(),
+            pad_name.into(),
+            "    ".into(),
+            pad_region.into(),
+            " ".into(),
+        ])
     } else {
         let i = name.find(filter).unwrap();
         let mut spans = highlight_matched_text(pad_name)
@@ -710,6 +926,8 @@ fn build_list_item<'a>(
             .matched_style(Style::default().fg(theme.list_filter_match))
             .into_spans();
         spans.insert(0, " ".into());
+        spans.push("    ".into());
+        spans.push(pad_region.into());
         spans.push(" ".into());
         Line::from(spans)
     };
@@ -718,6 +936,7 @@ fn build_list_item<'a>(
         Style::default()
             .bg(theme.list_selected_bg)
             .fg(theme.list_selected_fg)
+            .add_modifier(theme.list_selected_modifier)
     } else {
         Style::default()
     };
@@ -826,6 +1045,51 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_render_with_region() -> std::io::Result<()> {
+        let ctx = Rc::default();
+        let tx = sender();
+        let mut terminal = setup_terminal()?;
+
+        terminal.draw(|f| {
+            let items = [
+                ("bucket1", "us-east-1"),
+                ("bucket2", "ap-northeast-1"),
+                ("bucket3", "eu-west-1"),
+            ]
+            .into_iter()
+            .map(|(name, region)| BucketItem {
+                region: region.to_string(),
+                ..bucket_item(name)
+            })
+            .collect();
+            let mut page = BucketListPage::new(items, ctx, tx);
+            let area = Rect::new(0, 0, 30, 10);
+            page.render(f, area);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌───────────────────── 1 / 3 ┐",
+            "│  buck...    us-east-1      │",
+            "│  buck...    ap-northeast-1 │",
+            "│  buck...    eu-west-1      │",
+            "│                            │",
+            "│                            │",
+            "│                            │",
+            "│                            │",
+            "│                            │",
+            "└────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            (2..28, [1]) => bg: Color::Cyan, fg: Color::Black,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_render_filter_items() -> std::io::Result<()> {
         let ctx = Rc::default();
@@ -1148,6 +1412,7 @@ mod tests {
     fn bucket_item(name: &str) -> BucketItem {
         BucketItem {
             name: name.to_string(),
+            region: "".to_string(),
             s3_uri: "".to_string(),
             arn: "".to_string(),
             object_url: "".to_string(),
@@ -0,0 +1,223 @@
+use std::rc::Rc;
+
+use ansi_to_tui::IntoText;
+use ratatui::{
+    crossterm::event::KeyEvent, layout::Rect, style::Stylize, text::Line, widgets::Block, Frame,
+};
+
+use crate::{
+    app::AppContext,
+    color::ColorTheme,
+    command::CommandOutput,
+    event::{AppEventType, Sender},
+    handle_user_events,
+    help::{
+        build_help_spans, build_short_help_spans, BuildHelpsItem, BuildShortHelpsItem, Spans,
+        SpansWithPriority,
+    },
+    keys::{UserEvent, UserEventMapper},
+    mouse::MouseInput,
+    widget::{ScrollLines, ScrollLinesOptions, ScrollLinesState},
+};
+
+#[derive(Debug)]
+pub struct CommandOutputPage {
+    title: String,
+    scroll_lines_state: ScrollLinesState,
+
+    ctx: Rc<AppContext>,
+    tx: Sender,
+}
+
+impl CommandOutputPage {
+    pub fn new(command: String, output: CommandOutput, ctx: Rc<AppContext>, tx: Sender) -> Self {
+        let title = if output.status.success() {
+            format!("Output [{}]", command)
+        } else {
+            format!("Output [{}] ({})", command, output.status)
+        };
+        let lines = build_lines(&output, &ctx.theme);
+        let scroll_lines_state = ScrollLinesState::new(lines, ScrollLinesOptions::default());
+        Self {
+            title,
+            scroll_lines_state,
+            ctx,
+            tx,
+        }
+    }
+
+    pub fn handle_key(&mut self, user_events: Vec<UserEvent>, _key_event: KeyEvent) {
+        handle_user_events! { user_events =>
+            UserEvent::CommandOutputBack => {
+                self.tx.send(AppEventType::CloseCurrentPage);
+            }
+            UserEvent::CommandOutputDown => {
+                self.scroll_lines_state.scroll_forward();
+            }
+            UserEvent::CommandOutputUp => {
+                self.scroll_lines_state.scroll_backward();
+            }
+            UserEvent::CommandOutputPageDown => {
+                self.scroll_lines_state.scroll_page_forward();
+            }
+            UserEvent::CommandOutputPageUp => {
+                self.scroll_lines_state.scroll_page_backward();
+            }
+            UserEvent::CommandOutputGoToTop => {
+                self.scroll_lines_state.scroll_to_top();
+            }
+            UserEvent::CommandOutputGoToBottom => {
+                self.scroll_lines_state.scroll_to_end();
+            }
+            UserEvent::CommandOutputLeft => {
+                self.scroll_lines_state.scroll_left();
+            }
+            UserEvent::CommandOutputRight => {
+                self.scroll_lines_state.scroll_right();
+            }
+            UserEvent::CommandOutputToggleWrap => {
+                self.scroll_lines_state.toggle_wrap();
+            }
+            UserEvent::CommandOutputToggleNumber => {
+                self.scroll_lines_state.toggle_number();
+            }
+            UserEvent::Help => {
+                self.tx.send(AppEventType::OpenHelp);
+            }
+        }
+    }
+
+    pub fn handle_mouse(&mut self, input: MouseInput) -> Option<UserEvent> {
+        match input {
+            MouseInput::ScrollDown(_) => Some(UserEvent::CommandOutputDown),
+            MouseInput::ScrollUp(_) => Some(UserEvent::CommandOutputUp),
+            MouseInput::Click(_) | MouseInput::DoubleClick(_) => None,
+        }
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let scroll_lines = ScrollLines::default()
+            .block(Block::bordered().title(self.title.clone()))
+            .theme(&self.ctx.theme);
+        f.render_stateful_widget(scroll_lines, area, &mut self.scroll_lines_state);
+    }
+
+    pub fn helps(&self, mapper: &UserEventMapper) -> Vec<Spans> {
+        build_help_spans(self.help_items(), mapper, self.ctx.theme.help_key_fg)
+    }
+
+    pub fn help_items(&self) -> Vec<BuildHelpsItem> {
+        #[rustfmt::skip]
+        let helps = vec![
+            BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+            BuildHelpsItem::new(UserEvent::CommandOutputDown, "Scroll forward"),
+            BuildHelpsItem::new(UserEvent::CommandOutputUp, "Scroll backward"),
+            BuildHelpsItem::new(UserEvent::CommandOutputPageDown, "Scroll page forward"),
+            BuildHelpsItem::new(UserEvent::CommandOutputPageUp, "Scroll page backward"),
+            BuildHelpsItem::new(UserEvent::CommandOutputGoToTop, "Scroll to top"),
+            BuildHelpsItem::new(UserEvent::CommandOutputGoToBottom, "Scroll to end"),
+            BuildHelpsItem::new(UserEvent::CommandOutputLeft, "Scroll left"),
+            BuildHelpsItem::new(UserEvent::CommandOutputRight, "Scroll right"),
+            BuildHelpsItem::new(UserEvent::CommandOutputToggleWrap, "Toggle wrap"),
+            BuildHelpsItem::new(UserEvent::CommandOutputToggleNumber, "Toggle number"),
+            BuildHelpsItem::new(UserEvent::CommandOutputBack, "Close output"),
+        ];
+        helps
+    }
+
+    pub fn short_helps(&self, mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
+        #[rustfmt::skip]
+        let helps = vec![
+            BuildShortHelpsItem::single(UserEvent::Quit, "Quit", 0),
+            BuildShortHelpsItem::group(vec![UserEvent::CommandOutputDown, UserEvent::CommandOutputUp], "Scroll", 2),
+            BuildShortHelpsItem::group(vec![UserEvent::CommandOutputGoToTop, UserEvent::CommandOutputGoToBottom], "Top/End", 3),
+            BuildShortHelpsItem::single(UserEvent::CommandOutputBack, "Close", 1),
+            BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+        ];
+        build_short_help_spans(helps, mapper)
+    }
+}
+
+fn build_lines(output: &CommandOutput, theme: &ColorTheme) -> Vec<Line<'static>> {
+    let stdout = to_output_string(&output.stdout);
+    let stderr = to_output_string(&output.stderr);
+
+    // the output may contain color escape sequences (e.g. `jq -C`)
+    let mut lines: Vec<Line<'static>> = match stdout.into_text() {
+        Ok(text) => text.lines,
+        Err(_) => stdout.lines().map(|l| Line::raw(l.to_string())).collect(),
+    };
+    lines.extend(
+        stderr
+            .lines()
+            .map(|l| Line::raw(l.to_string()).fg(theme.status_error)),
+    );
+    lines
+}
+
+fn to_output_string(bytes: &[u8]) -> String {
+    // tab is not rendered correctly, so replace it
+    String::from_utf8_lossy(bytes).replace('\t', "    ")
+}
+
+#[cfg(all(test, not(windows)))]
+mod tests {
+    use std::{os::unix::process::ExitStatusExt, process::ExitStatus};
+
+    use crate::{event::Sender, set_cells};
+
+    use super::*;
+    use ratatui::{backend::TestBackend, buffer::Buffer, style::Color, Terminal};
+
+    #[tokio::test]
+    async fn test_render() -> std::io::Result<()> {
+        let ctx = Rc::default();
+        let tx = sender();
+        let mut terminal = setup_terminal()?;
+
+        terminal.draw(|f| {
+            let output = CommandOutput {
+                status: ExitStatus::from_raw(1 << 8), // exit code 1
+                stdout: b"{\n\t\"a\": 1\n}\n".to_vec(),
+                stderr: b"warning\n".to_vec(),
+            };
+            let mut page = CommandOutputPage::new("jq .".into(), output, ctx, tx);
+            let area = Rect::new(0, 0, 40, 8);
+            page.render(f, area);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌Output [jq .] (exit status: 1)────────┐",
+            "│ 1 {                                  │",
+            "│ 2     \"a\": 1                         │",
+            "│ 3 }                                  │",
+            "│ 4 warning                            │",
+            "│                                      │",
+            "│                                      │",
+            "└──────────────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            // line numbers
+            (2..3, [1, 2, 3, 4]) => fg: Color::DarkGray,
+            // stderr
+            (4..11, [4]) => fg: Color::Red,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
+    fn setup_terminal() -> std::io::Result<Terminal<TestBackend>> {
+        let backend = TestBackend::new(40, 8);
+        let mut terminal = Terminal::new(backend)?;
+        terminal.clear()?;
+        Ok(terminal)
+    }
+
+    fn sender() -> Sender {
+        let (tx, _) = tokio::sync::mpsc::unbounded_channel();
+        Sender::new(tx)
+    }
+}
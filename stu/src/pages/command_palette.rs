@@ -0,0 +1,356 @@
+use std::rc::Rc;
+
+use ratatui::{
+    crossterm::event::KeyEvent,
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Padding, Paragraph},
+    Frame,
+};
+
+use crate::{
+    app::AppContext,
+    color::ColorTheme,
+    event::{AppEventType, Sender},
+    handle_user_events_with_default,
+    help::{build_short_help_spans, BuildShortHelpsItem, CommandPaletteItem, SpansWithPriority},
+    keys::{UserEvent, UserEventMapper},
+    util::fuzzy_match,
+    widget::{calc_centered_dialog_rect, Dialog, InputDialog, InputDialogState, ScrollListState},
+};
+
+const MAX_WIDTH: u16 = 60;
+
+#[derive(Debug)]
+pub struct CommandPalettePage {
+    items: Vec<CommandPaletteItem>,
+    // indices of the matched items (sorted by score) and the char indices of the matched characters
+    view_items: Vec<(usize, Vec<usize>)>,
+
+    list_state: ScrollListState,
+    input_state: InputDialogState,
+
+    ctx: Rc<AppContext>,
+    tx: Sender,
+}
+
+impl CommandPalettePage {
+    pub fn new(items: Vec<CommandPaletteItem>, ctx: Rc<AppContext>, tx: Sender) -> Self {
+        let view_items: Vec<_> = (0..items.len()).map(|i| (i, Vec::new())).collect();
+        let list_state = ScrollListState::new(view_items.len());
+        Self {
+            items,
+            view_items,
+            list_state,
+            input_state: InputDialogState::default(),
+            ctx,
+            tx,
+        }
+    }
+
+    pub fn handle_key(&mut self, user_events: Vec<UserEvent>, key_event: KeyEvent) {
+        handle_user_events_with_default! { user_events =>
+            UserEvent::CommandPaletteClose => {
+                self.tx.send(AppEventType::CloseCurrentPage);
+            }
+            UserEvent::CommandPaletteExecute => {
+                self.execute_selected();
+            }
+            UserEvent::CommandPaletteDown => {
+                if self.non_empty() {
+                    self.list_state.select_next();
+                }
+            }
+            UserEvent::CommandPaletteUp => {
+                if self.non_empty() {
+                    self.list_state.select_prev();
+                }
+            }
+            => {
+                self.input_state.handle_key_event(key_event);
+                self.filter_view_items();
+            }
+        }
+    }
+
+    // all keys except the palette's own are typed into the input
+    pub fn is_text_input_active(&self) -> bool {
+        true
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let input_dialog = InputDialog::default()
+            .title("Command palette")
+            .max_width(MAX_WIDTH)
+            .theme(&self.ctx.theme);
+        f.render_stateful_widget(input_dialog, area, &mut self.input_state);
+
+        let (cursor_x, cursor_y) = self.input_state.cursor();
+        f.set_cursor_position((cursor_x, cursor_y));
+
+        if self.view_items.is_empty() {
+            return;
+        }
+
+        // place the list just below the input dialog
+        let dialog_width = (area.width - 4).min(MAX_WIDTH);
+        let input_area = calc_centered_dialog_rect(area, dialog_width, 3);
+        let max_list_height = area.bottom().saturating_sub(input_area.bottom() + 1);
+        let list_height = (self.view_items.len() as u16 + 2).min(max_list_height);
+        if list_height <= 2 {
+            return;
+        }
+        let [_, list_area, _] = Layout::vertical([
+            Constraint::Length(input_area.bottom() - area.top()),
+            Constraint::Length(list_height),
+            Constraint::Min(0),
+        ])
+        .areas(area);
+        let list_area = Rect {
+            x: input_area.x,
+            width: input_area.width,
+            ..list_area
+        };
+
+        let show_item_count = list_height as usize - 2 /* border */;
+        self.list_state.fit_height(show_item_count);
+        let lines = build_lines(
+            &self.items,
+            &self.view_items,
+            self.list_state.offset,
+            self.list_state.selected,
+            show_item_count,
+            list_area.width.saturating_sub(4 /* border + pad */),
+            &self.ctx.theme,
+        );
+
+        let theme = &self.ctx.theme;
+        let content = Paragraph::new(lines).block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .bg(theme.bg)
+                .fg(theme.fg)
+                .padding(Padding::horizontal(1)),
+        );
+        let dialog = Dialog::new(Box::new(content), theme.bg);
+        f.render_widget_ref(dialog, list_area);
+    }
+
+    pub fn short_helps(&self, mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
+        #[rustfmt::skip]
+        let helps = vec![
+            BuildShortHelpsItem::single(UserEvent::CommandPaletteClose, "Close", 2),
+            BuildShortHelpsItem::group(vec![UserEvent::CommandPaletteDown, UserEvent::CommandPaletteUp], "Select", 3),
+            BuildShortHelpsItem::single(UserEvent::CommandPaletteExecute, "Execute", 1),
+        ];
+        build_short_help_spans(helps, mapper)
+    }
+}
+
+impl CommandPalettePage {
+    fn filter_view_items(&mut self) {
+        let input = self.input_state.input();
+        let mut view_items: Vec<(usize, usize, Vec<usize>)> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(i, item)| {
+                fuzzy_match(input, &item.description).map(|(score, indices)| (i, score, indices))
+            })
+            .collect();
+        // stable sort, so the items with the same score are kept in the order of the help
+        view_items.sort_by_key(|(_, score, _)| std::cmp::Reverse(*score));
+        self.view_items = view_items
+            .into_iter()
+            .map(|(i, _, indices)| (i, indices))
+            .collect();
+        // reset list state
+        self.list_state = ScrollListState::new(self.view_items.len());
+    }
+
+    fn execute_selected(&self) {
+        if let Some((i, _)) = self.view_items.get(self.list_state.selected) {
+            let event = self.items[*i].event;
+            self.tx.send(AppEventType::ExecuteUserEvent(event));
+        }
+    }
+
+    fn non_empty(&self) -> bool {
+        !self.view_items.is_empty()
+    }
+}
+
+fn build_lines<'a>(
+    items: &'a [CommandPaletteItem],
+    view_items: &'a [(usize, Vec<usize>)],
+    offset: usize,
+    selected: usize,
+    show_item_count: usize,
+    width: u16,
+    theme: &ColorTheme,
+) -> Vec<Line<'a>> {
+    view_items
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(show_item_count)
+        .map(|(idx, (i, indices))| {
+            let selected = idx == selected;
+            build_line(&items[*i], indices, selected, width, theme)
+        })
+        .collect()
+}
+
+fn build_line<'a>(
+    item: &'a CommandPaletteItem,
+    matched_indices: &[usize],
+    selected: bool,
+    width: u16,
+    theme: &ColorTheme,
+) -> Line<'a> {
+    let keys_w = console::measure_text_width(&item.keys);
+    let desc_w = (width as usize).saturating_sub(keys_w + 1);
+    let desc = console::truncate_str(&item.description, desc_w, "...");
+    let pad_w = (width as usize).saturating_sub(console::measure_text_width(&desc) + keys_w);
+
+    let mut spans: Vec<Span> = desc
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let span = Span::raw(c.to_string());
+            if matched_indices.contains(&i) {
+                span.fg(theme.list_filter_match)
+            } else {
+                span
+            }
+        })
+        .collect();
+    spans.push(" ".repeat(pad_w).into());
+    spans.push(item.keys.as_str().fg(theme.help_key_fg));
+
+    let style = if selected {
+        Style::default()
+            .bg(theme.list_selected_bg)
+            .fg(theme.list_selected_fg)
+            .add_modifier(theme.list_selected_modifier)
+    } else {
+        Style::default()
+    };
+    Line::from(spans).style(style)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::set_cells;
+
+    use super::*;
+    use ratatui::{
+        backend::TestBackend, buffer::Buffer, crossterm::event::KeyCode, style::Color, Terminal,
+    };
+
+    #[tokio::test]
+    async fn test_render() -> std::io::Result<()> {
+        let ctx = Rc::default();
+        let tx = sender();
+        let mut terminal = setup_terminal()?;
+
+        let mut page = CommandPalettePage::new(items(), ctx, tx);
+        let area = Rect::new(0, 0, 40, 12);
+
+        terminal.draw(|f| {
+            page.render(f, area);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "                                        ",
+            "                                        ",
+            "                                        ",
+            "                                        ",
+            "  ╭Command palette───────────────────╮  ",
+            "  │                                  │  ",
+            "  ╰──────────────────────────────────╯  ",
+            "  ╭──────────────────────────────────╮  ",
+            "  │ Download object              <s> │  ",
+            "  │ Download object as       <S> <a> │  ",
+            "  ╰──────────────────────────────────╯  ",
+            "                                        ",
+        ]);
+        set_cells! { expected =>
+            // selected item
+            (4..36, [8]) => bg: Color::Cyan, fg: Color::Black,
+            // keys
+            (33..36, [8]) => fg: Color::Yellow,
+            (29..36, [9]) => fg: Color::Yellow,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_filter_and_execute() {
+        let ctx = Rc::default();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let tx = Sender::new(tx);
+
+        let mut terminal = setup_terminal().unwrap();
+
+        let mut page = CommandPalettePage::new(items(), ctx, tx);
+        for c in "dla".chars() {
+            page.handle_key(vec![], KeyEvent::from(KeyCode::Char(c)));
+        }
+        assert_eq!(page.view_items.len(), 2);
+        assert_eq!(page.view_items[0], (0, vec![0, 4, 6]));
+
+        terminal.draw(|f| page.render(f, f.area())).unwrap();
+
+        page.handle_key(
+            vec![UserEvent::CommandPaletteDown],
+            KeyEvent::from(KeyCode::Down),
+        );
+        page.handle_key(
+            vec![UserEvent::CommandPaletteExecute],
+            KeyEvent::from(KeyCode::Enter),
+        );
+
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            AppEventType::ExecuteUserEvent(UserEvent::ObjectListDownloadObjectAs)
+        ));
+    }
+
+    fn setup_terminal() -> std::io::Result<Terminal<TestBackend>> {
+        let backend = TestBackend::new(40, 12);
+        let mut terminal = Terminal::new(backend)?;
+        terminal.clear()?;
+        Ok(terminal)
+    }
+
+    fn sender() -> Sender {
+        let (tx, _) = tokio::sync::mpsc::unbounded_channel();
+        Sender::new(tx)
+    }
+
+    fn items() -> Vec<CommandPaletteItem> {
+        vec![
+            CommandPaletteItem {
+                event: UserEvent::ObjectListDownloadObject,
+                description: "Download object".into(),
+                keys: "<s>".into(),
+            },
+            CommandPaletteItem {
+                event: UserEvent::ObjectListDownloadObjectAs,
+                description: "Download object as".into(),
+                keys: "<S> <a>".into(),
+            },
+            CommandPaletteItem {
+                event: UserEvent::ObjectListCopyDetails,
+                description: "Copy details".into(),
+                keys: "<r>".into(),
+            },
+        ]
+    }
+}
@@ -0,0 +1,748 @@
+use std::rc::Rc;
+
+use ratatui::{
+    crossterm::event::KeyEvent,
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::{
+    app::AppContext,
+    color::ColorTheme,
+    event::{AppEventType, Sender},
+    handle_user_events, handle_user_events_with_default,
+    help::{
+        build_help_spans, build_short_help_spans, BuildHelpsItem, BuildShortHelpsItem, Spans,
+        SpansWithPriority,
+    },
+    keys::{UserEvent, UserEventMapper},
+    mouse::MouseInput,
+    object::{ObjectItem, ObjectKey},
+    pages::object_list::ObjectListPage,
+    transfer::{Transfer, TransferMode},
+    widget::{ConfirmDialog, ConfirmDialogState, InputDialog, InputDialogState},
+};
+
+const ELLIPSIS: &str = "...";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommanderPane {
+    Left,
+    Right,
+}
+
+impl CommanderPane {
+    fn other(self) -> CommanderPane {
+        match self {
+            CommanderPane::Left => CommanderPane::Right,
+            CommanderPane::Right => CommanderPane::Left,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CommanderPage {
+    left: Box<ObjectListPage>,
+    right: Box<ObjectListPage>,
+    active: CommanderPane,
+    // the connection of each pane, None is the connection specified at startup
+    left_connection: Option<String>,
+    right_connection: Option<String>,
+
+    view_state: ViewState,
+
+    ctx: Rc<AppContext>,
+    tx: Sender,
+}
+
+#[derive(Debug)]
+enum ViewState {
+    Default,
+    TransferConfirmDialog(Transfer, ConfirmDialogState),
+    GoToDialog(InputDialogState),
+}
+
+impl CommanderPage {
+    pub fn new(
+        left: ObjectListPage,
+        right: ObjectListPage,
+        connection: Option<String>,
+        ctx: Rc<AppContext>,
+        tx: Sender,
+    ) -> Self {
+        let mut page = Self {
+            left: Box::new(compact(left)),
+            right: Box::new(compact(right)),
+            active: CommanderPane::Left,
+            left_connection: connection.clone(),
+            right_connection: connection,
+            view_state: ViewState::Default,
+            ctx,
+            tx,
+        };
+        page.update_focus();
+        page
+    }
+
+    pub fn handle_key(&mut self, user_events: Vec<UserEvent>, key_event: KeyEvent) {
+        match self.view_state {
+            ViewState::Default => {
+                if self.active_pane().is_dialog_open() {
+                    // filter, sort or copy dialog of the pane
+                    self.active_pane_mut().handle_key(user_events, key_event);
+                    return;
+                }
+                handle_user_events! { user_events =>
+                    UserEvent::CommanderClose => {
+                        self.tx.send(AppEventType::CloseCurrentPage);
+                    }
+                    UserEvent::CommanderSwitchPane => {
+                        self.switch_pane();
+                    }
+                    UserEvent::CommanderCopy if self.active_pane().non_empty() => {
+                        self.open_transfer_confirm_dialog(TransferMode::Copy);
+                    }
+                    UserEvent::CommanderMove if self.active_pane().non_empty() => {
+                        self.open_transfer_confirm_dialog(TransferMode::Move);
+                    }
+                    UserEvent::CommanderGoTo => {
+                        self.open_go_to_dialog();
+                    }
+                    UserEvent::ObjectListSelect if self.active_pane().non_empty() => {
+                        self.open_selected_dir();
+                    }
+                    UserEvent::ObjectListBack => {
+                        self.open_parent_dir();
+                    }
+                    UserEvent::ObjectListRefresh => {
+                        self.refresh();
+                    }
+                    event @ (UserEvent::ObjectListDown
+                    | UserEvent::ObjectListUp
+                    | UserEvent::ObjectListGoToTop
+                    | UserEvent::ObjectListGoToBottom
+                    | UserEvent::ObjectListPageDown
+                    | UserEvent::ObjectListPageUp
+                    | UserEvent::ObjectListFilter
+                    | UserEvent::ObjectListResetFilter
+                    | UserEvent::ObjectListSort
+                    | UserEvent::ObjectListCopyDetails) => {
+                        self.active_pane_mut().handle_key(vec![*event], key_event);
+                    }
+                    UserEvent::Help => {
+                        self.tx.send(AppEventType::OpenHelp);
+                    }
+                }
+            }
+            ViewState::TransferConfirmDialog(_, ref mut state) => {
+                handle_user_events! { user_events =>
+                    UserEvent::SelectDialogClose => {
+                        self.close_transfer_confirm_dialog();
+                    }
+                    UserEvent::SelectDialogLeft | UserEvent::SelectDialogRight => {
+                        state.toggle();
+                    }
+                    UserEvent::SelectDialogSelect => {
+                        self.transfer();
+                    }
+                    UserEvent::Help => {
+                        self.tx.send(AppEventType::OpenHelp);
+                    }
+                }
+            }
+            ViewState::GoToDialog(ref mut state) => {
+                handle_user_events_with_default! { user_events =>
+                    UserEvent::InputDialogClose => {
+                        self.close_go_to_dialog();
+                    }
+                    UserEvent::InputDialogApply => {
+                        let input = state.input().to_string();
+                        self.go_to(input);
+                    }
+                    UserEvent::Help => {
+                        self.tx.send(AppEventType::OpenHelp);
+                    }
+                    => {
+                        state.handle_key_event(key_event);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn is_text_input_active(&self) -> bool {
+        match self.view_state {
+            ViewState::Default => self.active_pane().is_text_input_active(),
+            ViewState::GoToDialog(_) => true,
+            ViewState::TransferConfirmDialog(..) => false,
+        }
+    }
+
+    pub fn handle_mouse(&mut self, input: MouseInput, area: Rect) -> Option<UserEvent> {
+        if !matches!(self.view_state, ViewState::Default) {
+            return None;
+        }
+        let position = input.position();
+        let [left_area, right_area] = pane_areas(area);
+        let (pane, pane_area) = if left_area.contains(position) {
+            (CommanderPane::Left, left_area)
+        } else if right_area.contains(position) {
+            (CommanderPane::Right, right_area)
+        } else {
+            return None;
+        };
+        if pane != self.active && !self.active_pane().is_dialog_open() {
+            self.active = pane;
+            self.update_focus();
+        }
+        if pane != self.active {
+            return None;
+        }
+        let [_, list_area] = split_title_area(pane_area);
+        self.active_pane_mut().handle_mouse(input, list_area)
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let [left_area, right_area] = pane_areas(area);
+        // the connections are shown only if the panes use different ones
+        let show_connection = self.left_connection != self.right_connection;
+        for (pane, pane_area) in [
+            (CommanderPane::Left, left_area),
+            (CommanderPane::Right, right_area),
+        ] {
+            let [title_area, list_area] = split_title_area(pane_area);
+            let active = pane == self.active;
+            let uri = self.pane(pane).current_dir_object_key().prefix_uri();
+            let uri = match self.pane_connection(pane) {
+                Some(connection) if show_connection => format!("{} ({})", uri, connection),
+                _ => uri,
+            };
+            let title = build_title_line(&uri, title_area.width, active, &self.ctx.theme);
+            f.render_widget(Paragraph::new(title), title_area);
+            self.pane_mut(pane).render(f, list_area);
+        }
+
+        if let ViewState::TransferConfirmDialog(transfer, state) = &mut self.view_state {
+            let message_lines = build_transfer_confirm_message_lines(transfer, &self.ctx.theme);
+            let confirm_dialog = ConfirmDialog::new(message_lines).theme(&self.ctx.theme);
+            f.render_stateful_widget(confirm_dialog, area, state);
+        }
+
+        if let ViewState::GoToDialog(state) = &mut self.view_state {
+            let go_to_dialog = InputDialog::default()
+                .title("Go to: s3://<bucket>/<prefix>/")
+                .max_width(70)
+                .theme(&self.ctx.theme);
+            f.render_stateful_widget(go_to_dialog, area, state);
+
+            let (cursor_x, cursor_y) = state.cursor();
+            f.set_cursor_position((cursor_x, cursor_y));
+        }
+    }
+
+    pub fn helps(&self, mapper: &UserEventMapper) -> Vec<Spans> {
+        build_help_spans(self.help_items(), mapper, self.ctx.theme.help_key_fg)
+    }
+
+    pub fn help_items(&self) -> Vec<BuildHelpsItem> {
+        #[rustfmt::skip]
+        let helps = match self.view_state {
+            ViewState::Default if self.active_pane().is_dialog_open() => {
+                return self.active_pane().help_items();
+            }
+            ViewState::Default => {
+                vec![
+                    BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+                    BuildHelpsItem::new(UserEvent::CommanderClose, "Close commander"),
+                    BuildHelpsItem::new(UserEvent::CommanderSwitchPane, "Switch active pane"),
+                    BuildHelpsItem::new(UserEvent::ObjectListDown, "Select next item"),
+                    BuildHelpsItem::new(UserEvent::ObjectListUp, "Select previous item"),
+                    BuildHelpsItem::new(UserEvent::ObjectListGoToTop, "Go to top"),
+                    BuildHelpsItem::new(UserEvent::ObjectListGoToBottom, "Go to bottom"),
+                    BuildHelpsItem::new(UserEvent::ObjectListPageDown, "Scroll page forward"),
+                    BuildHelpsItem::new(UserEvent::ObjectListPageUp, "Scroll page backward"),
+                    BuildHelpsItem::new(UserEvent::ObjectListSelect, "Open folder"),
+                    BuildHelpsItem::new(UserEvent::ObjectListBack, "Go back to parent folder"),
+                    BuildHelpsItem::new(UserEvent::CommanderGoTo, "Go to location"),
+                    BuildHelpsItem::new(UserEvent::CommanderCopy, "Copy to the other pane"),
+                    BuildHelpsItem::new(UserEvent::CommanderMove, "Move to the other pane"),
+                    BuildHelpsItem::new(UserEvent::ObjectListFilter, "Filter object list"),
+                    BuildHelpsItem::new(UserEvent::ObjectListResetFilter, "Clear filter"),
+                    BuildHelpsItem::new(UserEvent::ObjectListSort, "Sort object list"),
+                    BuildHelpsItem::new(UserEvent::ObjectListCopyDetails, "Open copy dialog"),
+                    BuildHelpsItem::new(UserEvent::ObjectListRefresh, "Refresh object list"),
+                ]
+            }
+            ViewState::TransferConfirmDialog(..) => {
+                vec![
+                    BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+                    BuildHelpsItem::new(UserEvent::SelectDialogClose, "Close confirm dialog"),
+                    BuildHelpsItem::new(UserEvent::SelectDialogRight, "Select next"),
+                    BuildHelpsItem::new(UserEvent::SelectDialogLeft, "Select previous"),
+                    BuildHelpsItem::new(UserEvent::SelectDialogSelect, "Confirm"),
+                ]
+            }
+            ViewState::GoToDialog(_) => {
+                vec![
+                    BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+                    BuildHelpsItem::new(UserEvent::InputDialogClose, "Close go to dialog"),
+                    BuildHelpsItem::new(UserEvent::InputDialogApply, "Open location in the active pane"),
+                ]
+            }
+        };
+        helps
+    }
+
+    pub fn short_helps(&self, mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
+        #[rustfmt::skip]
+        let helps = match self.view_state {
+            ViewState::Default if self.active_pane().is_dialog_open() => {
+                return self.active_pane().short_helps(mapper);
+            }
+            ViewState::Default => {
+                vec![
+                    BuildShortHelpsItem::single(UserEvent::Quit, "Quit", 0),
+                    BuildShortHelpsItem::single(UserEvent::CommanderSwitchPane, "Switch", 1),
+                    BuildShortHelpsItem::group(vec![UserEvent::ObjectListDown, UserEvent::ObjectListUp], "Select", 5),
+                    BuildShortHelpsItem::single(UserEvent::ObjectListSelect, "Open", 3),
+                    BuildShortHelpsItem::single(UserEvent::ObjectListBack, "Go back", 4),
+                    BuildShortHelpsItem::single(UserEvent::CommanderCopy, "Copy", 2),
+                    BuildShortHelpsItem::single(UserEvent::CommanderMove, "Move", 2),
+                    BuildShortHelpsItem::single(UserEvent::CommanderGoTo, "Go to", 6),
+                    BuildShortHelpsItem::single(UserEvent::CommanderClose, "Close", 1),
+                    BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+                ]
+            }
+            ViewState::TransferConfirmDialog(..) => {
+                vec![
+                    BuildShortHelpsItem::single(UserEvent::SelectDialogClose, "Close", 2),
+                    BuildShortHelpsItem::group(vec![UserEvent::SelectDialogLeft, UserEvent::SelectDialogRight], "Select", 3),
+                    BuildShortHelpsItem::single(UserEvent::SelectDialogSelect, "Confirm", 1),
+                    BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+                ]
+            }
+            ViewState::GoToDialog(_) => {
+                vec![
+                    BuildShortHelpsItem::single(UserEvent::InputDialogClose, "Close", 2),
+                    BuildShortHelpsItem::single(UserEvent::InputDialogApply, "Go", 1),
+                    BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+                ]
+            }
+        };
+        build_short_help_spans(helps, mapper)
+    }
+}
+
+impl CommanderPage {
+    pub fn active(&self) -> CommanderPane {
+        self.active
+    }
+
+    pub fn pane(&self, pane: CommanderPane) -> &ObjectListPage {
+        match pane {
+            CommanderPane::Left => &self.left,
+            CommanderPane::Right => &self.right,
+        }
+    }
+
+    fn pane_mut(&mut self, pane: CommanderPane) -> &mut ObjectListPage {
+        match pane {
+            CommanderPane::Left => &mut self.left,
+            CommanderPane::Right => &mut self.right,
+        }
+    }
+
+    pub fn active_pane(&self) -> &ObjectListPage {
+        self.pane(self.active)
+    }
+
+    fn active_pane_mut(&mut self) -> &mut ObjectListPage {
+        self.pane_mut(self.active)
+    }
+
+    pub fn pane_connection(&self, pane: CommanderPane) -> Option<&str> {
+        match pane {
+            CommanderPane::Left => self.left_connection.as_deref(),
+            CommanderPane::Right => self.right_connection.as_deref(),
+        }
+    }
+
+    pub fn set_pane_connection(&mut self, pane: CommanderPane, connection: Option<String>) {
+        match pane {
+            CommanderPane::Left => self.left_connection = connection,
+            CommanderPane::Right => self.right_connection = connection,
+        }
+    }
+
+    pub fn set_pane(&mut self, pane: CommanderPane, page: ObjectListPage) {
+        *self.pane_mut(pane) = compact(page);
+        self.update_focus();
+    }
+
+    // the current directory and the name of the selected item, to restore the pane after reloading
+    pub fn pane_location(&self, pane: CommanderPane) -> (ObjectKey, Option<String>) {
+        let page = self.pane(pane);
+        let name = page
+            .non_empty()
+            .then(|| page.current_selected_item().name().to_string());
+        (page.current_dir_object_key().clone(), name)
+    }
+
+    fn switch_pane(&mut self) {
+        self.active = self.active.other();
+        self.update_focus();
+    }
+
+    fn update_focus(&mut self) {
+        let active = self.active;
+        self.left.set_focused(active == CommanderPane::Left);
+        self.right.set_focused(active == CommanderPane::Right);
+    }
+
+    fn open_selected_dir(&self) {
+        let page = self.active_pane();
+        if let ObjectItem::Dir { .. } = page.current_selected_item() {
+            let object_key = page.current_selected_object_key();
+            self.tx.send(AppEventType::CommanderLoadObjects(
+                self.active,
+                object_key,
+                None,
+            ));
+        }
+    }
+
+    fn open_parent_dir(&self) {
+        let current_key = self.active_pane().current_dir_object_key();
+        if let Some(parent_key) = current_key.parent() {
+            // select the directory that was opened
+            let name = current_key.object_path.last().cloned();
+            self.tx.send(AppEventType::CommanderLoadObjects(
+                self.active,
+                parent_key,
+                name,
+            ));
+        }
+    }
+
+    fn refresh(&self) {
+        self.tx.send(AppEventType::CommanderRefresh(self.active));
+    }
+
+    fn open_go_to_dialog(&mut self) {
+        let mut state = InputDialogState::default();
+        let uri = self.active_pane().current_dir_object_key().prefix_uri();
+        state.set_input(uri);
+        self.view_state = ViewState::GoToDialog(state);
+    }
+
+    fn close_go_to_dialog(&mut self) {
+        self.view_state = ViewState::Default;
+    }
+
+    fn go_to(&mut self, input: String) {
+        if input.trim().is_empty() {
+            return;
+        }
+        let Some(object_key) = ObjectKey::from_prefix_uri(&input) else {
+            let msg = format!("Invalid S3 URI: {}", input.trim());
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        };
+        self.tx.send(AppEventType::CommanderLoadObjects(
+            self.active,
+            object_key,
+            None,
+        ));
+        self.close_go_to_dialog();
+    }
+
+    fn open_transfer_confirm_dialog(&mut self, mode: TransferMode) {
+        let page = self.active_pane();
+        let source_is_dir = matches!(page.current_selected_item(), ObjectItem::Dir { .. });
+        let other = self.active.other();
+        let transfer = Transfer {
+            mode,
+            source: page.current_selected_object_key(),
+            source_is_dir,
+            destination: self.pane(other).current_dir_object_key().clone(),
+            source_connection: self.pane_connection(self.active).map(String::from),
+            destination_connection: self.pane_connection(other).map(String::from),
+        };
+        if let Err(msg) = transfer.validate() {
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        }
+        self.view_state = ViewState::TransferConfirmDialog(transfer, ConfirmDialogState::default());
+    }
+
+    fn close_transfer_confirm_dialog(&mut self) {
+        self.view_state = ViewState::Default;
+    }
+
+    fn transfer(&mut self) {
+        if let ViewState::TransferConfirmDialog(transfer, state) =
+            std::mem::replace(&mut self.view_state, ViewState::Default)
+        {
+            if state.is_ok() {
+                self.tx.send(AppEventType::StartTransferObjects(transfer));
+            }
+        }
+    }
+}
+
+// the panes are too narrow to show the last modified date
+fn compact(mut page: ObjectListPage) -> ObjectListPage {
+    page.set_compact(true);
+    page
+}
+
+fn pane_areas(area: Rect) -> [Rect; 2] {
+    Layout::horizontal(Constraint::from_percentages([50, 50])).areas(area)
+}
+
+fn split_title_area(area: Rect) -> [Rect; 2] {
+    Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area)
+}
+
+fn build_title_line<'a>(uri: &str, width: u16, active: bool, theme: &ColorTheme) -> Line<'a> {
+    let title_w = (width as usize).saturating_sub(2 /* spaces */);
+    let title = console::pad_str(uri, title_w, console::Alignment::Left, Some(ELLIPSIS));
+    let title = format!(" {} ", title);
+    if active {
+        let style = Style::default()
+            .bg(theme.list_selected_bg)
+            .fg(theme.list_selected_fg)
+            .bold();
+        Line::from(Span::styled(title, style))
+    } else {
+        Line::from(title.fg(theme.fg))
+    }
+}
+
+fn build_transfer_confirm_message_lines<'a>(
+    transfer: &Transfer,
+    theme: &ColorTheme,
+) -> Vec<Line<'a>> {
+    let target = if transfer.source_is_dir {
+        "folder"
+    } else {
+        "object"
+    };
+    let mut source_uri = if transfer.source_is_dir {
+        transfer.source.prefix_uri()
+    } else {
+        format!(
+            "s3://{}/{}",
+            transfer.source.bucket_name,
+            transfer.source.joined_object_path(true)
+        )
+    };
+    let mut destination_uri = transfer.destination.prefix_uri();
+    if !transfer.is_same_connection() {
+        let label = |connection: &Option<String>| {
+            connection
+                .as_ref()
+                .map_or(String::new(), |c| format!(" ({})", c))
+        };
+        source_uri.push_str(&label(&transfer.source_connection));
+        destination_uri.push_str(&label(&transfer.destination_connection));
+    }
+    let mut lines = vec![
+        Line::from(
+            format!(
+                "You are about to {} the following {}:",
+                transfer.mode.verb(),
+                target
+            )
+            .fg(theme.fg),
+        ),
+        Line::from(""),
+        Line::from(source_uri.fg(theme.fg).bold()),
+        Line::from("to".fg(theme.fg)),
+        Line::from(destination_uri.fg(theme.fg).bold()),
+        Line::from(""),
+    ];
+    if transfer.mode == TransferMode::Move {
+        lines.push(Line::from(
+            "The source objects are deleted after they are copied.".fg(theme.fg),
+        ));
+    }
+    lines.push(Line::from("Do you want to proceed?".fg(theme.fg)));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{event::Sender, set_cells};
+
+    use super::*;
+    use chrono::{DateTime, Local, NaiveDateTime};
+    use ratatui::{
+        backend::TestBackend,
+        buffer::Buffer,
+        crossterm::event::KeyCode,
+        style::{Color, Modifier},
+        Terminal,
+    };
+
+    #[tokio::test]
+    async fn test_render() -> std::io::Result<()> {
+        let ctx = Rc::new(AppContext::default());
+        let tx = sender();
+        let mut terminal = setup_terminal()?;
+
+        let mut page = commander_page(Rc::clone(&ctx), tx);
+        terminal.draw(|f| {
+            let area = Rect::new(0, 0, 60, 8);
+            page.render(f, area);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            " s3://bucket/dir/              s3://other/                  ",
+            "┌───────────────────── 1 / 2 ┐┌───────────────────── 1 / 1 ┐",
+            "│  sub/                      ││  file3                3 B  │",
+            "│  file1                1 B  ││                            │",
+            "│                            ││                            │",
+            "│                            ││                            │",
+            "│                            ││                            │",
+            "└────────────────────────────┘└────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            // active pane title
+            (0..30, [0]) => bg: Color::Cyan, fg: Color::Black, modifier: Modifier::BOLD,
+            // selected item of the active pane
+            (2..28, [2]) => bg: Color::Cyan, fg: Color::Black,
+            (3..26, [2]) => modifier: Modifier::BOLD,
+            // selected item of the inactive pane
+            (32..58, [2]) => bg: Color::DarkGray, fg: Color::Black,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_switch_pane() {
+        let ctx = Rc::new(AppContext::default());
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut page = commander_page(ctx, Sender::new(tx));
+        assert_eq!(page.active(), CommanderPane::Left);
+
+        page.handle_key(vec![UserEvent::CommanderSwitchPane], key_event());
+        assert_eq!(page.active(), CommanderPane::Right);
+
+        // the selected file is copied into the directory of the other pane
+        page.handle_key(vec![UserEvent::CommanderCopy], key_event());
+        page.handle_key(vec![UserEvent::SelectDialogSelect], key_event());
+        let Ok(AppEventType::StartTransferObjects(transfer)) = rx.try_recv() else {
+            panic!("transfer event is not sent");
+        };
+        assert_eq!(
+            transfer,
+            Transfer {
+                mode: TransferMode::Copy,
+                source: ObjectKey::from_prefix_uri("s3://other/file3").unwrap(),
+                source_is_dir: false,
+                destination: ObjectKey::from_prefix_uri("s3://bucket/dir/").unwrap(),
+                source_connection: None,
+                destination_connection: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_transfer_between_connections() {
+        let ctx = Rc::new(AppContext::default());
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let tx = Sender::new(tx);
+        let items = || vec![object_file_item("file1", 1)];
+        let key = ObjectKey::bucket("bucket");
+        let left = ObjectListPage::new(items(), key.clone(), Rc::clone(&ctx), tx.clone());
+        let right = ObjectListPage::new(items(), key.clone(), Rc::clone(&ctx), tx.clone());
+        let mut page = CommanderPage::new(left, right, Some("aws".into()), ctx, tx);
+
+        // the same location can not be the destination with the same connection
+        page.handle_key(vec![UserEvent::CommanderCopy], key_event());
+        assert!(matches!(rx.try_recv(), Ok(AppEventType::NotifyWarn(_))));
+
+        page.set_pane_connection(CommanderPane::Right, Some("minio".into()));
+        page.handle_key(vec![UserEvent::CommanderCopy], key_event());
+        page.handle_key(vec![UserEvent::SelectDialogSelect], key_event());
+        let Ok(AppEventType::StartTransferObjects(transfer)) = rx.try_recv() else {
+            panic!("transfer event is not sent");
+        };
+        assert_eq!(
+            transfer,
+            Transfer {
+                mode: TransferMode::Copy,
+                source: ObjectKey::from_prefix_uri("s3://bucket/file1").unwrap(),
+                source_is_dir: false,
+                destination: key,
+                source_connection: Some("aws".into()),
+                destination_connection: Some("minio".into()),
+            }
+        );
+    }
+
+    fn commander_page(ctx: Rc<AppContext>, tx: Sender) -> CommanderPage {
+        let left_items = vec![object_dir_item("sub"), object_file_item("file1", 1)];
+        let left_key = ObjectKey::from_prefix_uri("s3://bucket/dir/").unwrap();
+        let left = ObjectListPage::new(left_items, left_key, Rc::clone(&ctx), tx.clone());
+
+        let right_items = vec![object_file_item("file3", 3)];
+        let right_key = ObjectKey::bucket("other");
+        let right = ObjectListPage::new(right_items, right_key, Rc::clone(&ctx), tx.clone());
+
+        CommanderPage::new(left, right, None, ctx, tx)
+    }
+
+    fn object_dir_item(name: &str) -> ObjectItem {
+        ObjectItem::Dir {
+            name: name.to_string(),
+            key: "".to_string(),
+            s3_uri: "".to_string(),
+            object_url: "".to_string(),
+        }
+    }
+
+    fn object_file_item(name: &str, size_byte: usize) -> ObjectItem {
+        ObjectItem::File {
+            name: name.to_string(),
+            size_byte,
+            last_modified: parse_datetime("2024-01-02 13:01:02"),
+            key: "".to_string(),
+            s3_uri: "".to_string(),
+            arn: "".to_string(),
+            object_url: "".to_string(),
+            e_tag: "".to_string(),
+        }
+    }
+
+    fn parse_datetime(s: &str) -> DateTime<Local> {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_local_timezone(Local)
+            .unwrap()
+    }
+
+    fn key_event() -> KeyEvent {
+        KeyEvent::from(KeyCode::Null)
+    }
+
+    fn setup_terminal() -> std::io::Result<Terminal<TestBackend>> {
+        let backend = TestBackend::new(60, 8);
+        let mut terminal = Terminal::new(backend)?;
+        terminal.clear()?;
+        Ok(terminal)
+    }
+
+    fn sender() -> Sender {
+        let (tx, _) = tokio::sync::mpsc::unbounded_channel();
+        Sender::new(tx)
+    }
+}
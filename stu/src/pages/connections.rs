@@ -0,0 +1,166 @@
+use std::rc::Rc;
+
+use ratatui::{crossterm::event::KeyEvent, layout::Rect, Frame};
+
+use crate::{
+    app::AppContext,
+    event::{AppEventType, Sender},
+    handle_user_events,
+    help::{
+        build_help_spans, build_short_help_spans, BuildHelpsItem, BuildShortHelpsItem, Spans,
+        SpansWithPriority,
+    },
+    keys::{UserEvent, UserEventMapper},
+    mouse::{handle_dialog_mouse_input, MouseInput},
+    widget::{ConnectionDialog, ConnectionDialogState},
+};
+
+// the connection picker, shown over the page it was opened from
+#[derive(Debug)]
+pub struct ConnectionsPage {
+    state: ConnectionDialogState,
+
+    ctx: Rc<AppContext>,
+    tx: Sender,
+}
+
+impl ConnectionsPage {
+    pub fn new(names: Vec<String>, ctx: Rc<AppContext>, tx: Sender) -> Self {
+        Self {
+            state: ConnectionDialogState::new(names),
+            ctx,
+            tx,
+        }
+    }
+
+    pub fn handle_key(&mut self, user_events: Vec<UserEvent>, _key_event: KeyEvent) {
+        handle_user_events! { user_events =>
+            UserEvent::SelectDialogClose => {
+                self.tx.send(AppEventType::CloseCurrentPage);
+            }
+            UserEvent::SelectDialogDown => {
+                self.state.select_next();
+            }
+            UserEvent::SelectDialogUp => {
+                self.state.select_prev();
+            }
+            UserEvent::SelectDialogSelect => {
+                self.switch_connection();
+            }
+            UserEvent::Help => {
+                self.tx.send(AppEventType::OpenHelp);
+            }
+        }
+    }
+
+    pub fn handle_mouse(&mut self, input: MouseInput, area: Rect) -> Option<UserEvent> {
+        let index = self.state.item_index_at(area, input.position());
+        handle_dialog_mouse_input(input, index, |i| self.state.select_index(i))
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let connection_dialog = ConnectionDialog::default().theme(&self.ctx.theme);
+        f.render_stateful_widget(connection_dialog, area, &mut self.state);
+    }
+
+    pub fn helps(&self, mapper: &UserEventMapper) -> Vec<Spans> {
+        build_help_spans(self.help_items(), mapper, self.ctx.theme.help_key_fg)
+    }
+
+    pub fn help_items(&self) -> Vec<BuildHelpsItem> {
+        #[rustfmt::skip]
+        let helps = vec![
+            BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+            BuildHelpsItem::new(UserEvent::SelectDialogClose, "Close connection dialog"),
+            BuildHelpsItem::new(UserEvent::SelectDialogDown, "Select next item"),
+            BuildHelpsItem::new(UserEvent::SelectDialogUp, "Select previous item"),
+            BuildHelpsItem::new(UserEvent::SelectDialogSelect, "Switch to selected connection"),
+        ];
+        helps
+    }
+
+    pub fn short_helps(&self, mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
+        #[rustfmt::skip]
+        let helps = vec![
+            BuildShortHelpsItem::single(UserEvent::SelectDialogClose, "Close", 2),
+            BuildShortHelpsItem::group(vec![UserEvent::SelectDialogDown, UserEvent::SelectDialogUp], "Select", 3),
+            BuildShortHelpsItem::single(UserEvent::SelectDialogSelect, "Switch", 1),
+            BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+        ];
+        build_short_help_spans(helps, mapper)
+    }
+}
+
+impl ConnectionsPage {
+    fn switch_connection(&self) {
+        // close first so that the connection is switched for the page the picker was opened from
+        self.tx.send(AppEventType::CloseCurrentPage);
+        if let Some(name) = self.state.selected_name() {
+            self.tx
+                .send(AppEventType::StartSwitchConnection(name.into()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::set_cells;
+
+    use super::*;
+    use ratatui::{backend::TestBackend, buffer::Buffer, style::Color, Terminal};
+
+    #[tokio::test]
+    async fn test_switch_connection() {
+        let ctx = Rc::default();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let tx = Sender::new(tx);
+        let mut page = ConnectionsPage::new(vec!["dev".into(), "prod".into()], ctx, tx);
+
+        page.handle_key(vec![UserEvent::SelectDialogDown], key_event());
+        page.handle_key(vec![UserEvent::SelectDialogSelect], key_event());
+
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            AppEventType::CloseCurrentPage
+        ));
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            AppEventType::StartSwitchConnection(name) if name == "prod"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_render() -> std::io::Result<()> {
+        let ctx = Rc::default();
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let tx = Sender::new(tx);
+        let mut terminal = Terminal::new(TestBackend::new(30, 6))?;
+
+        terminal.draw(|f| {
+            let mut page = ConnectionsPage::new(vec!["dev".into(), "prod".into()], ctx, tx);
+            page.render(f, Rect::new(0, 0, 30, 6));
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "                              ",
+            "  ╭Connections─────────────╮  ",
+            "  │ dev                    │  ",
+            "  │ prod                   │  ",
+            "  ╰────────────────────────╯  ",
+            "                              ",
+        ]);
+        set_cells! { expected =>
+            // selected item
+            (4..26, [2]) => fg: Color::Cyan,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
+    fn key_event() -> KeyEvent {
+        KeyEvent::from(ratatui::crossterm::event::KeyCode::Null)
+    }
+}
@@ -20,12 +20,15 @@ use crate::{
         SpansWithPriority,
     },
     keys::{UserEvent, UserEventMapper},
+    mouse::MouseInput,
     widget::Divider,
 };
 
 #[derive(Debug)]
 pub struct HelpPage {
     helps: Vec<Spans>,
+    // the first line of the keybindings to show, clamped when rendering
+    offset: usize,
 
     ctx: Rc<AppContext>,
     tx: Sender,
@@ -33,17 +36,36 @@ pub struct HelpPage {
 
 impl HelpPage {
     pub fn new(helps: Vec<Spans>, ctx: Rc<AppContext>, tx: Sender) -> Self {
-        Self { helps, ctx, tx }
+        Self {
+            helps,
+            offset: 0,
+            ctx,
+            tx,
+        }
     }
 
     pub fn handle_key(&mut self, user_events: Vec<UserEvent>, _key_event: KeyEvent) {
         handle_user_events! { user_events =>
+            UserEvent::HelpDown => {
+                self.offset = self.offset.saturating_add(1);
+            }
+            UserEvent::HelpUp => {
+                self.offset = self.offset.saturating_sub(1);
+            }
             UserEvent::HelpClose => {
                 self.tx.send(AppEventType::CloseCurrentPage);
             }
         }
     }
 
+    pub fn handle_mouse(&mut self, input: MouseInput) -> Option<UserEvent> {
+        match input {
+            MouseInput::ScrollDown(_) => Some(UserEvent::HelpDown),
+            MouseInput::ScrollUp(_) => Some(UserEvent::HelpUp),
+            MouseInput::Click(_) | MouseInput::DoubleClick(_) => None,
+        }
+    }
+
     pub fn render(&mut self, f: &mut Frame, area: Rect) {
         let block = Block::bordered()
             .padding(Padding::horizontal(1))
@@ -68,6 +90,8 @@ impl HelpPage {
         );
         let divider = Divider::default().color(self.ctx.theme.divider);
         let help = Help::new(&self.helps, self.ctx.config.ui.help.max_help_width);
+        self.offset = self.offset.min(help.max_offset(chunks[2]));
+        let help = help.offset(self.offset);
 
         f.render_widget(block, area);
         f.render_widget(about, chunks[0]);
@@ -83,6 +107,7 @@ impl HelpPage {
         #[rustfmt::skip]
         let helps = vec![
             BuildShortHelpsItem::single(UserEvent::Quit, "Quit", 0),
+            BuildShortHelpsItem::group(vec![UserEvent::HelpDown, UserEvent::HelpUp], "Scroll", 1),
             BuildShortHelpsItem::single(UserEvent::HelpClose, "Close help", 0),
         ];
         build_short_help_spans(helps, mapper)
@@ -138,30 +163,51 @@ impl Widget for About<'_> {
 struct Help<'a> {
     helps: &'a [Spans],
     max_width: usize,
+    offset: usize,
 }
 
 impl<'a> Help<'a> {
     fn new(helps: &'a [Spans], max_width: usize) -> Self {
-        Self { helps, max_width }
+        Self {
+            helps,
+            max_width,
+            offset: 0,
+        }
+    }
+
+    fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    fn max_width(&self, area: Rect) -> usize {
+        self.max_width.min(area.width as usize) - 2
+    }
+
+    // the offset at which the last line is shown at the bottom
+    fn max_offset(&self, area: Rect) -> usize {
+        let line_count = build_help_lines(self.helps, self.max_width(area)).len();
+        let height = (area.height as usize).saturating_sub(2 /* padding */);
+        line_count.saturating_sub(height)
     }
 }
 
 impl Widget for Help<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let max_width = self.max_width.min(area.width as usize) - 2;
-
-        let help = build_help_lines(self.helps, max_width);
-
-        let paragraph = Paragraph::new(help).block(
-            Block::default()
-                .borders(Borders::NONE)
-                .padding(Padding::uniform(1)),
-        );
+        let help = build_help_lines(self.helps, self.max_width(area));
+
+        let paragraph = Paragraph::new(help)
+            .block(
+                Block::default()
+                    .borders(Borders::NONE)
+                    .padding(Padding::uniform(1)),
+            )
+            .scroll((self.offset as u16, 0));
         paragraph.render(area, buf);
     }
 }
 
-fn build_help_lines(helps: &[Spans], max_width: usize) -> Vec<Line> {
+fn build_help_lines(helps: &[Spans], max_width: usize) -> Vec<Line<'_>> {
     let word_groups = group_spans_to_fit_width(helps, max_width, "  ");
     let lines: Vec<Line> = word_groups.into_iter().map(Line::from).collect();
     with_empty_lines(lines)
@@ -184,7 +230,9 @@ mod tests {
     use crate::set_cells;
 
     use super::*;
-    use ratatui::{backend::TestBackend, buffer::Buffer, text::Span, Terminal};
+    use ratatui::{
+        backend::TestBackend, buffer::Buffer, crossterm::event::KeyCode, text::Span, Terminal,
+    };
 
     #[tokio::test]
     async fn test_render() -> std::io::Result<()> {
@@ -242,6 +290,42 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_scroll() -> std::io::Result<()> {
+        let ctx = Rc::default();
+        let tx = sender();
+        let mut terminal = setup_terminal()?;
+
+        let helps = (0..10)
+            .map(|i| {
+                Spans::new(vec![Span::raw(
+                    format!("<key{}>: action{} ", i, i).repeat(3),
+                )])
+            })
+            .collect();
+        let mut page = HelpPage::new(helps, ctx, tx);
+        assert!(matches!(
+            page.handle_mouse(MouseInput::ScrollDown((0, 0).into())),
+            Some(UserEvent::HelpDown)
+        ));
+        for _ in 0..100 {
+            page.handle_key(vec![UserEvent::HelpDown], KeyEvent::from(KeyCode::Null));
+        }
+
+        terminal.draw(|f| page.render(f, Rect::new(0, 0, 70, 20)))?;
+        // 10 helps and 9 empty lines, in the area of 8 lines (excluding the padding)
+        assert_eq!(page.offset, 11);
+
+        terminal.draw(|f| {
+            let area = Rect::new(0, 0, 70, 20);
+            page.handle_key(vec![UserEvent::HelpUp], KeyEvent::from(KeyCode::Null));
+            page.render(f, area);
+        })?;
+        assert_eq!(page.offset, 10);
+
+        Ok(())
+    }
+
     fn setup_terminal() -> std::io::Result<Terminal<TestBackend>> {
         let backend = TestBackend::new(70, 20);
         let mut terminal = Terminal::new(backend)?;
@@ -3,7 +3,7 @@ use std::rc::Rc;
 use ratatui::{
     buffer::Buffer,
     crossterm::event::KeyEvent,
-    layout::{Alignment, Constraint, Layout, Rect},
+    layout::{Alignment, Constraint, Layout, Position, Rect},
     style::{Color, Modifier, Style, Stylize},
     text::{Line, Span},
     widgets::{Block, Borders, ListItem, Padding, Paragraph, StatefulWidget, Tabs, Widget},
@@ -13,19 +13,24 @@ use ratatui::{
 use crate::{
     app::AppContext,
     color::ColorTheme,
-    config::UiConfig,
+    command::CustomCommandTarget,
+    config::{CustomCommandPage, UiConfig},
+    diff::DiffTarget,
     event::{AppEventType, Sender},
     format::{format_datetime, format_size_byte, format_version},
     handle_user_events, handle_user_events_with_default,
     help::{
-        build_help_spans, build_short_help_spans, BuildHelpsItem, BuildShortHelpsItem, Spans,
-        SpansWithPriority,
+        build_custom_command_helps, build_help_spans, build_short_help_spans, BuildHelpsItem,
+        BuildShortHelpsItem, Spans, SpansWithPriority,
     },
     keys::{UserEvent, UserEventMapper},
+    mouse::{handle_dialog_mouse_input, MouseInput},
     object::{FileDetail, FileVersion, ObjectItem, ObjectKey},
     widget::{
-        Bar, CopyDetailDialog, CopyDetailDialogState, Divider, InputDialog, InputDialogState,
-        ScrollLines, ScrollLinesOptions, ScrollLinesState, ScrollList, ScrollListState,
+        render_presigned_url_dialog, Bar, CopyDetailDialog, CopyDetailDialogState, Divider,
+        InputDialog, InputDialogState, PresignedUrlDialogAction, PresignedUrlDialogState,
+        PresignedUrlParams, ScrollLines, ScrollLinesOptions, ScrollLinesState, ScrollList,
+        ScrollListState,
     },
 };
 
@@ -65,6 +70,8 @@ enum ViewState {
     Default,
     SaveDialog(InputDialogState),
     CopyDetailDialog(Box<CopyDetailDialogState>),
+    PresignedUrlDialog(Box<PresignedUrlDialogState>),
+    PipeDialog(InputDialogState),
 }
 
 impl ObjectDetailPage {
@@ -145,6 +152,29 @@ impl ObjectDetailPage {
                     UserEvent::ObjectDetailManagementConsole => {
                         self.open_management_console();
                     }
+                    UserEvent::ObjectDetailPresignedUrl => {
+                        self.open_presigned_url_dialog();
+                    }
+                    UserEvent::ObjectDetailEdit => {
+                        self.edit();
+                    }
+                    UserEvent::ObjectDetailOpen => {
+                        self.open();
+                    }
+                    UserEvent::ObjectDetailPipe => {
+                        self.open_pipe_dialog();
+                    }
+                    UserEvent::ObjectDetailMarkVersion => {
+                        if let Tab::Version(ref mut state) = self.tab {
+                            state.toggle_mark();
+                        }
+                    }
+                    UserEvent::ObjectDetailDiff => {
+                        self.diff_versions();
+                    }
+                    UserEvent::ObjectDetailCustomCommand(index) => {
+                        self.run_custom_command(*index);
+                    }
                     UserEvent::Help => {
                         self.tx.send(AppEventType::OpenHelp);
                     }
@@ -167,11 +197,31 @@ impl ObjectDetailPage {
                     }
                 }
             }
+            ViewState::PipeDialog(ref mut state) => {
+                handle_user_events_with_default! { user_events =>
+                    UserEvent::InputDialogClose => {
+                        self.close_pipe_dialog();
+                    }
+                    UserEvent::InputDialogApply => {
+                        let input = state.input().into();
+                        self.pipe(input);
+                    }
+                    UserEvent::Help => {
+                        self.tx.send(AppEventType::OpenHelp);
+                    }
+                    => {
+                        state.handle_key_event(key_event);
+                    }
+                }
+            }
             ViewState::CopyDetailDialog(ref mut state) => {
                 handle_user_events! { user_events =>
                     UserEvent::SelectDialogClose => {
                         self.close_copy_detail_dialog();
                     }
+                    UserEvent::SelectDialogSelect if state.is_presigned_url_selected() => {
+                        self.open_presigned_url_dialog();
+                    }
                     UserEvent::SelectDialogSelect => {
                         let (name, value) = state.selected_name_and_value();
                         self.tx.send(AppEventType::CopyToClipboard(name, value));
@@ -187,6 +237,50 @@ impl ObjectDetailPage {
                     }
                 }
             }
+            ViewState::PresignedUrlDialog(ref mut state) => {
+                match state.handle_key(user_events, key_event, &self.tx) {
+                    Some(PresignedUrlDialogAction::Close) => {
+                        self.close_presigned_url_dialog();
+                    }
+                    Some(PresignedUrlDialogAction::Generate(params)) => {
+                        self.generate_presigned_url(params);
+                    }
+                    None => {}
+                }
+            }
+        }
+    }
+
+    // keys are typed into the input as is, without key sequences and counts
+    pub fn is_text_input_active(&self) -> bool {
+        match self.view_state {
+            ViewState::SaveDialog(_) | ViewState::PipeDialog(_) => true,
+            ViewState::PresignedUrlDialog(ref state) => state.is_text_input_active(),
+            _ => false,
+        }
+    }
+
+    pub fn handle_mouse(&mut self, input: MouseInput, area: Rect) -> Option<UserEvent> {
+        let position = input.position();
+        match self.view_state {
+            ViewState::Default => match input {
+                MouseInput::Click(_) | MouseInput::DoubleClick(_) => {
+                    let chunks =
+                        Layout::horizontal(Constraint::from_percentages([50, 50])).split(area);
+                    let chunks = Layout::vertical([Constraint::Length(2), Constraint::Min(0)])
+                        .margin(1)
+                        .split(chunks[1]);
+                    let index = tab_index_at(chunks[0], position)?;
+                    (index != self.tab.val()).then_some(UserEvent::ObjectDetailRight)
+                }
+                MouseInput::ScrollDown(_) => Some(UserEvent::ObjectDetailDown),
+                MouseInput::ScrollUp(_) => Some(UserEvent::ObjectDetailUp),
+            },
+            ViewState::CopyDetailDialog(ref mut state) => {
+                let index = state.item_index_at(area, position);
+                handle_dialog_mouse_input(input, index, |i| state.select_index(i))
+            }
+            _ => None,
         }
     }
 
@@ -239,15 +333,34 @@ impl ObjectDetailPage {
             f.set_cursor_position((cursor_x, cursor_y));
         }
 
+        if let ViewState::PipeDialog(state) = &mut self.view_state {
+            let pipe_dialog = InputDialog::default()
+                .title("Pipe to command")
+                .max_width(60)
+                .theme(&self.ctx.theme);
+            f.render_stateful_widget(pipe_dialog, area, state);
+
+            let (cursor_x, cursor_y) = state.cursor();
+            f.set_cursor_position((cursor_x, cursor_y));
+        }
+
         if let ViewState::CopyDetailDialog(state) = &mut self.view_state {
             let copy_detail_dialog = CopyDetailDialog::default().theme(&self.ctx.theme);
             f.render_stateful_widget(copy_detail_dialog, area, state);
         }
+
+        if let ViewState::PresignedUrlDialog(state) = &mut self.view_state {
+            render_presigned_url_dialog(f, area, state, &self.ctx.theme);
+        }
     }
 
     pub fn helps(&self, mapper: &UserEventMapper) -> Vec<Spans> {
+        build_help_spans(self.help_items(), mapper, self.ctx.theme.help_key_fg)
+    }
+
+    pub fn help_items(&self) -> Vec<BuildHelpsItem> {
         #[rustfmt::skip]
-        let helps = match self.view_state {
+        let mut helps = match self.view_state {
             ViewState::Default => match self.tab {
                 Tab::Detail(_) => {
                     vec![
@@ -261,6 +374,10 @@ impl ObjectDetailPage {
                         BuildHelpsItem::new(UserEvent::ObjectDetailDownload, "Download object"),
                         BuildHelpsItem::new(UserEvent::ObjectDetailDownloadAs, "Download object as"),
                         BuildHelpsItem::new(UserEvent::ObjectDetailPreview, "Preview object"),
+                        BuildHelpsItem::new(UserEvent::ObjectDetailEdit, "Edit object in editor"),
+                        BuildHelpsItem::new(UserEvent::ObjectDetailOpen, "Open object with external program"),
+                        BuildHelpsItem::new(UserEvent::ObjectDetailPipe, "Pipe object to command"),
+                        BuildHelpsItem::new(UserEvent::ObjectDetailPresignedUrl, "Generate presigned URL"),
                         BuildHelpsItem::new(UserEvent::ObjectDetailManagementConsole, "Open management console in browser"),
                     ]
                 },
@@ -278,6 +395,11 @@ impl ObjectDetailPage {
                         BuildHelpsItem::new(UserEvent::ObjectDetailDownload, "Download object"),
                         BuildHelpsItem::new(UserEvent::ObjectDetailDownloadAs, "Download object as"),
                         BuildHelpsItem::new(UserEvent::ObjectDetailPreview, "Preview object"),
+                        BuildHelpsItem::new(UserEvent::ObjectDetailOpen, "Open object with external program"),
+                        BuildHelpsItem::new(UserEvent::ObjectDetailPipe, "Pipe object to command"),
+                        BuildHelpsItem::new(UserEvent::ObjectDetailMarkVersion, "Mark version to compare"),
+                        BuildHelpsItem::new(UserEvent::ObjectDetailDiff, "Compare with marked or previous version"),
+                        BuildHelpsItem::new(UserEvent::ObjectDetailPresignedUrl, "Generate presigned URL"),
                         BuildHelpsItem::new(UserEvent::ObjectDetailManagementConsole, "Open management console in browser"),
                     ]
                 },
@@ -289,6 +411,13 @@ impl ObjectDetailPage {
                     BuildHelpsItem::new(UserEvent::InputDialogApply, "Download object"),
                 ]
             },
+            ViewState::PipeDialog(_) => {
+                vec![
+                    BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+                    BuildHelpsItem::new(UserEvent::InputDialogClose, "Close pipe dialog"),
+                    BuildHelpsItem::new(UserEvent::InputDialogApply, "Run command"),
+                ]
+            },
             ViewState::CopyDetailDialog(_) => {
                 vec![
                     BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
@@ -298,8 +427,15 @@ impl ObjectDetailPage {
                     BuildHelpsItem::new(UserEvent::SelectDialogSelect, "Copy selected value to clipboard"),
                 ]
             },
+            ViewState::PresignedUrlDialog(ref state) => state.help_items(),
         };
-        build_help_spans(helps, mapper, self.ctx.theme.help_key_fg)
+        if matches!(self.view_state, ViewState::Default) {
+            helps.extend(build_custom_command_helps(
+                &self.ctx.config,
+                CustomCommandPage::ObjectDetail,
+            ));
+        }
+        helps
     }
 
     pub fn short_helps(&self, mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
@@ -323,6 +459,7 @@ impl ObjectDetailPage {
                             BuildShortHelpsItem::single(UserEvent::Quit, "Quit", 0),
                             BuildShortHelpsItem::group(vec![UserEvent::ObjectDetailLeft, UserEvent::ObjectDetailRight], "Select tabs", 3),
                             BuildShortHelpsItem::group(vec![UserEvent::ObjectDetailDown, UserEvent::ObjectDetailUp], "Select", 5),
+                            BuildShortHelpsItem::single(UserEvent::ObjectDetailDiff, "Diff", 6),
                             BuildShortHelpsItem::group(vec![UserEvent::ObjectDetailDownload, UserEvent::ObjectDetailDownloadAs], "Download", 1),
                             BuildShortHelpsItem::single(UserEvent::ObjectDetailPreview, "Preview", 4),
                             BuildShortHelpsItem::single(UserEvent::ObjectDetailBack, "Close", 2),
@@ -338,6 +475,13 @@ impl ObjectDetailPage {
                     BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
                 ]
             },
+            ViewState::PipeDialog(_) => {
+                vec![
+                    BuildShortHelpsItem::single(UserEvent::InputDialogClose, "Close", 2),
+                    BuildShortHelpsItem::single(UserEvent::InputDialogApply, "Run", 1),
+                    BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+                ]
+            },
             ViewState::CopyDetailDialog(_) => {
                 vec![
                     BuildShortHelpsItem::single(UserEvent::SelectDialogClose, "Close", 2),
@@ -346,6 +490,7 @@ impl ObjectDetailPage {
                     BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
                 ]
             },
+            ViewState::PresignedUrlDialog(ref state) => state.short_help_items(),
         };
         build_short_help_spans(helps, mapper)
     }
@@ -388,6 +533,14 @@ impl ObjectDetailPage {
         self.view_state = ViewState::Default;
     }
 
+    fn open_pipe_dialog(&mut self) {
+        self.view_state = ViewState::PipeDialog(InputDialogState::default());
+    }
+
+    fn close_pipe_dialog(&mut self) {
+        self.view_state = ViewState::Default;
+    }
+
     fn open_copy_detail_dialog(&mut self) {
         match self.tab {
             Tab::Detail(_) => {
@@ -408,6 +561,29 @@ impl ObjectDetailPage {
         self.view_state = ViewState::Default;
     }
 
+    fn open_presigned_url_dialog(&mut self) {
+        self.view_state = ViewState::PresignedUrlDialog(Box::default());
+    }
+
+    fn close_presigned_url_dialog(&mut self) {
+        self.view_state = ViewState::Default;
+    }
+
+    fn generate_presigned_url(&mut self, params: PresignedUrlParams) {
+        self.view_state = ViewState::Default;
+
+        let object_key = self.object_key.clone();
+        let version_id = self.current_selected_version_id();
+        let content_disposition = params.content_disposition(&self.file_detail.name);
+        self.tx.send(AppEventType::GeneratePresignedUrl(
+            object_key,
+            version_id,
+            params.method,
+            params.expires_in,
+            content_disposition,
+        ));
+    }
+
     fn download(&self) {
         let object_key = self.object_key.clone();
         let object_name = self.file_detail.name.clone();
@@ -437,7 +613,7 @@ impl ObjectDetailPage {
         self.close_save_dialog();
     }
 
-    fn preview(&self) {
+    pub fn preview(&self) {
         let object_key = self.object_key.clone();
         let file_detail = self.file_detail.clone();
         let version_id = self.current_selected_version_id();
@@ -448,6 +624,85 @@ impl ObjectDetailPage {
         ));
     }
 
+    fn edit(&self) {
+        if self.current_selected_version_id().is_some() {
+            let msg = "Only the latest version can be edited";
+            self.tx.send(AppEventType::NotifyWarn(msg.into()));
+            return;
+        }
+        let object_key = self.object_key.clone();
+        self.tx.send(AppEventType::StartEditObject(object_key));
+    }
+
+    fn open(&self) {
+        let object_key = self.object_key.clone();
+        let version_id = self.current_selected_version_id();
+        self.tx
+            .send(AppEventType::StartOpenObject(object_key, version_id));
+    }
+
+    fn pipe(&mut self, input: String) {
+        let input: String = input.trim().into();
+        if input.is_empty() {
+            return;
+        }
+
+        let object_key = self.object_key.clone();
+        let version_id = self.current_selected_version_id();
+        self.tx
+            .send(AppEventType::StartPipeObject(object_key, version_id, input));
+
+        self.close_pipe_dialog();
+    }
+
+    fn diff_versions(&self) {
+        let Tab::Version(state) = &self.tab else {
+            return;
+        };
+        // compare with the marked version, or with the previous (older) version if none is marked
+        let other = match state.marked {
+            Some(marked) if marked != state.selected => marked,
+            _ => state.selected + 1,
+        };
+        let Some(other_version) = self.file_versions.get(other) else {
+            let msg = "There is no other version to compare";
+            self.tx.send(AppEventType::NotifyWarn(msg.into()));
+            return;
+        };
+        let selected_version = &self.file_versions[state.selected];
+
+        // versions are sorted from newest to oldest
+        let (old, new) = if other > state.selected {
+            (other_version, selected_version)
+        } else {
+            (selected_version, other_version)
+        };
+        let left = DiffTarget::new(
+            self.object_key.clone(),
+            Some(old.version_id.clone()),
+            old.size_byte,
+        );
+        let right = DiffTarget::new(
+            self.object_key.clone(),
+            Some(new.version_id.clone()),
+            new.size_byte,
+        );
+        self.tx.send(AppEventType::StartLoadTextDiff(left, right));
+    }
+
+    fn run_custom_command(&self, index: usize) {
+        let target = CustomCommandTarget {
+            bucket: self.object_key.bucket_name.clone(),
+            key: self.file_detail.key.clone(),
+            s3_uri: self.file_detail.s3_uri.clone(),
+            object_url: self.file_detail.object_url.clone(),
+            version_id: self.current_selected_version_id(),
+            is_file: true,
+        };
+        self.tx
+            .send(AppEventType::StartCustomCommand(index, target));
+    }
+
     fn open_management_console(&self) {
         let object_key = self.object_key.clone();
         self.tx
@@ -514,7 +769,8 @@ fn build_list_item_from_object_item<'a>(
         ListItem::new(content).style(
             Style::default()
                 .bg(theme.list_selected_inactive_bg)
-                .fg(theme.list_selected_inactive_fg),
+                .fg(theme.list_selected_inactive_fg)
+                .add_modifier(theme.list_selected_modifier),
         )
     } else {
         ListItem::new(content)
@@ -532,9 +788,10 @@ fn format_file_item(name: &str, width: u16) -> String {
     format!(" {:<name_w$} ", name, name_w = name_w)
 }
 
+const TAB_TITLES: [&str; 2] = ["Detail", "Version"];
+
 fn build_tabs(tab: &Tab, theme: &ColorTheme) -> Tabs<'static> {
-    let tabs = vec!["Detail", "Version"];
-    Tabs::new(tabs)
+    Tabs::new(TAB_TITLES)
         .select(tab.val())
         .highlight_style(
             Style::default()
@@ -544,6 +801,22 @@ fn build_tabs(tab: &Tab, theme: &ColorTheme) -> Tabs<'static> {
         .block(Block::default().borders(Borders::BOTTOM))
 }
 
+// the titles are rendered as " Detail │ Version " by default
+fn tab_index_at(area: Rect, position: Position) -> Option<usize> {
+    if position.y != area.y {
+        return None;
+    }
+    let mut x = area.x;
+    for (i, title) in TAB_TITLES.iter().enumerate() {
+        let width = title.len() as u16 + 2 /* padding */;
+        if (x..x + width).contains(&position.x) {
+            return Some(i);
+        }
+        x += width + 1 /* divider */;
+    }
+    None
+}
+
 fn build_detail_content_lines(detail: &FileDetail, ui_config: &UiConfig) -> Vec<Line<'static>> {
     let details = [
         ("Name:", &detail.name),
@@ -640,6 +913,7 @@ fn build_version_detail_lines(
 struct VersionTabState {
     lines: Vec<Vec<Line<'static>>>,
     selected: usize,
+    marked: Option<usize>,
     offset: usize,
     height: usize,
 }
@@ -686,6 +960,14 @@ impl VersionTabState {
         }
     }
 
+    fn toggle_mark(&mut self) {
+        if self.marked == Some(self.selected) {
+            self.marked = None;
+        } else {
+            self.marked = Some(self.selected);
+        }
+    }
+
     fn select_first(&mut self) {
         self.selected = 0;
         self.offset = 0;
@@ -713,6 +995,7 @@ impl VersionTabState {
 #[derive(Debug, Default)]
 struct VersionTabColor {
     selected: Color,
+    marked: Color,
     divider: Color,
 }
 
@@ -720,6 +1003,7 @@ impl VersionTabColor {
     fn new(theme: &ColorTheme) -> Self {
         Self {
             selected: theme.detail_selected,
+            marked: theme.status_warn,
             divider: theme.divider,
         }
     }
@@ -780,6 +1064,9 @@ impl StatefulWidget for VersionTab {
             if i == state.selected {
                 let bar = Bar::default().color(self.color.selected);
                 bar.render(chunks[0], buf);
+            } else if state.marked == Some(i) {
+                let bar = Bar::default().color(self.color.marked);
+                bar.render(chunks[0], buf);
             }
             version_paragraph.render(chunks[1], buf);
         }
@@ -1169,7 +1456,6 @@ mod tests {
         let mut expected = Buffer::with_lines([
             "┌───────────────────── 1 / 3 ┐┌────────────────────────────┐",
             "│  file1                     ││ Detail │ Version           │",
-            "│  file2                     ││────────────────────────────│",
             "│ ╭Copy──────────────────────────────────────────────────╮ │",
             "│ │ Name:                                                │ │",
             "│ │   file1                                              │ │",
@@ -1183,8 +1469,9 @@ mod tests {
             "│ │   https://bucket-1.s3.ap-northeast-1.amazonaws.com/f │ │",
             "│ │ ETag:                                                │ │",
             "│ │   bef684de-a260-48a4-8178-8a535ecccadb               │ │",
+            "│ │ Presigned URL:                                       │ │",
+            "│ │   (Select to generate)                               │ │",
             "│ ╰──────────────────────────────────────────────────────╯ │",
-            "│                            ││  text/plain                │",
             "│                            ││                            │",
             "└────────────────────────────┘└────────────────────────────┘",
         ]);
@@ -1194,19 +1481,21 @@ mod tests {
             // "Detail" is selected
             (32..38, [1]) => fg: Color::Cyan, modifier: Modifier::BOLD,
             // "Name" label
-            (4..9, [4]) => modifier: Modifier::BOLD,
+            (4..9, [3]) => modifier: Modifier::BOLD,
             // "Key" label
-            (4..8, [6]) => modifier: Modifier::BOLD,
+            (4..8, [5]) => modifier: Modifier::BOLD,
             // "S3 URI" label
-            (4..11, [8]) => modifier: Modifier::BOLD,
+            (4..11, [7]) => modifier: Modifier::BOLD,
             // "ARN" label
-            (4..8, [10]) => modifier: Modifier::BOLD,
+            (4..8, [9]) => modifier: Modifier::BOLD,
             // "Object URL" label
-            (4..15, [12]) => modifier: Modifier::BOLD,
+            (4..15, [11]) => modifier: Modifier::BOLD,
             // "ETag" label
-            (4..9, [14]) => modifier: Modifier::BOLD,
+            (4..9, [13]) => modifier: Modifier::BOLD,
+            // "Presigned URL" label
+            (4..18, [15]) => modifier: Modifier::BOLD,
             // "Name" is selected
-            (4..56, [4, 5]) => fg: Color::Cyan,
+            (4..56, [3, 4]) => fg: Color::Cyan,
         }
 
         terminal.backend().assert_buffer(&expected);
@@ -1254,7 +1543,6 @@ mod tests {
         let mut expected = Buffer::with_lines([
             "┌───────────────────── 1 / 3 ┐┌────────────────────────────┐",
             "│  file1                     ││ Detail │ Version           │",
-            "│  file2                     ││────────────────────────────│",
             "│ ╭Copy──────────────────────────────────────────────────╮ │",
             "│ │ Name:                                                │ │",
             "│ │   file1                                              │ │",
@@ -1268,9 +1556,10 @@ mod tests {
             "│ │   https://bucket-1.s3.ap-northeast-1.amazonaws.com/f │ │",
             "│ │ ETag:                                                │ │",
             "│ │   6c5db847-d206-4a27-9723-713e3a6cad86               │ │",
+            "│ │ Presigned URL:                                       │ │",
+            "│ │   (Select to generate)                               │ │",
             "│ ╰──────────────────────────────────────────────────────╯ │",
             "│                            ││                            │",
-            "│                            ││                            │",
             "└────────────────────────────┘└────────────────────────────┘",
         ]);
         set_cells! { expected =>
@@ -1279,19 +1568,21 @@ mod tests {
             // "Version" is selected
             (41..48, [1]) => fg: Color::Cyan, modifier: Modifier::BOLD,
             // "Name" label
-            (4..9, [4]) => modifier: Modifier::BOLD,
+            (4..9, [3]) => modifier: Modifier::BOLD,
             // "Key" label
-            (4..8, [6]) => modifier: Modifier::BOLD,
+            (4..8, [5]) => modifier: Modifier::BOLD,
             // "S3 URI" label
-            (4..11, [8]) => modifier: Modifier::BOLD,
+            (4..11, [7]) => modifier: Modifier::BOLD,
             // "ARN" label
-            (4..8, [10]) => modifier: Modifier::BOLD,
+            (4..8, [9]) => modifier: Modifier::BOLD,
             // "Object URL" label
-            (4..15, [12]) => modifier: Modifier::BOLD,
+            (4..15, [11]) => modifier: Modifier::BOLD,
             // "ETag" label
-            (4..9, [14]) => modifier: Modifier::BOLD,
+            (4..9, [13]) => modifier: Modifier::BOLD,
+            // "Presigned URL" label
+            (4..18, [15]) => modifier: Modifier::BOLD,
             // "Name" is selected
-            (4..56, [4, 5]) => fg: Color::Cyan,
+            (4..56, [3, 4]) => fg: Color::Cyan,
         }
 
         terminal.backend().assert_buffer(&expected);
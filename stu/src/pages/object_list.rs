@@ -1,33 +1,43 @@
-use std::rc::Rc;
+use std::{collections::HashMap, rc::Rc};
 
 use chrono::{DateTime, Local};
 use laurier::highlight::highlight_matched_text;
 use ratatui::{
     crossterm::event::KeyEvent,
-    layout::Rect,
+    layout::{Constraint, Layout, Rect},
     style::{Style, Stylize},
-    text::Line,
-    widgets::ListItem,
+    text::{Line, Span},
+    widgets::{Block, List, ListItem, Padding, Paragraph, Wrap},
     Frame,
 };
 
 use crate::{
     app::AppContext,
     color::ColorTheme,
-    config::UiConfig,
+    command::CustomCommandTarget,
+    config::{CustomCommandPage, UiConfig},
+    diff::DiffTarget,
     event::{AppEventType, Sender},
-    format::{format_datetime, format_size_byte},
+    format::{format_datetime, format_object_count, format_size_byte},
     handle_user_events, handle_user_events_with_default,
     help::{
-        build_help_spans, build_short_help_spans, BuildHelpsItem, BuildShortHelpsItem, Spans,
-        SpansWithPriority,
+        build_custom_command_helps, build_help_spans, build_short_help_spans, BuildHelpsItem,
+        BuildShortHelpsItem, Spans, SpansWithPriority,
     },
     keys::{UserEvent, UserEventMapper},
-    object::{DownloadObjectInfo, ObjectItem, ObjectKey},
+    location::parse_location,
+    mouse::{handle_dialog_mouse_input, handle_list_mouse_input, MouseInput},
+    object::{
+        DownloadObjectInfo, FileDetail, ObjectItem, ObjectKey, PrefixSize, SplitPreviewContent,
+    },
+    session::ListViewSession,
+    sync::SyncOptions,
     widget::{
-        ConfirmDialog, ConfirmDialogState, CopyDetailDialog, CopyDetailDialogState, InputDialog,
-        InputDialogState, ObjectListSortDialog, ObjectListSortDialogState, ObjectListSortType,
-        ScrollList, ScrollListState,
+        render_presigned_url_dialog, ConfirmDialog, ConfirmDialogState, CopyDetailDialog,
+        CopyDetailDialogState, GoToDialog, GoToDialogState, InputDialog, InputDialogState,
+        ObjectListSortDialog, ObjectListSortDialogState, ObjectListSortType, PrefixSizeDialog,
+        PresignedUrlDialogAction, PresignedUrlDialogState, PresignedUrlParams, ScrollList,
+        ScrollListState,
     },
 };
 
@@ -38,17 +48,37 @@ pub struct ObjectListPage {
     object_items: Vec<ObjectItem>,
     object_key: ObjectKey,
     view_indices: Vec<usize>,
+    // dir name => calculated (or calculating) size
+    prefix_sizes: HashMap<String, PrefixSize>,
+    // the name of the object marked to be compared with the selected one
+    marked: Option<String>,
 
     view_state: ViewState,
 
     list_state: ScrollListState,
     filter_input_state: InputDialogState,
     sort_dialog_state: ObjectListSortDialogState,
+    split_preview: SplitPreview,
+    // only the size is shown for each item
+    compact: bool,
+    // false if the page is shown as the inactive pane of the commander
+    focused: bool,
 
     ctx: Rc<AppContext>,
     tx: Sender,
 }
 
+#[derive(Debug)]
+enum SplitPreview {
+    Disabled,
+    // enabled, but no item is selected
+    Empty,
+    Loading(ObjectKey),
+    Loaded(ObjectKey, SplitPreviewContent),
+    // the error message is shown in the pane
+    Failed(ObjectKey, String),
+}
+
 #[derive(Debug)]
 enum ViewState {
     Default,
@@ -57,6 +87,11 @@ enum ViewState {
     CopyDetailDialog(Box<CopyDetailDialogState>),
     DownloadConfirmDialog(Vec<DownloadObjectInfo>, ConfirmDialogState, bool),
     SaveDialog(InputDialogState, Option<Vec<DownloadObjectInfo>>),
+    PresignedUrlDialog(Box<PresignedUrlDialogState>),
+    PrefixSizeDialog(String),
+    SyncDialog(InputDialogState),
+    CompareDialog(InputDialogState),
+    GoToDialog(GoToDialogState),
 }
 
 impl ObjectListPage {
@@ -72,10 +107,15 @@ impl ObjectListPage {
             object_items,
             object_key,
             view_indices,
+            prefix_sizes: HashMap::new(),
+            marked: None,
             view_state: ViewState::Default,
             list_state: ScrollListState::new(items_len),
             filter_input_state: InputDialogState::default(),
             sort_dialog_state: ObjectListSortDialogState::default(),
+            split_preview: SplitPreview::Disabled,
+            compact: false,
+            focused: true,
             ctx,
             tx,
         }
@@ -112,6 +152,12 @@ impl ObjectListPage {
                     UserEvent::ObjectListRefresh if self.non_empty() => {
                         self.tx.send(AppEventType::ObjectListRefresh);
                     }
+                    UserEvent::ObjectListToggleSplitPreview => {
+                        self.tx.send(AppEventType::ObjectListToggleSplitPreview);
+                    }
+                    UserEvent::ObjectListCommander => {
+                        self.tx.send(AppEventType::OpenCommander);
+                    }
                     UserEvent::ObjectListBucketList => {
                         self.tx.send(AppEventType::BackToBucketList);
                     }
@@ -133,6 +179,33 @@ impl ObjectListPage {
                     UserEvent::ObjectListDownloadObjectAs if self.non_empty() => {
                         self.start_download_as();
                     }
+                    UserEvent::ObjectListCalculateSize if self.non_empty() => {
+                        self.calculate_size();
+                    }
+                    UserEvent::ObjectListCalculateSizeAll if self.non_empty() => {
+                        self.calculate_size_all();
+                    }
+                    UserEvent::ObjectListAnalytics => {
+                        self.open_analytics();
+                    }
+                    UserEvent::ObjectListSync => {
+                        self.open_sync_dialog();
+                    }
+                    UserEvent::ObjectListCompare => {
+                        self.open_compare_dialog();
+                    }
+                    UserEvent::ObjectListGoTo => {
+                        self.open_go_to_dialog();
+                    }
+                    UserEvent::ObjectListMark if self.non_empty() => {
+                        self.toggle_mark();
+                    }
+                    UserEvent::ObjectListDiff if self.non_empty() => {
+                        self.diff_marked();
+                    }
+                    UserEvent::ObjectListCustomCommand(index) if self.non_empty() => {
+                        self.run_custom_command(*index);
+                    }
                     UserEvent::Help => {
                         self.tx.send(AppEventType::OpenHelp);
                     }
@@ -188,6 +261,9 @@ impl ObjectListPage {
                     UserEvent::SelectDialogUp => {
                         state.select_prev();
                     }
+                    UserEvent::SelectDialogSelect if state.is_presigned_url_selected() => {
+                        self.open_presigned_url_dialog();
+                    }
                     UserEvent::SelectDialogSelect => {
                         let (name, value) = state.selected_name_and_value();
                         self.tx.send(AppEventType::CopyToClipboard(name, value));
@@ -230,26 +306,168 @@ impl ObjectListPage {
                     }
                 }
             }
+            ViewState::PresignedUrlDialog(ref mut state) => {
+                match state.handle_key(user_events, key_event, &self.tx) {
+                    Some(PresignedUrlDialogAction::Close) => {
+                        self.close_presigned_url_dialog();
+                    }
+                    Some(PresignedUrlDialogAction::Generate(params)) => {
+                        self.generate_presigned_url(params);
+                    }
+                    None => {}
+                }
+            }
+            ViewState::SyncDialog(ref mut state) => {
+                handle_user_events_with_default! { user_events =>
+                    UserEvent::InputDialogClose => {
+                        self.close_sync_dialog();
+                    }
+                    UserEvent::InputDialogApply => {
+                        let input = state.input().to_string();
+                        self.sync(input);
+                    }
+                    UserEvent::Help => {
+                        self.tx.send(AppEventType::OpenHelp);
+                    }
+                    => {
+                        state.handle_key_event(key_event);
+                    }
+                }
+            }
+            ViewState::CompareDialog(ref mut state) => {
+                handle_user_events_with_default! { user_events =>
+                    UserEvent::InputDialogClose => {
+                        self.close_compare_dialog();
+                    }
+                    UserEvent::InputDialogApply => {
+                        let input = state.input().to_string();
+                        self.compare(input);
+                    }
+                    UserEvent::Help => {
+                        self.tx.send(AppEventType::OpenHelp);
+                    }
+                    => {
+                        state.handle_key_event(key_event);
+                    }
+                }
+            }
+            ViewState::GoToDialog(ref mut state) => {
+                handle_user_events_with_default! { user_events =>
+                    UserEvent::InputDialogClose => {
+                        self.close_go_to_dialog();
+                    }
+                    UserEvent::InputDialogApply => {
+                        let input = state.input().to_string();
+                        self.go_to(input, false);
+                    }
+                    UserEvent::GoToDialogPreview => {
+                        let input = state.input().to_string();
+                        self.go_to(input, true);
+                    }
+                    UserEvent::GoToDialogComplete => {
+                        let input = state.input().to_string();
+                        self.tx.send(AppEventType::LoadGoToCandidates(input));
+                    }
+                    UserEvent::Help => {
+                        self.tx.send(AppEventType::OpenHelp);
+                    }
+                    => {
+                        state.handle_key_event(key_event);
+                    }
+                }
+            }
+            ViewState::PrefixSizeDialog(_) => {
+                handle_user_events! { user_events =>
+                    UserEvent::SelectDialogClose | UserEvent::SelectDialogSelect => {
+                        self.close_prefix_size_dialog();
+                    }
+                    UserEvent::Help => {
+                        self.tx.send(AppEventType::OpenHelp);
+                    }
+                }
+            }
+        }
+    }
+
+    // keys are typed into the input as is, without key sequences and counts
+    pub fn is_text_input_active(&self) -> bool {
+        match self.view_state {
+            ViewState::FilterDialog
+            | ViewState::SaveDialog(..)
+            | ViewState::SyncDialog(_)
+            | ViewState::CompareDialog(_)
+            | ViewState::GoToDialog(_) => true,
+            ViewState::PresignedUrlDialog(ref state) => state.is_text_input_active(),
+            _ => false,
+        }
+    }
+
+    pub fn handle_mouse(&mut self, input: MouseInput, area: Rect) -> Option<UserEvent> {
+        let position = input.position();
+        match self.view_state {
+            ViewState::Default if self.non_empty() => {
+                let event = handle_list_mouse_input(
+                    input,
+                    &mut self.list_state,
+                    Some(UserEvent::ObjectListSelect),
+                    UserEvent::ObjectListDown,
+                    UserEvent::ObjectListUp,
+                );
+                self.request_split_preview();
+                event
+            }
+            ViewState::SortDialog => {
+                let index = self.sort_dialog_state.item_index_at(area, position);
+                handle_dialog_mouse_input(input, index, |i| {
+                    self.sort_dialog_state.select_index(i);
+                    self.sort_view_indices();
+                })
+            }
+            ViewState::CopyDetailDialog(ref mut state) => {
+                let index = state.item_index_at(area, position);
+                handle_dialog_mouse_input(input, index, |i| state.select_index(i))
+            }
+            _ => None,
         }
     }
 
     pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let (list_area, preview_area) = if self.split_preview_enabled() {
+            let ratio = self.ctx.config.ui.object_list.split_ratio.clamp(10, 90);
+            let [list_area, preview_area] =
+                Layout::horizontal(Constraint::from_percentages([ratio, 100 - ratio])).areas(area);
+            (list_area, Some(preview_area))
+        } else {
+            (area, None)
+        };
+
+        self.list_state
+            .fit_height(list_area.height as usize - 2 /* border */);
+
         let offset = self.list_state.offset;
         let selected = self.list_state.selected;
 
         let list_items = build_list_items(
             &self.object_items,
             &self.view_indices,
+            &self.prefix_sizes,
+            self.marked.as_deref(),
             self.filter_input_state.input(),
             offset,
             selected,
-            area,
+            list_area,
+            self.compact || preview_area.is_some(),
+            self.focused,
             &self.ctx.config.ui,
             &self.ctx.theme,
         );
 
         let list = ScrollList::new(list_items).theme(&self.ctx.theme);
-        f.render_stateful_widget(list, area, &mut self.list_state);
+        f.render_stateful_widget(list, list_area, &mut self.list_state);
+
+        if let Some(preview_area) = preview_area {
+            self.render_split_preview(f, preview_area);
+        }
 
         if let ViewState::FilterDialog = self.view_state {
             let filter_dialog = InputDialog::default()
@@ -289,11 +507,55 @@ impl ObjectListPage {
             let (cursor_x, cursor_y) = state.cursor();
             f.set_cursor_position((cursor_x, cursor_y));
         }
+
+        if let ViewState::PresignedUrlDialog(state) = &mut self.view_state {
+            render_presigned_url_dialog(f, area, state, &self.ctx.theme);
+        }
+
+        if let ViewState::SyncDialog(state) = &mut self.view_state {
+            let sync_dialog = InputDialog::default()
+                .title("Sync: <dir> [--upload] [--delete] [--exclude/--include <glob>]")
+                .max_width(70)
+                .theme(&self.ctx.theme);
+            f.render_stateful_widget(sync_dialog, area, state);
+
+            let (cursor_x, cursor_y) = state.cursor();
+            f.set_cursor_position((cursor_x, cursor_y));
+        }
+
+        if let ViewState::CompareDialog(state) = &mut self.view_state {
+            let compare_dialog = InputDialog::default()
+                .title("Compare with: s3://<bucket>/<prefix>/")
+                .max_width(70)
+                .theme(&self.ctx.theme);
+            f.render_stateful_widget(compare_dialog, area, state);
+
+            let (cursor_x, cursor_y) = state.cursor();
+            f.set_cursor_position((cursor_x, cursor_y));
+        }
+
+        if let ViewState::GoToDialog(state) = &mut self.view_state {
+            let go_to_dialog = GoToDialog::default().theme(&self.ctx.theme);
+            f.render_stateful_widget(go_to_dialog, area, state);
+
+            let (cursor_x, cursor_y) = state.cursor();
+            f.set_cursor_position((cursor_x, cursor_y));
+        }
+
+        if let ViewState::PrefixSizeDialog(name) = &self.view_state {
+            let prefix_size_dialog =
+                PrefixSizeDialog::new(name, self.prefix_sizes.get(name)).theme(&self.ctx.theme);
+            f.render_widget(prefix_size_dialog, area);
+        }
     }
 
     pub fn helps(&self, mapper: &UserEventMapper) -> Vec<Spans> {
+        build_help_spans(self.help_items(), mapper, self.ctx.theme.help_key_fg)
+    }
+
+    pub fn help_items(&self) -> Vec<BuildHelpsItem> {
         #[rustfmt::skip]
-        let helps = match self.view_state {
+        let mut helps = match self.view_state {
             ViewState::Default => {
                 if self.filter_input_state.is_empty() {
                     vec![
@@ -314,6 +576,16 @@ impl ObjectListPage {
                         BuildHelpsItem::new(UserEvent::ObjectListCopyDetails, "Open copy dialog"),
                         BuildHelpsItem::new(UserEvent::ObjectListRefresh, "Refresh object list"),
                         BuildHelpsItem::new(UserEvent::ObjectListManagementConsole, "Open management console in browser"),
+                        BuildHelpsItem::new(UserEvent::ObjectListCalculateSize, "Calculate size of selected folder"),
+                        BuildHelpsItem::new(UserEvent::ObjectListCalculateSizeAll, "Calculate size of all folders"),
+                        BuildHelpsItem::new(UserEvent::ObjectListAnalytics, "Open prefix analytics"),
+                        BuildHelpsItem::new(UserEvent::ObjectListSync, "Sync with local directory"),
+                        BuildHelpsItem::new(UserEvent::ObjectListCompare, "Compare with another prefix"),
+                        BuildHelpsItem::new(UserEvent::ObjectListMark, "Mark object to compare"),
+                        BuildHelpsItem::new(UserEvent::ObjectListDiff, "Compare with marked object"),
+                        BuildHelpsItem::new(UserEvent::ObjectListGoTo, "Go to location"),
+                        BuildHelpsItem::new(UserEvent::ObjectListToggleSplitPreview, "Toggle preview pane"),
+                        BuildHelpsItem::new(UserEvent::ObjectListCommander, "Open dual-pane commander"),
                     ]
                 } else {
                     vec![
@@ -335,9 +607,42 @@ impl ObjectListPage {
                         BuildHelpsItem::new(UserEvent::ObjectListCopyDetails, "Open copy dialog"),
                         BuildHelpsItem::new(UserEvent::ObjectListRefresh, "Refresh object list"),
                         BuildHelpsItem::new(UserEvent::ObjectListManagementConsole, "Open management console in browser"),
+                        BuildHelpsItem::new(UserEvent::ObjectListCalculateSize, "Calculate size of selected folder"),
+                        BuildHelpsItem::new(UserEvent::ObjectListCalculateSizeAll, "Calculate size of all folders"),
+                        BuildHelpsItem::new(UserEvent::ObjectListAnalytics, "Open prefix analytics"),
+                        BuildHelpsItem::new(UserEvent::ObjectListSync, "Sync with local directory"),
+                        BuildHelpsItem::new(UserEvent::ObjectListCompare, "Compare with another prefix"),
+                        BuildHelpsItem::new(UserEvent::ObjectListMark, "Mark object to compare"),
+                        BuildHelpsItem::new(UserEvent::ObjectListDiff, "Compare with marked object"),
+                        BuildHelpsItem::new(UserEvent::ObjectListGoTo, "Go to location"),
+                        BuildHelpsItem::new(UserEvent::ObjectListToggleSplitPreview, "Toggle preview pane"),
+                        BuildHelpsItem::new(UserEvent::ObjectListCommander, "Open dual-pane commander"),
                     ]
                 }
             },
+            ViewState::SyncDialog(_) => {
+                vec![
+                    BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+                    BuildHelpsItem::new(UserEvent::InputDialogClose, "Close sync dialog"),
+                    BuildHelpsItem::new(UserEvent::InputDialogApply, "Compare and show sync plan"),
+                ]
+            },
+            ViewState::CompareDialog(_) => {
+                vec![
+                    BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+                    BuildHelpsItem::new(UserEvent::InputDialogClose, "Close compare dialog"),
+                    BuildHelpsItem::new(UserEvent::InputDialogApply, "Compare prefixes"),
+                ]
+            },
+            ViewState::GoToDialog(_) => {
+                vec![
+                    BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+                    BuildHelpsItem::new(UserEvent::InputDialogClose, "Close go to dialog"),
+                    BuildHelpsItem::new(UserEvent::GoToDialogComplete, "Complete bucket or prefix"),
+                    BuildHelpsItem::new(UserEvent::InputDialogApply, "Go to location"),
+                    BuildHelpsItem::new(UserEvent::GoToDialogPreview, "Go to location and preview object"),
+                ]
+            },
             ViewState::FilterDialog => {
                 vec![
                     BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
@@ -379,8 +684,21 @@ impl ObjectListPage {
                     BuildHelpsItem::new(UserEvent::InputDialogApply, "Download object"),
                 ]
             }
+            ViewState::PresignedUrlDialog(ref state) => state.help_items(),
+            ViewState::PrefixSizeDialog(_) => {
+                vec![
+                    BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+                    BuildHelpsItem::new(UserEvent::SelectDialogClose, "Close size dialog"),
+                ]
+            },
         };
-        build_help_spans(helps, mapper, self.ctx.theme.help_key_fg)
+        if matches!(self.view_state, ViewState::Default) {
+            helps.extend(build_custom_command_helps(
+                &self.ctx.config,
+                CustomCommandPage::ObjectList,
+            ));
+        }
+        helps
     }
 
     pub fn short_helps(&self, mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
@@ -415,6 +733,22 @@ impl ObjectListPage {
                     ]
                 }
             },
+            ViewState::SyncDialog(_) | ViewState::CompareDialog(_) => {
+                vec![
+                    BuildShortHelpsItem::single(UserEvent::InputDialogClose, "Close", 2),
+                    BuildShortHelpsItem::single(UserEvent::InputDialogApply, "Compare", 1),
+                    BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+                ]
+            },
+            ViewState::GoToDialog(_) => {
+                vec![
+                    BuildShortHelpsItem::single(UserEvent::InputDialogClose, "Close", 2),
+                    BuildShortHelpsItem::single(UserEvent::GoToDialogComplete, "Complete", 3),
+                    BuildShortHelpsItem::single(UserEvent::InputDialogApply, "Go", 1),
+                    BuildShortHelpsItem::single(UserEvent::GoToDialogPreview, "Preview", 4),
+                    BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+                ]
+            },
             ViewState::FilterDialog => {
                 vec![
                     BuildShortHelpsItem::single(UserEvent::InputDialogClose, "Close", 2),
@@ -453,6 +787,13 @@ impl ObjectListPage {
                     BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
                 ]
             }
+            ViewState::PresignedUrlDialog(ref state) => state.short_help_items(),
+            ViewState::PrefixSizeDialog(_) => {
+                vec![
+                    BuildShortHelpsItem::single(UserEvent::SelectDialogClose, "Close", 1),
+                    BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+                ]
+            },
         };
         build_short_help_spans(helps, mapper)
     }
@@ -461,26 +802,32 @@ impl ObjectListPage {
 impl ObjectListPage {
     fn select_next(&mut self) {
         self.list_state.select_next();
+        self.request_split_preview();
     }
 
     fn select_prev(&mut self) {
         self.list_state.select_prev();
+        self.request_split_preview();
     }
 
     fn select_first(&mut self) {
         self.list_state.select_first();
+        self.request_split_preview();
     }
 
     fn select_last(&mut self) {
         self.list_state.select_last();
+        self.request_split_preview();
     }
 
     fn select_next_page(&mut self) {
         self.list_state.select_next_page();
+        self.request_split_preview();
     }
 
     fn select_prev_page(&mut self) {
         self.list_state.select_prev_page();
+        self.request_split_preview();
     }
 
     fn open_filter_dialog(&mut self) {
@@ -516,6 +863,131 @@ impl ObjectListPage {
         self.view_state = ViewState::Default;
     }
 
+    fn calculate_size(&mut self) {
+        let item = self.current_selected_item();
+        if let ObjectItem::Dir { name, .. } = item {
+            let name = name.clone();
+            let object_key = self.current_selected_object_key();
+            self.tx
+                .send(AppEventType::CalculatePrefixSize(vec![object_key]));
+            self.view_state = ViewState::PrefixSizeDialog(name);
+        }
+    }
+
+    fn open_sync_dialog(&mut self) {
+        self.view_state = ViewState::SyncDialog(InputDialogState::default());
+    }
+
+    fn close_sync_dialog(&mut self) {
+        self.view_state = ViewState::Default;
+    }
+
+    fn sync(&mut self, input: String) {
+        if input.trim().is_empty() {
+            return;
+        }
+        match SyncOptions::parse(&input) {
+            Ok(options) => {
+                let object_key = self.current_dir_object_key().clone();
+                self.tx
+                    .send(AppEventType::StartLoadSyncPlan(object_key, options));
+                self.close_sync_dialog();
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+    }
+
+    fn open_compare_dialog(&mut self) {
+        self.view_state = ViewState::CompareDialog(InputDialogState::default());
+    }
+
+    fn close_compare_dialog(&mut self) {
+        self.view_state = ViewState::Default;
+    }
+
+    fn compare(&mut self, input: String) {
+        if input.trim().is_empty() {
+            return;
+        }
+        let Some(right) = ObjectKey::from_prefix_uri(&input) else {
+            let msg = format!("Invalid S3 URI: {}", input.trim());
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        };
+        let left = self.current_dir_object_key().clone();
+        self.tx.send(AppEventType::StartLoadPrefixDiff(left, right));
+        self.close_compare_dialog();
+    }
+
+    fn open_go_to_dialog(&mut self) {
+        let mut state = GoToDialogState::default();
+        let uri = self.current_dir_object_key().prefix_uri();
+        state.set_completion(uri, Vec::new());
+        self.view_state = ViewState::GoToDialog(state);
+    }
+
+    fn close_go_to_dialog(&mut self) {
+        self.view_state = ViewState::Default;
+    }
+
+    fn go_to(&mut self, input: String, preview: bool) {
+        if input.trim().is_empty() {
+            return;
+        }
+        let Some(location) = parse_location(&input) else {
+            let msg = format!("Invalid location: {}", input.trim());
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        };
+        if preview && location.is_dir {
+            let msg = "Only objects can be previewed";
+            self.tx.send(AppEventType::NotifyWarn(msg.into()));
+            return;
+        }
+        self.tx.send(AppEventType::GoToLocation(location, preview));
+        self.close_go_to_dialog();
+    }
+
+    pub fn set_go_to_completion(
+        &mut self,
+        input: &str,
+        completed: String,
+        candidates: Vec<String>,
+    ) {
+        if let ViewState::GoToDialog(state) = &mut self.view_state {
+            // ignore if the input has been changed while loading
+            if state.input() == input {
+                state.set_completion(completed, candidates);
+            }
+        }
+    }
+
+    fn open_analytics(&self) {
+        let object_key = self.current_dir_object_key().clone();
+        self.tx
+            .send(AppEventType::StartLoadPrefixAnalytics(object_key));
+    }
+
+    fn calculate_size_all(&mut self) {
+        let object_keys: Vec<ObjectKey> = self
+            .view_indices
+            .iter()
+            .filter_map(|&i| match &self.object_items[i] {
+                ObjectItem::Dir { name, .. } => Some(self.object_key.child(name)),
+                ObjectItem::File { .. } => None,
+            })
+            .collect();
+        if !object_keys.is_empty() {
+            self.tx.send(AppEventType::CalculatePrefixSize(object_keys));
+        }
+    }
+
+    fn close_prefix_size_dialog(&mut self) {
+        self.view_state = ViewState::Default;
+    }
+
     fn apply_filter(&mut self) {
         self.view_state = ViewState::Default;
 
@@ -594,6 +1066,8 @@ impl ObjectListPage {
                     .sort_by(|a, b| items[*b].size_byte().cmp(&items[*a].size_byte()));
             }
         }
+
+        self.request_split_preview();
     }
 
     pub fn open_download_confirm_dialog(
@@ -700,6 +1174,63 @@ impl ObjectListPage {
         self.view_state = ViewState::Default;
     }
 
+    fn open_presigned_url_dialog(&mut self) {
+        self.view_state = ViewState::PresignedUrlDialog(Box::default());
+    }
+
+    fn close_presigned_url_dialog(&mut self) {
+        self.view_state = ViewState::Default;
+    }
+
+    fn generate_presigned_url(&mut self, params: PresignedUrlParams) {
+        self.view_state = ViewState::Default;
+
+        let object_key = self.current_selected_object_key();
+        let name = self.current_selected_item().name();
+        let content_disposition = params.content_disposition(name);
+        self.tx.send(AppEventType::GeneratePresignedUrl(
+            object_key,
+            None,
+            params.method,
+            params.expires_in,
+            content_disposition,
+        ));
+    }
+
+    fn run_custom_command(&self, index: usize) {
+        let bucket = self.object_key.bucket_name.clone();
+        let target = match self.current_selected_item() {
+            ObjectItem::Dir {
+                key,
+                s3_uri,
+                object_url,
+                ..
+            } => CustomCommandTarget {
+                bucket,
+                key: key.clone(),
+                s3_uri: s3_uri.clone(),
+                object_url: object_url.clone(),
+                version_id: None,
+                is_file: false,
+            },
+            ObjectItem::File {
+                key,
+                s3_uri,
+                object_url,
+                ..
+            } => CustomCommandTarget {
+                bucket,
+                key: key.clone(),
+                s3_uri: s3_uri.clone(),
+                object_url: object_url.clone(),
+                version_id: None,
+                is_file: true,
+            },
+        };
+        self.tx
+            .send(AppEventType::StartCustomCommand(index, target));
+    }
+
     fn open_management_console(&self) {
         let object_key = self.current_dir_object_key().clone();
         self.tx
@@ -731,6 +1262,51 @@ impl ObjectListPage {
         &self.object_key
     }
 
+    fn toggle_mark(&mut self) {
+        let ObjectItem::File { name, .. } = self.current_selected_item() else {
+            let msg = "Only objects can be marked";
+            self.tx.send(AppEventType::NotifyWarn(msg.into()));
+            return;
+        };
+        if self.marked.as_ref() == Some(name) {
+            self.marked = None;
+        } else {
+            self.marked = Some(name.clone());
+        }
+    }
+
+    fn diff_marked(&self) {
+        let marked = self.marked.as_ref().and_then(|marked| {
+            self.object_items.iter().find_map(|item| match item {
+                ObjectItem::File {
+                    name, size_byte, ..
+                } if name == marked => Some((name, *size_byte)),
+                _ => None,
+            })
+        });
+        let Some((marked_name, marked_size_byte)) = marked else {
+            let msg = "Mark an object to compare with first";
+            self.tx.send(AppEventType::NotifyWarn(msg.into()));
+            return;
+        };
+        let ObjectItem::File {
+            name, size_byte, ..
+        } = self.current_selected_item()
+        else {
+            let msg = "Only objects can be compared";
+            self.tx.send(AppEventType::NotifyWarn(msg.into()));
+            return;
+        };
+        if name == marked_name {
+            let msg = "Select another object to compare with the marked one";
+            self.tx.send(AppEventType::NotifyWarn(msg.into()));
+            return;
+        }
+        let left = DiffTarget::new(self.object_key.child(marked_name), None, marked_size_byte);
+        let right = DiffTarget::new(self.current_selected_object_key(), None, *size_byte);
+        self.tx.send(AppEventType::StartLoadTextDiff(left, right));
+    }
+
     pub fn current_selected_object_key(&self) -> ObjectKey {
         let item = self.current_selected_item();
         let mut object_path = self.object_key.object_path.clone();
@@ -753,7 +1329,174 @@ impl ObjectListPage {
         self.list_state
     }
 
-    fn non_empty(&self) -> bool {
+    pub fn select_item_by_name(&mut self, name: &str) -> bool {
+        let position = self
+            .view_indices
+            .iter()
+            .position(|&i| self.object_items[i].name() == name);
+        if let Some(i) = position {
+            self.list_state.select_index(i);
+            self.request_split_preview();
+        }
+        position.is_some()
+    }
+
+    pub fn view_session(&self) -> ListViewSession {
+        ListViewSession {
+            selected: self
+                .non_empty()
+                .then(|| self.current_selected_item().name().to_string()),
+            filter: self.filter_input_state.input().into(),
+            sort: self.sort_dialog_state.selected().val(),
+        }
+    }
+
+    pub fn restore_view_session(&mut self, session: &ListViewSession) {
+        self.filter_input_state.set_input(session.filter.clone());
+        let sort = ObjectListSortType::try_from(session.sort).unwrap_or_default();
+        self.sort_dialog_state.set_selected(sort);
+        self.filter_view_indices();
+
+        if let Some(name) = &session.selected {
+            self.select_item_by_name(name);
+        }
+    }
+
+    pub fn set_prefix_size(&mut self, name: String, size: PrefixSize) {
+        self.prefix_sizes.insert(name, size);
+    }
+
+    pub fn remove_prefix_size(&mut self, name: &str) {
+        self.prefix_sizes.remove(name);
+    }
+
+    pub fn set_compact(&mut self, compact: bool) {
+        self.compact = compact;
+    }
+
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    // whether a dialog is shown over the list
+    pub fn is_dialog_open(&self) -> bool {
+        !matches!(self.view_state, ViewState::Default)
+    }
+
+    pub fn split_preview_enabled(&self) -> bool {
+        !matches!(self.split_preview, SplitPreview::Disabled)
+    }
+
+    pub fn set_split_preview_enabled(&mut self, enabled: bool) {
+        if enabled == self.split_preview_enabled() {
+            return;
+        }
+        self.split_preview = if enabled {
+            SplitPreview::Empty
+        } else {
+            SplitPreview::Disabled
+        };
+        self.request_split_preview();
+    }
+
+    // the key of the item whose preview is waiting to be loaded
+    pub fn split_preview_loading_key(&self) -> Option<&ObjectKey> {
+        match &self.split_preview {
+            SplitPreview::Loading(key) => Some(key),
+            _ => None,
+        }
+    }
+
+    // `content` is the error message if loading has failed
+    pub fn set_split_preview(
+        &mut self,
+        object_key: &ObjectKey,
+        content: Result<SplitPreviewContent, String>,
+    ) {
+        if self.split_preview_loading_key() != Some(object_key) {
+            return;
+        }
+        let key = object_key.clone();
+        self.split_preview = match content {
+            Ok(content) => SplitPreview::Loaded(key, content),
+            Err(msg) => SplitPreview::Failed(key, msg),
+        };
+    }
+
+    fn request_split_preview(&mut self) {
+        if !self.split_preview_enabled() {
+            return;
+        }
+        if !self.non_empty() {
+            self.split_preview = SplitPreview::Empty;
+            return;
+        }
+        let object_key = self.current_selected_object_key();
+        let current_key = match &self.split_preview {
+            SplitPreview::Loading(key)
+            | SplitPreview::Loaded(key, _)
+            | SplitPreview::Failed(key, _) => Some(key),
+            SplitPreview::Disabled | SplitPreview::Empty => None,
+        };
+        if current_key == Some(&object_key) {
+            return;
+        }
+        let dir = matches!(self.current_selected_item(), ObjectItem::Dir { .. });
+        self.split_preview = SplitPreview::Loading(object_key.clone());
+        self.tx
+            .send(AppEventType::StartLoadSplitPreview(object_key, dir));
+    }
+
+    fn render_split_preview(&self, f: &mut Frame, area: Rect) {
+        let theme = &self.ctx.theme;
+        let block = Block::bordered()
+            .padding(Padding::horizontal(1))
+            .fg(theme.fg);
+        match &self.split_preview {
+            SplitPreview::Loaded(_, SplitPreviewContent::Dir(items)) => {
+                let view_indices: Vec<usize> = (0..items.len()).collect();
+                let list_items = build_list_items(
+                    items,
+                    &view_indices,
+                    &HashMap::new(),
+                    None,
+                    "",
+                    0,
+                    usize::MAX, // nothing is selected
+                    area,
+                    true,
+                    true,
+                    &self.ctx.config.ui,
+                    theme,
+                );
+                let list = List::new(list_items).block(block);
+                f.render_widget(list, area);
+            }
+            SplitPreview::Loaded(_, SplitPreviewContent::File(detail, head)) => {
+                let lines = build_file_preview_lines(detail, head, &self.ctx.config.ui, theme);
+                f.render_widget(Paragraph::new(lines).block(block), area);
+            }
+            SplitPreview::Loading(_) => {
+                let line = Line::from("Loading...");
+                f.render_widget(Paragraph::new(line).block(block), area);
+            }
+            SplitPreview::Failed(_, msg) => {
+                let lines = vec![
+                    Line::from("Failed to load preview".fg(theme.status_error)),
+                    Line::from(msg.as_str()),
+                ];
+                let paragraph = Paragraph::new(lines)
+                    .block(block)
+                    .wrap(Wrap { trim: false });
+                f.render_widget(paragraph, area);
+            }
+            SplitPreview::Disabled | SplitPreview::Empty => {
+                f.render_widget(block, area);
+            }
+        }
+    }
+
+    pub fn non_empty(&self) -> bool {
         !self.view_indices.is_empty()
     }
 }
@@ -761,10 +1504,14 @@ impl ObjectListPage {
 fn build_list_items<'a>(
     current_items: &'a [ObjectItem],
     view_indices: &'a [usize],
+    prefix_sizes: &HashMap<String, PrefixSize>,
+    marked: Option<&str>,
     filter: &'a str,
     offset: usize,
     selected: usize,
     area: Rect,
+    compact: bool,
+    focused: bool,
     ui_config: &UiConfig,
     theme: &ColorTheme,
 ) -> Vec<ListItem<'a>> {
@@ -778,9 +1525,13 @@ fn build_list_items<'a>(
         .map(|(idx, item)| {
             build_list_item(
                 item,
+                prefix_sizes.get(item.name()),
                 idx + offset == selected,
+                marked == Some(item.name()),
                 filter,
                 area,
+                compact,
+                focused,
                 ui_config,
                 theme,
             )
@@ -790,14 +1541,23 @@ fn build_list_items<'a>(
 
 fn build_list_item<'a>(
     item: &'a ObjectItem,
+    prefix_size: Option<&PrefixSize>,
     selected: bool,
+    marked: bool,
     filter: &'a str,
     area: Rect,
+    compact: bool,
+    focused: bool,
     ui_config: &UiConfig,
     theme: &ColorTheme,
 ) -> ListItem<'a> {
     let line = match item {
-        ObjectItem::Dir { name, .. } => build_object_dir_line(name, filter, area.width, theme),
+        ObjectItem::Dir { name, .. } => match prefix_size {
+            Some(size) => build_object_dir_line_with_size(
+                name, size, filter, area.width, compact, ui_config, theme,
+            ),
+            None => build_object_dir_line(name, filter, area.width, theme),
+        },
         ObjectItem::File {
             name,
             size_byte,
@@ -809,15 +1569,24 @@ fn build_list_item<'a>(
             last_modified,
             filter,
             area.width,
+            compact,
             ui_config,
             theme,
         ),
     };
 
-    let style = if selected {
+    let style = if selected && focused {
         Style::default()
             .bg(theme.list_selected_bg)
             .fg(theme.list_selected_fg)
+            .add_modifier(theme.list_selected_modifier)
+    } else if selected {
+        Style::default()
+            .bg(theme.list_selected_inactive_bg)
+            .fg(theme.list_selected_inactive_fg)
+            .add_modifier(theme.list_selected_modifier)
+    } else if marked {
+        Style::default().fg(theme.status_warn)
     } else {
         Style::default()
     };
@@ -851,12 +1620,70 @@ fn build_object_dir_line<'a>(
     }
 }
 
+fn build_object_dir_line_with_size<'a>(
+    name: &'a str,
+    prefix_size: &PrefixSize,
+    filter: &'a str,
+    width: u16,
+    compact: bool,
+    ui_config: &UiConfig,
+    theme: &ColorTheme,
+) -> Line<'a> {
+    let name = format!("{}/", name);
+    let mut count = format_object_count(prefix_size.object_count);
+    if !prefix_size.completed {
+        count.push_str(ELLIPSIS);
+    }
+    let size = format_size_byte(prefix_size.size_byte);
+    let count_w: usize = ui_config.object_list.date_width;
+    let size_w: usize = 10;
+
+    let pad_count =
+        console::pad_str(&count, count_w, console::Alignment::Left, Some(ELLIPSIS)).to_string();
+    let pad_size = console::pad_str(&size, size_w, console::Alignment::Right, None).to_string();
+    // the object count is omitted in the compact line
+    let tail_spans: Vec<Span> = if compact {
+        vec!["  ".into(), pad_size.into(), " ".into()]
+    } else {
+        vec![
+            "    ".into(),
+            pad_count.into(),
+            "    ".into(),
+            pad_size.into(),
+            " ".into(),
+        ]
+    };
+    let tail_w: usize = tail_spans.iter().map(|s| s.width()).sum();
+    let name_w: usize =
+        (width as usize).saturating_sub(tail_w + 1 /* space */ + 4 /* border + pad */);
+
+    let pad_name =
+        console::pad_str(&name, name_w, console::Alignment::Left, Some(ELLIPSIS)).to_string();
+
+    let mut spans = if filter.is_empty() {
+        vec![" ".into(), pad_name.bold()]
+    } else {
+        let i = name.find(filter).unwrap();
+        let mut spans = highlight_matched_text(pad_name)
+            .ellipsis(ELLIPSIS)
+            .matched_range(i, i + filter.len())
+            .not_matched_style(Style::default().bold())
+            .matched_style(Style::default().fg(theme.list_filter_match).bold())
+            .into_spans();
+        spans.insert(0, " ".into());
+        spans
+    };
+    spans.extend(tail_spans);
+    Line::from(spans)
+}
+
 fn build_object_file_line<'a>(
     name: &'a str,
     size_byte: usize,
     last_modified: &'a DateTime<Local>,
     filter: &'a str,
     width: u16,
+    compact: bool,
     ui_config: &UiConfig,
     theme: &ColorTheme,
 ) -> Line<'a> {
@@ -864,23 +1691,32 @@ fn build_object_file_line<'a>(
     let date = format_datetime(last_modified, &ui_config.object_list.date_format);
     let date_w: usize = ui_config.object_list.date_width;
     let size_w: usize = 10;
-    let name_w: usize = (width as usize) - date_w - size_w - 10 /* spaces */ - 4 /* border + pad */;
 
-    let pad_name =
-        console::pad_str(name, name_w, console::Alignment::Left, Some(ELLIPSIS)).to_string();
     let pad_date = console::pad_str(&date, date_w, console::Alignment::Left, None).to_string();
     let pad_size = console::pad_str(&size, size_w, console::Alignment::Right, None).to_string();
-
-    if filter.is_empty() {
-        Line::from(vec![
-            " ".into(),
-            pad_name.into(),
+    // the last modified is omitted in the compact line
+    let tail_spans: Vec<Span> = if compact {
+        vec!["  ".into(), pad_size.into(), " ".into()]
+    } else {
+        vec![
             "    ".into(),
             pad_date.into(),
             "    ".into(),
             pad_size.into(),
             " ".into(),
-        ])
+        ]
+    };
+    let tail_w: usize = tail_spans.iter().map(|s| s.width()).sum();
+    let name_w: usize =
+        (width as usize).saturating_sub(tail_w + 1 /* space */ + 4 /* border + pad */);
+
+    let pad_name =
+        console::pad_str(name, name_w, console::Alignment::Left, Some(ELLIPSIS)).to_string();
+
+    if filter.is_empty() {
+        let mut spans = vec![" ".into(), pad_name.into()];
+        spans.extend(tail_spans);
+        Line::from(spans)
     } else {
         let i = name.find(filter).unwrap();
         let mut spans = highlight_matched_text(pad_name)
@@ -890,15 +1726,59 @@ fn build_object_file_line<'a>(
             .matched_style(Style::default().fg(theme.list_filter_match))
             .into_spans();
         spans.insert(0, " ".into());
-        spans.push("    ".into());
-        spans.push(pad_date.into());
-        spans.push("    ".into());
-        spans.push(pad_size.into());
-        spans.push(" ".into());
+        spans.extend(tail_spans);
         Line::from(spans)
     }
 }
 
+fn build_file_preview_lines<'a>(
+    detail: &FileDetail,
+    head: &[u8],
+    ui_config: &UiConfig,
+    theme: &ColorTheme,
+) -> Vec<Line<'a>> {
+    let details = [
+        ("Size: ", format_size_byte(detail.size_byte)),
+        (
+            "Last Modified: ",
+            format_datetime(&detail.last_modified, &ui_config.object_detail.date_format),
+        ),
+        ("Content-Type: ", detail.content_type.clone()),
+    ];
+    let mut lines: Vec<Line> = details
+        .into_iter()
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(label, value)| Line::from(vec![label.bold(), value.into()]))
+        .collect();
+    lines.push(Line::from(""));
+
+    match head_text(head) {
+        Some(text) => {
+            let text_lines = text.replace('\t', "    ");
+            lines.extend(text_lines.lines().map(|l| Line::from(l.to_string())));
+        }
+        None => {
+            lines.push(Line::from("Binary object".fg(theme.preview_line_number)));
+        }
+    }
+    lines
+}
+
+// returns None if the bytes do not look like a text
+fn head_text(bytes: &[u8]) -> Option<String> {
+    if bytes.contains(&0) {
+        return None;
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(s) => Some(s.to_string()),
+        // the last character may be cut in the middle
+        Err(e) if e.error_len().is_none() => {
+            Some(String::from_utf8_lossy(&bytes[..e.valid_up_to()]).into_owned())
+        }
+        Err(_) => None,
+    }
+}
+
 fn build_download_confirm_message_lines<'a>(
     objs: &[DownloadObjectInfo],
     theme: &ColorTheme,
@@ -1068,6 +1948,62 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_render_with_prefix_size() -> std::io::Result<()> {
+        let ctx = Rc::default();
+        let tx = sender();
+        let mut terminal = setup_terminal()?;
+
+        terminal.draw(|f| {
+            let items = vec![
+                object_dir_item("dir1"),
+                object_dir_item("dir2"),
+                object_file_item("file1", 1024 + 10, "2024-01-02 13:01:02"),
+            ];
+            let object_key = ObjectKey {
+                bucket_name: "test-bucket".to_string(),
+                object_path: vec!["path".to_string(), "to".to_string()],
+            };
+            let mut page = ObjectListPage::new(items, object_key, ctx, tx);
+
+            let mut completed = PrefixSize::default();
+            completed.add("STANDARD", 1024 * 3);
+            completed.add("STANDARD", 1024);
+            completed.completed = true;
+            page.set_prefix_size("dir1".into(), completed);
+            let mut calculating = PrefixSize::default();
+            calculating.add("STANDARD", 10);
+            page.set_prefix_size("dir2".into(), calculating);
+
+            let area = Rect::new(0, 0, 60, 10);
+            page.render(f, area);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌─────────────────────────────────────────────────── 1 / 3 ┐",
+            "│  dir1/                2 objects                   4 KiB  │",
+            "│  dir2/                1 object...                  10 B  │",
+            "│  file1                2024-01-02 13:01:02      1.01 KiB  │",
+            "│                                                          │",
+            "│                                                          │",
+            "│                                                          │",
+            "│                                                          │",
+            "│                                                          │",
+            "└──────────────────────────────────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            // dir items
+            (3..20, [1, 2]) => modifier: Modifier::BOLD,
+            // selected item
+            (2..58, [1]) => bg: Color::Cyan, fg: Color::Black,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_sort_items() {
         let ctx = Rc::default();
@@ -1137,6 +2073,199 @@ mod tests {
         assert_eq!(page.view_indices, vec![3, 1, 4, 0, 2]);
     }
 
+    #[tokio::test]
+    async fn test_diff_marked() -> std::io::Result<()> {
+        let ctx = Rc::default();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let tx = Sender::new(tx);
+        let mut terminal = setup_terminal()?;
+
+        let items = vec![
+            object_dir_item("dir1"),
+            object_file_item("file1", 10, "2024-01-02 13:01:02"),
+            object_file_item("file2", 20, "2024-01-02 13:01:02"),
+        ];
+        let object_key = ObjectKey {
+            bucket_name: "test-bucket".to_string(),
+            object_path: vec!["path".to_string()],
+        };
+        let mut page = ObjectListPage::new(items, object_key.clone(), ctx, tx);
+        let key_event = KeyEvent::from(KeyCode::Null);
+        // the height of the list is set by rendering
+        terminal.draw(|f| page.render(f, Rect::new(0, 0, 60, 10)))?;
+
+        // directories cannot be marked
+        page.handle_key(vec![UserEvent::ObjectListMark], key_event);
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            AppEventType::NotifyWarn(_)
+        ));
+
+        page.handle_key(vec![UserEvent::ObjectListDown], key_event);
+        page.handle_key(vec![UserEvent::ObjectListDiff], key_event);
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            AppEventType::NotifyWarn(_)
+        ));
+
+        page.handle_key(vec![UserEvent::ObjectListMark], key_event);
+        page.handle_key(vec![UserEvent::ObjectListDiff], key_event);
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            AppEventType::NotifyWarn(_)
+        ));
+
+        page.handle_key(vec![UserEvent::ObjectListDown], key_event);
+        page.handle_key(vec![UserEvent::ObjectListDiff], key_event);
+        match rx.try_recv().unwrap() {
+            AppEventType::StartLoadTextDiff(left, right) => {
+                assert_eq!(left, DiffTarget::new(object_key.child("file1"), None, 10));
+                assert_eq!(right, DiffTarget::new(object_key.child("file2"), None, 20));
+            }
+            _ => panic!("text diff is not started"),
+        }
+
+        // unmark
+        page.handle_key(vec![UserEvent::ObjectListUp], key_event);
+        page.handle_key(vec![UserEvent::ObjectListMark], key_event);
+        assert_eq!(page.marked, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_render_split_preview() -> std::io::Result<()> {
+        let ctx = Rc::default();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let tx = Sender::new(tx);
+        let mut terminal = setup_terminal()?;
+
+        let items = vec![
+            object_dir_item("dir1"),
+            object_file_item("file1", 1024 + 10, "2024-01-02 13:01:02"),
+        ];
+        let object_key = ObjectKey {
+            bucket_name: "test-bucket".to_string(),
+            object_path: vec!["path".to_string()],
+        };
+        let mut page = ObjectListPage::new(items, object_key.clone(), ctx, tx);
+
+        page.set_split_preview_enabled(true);
+        let dir_key = object_key.child("dir1");
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            AppEventType::StartLoadSplitPreview(key, true) if key == dir_key
+        ));
+
+        // ignored, not the selected item
+        let content = SplitPreviewContent::Dir(vec![object_dir_item("x")]);
+        page.set_split_preview(&object_key.child("file1"), Ok(content));
+
+        let content = SplitPreviewContent::Dir(vec![
+            object_dir_item("child"),
+            object_file_item("a.txt", 10, "2024-01-02 13:01:02"),
+        ]);
+        page.set_split_preview(&dir_key, Ok(content));
+
+        terminal.draw(|f| {
+            let area = Rect::new(0, 0, 60, 10);
+            page.render(f, area);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌───────────────────── 1 / 2 ┐┌────────────────────────────┐",
+            "│  dir1/                     ││  child/                    │",
+            "│  file1           1.01 KiB  ││  a.txt               10 B  │",
+            "│                            ││                            │",
+            "│                            ││                            │",
+            "│                            ││                            │",
+            "│                            ││                            │",
+            "│                            ││                            │",
+            "│                            ││                            │",
+            "└────────────────────────────┘└────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            // dir items
+            (3..26, [1]) => modifier: Modifier::BOLD,
+            (33..56, [1]) => modifier: Modifier::BOLD,
+            // selected item
+            (2..28, [1]) => bg: Color::Cyan, fg: Color::Black,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        page.handle_key(
+            vec![UserEvent::ObjectListDown],
+            KeyEvent::from(KeyCode::Char('j')),
+        );
+        let file_key = object_key.child("file1");
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            AppEventType::StartLoadSplitPreview(key, false) if key == file_key
+        ));
+        assert_eq!(page.split_preview_loading_key(), Some(&file_key));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_render_split_preview_failed() -> std::io::Result<()> {
+        let ctx = Rc::default();
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let tx = Sender::new(tx);
+        let mut terminal = setup_terminal()?;
+
+        let items = vec![object_file_item("file1", 10, "2024-01-02 13:01:02")];
+        let object_key = ObjectKey {
+            bucket_name: "test-bucket".to_string(),
+            object_path: vec![],
+        };
+        let mut page = ObjectListPage::new(items, object_key.clone(), ctx, tx);
+
+        page.set_split_preview_enabled(true);
+        let msg = "Failed to load object: access denied".to_string();
+        page.set_split_preview(&object_key.child("file1"), Err(msg));
+
+        terminal.draw(|f| {
+            let area = Rect::new(0, 0, 60, 10);
+            page.render(f, area);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌───────────────────── 1 / 1 ┐┌────────────────────────────┐",
+            "│  file1               10 B  ││ Failed to load preview     │",
+            "│                            ││ Failed to load object:     │",
+            "│                            ││ access denied              │",
+            "│                            ││                            │",
+            "│                            ││                            │",
+            "│                            ││                            │",
+            "│                            ││                            │",
+            "│                            ││                            │",
+            "└────────────────────────────┘└────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            // selected item
+            (2..28, [1]) => bg: Color::Cyan, fg: Color::Black,
+            // error title
+            (32..54, [1]) => fg: Color::Red,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_head_text() {
+        assert_eq!(head_text(b"abc\ndef"), Some("abc\ndef".to_string()));
+        // cut in the middle of a character
+        assert_eq!(head_text(&"あい".as_bytes()[..4]), Some("あ".to_string()));
+        assert_eq!(head_text(b"ab\0c"), None);
+        assert_eq!(head_text(&[0xff, 0xfe, 0x61]), None);
+    }
+
     fn setup_terminal() -> std::io::Result<Terminal<TestBackend>> {
         let backend = TestBackend::new(60, 10);
         let mut terminal = Terminal::new(backend)?;
@@ -12,10 +12,11 @@ use crate::{
         SpansWithPriority,
     },
     keys::{UserEvent, UserEventMapper},
-    object::{FileDetail, RawObject},
+    mouse::{handle_dialog_mouse_input, MouseInput},
+    object::{FileDetail, ObjectKey, RawObject},
     widget::{
         self, EncodingDialog, EncodingDialogState, ImagePreview, ImagePreviewState, InputDialog,
-        InputDialogState, TextPreview, TextPreviewState,
+        InputDialogState, SearchDirection, SearchOptions, TextPreview, TextPreviewState,
     },
 };
 
@@ -23,12 +24,14 @@ use crate::{
 pub struct ObjectPreviewPage {
     preview_type: PreviewType,
 
+    object_key: ObjectKey,
     file_detail: FileDetail,
     file_version_id: Option<String>,
     object: Arc<RawObject>,
 
     view_state: ViewState,
     encoding_dialog_state: EncodingDialogState,
+    search_options: SearchOptions,
 
     ctx: Rc<AppContext>,
     tx: Sender,
@@ -46,10 +49,14 @@ enum ViewState {
     Default,
     SaveDialog(InputDialogState),
     EncodingDialog,
+    PipeDialog(InputDialogState),
+    SearchDialog(InputDialogState, SearchDirection),
+    GoToLineDialog(InputDialogState),
 }
 
 impl ObjectPreviewPage {
     pub fn new(
+        object_key: ObjectKey,
         file_detail: FileDetail,
         file_version_id: Option<String>,
         object: RawObject,
@@ -82,10 +89,12 @@ impl ObjectPreviewPage {
         Self {
             preview_type,
             object: Arc::new(object),
+            object_key,
             file_detail,
             file_version_id,
             view_state: ViewState::Default,
             encoding_dialog_state,
+            search_options: SearchOptions::default(),
             ctx,
             tx,
         }
@@ -137,7 +146,32 @@ impl ObjectPreviewPage {
                     UserEvent::ObjectPreviewEncoding => {
                         self.open_encoding_dialog();
                     }
-                    UserEvent::Help => {
+                    UserEvent::ObjectPreviewEdit => {
+                        self.edit();
+                    }
+                    UserEvent::ObjectPreviewOpen => {
+                        self.open();
+                    }
+                    UserEvent::ObjectPreviewPipe => {
+                        self.open_pipe_dialog();
+                    }
+                    UserEvent::ObjectPreviewSearch => {
+                        self.open_search_dialog(SearchDirection::Forward);
+                    }
+                    UserEvent::ObjectPreviewSearchBackward => {
+                        self.open_search_dialog(SearchDirection::Backward);
+                    }
+                    UserEvent::ObjectPreviewSearchNext => {
+                        self.search_next(false);
+                    }
+                    UserEvent::ObjectPreviewSearchPrev => {
+                        self.search_next(true);
+                    }
+                    UserEvent::ObjectPreviewGoToLine => {
+                        self.open_go_to_line_dialog();
+                    }
+                    // `?` starts a backward search in the text preview by default
+                    UserEvent::Help if !user_events.contains(&UserEvent::ObjectPreviewSearchBackward) => {
                         self.tx.send(AppEventType::OpenHelp);
                     }
                 }
@@ -155,6 +189,9 @@ impl ObjectPreviewPage {
                         self.open_save_dialog();
                         self.disable_image_render();
                     }
+                    UserEvent::ObjectPreviewOpen => {
+                        self.open();
+                    }
                     UserEvent::Help => {
                         self.tx.send(AppEventType::OpenHelp);
                     }
@@ -179,6 +216,58 @@ impl ObjectPreviewPage {
                     }
                 }
             }
+            (ViewState::PipeDialog(state), _) => {
+                handle_user_events_with_default! { user_events =>
+                    UserEvent::InputDialogClose => {
+                        self.close_pipe_dialog();
+                    }
+                    UserEvent::InputDialogApply => {
+                        let input = state.input().into();
+                        self.pipe(input);
+                    }
+                    UserEvent::Help => {
+                        self.tx.send(AppEventType::OpenHelp);
+                    }
+                    => {
+                        state.handle_key_event(key_event);
+                    }
+                }
+            }
+            (ViewState::SearchDialog(state, direction), _) => {
+                handle_user_events_with_default! { user_events =>
+                    UserEvent::InputDialogClose => {
+                        self.close_search_dialog();
+                    }
+                    UserEvent::InputDialogApply => {
+                        let input = state.input().into();
+                        let direction = *direction;
+                        self.search(input, direction);
+                    }
+                    UserEvent::SearchDialogToggleRegex => {
+                        self.search_options.regex = !self.search_options.regex;
+                    }
+                    UserEvent::SearchDialogToggleIgnoreCase => {
+                        self.search_options.ignore_case = !self.search_options.ignore_case;
+                    }
+                    => {
+                        state.handle_key_event(key_event);
+                    }
+                }
+            }
+            (ViewState::GoToLineDialog(state), _) => {
+                handle_user_events_with_default! { user_events =>
+                    UserEvent::InputDialogClose => {
+                        self.close_go_to_line_dialog();
+                    }
+                    UserEvent::InputDialogApply => {
+                        let input = state.input().into();
+                        self.go_to_line(input);
+                    }
+                    => {
+                        state.handle_key_event(key_event);
+                    }
+                }
+            }
             (ViewState::EncodingDialog, _) => {
                 handle_user_events! { user_events =>
                     UserEvent::SelectDialogClose => {
@@ -201,6 +290,36 @@ impl ObjectPreviewPage {
         }
     }
 
+    // keys are typed into the input as is, without key sequences and counts
+    pub fn is_text_input_active(&self) -> bool {
+        matches!(
+            self.view_state,
+            ViewState::SaveDialog(_)
+                | ViewState::PipeDialog(_)
+                | ViewState::SearchDialog(_, _)
+                | ViewState::GoToLineDialog(_)
+        )
+    }
+
+    pub fn handle_mouse(&mut self, input: MouseInput, area: Rect) -> Option<UserEvent> {
+        match (&self.view_state, &self.preview_type) {
+            (ViewState::Default, PreviewType::Text(_)) => match input {
+                MouseInput::ScrollDown(_) => Some(UserEvent::ObjectPreviewDown),
+                MouseInput::ScrollUp(_) => Some(UserEvent::ObjectPreviewUp),
+                MouseInput::Click(_) | MouseInput::DoubleClick(_) => None,
+            },
+            (ViewState::EncodingDialog, _) => {
+                let index = self
+                    .encoding_dialog_state
+                    .item_index_at(area, input.position());
+                handle_dialog_mouse_input(input, index, |i| {
+                    self.encoding_dialog_state.select_index(i)
+                })
+            }
+            _ => None,
+        }
+    }
+
     pub fn render(&mut self, f: &mut Frame, area: Rect) {
         match self.preview_type {
             PreviewType::Text(ref mut state) => {
@@ -231,6 +350,39 @@ impl ObjectPreviewPage {
             f.set_cursor_position((cursor_x, cursor_y));
         }
 
+        if let ViewState::PipeDialog(state) = &mut self.view_state {
+            let pipe_dialog = InputDialog::default()
+                .title("Pipe to command")
+                .max_width(60)
+                .theme(&self.ctx.theme);
+            f.render_stateful_widget(pipe_dialog, area, state);
+
+            let (cursor_x, cursor_y) = state.cursor();
+            f.set_cursor_position((cursor_x, cursor_y));
+        }
+
+        if let ViewState::SearchDialog(state, direction) = &mut self.view_state {
+            let search_dialog = InputDialog::default()
+                .title(search_dialog_title(*direction, self.search_options))
+                .max_width(60)
+                .theme(&self.ctx.theme);
+            f.render_stateful_widget(search_dialog, area, state);
+
+            let (cursor_x, cursor_y) = state.cursor();
+            f.set_cursor_position((cursor_x, cursor_y));
+        }
+
+        if let ViewState::GoToLineDialog(state) = &mut self.view_state {
+            let go_to_line_dialog = InputDialog::default()
+                .title("Go to line")
+                .max_width(30)
+                .theme(&self.ctx.theme);
+            f.render_stateful_widget(go_to_line_dialog, area, state);
+
+            let (cursor_x, cursor_y) = state.cursor();
+            f.set_cursor_position((cursor_x, cursor_y));
+        }
+
         if let ViewState::EncodingDialog = &mut self.view_state {
             let encoding_dialog =
                 EncodingDialog::new(&self.encoding_dialog_state).theme(&self.ctx.theme);
@@ -239,6 +391,10 @@ impl ObjectPreviewPage {
     }
 
     pub fn helps(&self, mapper: &UserEventMapper) -> Vec<Spans> {
+        build_help_spans(self.help_items(), mapper, self.ctx.theme.help_key_fg)
+    }
+
+    pub fn help_items(&self) -> Vec<BuildHelpsItem> {
         #[rustfmt::skip]
         let helps = match (&self.view_state, &self.preview_type) {
             (ViewState::Default, PreviewType::Text(_)) => {
@@ -258,6 +414,14 @@ impl ObjectPreviewPage {
                     BuildHelpsItem::new(UserEvent::ObjectPreviewDownload, "Download object"),
                     BuildHelpsItem::new(UserEvent::ObjectPreviewDownloadAs, "Download object as"),
                     BuildHelpsItem::new(UserEvent::ObjectPreviewEncoding, "Open encoding dialog"),
+                    BuildHelpsItem::new(UserEvent::ObjectPreviewEdit, "Edit object in editor"),
+                    BuildHelpsItem::new(UserEvent::ObjectPreviewOpen, "Open object with external program"),
+                    BuildHelpsItem::new(UserEvent::ObjectPreviewPipe, "Pipe object to command"),
+                    BuildHelpsItem::new(UserEvent::ObjectPreviewSearch, "Search forward"),
+                    BuildHelpsItem::new(UserEvent::ObjectPreviewSearchBackward, "Search backward"),
+                    BuildHelpsItem::new(UserEvent::ObjectPreviewSearchNext, "Go to next match"),
+                    BuildHelpsItem::new(UserEvent::ObjectPreviewSearchPrev, "Go to previous match"),
+                    BuildHelpsItem::new(UserEvent::ObjectPreviewGoToLine, "Go to line"),
                 ]
             },
             (ViewState::Default, PreviewType::Image(_)) => {
@@ -266,6 +430,7 @@ impl ObjectPreviewPage {
                     BuildHelpsItem::new(UserEvent::ObjectPreviewBack, "Close preview"),
                     BuildHelpsItem::new(UserEvent::ObjectPreviewDownload, "Download object"),
                     BuildHelpsItem::new(UserEvent::ObjectPreviewDownloadAs, "Download object as"),
+                    BuildHelpsItem::new(UserEvent::ObjectPreviewOpen, "Open object with external program"),
                 ]
             },
             (ViewState::SaveDialog(_), _) => {
@@ -275,6 +440,29 @@ impl ObjectPreviewPage {
                     BuildHelpsItem::new(UserEvent::InputDialogApply, "Download object"),
                 ]
             },
+            (ViewState::PipeDialog(_), _) => {
+                vec![
+                    BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+                    BuildHelpsItem::new(UserEvent::InputDialogClose, "Close pipe dialog"),
+                    BuildHelpsItem::new(UserEvent::InputDialogApply, "Run command"),
+                ]
+            },
+            (ViewState::SearchDialog(_, _), _) => {
+                vec![
+                    BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+                    BuildHelpsItem::new(UserEvent::InputDialogClose, "Close search dialog"),
+                    BuildHelpsItem::new(UserEvent::InputDialogApply, "Search"),
+                    BuildHelpsItem::new(UserEvent::SearchDialogToggleRegex, "Toggle regex"),
+                    BuildHelpsItem::new(UserEvent::SearchDialogToggleIgnoreCase, "Toggle ignore case"),
+                ]
+            },
+            (ViewState::GoToLineDialog(_), _) => {
+                vec![
+                    BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+                    BuildHelpsItem::new(UserEvent::InputDialogClose, "Close go to line dialog"),
+                    BuildHelpsItem::new(UserEvent::InputDialogApply, "Go to line"),
+                ]
+            },
             (ViewState::EncodingDialog, _) => {
                 vec![
                     BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
@@ -285,7 +473,7 @@ impl ObjectPreviewPage {
                 ]
             },
         };
-        build_help_spans(helps, mapper, self.ctx.theme.help_key_fg)
+        helps
     }
 
     pub fn short_helps(&self, mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
@@ -298,6 +486,8 @@ impl ObjectPreviewPage {
                     BuildShortHelpsItem::group(vec![UserEvent::ObjectPreviewGoToTop, UserEvent::ObjectPreviewGoToBottom], "Top/End", 5),
                     BuildShortHelpsItem::group(vec![UserEvent::ObjectPreviewDownload, UserEvent::ObjectPreviewDownloadAs], "Download", 3),
                     BuildShortHelpsItem::single(UserEvent::ObjectPreviewEncoding, "Encoding", 4),
+                    BuildShortHelpsItem::single(UserEvent::ObjectPreviewEdit, "Edit", 7),
+                    BuildShortHelpsItem::group(vec![UserEvent::ObjectPreviewSearch, UserEvent::ObjectPreviewSearchNext], "Search", 6),
                     BuildShortHelpsItem::single(UserEvent::ObjectPreviewBack, "Close", 1),
                     BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
                 ]
@@ -316,6 +506,27 @@ impl ObjectPreviewPage {
                     BuildShortHelpsItem::single(UserEvent::InputDialogApply, "Download", 1),
                     BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
                 ]
+            },
+            (ViewState::PipeDialog(_), _) => {
+                vec![
+                    BuildShortHelpsItem::single(UserEvent::InputDialogClose, "Close", 2),
+                    BuildShortHelpsItem::single(UserEvent::InputDialogApply, "Run", 1),
+                    BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+                ]
+            },
+            (ViewState::SearchDialog(_, _), _) => {
+                vec![
+                    BuildShortHelpsItem::single(UserEvent::InputDialogClose, "Close", 2),
+                    BuildShortHelpsItem::single(UserEvent::InputDialogApply, "Search", 1),
+                    BuildShortHelpsItem::single(UserEvent::SearchDialogToggleRegex, "Regex", 3),
+                    BuildShortHelpsItem::single(UserEvent::SearchDialogToggleIgnoreCase, "Ignore case", 4),
+                ]
+            },
+            (ViewState::GoToLineDialog(_), _) => {
+                vec![
+                    BuildShortHelpsItem::single(UserEvent::InputDialogClose, "Close", 2),
+                    BuildShortHelpsItem::single(UserEvent::InputDialogApply, "Go", 1),
+                ]
             },
              (ViewState::EncodingDialog, _) => {
                 vec![
@@ -339,6 +550,75 @@ impl ObjectPreviewPage {
         self.view_state = ViewState::Default;
     }
 
+    fn open_pipe_dialog(&mut self) {
+        self.view_state = ViewState::PipeDialog(InputDialogState::default());
+    }
+
+    fn close_pipe_dialog(&mut self) {
+        self.view_state = ViewState::Default;
+    }
+
+    fn open_search_dialog(&mut self, direction: SearchDirection) {
+        let mut state = InputDialogState::default();
+        if let PreviewType::Text(preview_state) = &self.preview_type {
+            if let Some(query) = preview_state.last_search_query() {
+                state.set_input(query.into());
+            }
+        }
+        self.view_state = ViewState::SearchDialog(state, direction);
+    }
+
+    fn close_search_dialog(&mut self) {
+        self.view_state = ViewState::Default;
+    }
+
+    fn open_go_to_line_dialog(&mut self) {
+        self.view_state = ViewState::GoToLineDialog(InputDialogState::default());
+    }
+
+    fn close_go_to_line_dialog(&mut self) {
+        self.view_state = ViewState::Default;
+    }
+
+    fn search(&mut self, input: String, direction: SearchDirection) {
+        self.close_search_dialog();
+        if let PreviewType::Text(state) = &mut self.preview_type {
+            match state.search(&input, self.search_options, direction) {
+                Ok(()) => self.notify_search_status(),
+                Err(msg) => self.tx.send(AppEventType::NotifyWarn(msg)),
+            }
+        }
+    }
+
+    fn search_next(&mut self, reverse: bool) {
+        if let PreviewType::Text(state) = &mut self.preview_type {
+            match state.search_next(reverse) {
+                Ok(()) => self.notify_search_status(),
+                Err(msg) => self.tx.send(AppEventType::NotifyWarn(msg)),
+            }
+        }
+    }
+
+    fn notify_search_status(&self) {
+        if let PreviewType::Text(state) = &self.preview_type {
+            if let Some(msg) = state.search_status() {
+                self.tx.send(AppEventType::NotifyInfo(msg));
+            }
+        }
+    }
+
+    fn go_to_line(&mut self, input: String) {
+        let Ok(line) = input.trim().parse::<usize>() else {
+            let msg = format!("Invalid line number: {}", input.trim());
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        };
+        if let PreviewType::Text(state) = &mut self.preview_type {
+            state.scroll_lines_state.scroll_to(line.saturating_sub(1));
+        }
+        self.close_go_to_line_dialog();
+    }
+
     fn open_encoding_dialog(&mut self) {
         if let PreviewType::Text(_) = &mut self.preview_type {
             self.view_state = ViewState::EncodingDialog;
@@ -377,10 +657,45 @@ impl ObjectPreviewPage {
         }
     }
 
+    pub fn current_object_key(&self) -> &ObjectKey {
+        &self.object_key
+    }
+
     pub fn is_image_preview(&self) -> bool {
         matches!(self.preview_type, PreviewType::Image(_))
     }
 
+    fn edit(&self) {
+        if self.file_version_id.is_some() {
+            let msg = "Only the latest version can be edited";
+            self.tx.send(AppEventType::NotifyWarn(msg.into()));
+            return;
+        }
+        let object_key = self.object_key.clone();
+        self.tx.send(AppEventType::StartEditObject(object_key));
+    }
+
+    fn open(&self) {
+        let object_key = self.object_key.clone();
+        let version_id = self.file_version_id.clone();
+        self.tx
+            .send(AppEventType::StartOpenObject(object_key, version_id));
+    }
+
+    fn pipe(&mut self, input: String) {
+        let input: String = input.trim().into();
+        if input.is_empty() {
+            return;
+        }
+
+        let object_key = self.object_key.clone();
+        let version_id = self.file_version_id.clone();
+        self.tx
+            .send(AppEventType::StartPipeObject(object_key, version_id, input));
+
+        self.close_pipe_dialog();
+    }
+
     fn download(&self) {
         self.tx.send(AppEventType::StartSaveObject(
             self.file_detail.name.clone(),
@@ -413,6 +728,20 @@ impl From<ImagePicker> for widget::ImagePicker {
     }
 }
 
+fn search_dialog_title(direction: SearchDirection, options: SearchOptions) -> String {
+    let direction = match direction {
+        SearchDirection::Forward => "forward",
+        SearchDirection::Backward => "backward",
+    };
+    let on_off = |b: bool| if b { "on" } else { "off" };
+    format!(
+        "Search {} (regex: {}, ignore case: {})",
+        direction,
+        on_off(options.regex),
+        on_off(options.ignore_case)
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use crate::set_cells;
@@ -442,7 +771,7 @@ mod tests {
                 "Thank you!",
             ];
             let object = object(&preview);
-            let mut page = ObjectPreviewPage::new(file_detail, None, object, ctx, tx);
+            let mut page = ObjectPreviewPage::new(object_key(), file_detail, None, object, ctx, tx);
             let area = Rect::new(0, 0, 30, 10);
             page.render(f, area);
         })?;
@@ -479,7 +808,7 @@ mod tests {
             let file_detail = file_detail();
             let preview = ["Hello, world!"; 20];
             let object = object(&preview);
-            let mut page = ObjectPreviewPage::new(file_detail, None, object, ctx, tx);
+            let mut page = ObjectPreviewPage::new(object_key(), file_detail, None, object, ctx, tx);
             let area = Rect::new(0, 0, 30, 10);
             page.render(f, area);
         })?;
@@ -506,6 +835,124 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_render_with_search() -> std::io::Result<()> {
+        let ctx = Rc::default();
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let tx = Sender::new(tx);
+        let mut terminal = setup_terminal()?;
+
+        terminal.draw(|f| {
+            let file_detail = file_detail();
+            let preview = [
+                "Hello, world!",
+                "This is a test file.",
+                "This file is used for testing.",
+                "Thank you!",
+            ];
+            let object = object(&preview);
+            let mut page = ObjectPreviewPage::new(object_key(), file_detail, None, object, ctx, tx);
+            page.search("test".into(), SearchDirection::Forward);
+            let area = Rect::new(0, 0, 30, 10);
+            page.render(f, area);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌Preview [file.txt]──────────┐",
+            "│ 2 This is a test file.     │",
+            "│ 3 This file is used for    │",
+            "│   testing.                 │",
+            "│ 4 Thank you!               │",
+            "│                            │",
+            "│                            │",
+            "│                            │",
+            "│                            │",
+            "└────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            ([2], [1, 2, 4]) => fg: Color::DarkGray,
+            // current match
+            (14..18, [1]) => bg: Color::Cyan, fg: Color::Black,
+            // other matches
+            (4..8, [3]) => bg: Color::DarkGray, fg: Color::Black,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_and_go_to_line() {
+        let ctx = Rc::default();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let tx = Sender::new(tx);
+        let preview = ["foo", "bar", "Foo", "baz", "foo"];
+        let object = object(&preview);
+        let mut page = ObjectPreviewPage::new(object_key(), file_detail(), None, object, ctx, tx);
+
+        let current_line = |page: &ObjectPreviewPage| match &page.preview_type {
+            PreviewType::Text(state) => state.scroll_lines_state.current_line(),
+            PreviewType::Image(_) => unreachable!(),
+        };
+        let mut notified = || match rx.try_recv().unwrap() {
+            AppEventType::NotifyInfo(msg) => msg,
+            AppEventType::NotifyWarn(msg) => format!("warn: {msg}"),
+            _ => unreachable!(),
+        };
+
+        page.search("foo".into(), SearchDirection::Forward);
+        assert_eq!(current_line(&page), 0);
+        assert_eq!(notified(), "[1/2] /foo");
+
+        page.search_next(false);
+        assert_eq!(current_line(&page), 4);
+        assert_eq!(notified(), "[2/2] /foo");
+
+        page.search_next(false);
+        assert_eq!(current_line(&page), 0);
+        assert_eq!(notified(), "[1/2] /foo");
+
+        page.search_next(true);
+        assert_eq!(current_line(&page), 4);
+        assert_eq!(notified(), "[2/2] /foo");
+
+        page.search_options.ignore_case = true;
+        page.search("foo".into(), SearchDirection::Backward);
+        assert_eq!(current_line(&page), 2);
+        assert_eq!(notified(), "[2/3] ?foo");
+
+        // `n` follows the direction of the search
+        page.search_next(false);
+        assert_eq!(current_line(&page), 0);
+        assert_eq!(notified(), "[1/3] ?foo");
+
+        page.search_options.regex = true;
+        page.search("^ba".into(), SearchDirection::Forward);
+        assert_eq!(current_line(&page), 1);
+        assert_eq!(notified(), "[1/2] /^ba");
+
+        page.search("(".into(), SearchDirection::Forward);
+        assert_eq!(notified(), "warn: Invalid regex: (");
+
+        page.search("qux".into(), SearchDirection::Forward);
+        assert_eq!(notified(), "warn: Pattern not found: qux");
+
+        page.search("".into(), SearchDirection::Forward);
+        page.search_next(false);
+        assert_eq!(notified(), "warn: No previous search");
+
+        page.go_to_line("4".into());
+        assert_eq!(current_line(&page), 3);
+
+        page.go_to_line("100".into());
+        assert_eq!(current_line(&page), 4);
+
+        page.go_to_line("x".into());
+        assert_eq!(notified(), "warn: Invalid line number: x");
+    }
+
     #[tokio::test]
     async fn test_render_save_dialog_without_scroll() -> std::io::Result<()> {
         let ctx = Rc::default();
@@ -521,7 +968,7 @@ mod tests {
                 "Thank you!",
             ];
             let object = object(&preview);
-            let mut page = ObjectPreviewPage::new(file_detail, None, object, ctx, tx);
+            let mut page = ObjectPreviewPage::new(object_key(), file_detail, None, object, ctx, tx);
             page.open_save_dialog();
             let area = Rect::new(0, 0, 30, 10);
             page.render(f, area);
@@ -582,4 +1029,11 @@ mod tests {
             object_url: "https://bucket-1.s3.ap-northeast-1.amazonaws.com/file.txt".to_string(),
         }
     }
+
+    fn object_key() -> ObjectKey {
+        ObjectKey {
+            bucket_name: "bucket-1".to_string(),
+            object_path: vec!["file.txt".to_string()],
+        }
+    }
 }
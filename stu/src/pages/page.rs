@@ -4,15 +4,25 @@ use ratatui::{crossterm::event::KeyEvent, layout::Rect, Frame};
 
 use crate::{
     app::AppContext,
+    bookmark::Bookmark,
+    command::CommandOutput,
+    compare::PrefixDiff,
+    diff::DiffTarget,
     event::Sender,
-    help::{Spans, SpansWithPriority},
+    help::{BuildHelpsItem, CommandPaletteItem, Spans, SpansWithPriority},
     keys::{UserEvent, UserEventMapper},
-    object::{BucketItem, FileDetail, ObjectItem, ObjectKey, RawObject},
+    mouse::MouseInput,
+    object::{BucketItem, FileDetail, ObjectItem, ObjectKey, ObjectSummary, RawObject},
     pages::{
-        bucket_list::BucketListPage, help::HelpPage, initializing::InitializingPage,
+        bookmarks::BookmarksPage, bucket_list::BucketListPage, command_output::CommandOutputPage,
+        command_palette::CommandPalettePage, commander::CommanderPage,
+        connections::ConnectionsPage, help::HelpPage, initializing::InitializingPage,
         object_detail::ObjectDetailPage, object_list::ObjectListPage,
-        object_preview::ObjectPreviewPage,
+        object_preview::ObjectPreviewPage, prefix_analytics::PrefixAnalyticsPage,
+        prefix_diff::PrefixDiffPage, recent_locations::RecentLocationsPage,
+        sync_plan::SyncPlanPage, text_diff::TextDiffPage,
     },
+    sync::SyncPlan,
     widget::ScrollListState,
 };
 
@@ -23,7 +33,17 @@ pub enum Page {
     ObjectList(Box<ObjectListPage>),
     ObjectDetail(Box<ObjectDetailPage>),
     ObjectPreview(Box<ObjectPreviewPage>),
+    PrefixAnalytics(Box<PrefixAnalyticsPage>),
+    CommandOutput(Box<CommandOutputPage>),
+    SyncPlan(Box<SyncPlanPage>),
+    PrefixDiff(Box<PrefixDiffPage>),
+    TextDiff(Box<TextDiffPage>),
+    Commander(Box<CommanderPage>),
+    Bookmarks(Box<BookmarksPage>),
+    RecentLocations(Box<RecentLocationsPage>),
+    Connections(Box<ConnectionsPage>),
     Help(Box<HelpPage>),
+    CommandPalette(Box<CommandPalettePage>),
 }
 
 impl Page {
@@ -34,7 +54,72 @@ impl Page {
             Page::ObjectList(page) => page.handle_key(user_events, key_event),
             Page::ObjectDetail(page) => page.handle_key(user_events, key_event),
             Page::ObjectPreview(page) => page.handle_key(user_events, key_event),
+            Page::PrefixAnalytics(page) => page.handle_key(user_events, key_event),
+            Page::CommandOutput(page) => page.handle_key(user_events, key_event),
+            Page::SyncPlan(page) => page.handle_key(user_events, key_event),
+            Page::PrefixDiff(page) => page.handle_key(user_events, key_event),
+            Page::TextDiff(page) => page.handle_key(user_events, key_event),
+            Page::Commander(page) => page.handle_key(user_events, key_event),
+            Page::Bookmarks(page) => page.handle_key(user_events, key_event),
+            Page::RecentLocations(page) => page.handle_key(user_events, key_event),
+            Page::Connections(page) => page.handle_key(user_events, key_event),
             Page::Help(page) => page.handle_key(user_events, key_event),
+            Page::CommandPalette(page) => page.handle_key(user_events, key_event),
+        }
+    }
+
+    // the keybindings sections of the events handled by the page
+    pub fn key_sections(&self) -> &'static [&'static str] {
+        match self {
+            Page::Initializing(_) | Page::Connections(_) => &[],
+            Page::BucketList(_) => &["bucket_list"],
+            Page::ObjectList(_) => &["object_list"],
+            Page::ObjectDetail(_) => &["object_detail"],
+            Page::ObjectPreview(_) => &["object_preview"],
+            Page::PrefixAnalytics(_) => &["prefix_analytics"],
+            Page::CommandOutput(_) => &["command_output"],
+            Page::SyncPlan(_) => &["sync_plan"],
+            Page::PrefixDiff(_) => &["prefix_diff"],
+            Page::TextDiff(_) => &["text_diff"],
+            Page::Commander(_) => &["commander", "object_list"],
+            Page::Bookmarks(_) => &["bookmarks"],
+            Page::RecentLocations(_) => &["recent_locations"],
+            Page::Help(_) => &["help"],
+            Page::CommandPalette(_) => &["command_palette"],
+        }
+    }
+
+    pub fn is_text_input_active(&self) -> bool {
+        match self {
+            Page::BucketList(page) => page.is_text_input_active(),
+            Page::ObjectList(page) => page.is_text_input_active(),
+            Page::ObjectDetail(page) => page.is_text_input_active(),
+            Page::ObjectPreview(page) => page.is_text_input_active(),
+            Page::Commander(page) => page.is_text_input_active(),
+            Page::Bookmarks(page) => page.is_text_input_active(),
+            Page::RecentLocations(page) => page.is_text_input_active(),
+            Page::CommandPalette(page) => page.is_text_input_active(),
+            _ => false,
+        }
+    }
+
+    pub fn handle_mouse_input(&mut self, input: MouseInput, area: Rect) -> Option<UserEvent> {
+        match self {
+            Page::BucketList(page) => page.handle_mouse(input, area),
+            Page::ObjectList(page) => page.handle_mouse(input, area),
+            Page::ObjectDetail(page) => page.handle_mouse(input, area),
+            Page::ObjectPreview(page) => page.handle_mouse(input, area),
+            Page::Commander(page) => page.handle_mouse(input, area),
+            Page::PrefixAnalytics(page) => page.handle_mouse(input),
+            Page::CommandOutput(page) => page.handle_mouse(input),
+            Page::SyncPlan(page) => page.handle_mouse(input),
+            Page::PrefixDiff(page) => page.handle_mouse(input),
+            Page::TextDiff(page) => page.handle_mouse(input),
+            Page::Bookmarks(page) => page.handle_mouse(input),
+            Page::RecentLocations(page) => page.handle_mouse(input),
+            Page::Connections(page) => page.handle_mouse(input, area),
+            Page::Help(page) => page.handle_mouse(input),
+            Page::Initializing(_) | Page::CommandPalette(_) => None,
         }
     }
 
@@ -45,7 +130,17 @@ impl Page {
             Page::ObjectList(page) => page.render(f, area),
             Page::ObjectDetail(page) => page.render(f, area),
             Page::ObjectPreview(page) => page.render(f, area),
+            Page::PrefixAnalytics(page) => page.render(f, area),
+            Page::CommandOutput(page) => page.render(f, area),
+            Page::SyncPlan(page) => page.render(f, area),
+            Page::PrefixDiff(page) => page.render(f, area),
+            Page::TextDiff(page) => page.render(f, area),
+            Page::Commander(page) => page.render(f, area),
+            Page::Bookmarks(page) => page.render(f, area),
+            Page::RecentLocations(page) => page.render(f, area),
+            Page::Connections(page) => page.render(f, area),
             Page::Help(page) => page.render(f, area),
+            Page::CommandPalette(page) => page.render(f, area),
         }
     }
 
@@ -57,7 +152,37 @@ impl Page {
             Page::ObjectList(page) => page.helps(mapper),
             Page::ObjectDetail(page) => page.helps(mapper),
             Page::ObjectPreview(page) => page.helps(mapper),
+            Page::PrefixAnalytics(page) => page.helps(mapper),
+            Page::CommandOutput(page) => page.helps(mapper),
+            Page::SyncPlan(page) => page.helps(mapper),
+            Page::PrefixDiff(page) => page.helps(mapper),
+            Page::TextDiff(page) => page.helps(mapper),
+            Page::Commander(page) => page.helps(mapper),
+            Page::Bookmarks(page) => page.helps(mapper),
+            Page::RecentLocations(page) => page.helps(mapper),
+            Page::Connections(page) => page.helps(mapper),
             Page::Help(page) => page.helps(mapper),
+            Page::CommandPalette(_) => Vec::new(),
+        }
+    }
+
+    // the actions listed in the help, also used by the command palette
+    pub fn help_items(&self) -> Vec<BuildHelpsItem> {
+        match self {
+            Page::BucketList(page) => page.help_items(),
+            Page::ObjectList(page) => page.help_items(),
+            Page::ObjectDetail(page) => page.help_items(),
+            Page::ObjectPreview(page) => page.help_items(),
+            Page::PrefixAnalytics(page) => page.help_items(),
+            Page::CommandOutput(page) => page.help_items(),
+            Page::SyncPlan(page) => page.help_items(),
+            Page::PrefixDiff(page) => page.help_items(),
+            Page::TextDiff(page) => page.help_items(),
+            Page::Commander(page) => page.help_items(),
+            Page::Bookmarks(page) => page.help_items(),
+            Page::RecentLocations(page) => page.help_items(),
+            Page::Connections(page) => page.help_items(),
+            Page::Initializing(_) | Page::Help(_) | Page::CommandPalette(_) => Vec::new(),
         }
     }
 
@@ -69,7 +194,17 @@ impl Page {
             Page::ObjectList(page) => page.short_helps(mapper),
             Page::ObjectDetail(page) => page.short_helps(mapper),
             Page::ObjectPreview(page) => page.short_helps(mapper),
+            Page::PrefixAnalytics(page) => page.short_helps(mapper),
+            Page::CommandOutput(page) => page.short_helps(mapper),
+            Page::SyncPlan(page) => page.short_helps(mapper),
+            Page::PrefixDiff(page) => page.short_helps(mapper),
+            Page::TextDiff(page) => page.short_helps(mapper),
+            Page::Commander(page) => page.short_helps(mapper),
+            Page::Bookmarks(page) => page.short_helps(mapper),
+            Page::RecentLocations(page) => page.short_helps(mapper),
+            Page::Connections(page) => page.short_helps(mapper),
             Page::Help(page) => page.short_helps(mapper),
+            Page::CommandPalette(page) => page.short_helps(mapper),
         }
     }
 }
@@ -116,6 +251,7 @@ impl Page {
     }
 
     pub fn of_object_preview(
+        object_key: ObjectKey,
         file_detail: FileDetail,
         file_version_id: Option<String>,
         object: RawObject,
@@ -123,6 +259,7 @@ impl Page {
         tx: Sender,
     ) -> Self {
         Self::ObjectPreview(Box::new(ObjectPreviewPage::new(
+            object_key,
             file_detail,
             file_version_id,
             object,
@@ -131,10 +268,88 @@ impl Page {
         )))
     }
 
+    pub fn of_prefix_analytics(
+        object_key: ObjectKey,
+        objects: Vec<ObjectSummary>,
+        ctx: Rc<AppContext>,
+        tx: Sender,
+    ) -> Self {
+        Self::PrefixAnalytics(Box::new(PrefixAnalyticsPage::new(
+            object_key, objects, ctx, tx,
+        )))
+    }
+
+    pub fn of_command_output(
+        command: String,
+        output: CommandOutput,
+        ctx: Rc<AppContext>,
+        tx: Sender,
+    ) -> Self {
+        Self::CommandOutput(Box::new(CommandOutputPage::new(command, output, ctx, tx)))
+    }
+
+    pub fn of_sync_plan(plan: SyncPlan, ctx: Rc<AppContext>, tx: Sender) -> Self {
+        Self::SyncPlan(Box::new(SyncPlanPage::new(plan, ctx, tx)))
+    }
+
+    pub fn of_prefix_diff(diff: PrefixDiff, ctx: Rc<AppContext>, tx: Sender) -> Self {
+        Self::PrefixDiff(Box::new(PrefixDiffPage::new(diff, ctx, tx)))
+    }
+
+    pub fn of_text_diff(
+        left: DiffTarget,
+        right: DiffTarget,
+        left_object: RawObject,
+        right_object: RawObject,
+        ctx: Rc<AppContext>,
+        tx: Sender,
+    ) -> Self {
+        Self::TextDiff(Box::new(TextDiffPage::new(
+            left,
+            right,
+            left_object,
+            right_object,
+            ctx,
+            tx,
+        )))
+    }
+
+    pub fn of_commander(
+        left: ObjectListPage,
+        right: ObjectListPage,
+        connection: Option<String>,
+        ctx: Rc<AppContext>,
+        tx: Sender,
+    ) -> Self {
+        Self::Commander(Box::new(CommanderPage::new(
+            left, right, connection, ctx, tx,
+        )))
+    }
+
+    pub fn of_bookmarks(bookmarks: Vec<Bookmark>, ctx: Rc<AppContext>, tx: Sender) -> Self {
+        Self::Bookmarks(Box::new(BookmarksPage::new(bookmarks, ctx, tx)))
+    }
+
+    pub fn of_recent_locations(locations: Vec<String>, ctx: Rc<AppContext>, tx: Sender) -> Self {
+        Self::RecentLocations(Box::new(RecentLocationsPage::new(locations, ctx, tx)))
+    }
+
+    pub fn of_connections(names: Vec<String>, ctx: Rc<AppContext>, tx: Sender) -> Self {
+        Self::Connections(Box::new(ConnectionsPage::new(names, ctx, tx)))
+    }
+
     pub fn of_help(helps: Vec<Spans>, ctx: Rc<AppContext>, tx: Sender) -> Self {
         Self::Help(Box::new(HelpPage::new(helps, ctx, tx)))
     }
 
+    pub fn of_command_palette(
+        items: Vec<CommandPaletteItem>,
+        ctx: Rc<AppContext>,
+        tx: Sender,
+    ) -> Self {
+        Self::CommandPalette(Box::new(CommandPalettePage::new(items, ctx, tx)))
+    }
+
     pub fn as_object_list(&self) -> &ObjectListPage {
         match self {
             Self::ObjectList(page) => page,
@@ -142,6 +357,20 @@ impl Page {
         }
     }
 
+    pub fn as_mut_bucket_list(&mut self) -> &mut BucketListPage {
+        match self {
+            Self::BucketList(page) => page,
+            page => panic!("Page is not BucketList: {:?}", page),
+        }
+    }
+
+    pub fn as_mut_object_list(&mut self) -> &mut ObjectListPage {
+        match self {
+            Self::ObjectList(page) => page,
+            page => panic!("Page is not ObjectList: {:?}", page),
+        }
+    }
+
     pub fn as_object_detail(&self) -> &ObjectDetailPage {
         match self {
             Self::ObjectDetail(page) => page,
@@ -203,7 +432,11 @@ impl PageStack {
         self.stack.last_mut().unwrap()
     }
 
-    pub fn iter(&self) -> std::slice::Iter<Page> {
+    pub fn iter(&self) -> std::slice::Iter<'_, Page> {
         self.stack.iter()
     }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, Page> {
+        self.stack.iter_mut()
+    }
 }
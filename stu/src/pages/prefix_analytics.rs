@@ -0,0 +1,642 @@
+use std::{collections::HashMap, rc::Rc};
+
+use chrono::{DateTime, Local, TimeDelta};
+use ratatui::{
+    crossterm::event::KeyEvent,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::Line,
+    widgets::ListItem,
+    Frame,
+};
+
+use crate::{
+    app::AppContext,
+    color::ColorTheme,
+    config::UiConfig,
+    event::{AppEventType, Sender},
+    format::{format_datetime, format_object_count, format_size_byte},
+    handle_user_events,
+    help::{
+        build_help_spans, build_short_help_spans, BuildHelpsItem, BuildShortHelpsItem, Spans,
+        SpansWithPriority,
+    },
+    keys::{UserEvent, UserEventMapper},
+    mouse::{handle_list_mouse_input, MouseInput},
+    object::{ObjectKey, ObjectSummary},
+    widget::{ScrollList, ScrollListState},
+};
+
+const ELLIPSIS: &str = "...";
+const TOP_N: usize = 10;
+const NO_EXTENSION: &str = "(none)";
+const CURRENT_PREFIX: &str = "(this prefix)";
+
+// (label, upper bound in days)
+const AGE_BUCKETS: [(&str, Option<i64>); 6] = [
+    ("< 1 day", Some(1)),
+    ("1 - 7 days", Some(7)),
+    ("7 - 30 days", Some(30)),
+    ("30 - 90 days", Some(90)),
+    ("90 - 365 days", Some(365)),
+    ("> 1 year", None),
+];
+
+#[derive(Debug)]
+pub struct PrefixAnalyticsPage {
+    rows: Vec<AnalyticsRow>,
+
+    list_state: ScrollListState,
+
+    ctx: Rc<AppContext>,
+    tx: Sender,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum AnalyticsRow {
+    Blank,
+    Header(&'static str),
+    Group {
+        label: String,
+        size_byte: usize,
+        count: usize,
+        target: Option<ObjectKey>,
+    },
+    Object {
+        key: String,
+        size_byte: usize,
+        last_modified: DateTime<Local>,
+        target: (ObjectKey, String),
+    },
+}
+
+impl PrefixAnalyticsPage {
+    pub fn new(
+        object_key: ObjectKey,
+        objects: Vec<ObjectSummary>,
+        ctx: Rc<AppContext>,
+        tx: Sender,
+    ) -> Self {
+        let rows = build_rows(&object_key, &objects, Local::now());
+        let list_state = ScrollListState::new(rows.len());
+        Self {
+            rows,
+            list_state,
+            ctx,
+            tx,
+        }
+    }
+
+    pub fn handle_key(&mut self, user_events: Vec<UserEvent>, _key_event: KeyEvent) {
+        handle_user_events! { user_events =>
+            UserEvent::PrefixAnalyticsBack => {
+                self.tx.send(AppEventType::CloseCurrentPage);
+            }
+            UserEvent::PrefixAnalyticsDown => {
+                self.list_state.select_next();
+            }
+            UserEvent::PrefixAnalyticsUp => {
+                self.list_state.select_prev();
+            }
+            UserEvent::PrefixAnalyticsGoToTop => {
+                self.list_state.select_first();
+            }
+            UserEvent::PrefixAnalyticsGoToBottom => {
+                self.list_state.select_last();
+            }
+            UserEvent::PrefixAnalyticsPageDown => {
+                self.list_state.select_next_page();
+            }
+            UserEvent::PrefixAnalyticsPageUp => {
+                self.list_state.select_prev_page();
+            }
+            UserEvent::PrefixAnalyticsSelect => {
+                self.go_to_selected();
+            }
+            UserEvent::Help => {
+                self.tx.send(AppEventType::OpenHelp);
+            }
+        }
+    }
+
+    pub fn handle_mouse(&mut self, input: MouseInput) -> Option<UserEvent> {
+        handle_list_mouse_input(
+            input,
+            &mut self.list_state,
+            Some(UserEvent::PrefixAnalyticsSelect),
+            UserEvent::PrefixAnalyticsDown,
+            UserEvent::PrefixAnalyticsUp,
+        )
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let offset = self.list_state.offset;
+        let selected = self.list_state.selected;
+
+        let show_item_count = (area.height as usize) - 2 /* border */;
+        let list_items: Vec<ListItem> = self
+            .rows
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(show_item_count)
+            .map(|(i, row)| {
+                build_list_item(
+                    row,
+                    i == selected,
+                    area.width,
+                    &self.ctx.config.ui,
+                    &self.ctx.theme,
+                )
+            })
+            .collect();
+
+        let list = ScrollList::new(list_items).theme(&self.ctx.theme);
+        f.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    pub fn helps(&self, mapper: &UserEventMapper) -> Vec<Spans> {
+        build_help_spans(self.help_items(), mapper, self.ctx.theme.help_key_fg)
+    }
+
+    pub fn help_items(&self) -> Vec<BuildHelpsItem> {
+        #[rustfmt::skip]
+        let helps = vec![
+            BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+            BuildHelpsItem::new(UserEvent::PrefixAnalyticsDown, "Select next item"),
+            BuildHelpsItem::new(UserEvent::PrefixAnalyticsUp, "Select previous item"),
+            BuildHelpsItem::new(UserEvent::PrefixAnalyticsGoToTop, "Go to top"),
+            BuildHelpsItem::new(UserEvent::PrefixAnalyticsGoToBottom, "Go to bottom"),
+            BuildHelpsItem::new(UserEvent::PrefixAnalyticsPageDown, "Scroll page forward"),
+            BuildHelpsItem::new(UserEvent::PrefixAnalyticsPageUp, "Scroll page backward"),
+            BuildHelpsItem::new(UserEvent::PrefixAnalyticsSelect, "Open selected folder or object in list"),
+            BuildHelpsItem::new(UserEvent::PrefixAnalyticsBack, "Close analytics"),
+        ];
+        helps
+    }
+
+    pub fn short_helps(&self, mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
+        #[rustfmt::skip]
+        let helps = vec![
+            BuildShortHelpsItem::single(UserEvent::Quit, "Quit", 0),
+            BuildShortHelpsItem::group(vec![UserEvent::PrefixAnalyticsDown, UserEvent::PrefixAnalyticsUp], "Select", 1),
+            BuildShortHelpsItem::group(vec![UserEvent::PrefixAnalyticsGoToTop, UserEvent::PrefixAnalyticsGoToBottom], "Top/Bottom", 4),
+            BuildShortHelpsItem::single(UserEvent::PrefixAnalyticsSelect, "Open", 2),
+            BuildShortHelpsItem::single(UserEvent::PrefixAnalyticsBack, "Close", 3),
+            BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+        ];
+        build_short_help_spans(helps, mapper)
+    }
+}
+
+impl PrefixAnalyticsPage {
+    fn go_to_selected(&self) {
+        let Some(row) = self.rows.get(self.list_state.selected) else {
+            return;
+        };
+        match row {
+            AnalyticsRow::Group {
+                target: Some(target),
+                ..
+            } => {
+                self.tx
+                    .send(AppEventType::GoToObjectKey(target.clone(), None));
+            }
+            AnalyticsRow::Object {
+                target: (dir_key, name),
+                ..
+            } => {
+                self.tx.send(AppEventType::GoToObjectKey(
+                    dir_key.clone(),
+                    Some(name.clone()),
+                ));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn build_rows(
+    object_key: &ObjectKey,
+    objects: &[ObjectSummary],
+    now: DateTime<Local>,
+) -> Vec<AnalyticsRow> {
+    let prefix = object_key.joined_object_path(false);
+    let relative_key = |key: &str| key.strip_prefix(&prefix).unwrap_or(key).to_string();
+
+    let mut rows = Vec::new();
+
+    let total_size_byte = objects.iter().map(|o| o.size_byte).sum();
+    rows.push(AnalyticsRow::Group {
+        label: "Total".into(),
+        size_byte: total_size_byte,
+        count: objects.len(),
+        target: None,
+    });
+
+    let storage_classes = group_by(objects, |o| o.storage_class.clone());
+    push_group_rows(&mut rows, "Storage class", storage_classes, |_| None);
+
+    let extensions = group_by(objects, |o| extension(&o.key));
+    push_group_rows(&mut rows, "Extension", extensions, |_| None);
+
+    rows.push(AnalyticsRow::Blank);
+    rows.push(AnalyticsRow::Header("Last modified"));
+    for (i, (label, _)) in AGE_BUCKETS.iter().enumerate() {
+        let (size_byte, count) = objects
+            .iter()
+            .filter(|o| age_bucket_index(o.last_modified, now) == i)
+            .fold((0, 0), |(size, count), o| (size + o.size_byte, count + 1));
+        rows.push(AnalyticsRow::Group {
+            label: format!("  {}", label),
+            size_byte,
+            count,
+            target: None,
+        });
+    }
+
+    let child_prefixes = group_by(objects, |o| match relative_key(&o.key).split_once('/') {
+        Some((dir, _)) => format!("{}/", dir),
+        None => CURRENT_PREFIX.into(),
+    });
+    push_group_rows(
+        &mut rows,
+        "Child prefix",
+        child_prefixes,
+        |label| match label.strip_suffix('/') {
+            Some(dir) => Some(object_key.child(dir)),
+            None => Some(object_key.clone()),
+        },
+    );
+
+    let mut largest: Vec<&ObjectSummary> = objects.iter().collect();
+    largest.sort_by(|a, b| b.size_byte.cmp(&a.size_byte).then(a.key.cmp(&b.key)));
+    rows.push(AnalyticsRow::Blank);
+    rows.push(AnalyticsRow::Header("Largest objects"));
+    rows.extend(
+        largest
+            .into_iter()
+            .take(TOP_N)
+            .map(|o| build_object_row(o, object_key, &relative_key(&o.key))),
+    );
+
+    let mut oldest: Vec<&ObjectSummary> = objects.iter().collect();
+    oldest.sort_by(|a, b| {
+        a.last_modified
+            .cmp(&b.last_modified)
+            .then(a.key.cmp(&b.key))
+    });
+    rows.push(AnalyticsRow::Blank);
+    rows.push(AnalyticsRow::Header("Oldest objects"));
+    rows.extend(
+        oldest
+            .into_iter()
+            .take(TOP_N)
+            .map(|o| build_object_row(o, object_key, &relative_key(&o.key))),
+    );
+
+    rows
+}
+
+fn group_by<F>(objects: &[ObjectSummary], f: F) -> Vec<(String, usize, usize)>
+where
+    F: Fn(&ObjectSummary) -> String,
+{
+    let mut map: HashMap<String, (usize, usize)> = HashMap::new();
+    for o in objects {
+        let entry = map.entry(f(o)).or_default();
+        entry.0 += o.size_byte;
+        entry.1 += 1;
+    }
+    let mut groups: Vec<(String, usize, usize)> = map
+        .into_iter()
+        .map(|(label, (size_byte, count))| (label, size_byte, count))
+        .collect();
+    // largest first
+    groups.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    groups
+}
+
+fn push_group_rows<F>(
+    rows: &mut Vec<AnalyticsRow>,
+    header: &'static str,
+    groups: Vec<(String, usize, usize)>,
+    target: F,
+) where
+    F: Fn(&str) -> Option<ObjectKey>,
+{
+    rows.push(AnalyticsRow::Blank);
+    rows.push(AnalyticsRow::Header(header));
+    for (label, size_byte, count) in groups {
+        let target = target(&label);
+        rows.push(AnalyticsRow::Group {
+            label: format!("  {}", label),
+            size_byte,
+            count,
+            target,
+        });
+    }
+}
+
+fn build_object_row(o: &ObjectSummary, object_key: &ObjectKey, relative_key: &str) -> AnalyticsRow {
+    let mut dir_key = object_key.clone();
+    let mut paths: Vec<&str> = relative_key.split('/').collect();
+    let name = paths.pop().unwrap_or_default().to_string();
+    dir_key
+        .object_path
+        .extend(paths.into_iter().map(String::from));
+    AnalyticsRow::Object {
+        key: format!("  {}", relative_key),
+        size_byte: o.size_byte,
+        last_modified: o.last_modified,
+        target: (dir_key, name),
+    }
+}
+
+fn extension(key: &str) -> String {
+    let name = key.rsplit('/').next().unwrap_or(key);
+    match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() && !ext.is_empty() => {
+            format!(".{}", ext.to_lowercase())
+        }
+        _ => NO_EXTENSION.into(),
+    }
+}
+
+fn age_bucket_index(last_modified: DateTime<Local>, now: DateTime<Local>) -> usize {
+    let age = now.signed_duration_since(last_modified);
+    AGE_BUCKETS
+        .iter()
+        .position(|(_, days)| match days {
+            Some(days) => age < TimeDelta::days(*days),
+            None => true,
+        })
+        .unwrap()
+}
+
+fn build_list_item<'a>(
+    row: &'a AnalyticsRow,
+    selected: bool,
+    width: u16,
+    ui_config: &UiConfig,
+    theme: &ColorTheme,
+) -> ListItem<'a> {
+    let line = match row {
+        AnalyticsRow::Blank => Line::raw(""),
+        AnalyticsRow::Header(label) => Line::from(vec![" ".into(), label.bold()]),
+        AnalyticsRow::Group {
+            label,
+            size_byte,
+            count,
+            ..
+        } => build_line(
+            label,
+            &format_object_count(*count),
+            *size_byte,
+            width,
+            ui_config,
+        ),
+        AnalyticsRow::Object {
+            key,
+            size_byte,
+            last_modified,
+            ..
+        } => {
+            let date = format_datetime(last_modified, &ui_config.object_list.date_format);
+            build_line(key, &date, *size_byte, width, ui_config)
+        }
+    };
+
+    let style = if selected {
+        Style::default()
+            .bg(theme.list_selected_bg)
+            .fg(theme.list_selected_fg)
+            .add_modifier(theme.list_selected_modifier)
+    } else {
+        Style::default()
+    };
+    ListItem::new(line).style(style)
+}
+
+fn build_line<'a>(
+    label: &str,
+    value: &str,
+    size_byte: usize,
+    width: u16,
+    ui_config: &UiConfig,
+) -> Line<'a> {
+    let size = format_size_byte(size_byte);
+    let value_w: usize = ui_config.object_list.date_width;
+    let size_w: usize = 10;
+    let label_w: usize = (width as usize) - value_w - size_w - 10 /* spaces */ - 4 /* border + pad */;
+
+    let pad_label =
+        console::pad_str(label, label_w, console::Alignment::Left, Some(ELLIPSIS)).to_string();
+    let pad_value =
+        console::pad_str(value, value_w, console::Alignment::Left, Some(ELLIPSIS)).to_string();
+    let pad_size = console::pad_str(&size, size_w, console::Alignment::Right, None).to_string();
+
+    Line::from(vec![
+        " ".into(),
+        pad_label.into(),
+        "    ".into(),
+        pad_value.into(),
+        "    ".into(),
+        pad_size.into(),
+        " ".into(),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{event::Sender, set_cells};
+
+    use super::*;
+    use chrono::NaiveDateTime;
+    use ratatui::{
+        backend::TestBackend,
+        buffer::Buffer,
+        style::{Color, Modifier},
+        Terminal,
+    };
+
+    #[test]
+    fn test_build_rows() {
+        let object_key = ObjectKey {
+            bucket_name: "bucket".into(),
+            object_path: vec!["logs".into()],
+        };
+        let now = parse_datetime("2024-01-10 00:00:00");
+        let objects = vec![
+            object_summary("logs/a.json", 100, "2024-01-09 12:00:00", "STANDARD"),
+            object_summary("logs/2023/b.JSON", 300, "2023-12-01 00:00:00", "GLACIER"),
+            object_summary("logs/2023/c", 50, "2022-01-01 00:00:00", "STANDARD"),
+        ];
+
+        let rows = build_rows(&object_key, &objects, now);
+
+        let group =
+            |label: &str, size_byte, count, target: Option<ObjectKey>| AnalyticsRow::Group {
+                label: label.into(),
+                size_byte,
+                count,
+                target,
+            };
+        let object =
+            |key: &str, size_byte, last_modified, dir: &[&str], name: &str| AnalyticsRow::Object {
+                key: key.into(),
+                size_byte,
+                last_modified: parse_datetime(last_modified),
+                target: (
+                    ObjectKey {
+                        bucket_name: "bucket".into(),
+                        object_path: dir.iter().map(|s| s.to_string()).collect(),
+                    },
+                    name.into(),
+                ),
+            };
+        let dir_key = |paths: &[&str]| ObjectKey {
+            bucket_name: "bucket".into(),
+            object_path: paths.iter().map(|s| s.to_string()).collect(),
+        };
+
+        let expected = vec![
+            group("Total", 450, 3, None),
+            AnalyticsRow::Blank,
+            AnalyticsRow::Header("Storage class"),
+            group("  GLACIER", 300, 1, None),
+            group("  STANDARD", 150, 2, None),
+            AnalyticsRow::Blank,
+            AnalyticsRow::Header("Extension"),
+            group("  .json", 400, 2, None),
+            group("  (none)", 50, 1, None),
+            AnalyticsRow::Blank,
+            AnalyticsRow::Header("Last modified"),
+            group("  < 1 day", 100, 1, None),
+            group("  1 - 7 days", 0, 0, None),
+            group("  7 - 30 days", 0, 0, None),
+            group("  30 - 90 days", 300, 1, None),
+            group("  90 - 365 days", 0, 0, None),
+            group("  > 1 year", 50, 1, None),
+            AnalyticsRow::Blank,
+            AnalyticsRow::Header("Child prefix"),
+            group("  2023/", 350, 2, Some(dir_key(&["logs", "2023"]))),
+            group("  (this prefix)", 100, 1, Some(dir_key(&["logs"]))),
+            AnalyticsRow::Blank,
+            AnalyticsRow::Header("Largest objects"),
+            object(
+                "  2023/b.JSON",
+                300,
+                "2023-12-01 00:00:00",
+                &["logs", "2023"],
+                "b.JSON",
+            ),
+            object("  a.json", 100, "2024-01-09 12:00:00", &["logs"], "a.json"),
+            object(
+                "  2023/c",
+                50,
+                "2022-01-01 00:00:00",
+                &["logs", "2023"],
+                "c",
+            ),
+            AnalyticsRow::Blank,
+            AnalyticsRow::Header("Oldest objects"),
+            object(
+                "  2023/c",
+                50,
+                "2022-01-01 00:00:00",
+                &["logs", "2023"],
+                "c",
+            ),
+            object(
+                "  2023/b.JSON",
+                300,
+                "2023-12-01 00:00:00",
+                &["logs", "2023"],
+                "b.JSON",
+            ),
+            object("  a.json", 100, "2024-01-09 12:00:00", &["logs"], "a.json"),
+        ];
+        assert_eq!(rows, expected);
+    }
+
+    #[tokio::test]
+    async fn test_render() -> std::io::Result<()> {
+        let ctx = Rc::default();
+        let tx = sender();
+        let mut terminal = setup_terminal()?;
+
+        terminal.draw(|f| {
+            let object_key = ObjectKey {
+                bucket_name: "bucket".into(),
+                object_path: vec![],
+            };
+            let objects = vec![
+                object_summary("dir/a.txt", 2048, "2020-01-02 13:01:02", "STANDARD"),
+                object_summary("b.txt", 1024, "2020-01-03 13:01:02", "STANDARD"),
+            ];
+            let mut page = PrefixAnalyticsPage::new(object_key, objects, ctx, tx);
+            let area = Rect::new(0, 0, 60, 10);
+            page.render(f, area);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌─────────────────────────────────────────────────  1 / 27 ┐",
+            "│  Total                2 objects                   3 KiB ││",
+            "│                                                         ││",
+            "│  Storage class                                           │",
+            "│    STANDARD           2 objects                   3 KiB  │",
+            "│                                                          │",
+            "│  Extension                                               │",
+            "│    .txt               2 objects                   3 KiB  │",
+            "│                                                          │",
+            "└──────────────────────────────────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            // headers
+            (3..16, [3]) => modifier: Modifier::BOLD,
+            (3..12, [6]) => modifier: Modifier::BOLD,
+            // selected item
+            (2..58, [1]) => bg: Color::Cyan, fg: Color::Black,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
+    fn setup_terminal() -> std::io::Result<Terminal<TestBackend>> {
+        let backend = TestBackend::new(60, 10);
+        let mut terminal = Terminal::new(backend)?;
+        terminal.clear()?;
+        Ok(terminal)
+    }
+
+    fn sender() -> Sender {
+        let (tx, _) = tokio::sync::mpsc::unbounded_channel();
+        Sender::new(tx)
+    }
+
+    fn parse_datetime(s: &str) -> DateTime<Local> {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_local_timezone(Local)
+            .unwrap()
+    }
+
+    fn object_summary(
+        key: &str,
+        size_byte: usize,
+        last_modified: &str,
+        storage_class: &str,
+    ) -> ObjectSummary {
+        ObjectSummary {
+            key: key.into(),
+            size_byte,
+            last_modified: parse_datetime(last_modified),
+            storage_class: storage_class.into(),
+            e_tag: String::new(),
+        }
+    }
+}
@@ -0,0 +1,477 @@
+use std::rc::Rc;
+
+use ratatui::{
+    crossterm::event::KeyEvent,
+    layout::Rect,
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::ListItem,
+    Frame,
+};
+
+use crate::{
+    app::AppContext,
+    color::ColorTheme,
+    compare::{DiffKind, DiffSide, PrefixDiff, PrefixDiffItem},
+    diff::DiffTarget,
+    event::{AppEventType, Sender},
+    format::{format_object_count, format_size_byte},
+    handle_user_events,
+    help::{
+        build_help_spans, build_short_help_spans, BuildHelpsItem, BuildShortHelpsItem, Spans,
+        SpansWithPriority,
+    },
+    keys::{UserEvent, UserEventMapper},
+    mouse::{handle_list_mouse_input, MouseInput},
+    object::ObjectKey,
+    widget::{ScrollList, ScrollListState},
+};
+
+const ELLIPSIS: &str = "...";
+
+const DIFF_KINDS: [DiffKind; 4] = [
+    DiffKind::OnlyLeft,
+    DiffKind::OnlyRight,
+    DiffKind::Different,
+    DiffKind::Identical,
+];
+
+#[derive(Debug)]
+pub struct PrefixDiffPage {
+    diff: PrefixDiff,
+    filter: Option<DiffKind>,
+    rows: Vec<DiffRow>,
+
+    list_state: ScrollListState,
+
+    ctx: Rc<AppContext>,
+    tx: Sender,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum DiffRow {
+    Blank,
+    Header(String),
+    Count(DiffKind, usize),
+    // index of the item in the diff
+    Item(usize),
+    Message(&'static str),
+}
+
+impl PrefixDiffPage {
+    pub fn new(diff: PrefixDiff, ctx: Rc<AppContext>, tx: Sender) -> Self {
+        let filter = None;
+        let rows = build_rows(&diff, filter);
+        let list_state = ScrollListState::new(rows.len());
+        Self {
+            diff,
+            filter,
+            rows,
+            list_state,
+            ctx,
+            tx,
+        }
+    }
+
+    pub fn handle_key(&mut self, user_events: Vec<UserEvent>, _key_event: KeyEvent) {
+        handle_user_events! { user_events =>
+            UserEvent::PrefixDiffBack => {
+                self.tx.send(AppEventType::CloseCurrentPage);
+            }
+            UserEvent::PrefixDiffDown => {
+                self.list_state.select_next();
+            }
+            UserEvent::PrefixDiffUp => {
+                self.list_state.select_prev();
+            }
+            UserEvent::PrefixDiffGoToTop => {
+                self.list_state.select_first();
+            }
+            UserEvent::PrefixDiffGoToBottom => {
+                self.list_state.select_last();
+            }
+            UserEvent::PrefixDiffPageDown => {
+                self.list_state.select_next_page();
+            }
+            UserEvent::PrefixDiffPageUp => {
+                self.list_state.select_prev_page();
+            }
+            UserEvent::PrefixDiffToggleFilter => {
+                self.toggle_filter();
+            }
+            UserEvent::PrefixDiffOpenLeft => {
+                self.open_selected(|diff, item| diff.left_object_key(item));
+            }
+            UserEvent::PrefixDiffOpenRight => {
+                self.open_selected(|diff, item| diff.right_object_key(item));
+            }
+            UserEvent::PrefixDiffTextDiff => {
+                self.text_diff_selected();
+            }
+            UserEvent::Help => {
+                self.tx.send(AppEventType::OpenHelp);
+            }
+        }
+    }
+
+    pub fn handle_mouse(&mut self, input: MouseInput) -> Option<UserEvent> {
+        handle_list_mouse_input(
+            input,
+            &mut self.list_state,
+            Some(UserEvent::PrefixDiffOpenLeft),
+            UserEvent::PrefixDiffDown,
+            UserEvent::PrefixDiffUp,
+        )
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let offset = self.list_state.offset;
+        let selected = self.list_state.selected;
+
+        let show_item_count = (area.height as usize) - 2 /* border */;
+        let list_items: Vec<ListItem> = self
+            .rows
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(show_item_count)
+            .map(|(i, row)| {
+                build_list_item(row, &self.diff, i == selected, area.width, &self.ctx.theme)
+            })
+            .collect();
+
+        let list = ScrollList::new(list_items).theme(&self.ctx.theme);
+        f.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    pub fn helps(&self, mapper: &UserEventMapper) -> Vec<Spans> {
+        build_help_spans(self.help_items(), mapper, self.ctx.theme.help_key_fg)
+    }
+
+    pub fn help_items(&self) -> Vec<BuildHelpsItem> {
+        #[rustfmt::skip]
+        let helps = vec![
+            BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+            BuildHelpsItem::new(UserEvent::PrefixDiffDown, "Select next item"),
+            BuildHelpsItem::new(UserEvent::PrefixDiffUp, "Select previous item"),
+            BuildHelpsItem::new(UserEvent::PrefixDiffGoToTop, "Go to top"),
+            BuildHelpsItem::new(UserEvent::PrefixDiffGoToBottom, "Go to bottom"),
+            BuildHelpsItem::new(UserEvent::PrefixDiffPageDown, "Scroll page forward"),
+            BuildHelpsItem::new(UserEvent::PrefixDiffPageUp, "Scroll page backward"),
+            BuildHelpsItem::new(UserEvent::PrefixDiffToggleFilter, "Switch filter"),
+            BuildHelpsItem::new(UserEvent::PrefixDiffOpenLeft, "Open left object"),
+            BuildHelpsItem::new(UserEvent::PrefixDiffOpenRight, "Open right object"),
+            BuildHelpsItem::new(UserEvent::PrefixDiffTextDiff, "Show text diff of left and right"),
+            BuildHelpsItem::new(UserEvent::PrefixDiffBack, "Close prefix diff"),
+        ];
+        helps
+    }
+
+    pub fn short_helps(&self, mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
+        #[rustfmt::skip]
+        let helps = vec![
+            BuildShortHelpsItem::single(UserEvent::Quit, "Quit", 0),
+            BuildShortHelpsItem::group(vec![UserEvent::PrefixDiffDown, UserEvent::PrefixDiffUp], "Select", 4),
+            BuildShortHelpsItem::group(vec![UserEvent::PrefixDiffGoToTop, UserEvent::PrefixDiffGoToBottom], "Top/Bottom", 5),
+            BuildShortHelpsItem::single(UserEvent::PrefixDiffToggleFilter, "Filter", 2),
+            BuildShortHelpsItem::group(vec![UserEvent::PrefixDiffOpenLeft, UserEvent::PrefixDiffOpenRight], "Open left/right", 1),
+            BuildShortHelpsItem::single(UserEvent::PrefixDiffBack, "Close", 3),
+            BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+        ];
+        build_short_help_spans(helps, mapper)
+    }
+}
+
+impl PrefixDiffPage {
+    fn toggle_filter(&mut self) {
+        // all -> only left -> only right -> different -> identical -> all
+        self.filter = match self.filter {
+            None => Some(DIFF_KINDS[0]),
+            Some(kind) => {
+                let i = DIFF_KINDS.iter().position(|k| *k == kind).unwrap_or(0);
+                DIFF_KINDS.get(i + 1).copied()
+            }
+        };
+        self.rows = build_rows(&self.diff, self.filter);
+        self.list_state = ScrollListState::new(self.rows.len());
+    }
+
+    fn open_selected<F>(&self, f: F)
+    where
+        F: Fn(&PrefixDiff, &PrefixDiffItem) -> Option<ObjectKey>,
+    {
+        let Some(DiffRow::Item(i)) = self.rows.get(self.list_state.selected) else {
+            return;
+        };
+        let item = &self.diff.items[*i];
+        let Some(object_key) = f(&self.diff, item) else {
+            let msg = "The object does not exist on this side";
+            self.tx.send(AppEventType::NotifyWarn(msg.into()));
+            return;
+        };
+        self.tx.send(AppEventType::OpenObjectDetail(object_key));
+    }
+
+    fn text_diff_selected(&self) {
+        let Some(DiffRow::Item(i)) = self.rows.get(self.list_state.selected) else {
+            return;
+        };
+        let item = &self.diff.items[*i];
+        let (Some(left), Some(right), Some(left_side), Some(right_side)) = (
+            self.diff.left_object_key(item),
+            self.diff.right_object_key(item),
+            item.left.as_ref(),
+            item.right.as_ref(),
+        ) else {
+            let msg = "The object does not exist on both sides";
+            self.tx.send(AppEventType::NotifyWarn(msg.into()));
+            return;
+        };
+        let left = DiffTarget::new(left, None, left_side.size_byte);
+        let right = DiffTarget::new(right, None, right_side.size_byte);
+        self.tx.send(AppEventType::StartLoadTextDiff(left, right));
+    }
+}
+
+fn build_rows(diff: &PrefixDiff, filter: Option<DiffKind>) -> Vec<DiffRow> {
+    let mut rows = vec![
+        DiffRow::Header(format!("Left:  {}", diff.left.prefix_uri())),
+        DiffRow::Header(format!("Right: {}", diff.right.prefix_uri())),
+    ];
+    for kind in DIFF_KINDS {
+        rows.push(DiffRow::Count(kind, diff.count(kind)));
+    }
+
+    rows.push(DiffRow::Blank);
+    let filter_label = filter.map(kind_label).unwrap_or("All");
+    rows.push(DiffRow::Header(format!("Objects ({})", filter_label)));
+    let items: Vec<DiffRow> = diff
+        .items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| filter.map_or(true, |kind| item.kind == kind))
+        .map(|(i, _)| DiffRow::Item(i))
+        .collect();
+    if items.is_empty() {
+        rows.push(DiffRow::Message("  No objects"));
+    }
+    rows.extend(items);
+
+    rows
+}
+
+fn build_list_item<'a>(
+    row: &'a DiffRow,
+    diff: &'a PrefixDiff,
+    selected: bool,
+    width: u16,
+    theme: &ColorTheme,
+) -> ListItem<'a> {
+    let line = match row {
+        DiffRow::Blank => Line::raw(""),
+        DiffRow::Header(label) => Line::from(vec![" ".into(), label.as_str().bold()]),
+        DiffRow::Message(msg) => Line::raw(format!(" {}", msg)),
+        DiffRow::Count(kind, count) => {
+            let label = format!("  {}", kind_label(*kind));
+            build_line(&label, &format_object_count(*count), width)
+        }
+        DiffRow::Item(i) => {
+            let item = &diff.items[*i];
+            let label = format!("  {} {}", kind_mark(item.kind), item.relative_path);
+            let value = format!("{} | {}", side_size(&item.left), side_size(&item.right));
+            let line = build_line(&label, &value, width);
+            if selected {
+                line
+            } else {
+                line.fg(kind_color(item.kind, theme))
+            }
+        }
+    };
+
+    let style = if selected {
+        Style::default()
+            .bg(theme.list_selected_bg)
+            .fg(theme.list_selected_fg)
+            .add_modifier(theme.list_selected_modifier)
+    } else {
+        Style::default()
+    };
+    ListItem::new(line).style(style)
+}
+
+fn build_line<'a>(label: &str, value: &str, width: u16) -> Line<'a> {
+    let value_w: usize = 21;
+    let label_w: usize = (width as usize) - value_w - 6 /* spaces */ - 4 /* border + pad */;
+
+    let pad_label =
+        console::pad_str(label, label_w, console::Alignment::Left, Some(ELLIPSIS)).to_string();
+    let pad_value = console::pad_str(value, value_w, console::Alignment::Right, None).to_string();
+
+    Line::from(vec![
+        " ".into(),
+        pad_label.into(),
+        "    ".into(),
+        pad_value.into(),
+        " ".into(),
+    ])
+}
+
+fn side_size(side: &Option<DiffSide>) -> String {
+    let size = side
+        .as_ref()
+        .map(|s| format_size_byte(s.size_byte))
+        .unwrap_or("-".into());
+    format!("{:>9}", size)
+}
+
+fn kind_label(kind: DiffKind) -> &'static str {
+    match kind {
+        DiffKind::OnlyLeft => "Only left",
+        DiffKind::OnlyRight => "Only right",
+        DiffKind::Different => "Different",
+        DiffKind::Identical => "Identical",
+    }
+}
+
+fn kind_mark(kind: DiffKind) -> char {
+    match kind {
+        DiffKind::OnlyLeft => '<',
+        DiffKind::OnlyRight => '>',
+        DiffKind::Different => '~',
+        DiffKind::Identical => '=',
+    }
+}
+
+fn kind_color(kind: DiffKind, theme: &ColorTheme) -> Color {
+    match kind {
+        DiffKind::OnlyLeft => theme.status_error,
+        DiffKind::OnlyRight => theme.status_success,
+        DiffKind::Different => theme.status_warn,
+        DiffKind::Identical => theme.fg,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{compare::build_prefix_diff, event::Sender, object::ObjectSummary, set_cells};
+
+    use super::*;
+    use chrono::{Local, NaiveDateTime};
+    use ratatui::{backend::TestBackend, buffer::Buffer, style::Modifier, Terminal};
+
+    #[tokio::test]
+    async fn test_render() -> std::io::Result<()> {
+        let ctx = Rc::default();
+        let tx = sender();
+        let mut terminal = setup_terminal()?;
+
+        terminal.draw(|f| {
+            let mut page = PrefixDiffPage::new(diff(), ctx, tx);
+            let area = Rect::new(0, 0, 60, 14);
+            page.render(f, area);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌─────────────────────────────────────────────────  1 / 12 ┐",
+            "│  Left:  s3://bucket-a/x/                                 │",
+            "│  Right: s3://bucket-b/y/                                 │",
+            "│    Only left                                   1 object  │",
+            "│    Only right                                  1 object  │",
+            "│    Different                                   1 object  │",
+            "│    Identical                                   1 object  │",
+            "│                                                          │",
+            "│  Objects (All)                                           │",
+            "│    ~ a.txt                             10 B |      20 B  │",
+            "│    = b.txt                             10 B |      10 B  │",
+            "│    < c.txt                             10 B |         -  │",
+            "│    > d.txt                                - |      10 B  │",
+            "└──────────────────────────────────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            // selected
+            (2..58, [1]) => bg: Color::Cyan, fg: Color::Black,
+            // header
+            (3..26, [1]) => modifier: Modifier::BOLD,
+            (3..26, [2]) => modifier: Modifier::BOLD,
+            (3..16, [8]) => modifier: Modifier::BOLD,
+            // different
+            (2..58, [9]) => fg: Color::Yellow,
+            // only left
+            (2..58, [11]) => fg: Color::Red,
+            // only right
+            (2..58, [12]) => fg: Color::Green,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_toggle_filter() {
+        let ctx = Rc::default();
+        let tx = sender();
+        let mut page = PrefixDiffPage::new(diff(), ctx, tx);
+
+        page.toggle_filter();
+        assert_eq!(page.filter, Some(DiffKind::OnlyLeft));
+        assert_eq!(page.rows.last(), Some(&DiffRow::Item(2)));
+
+        page.toggle_filter();
+        page.toggle_filter();
+        page.toggle_filter();
+        assert_eq!(page.filter, Some(DiffKind::Identical));
+        assert_eq!(page.rows.last(), Some(&DiffRow::Item(1)));
+
+        page.toggle_filter();
+        assert_eq!(page.filter, None);
+    }
+
+    fn diff() -> PrefixDiff {
+        let left = ObjectKey::from_prefix_uri("s3://bucket-a/x/").unwrap();
+        let right = ObjectKey::from_prefix_uri("s3://bucket-b/y/").unwrap();
+        build_prefix_diff(
+            left,
+            right,
+            vec![
+                object("x/a.txt", 10, "1"),
+                object("x/b.txt", 10, "2"),
+                object("x/c.txt", 10, "3"),
+            ],
+            vec![
+                object("y/a.txt", 20, "1"),
+                object("y/b.txt", 10, "2"),
+                object("y/d.txt", 10, "4"),
+            ],
+        )
+    }
+
+    fn object(key: &str, size_byte: usize, e_tag: &str) -> ObjectSummary {
+        ObjectSummary {
+            key: key.into(),
+            size_byte,
+            last_modified: NaiveDateTime::parse_from_str(
+                "2024-01-02 13:01:02",
+                "%Y-%m-%d %H:%M:%S",
+            )
+            .unwrap()
+            .and_local_timezone(Local)
+            .unwrap(),
+            storage_class: "STANDARD".into(),
+            e_tag: e_tag.into(),
+        }
+    }
+
+    fn setup_terminal() -> std::io::Result<Terminal<TestBackend>> {
+        let backend = TestBackend::new(60, 14);
+        let mut terminal = Terminal::new(backend)?;
+        terminal.clear()?;
+        Ok(terminal)
+    }
+
+    fn sender() -> Sender {
+        let (tx, _) = tokio::sync::mpsc::unbounded_channel();
+        Sender::new(tx)
+    }
+}
@@ -0,0 +1,453 @@
+use std::rc::Rc;
+
+use laurier::highlight::highlight_matched_text;
+use ratatui::{
+    crossterm::event::KeyEvent, layout::Rect, style::Style, text::Line, widgets::ListItem, Frame,
+};
+
+use crate::{
+    app::AppContext,
+    color::ColorTheme,
+    event::{AppEventType, Sender},
+    handle_user_events, handle_user_events_with_default,
+    help::{
+        build_help_spans, build_short_help_spans, BuildHelpsItem, BuildShortHelpsItem, Spans,
+        SpansWithPriority,
+    },
+    keys::{UserEvent, UserEventMapper},
+    location::parse_location,
+    mouse::{handle_list_mouse_input, MouseInput},
+    widget::{InputDialog, InputDialogState, ScrollList, ScrollListState},
+};
+
+const ELLIPSIS: &str = "...";
+
+#[derive(Debug)]
+pub struct RecentLocationsPage {
+    // prefix uris, the most recent first
+    locations: Vec<String>,
+    view_indices: Vec<usize>,
+
+    view_state: ViewState,
+
+    list_state: ScrollListState,
+    filter_input_state: InputDialogState,
+
+    ctx: Rc<AppContext>,
+    tx: Sender,
+}
+
+#[derive(Debug)]
+enum ViewState {
+    Default,
+    FilterDialog,
+}
+
+impl RecentLocationsPage {
+    pub fn new(locations: Vec<String>, ctx: Rc<AppContext>, tx: Sender) -> Self {
+        let items_len = locations.len();
+        let view_indices = (0..items_len).collect();
+        Self {
+            locations,
+            view_indices,
+            view_state: ViewState::Default,
+            list_state: ScrollListState::new(items_len),
+            filter_input_state: InputDialogState::default(),
+            ctx,
+            tx,
+        }
+    }
+
+    pub fn handle_key(&mut self, user_events: Vec<UserEvent>, key_event: KeyEvent) {
+        match self.view_state {
+            ViewState::Default => {
+                handle_user_events! { user_events =>
+                    UserEvent::RecentLocationsBack => {
+                        self.tx.send(AppEventType::CloseCurrentPage);
+                    }
+                    UserEvent::RecentLocationsSelect if self.non_empty() => {
+                        self.open_selected();
+                    }
+                    UserEvent::RecentLocationsDown if self.non_empty() => {
+                        self.list_state.select_next();
+                    }
+                    UserEvent::RecentLocationsUp if self.non_empty() => {
+                        self.list_state.select_prev();
+                    }
+                    UserEvent::RecentLocationsGoToTop if self.non_empty() => {
+                        self.list_state.select_first();
+                    }
+                    UserEvent::RecentLocationsGoToBottom if self.non_empty() => {
+                        self.list_state.select_last();
+                    }
+                    UserEvent::RecentLocationsPageDown if self.non_empty() => {
+                        self.list_state.select_next_page();
+                    }
+                    UserEvent::RecentLocationsPageUp if self.non_empty() => {
+                        self.list_state.select_prev_page();
+                    }
+                    UserEvent::RecentLocationsFilter => {
+                        self.open_filter_dialog();
+                    }
+                    UserEvent::RecentLocationsResetFilter if self.filter_input_state.non_empty() => {
+                        self.reset_filter();
+                    }
+                    UserEvent::Help => {
+                        self.tx.send(AppEventType::OpenHelp);
+                    }
+                }
+            }
+            ViewState::FilterDialog => {
+                handle_user_events_with_default! { user_events =>
+                    UserEvent::InputDialogApply => {
+                        self.apply_filter();
+                    }
+                    UserEvent::InputDialogClose => {
+                        self.close_filter_dialog();
+                    }
+                    UserEvent::Help => {
+                        self.tx.send(AppEventType::OpenHelp);
+                    }
+                    => {
+                        self.filter_input_state.handle_key_event(key_event);
+                        self.filter_view_indices();
+                    }
+                }
+            }
+        }
+    }
+
+    // keys are typed into the input as is, without key sequences and counts
+    pub fn is_text_input_active(&self) -> bool {
+        matches!(self.view_state, ViewState::FilterDialog)
+    }
+
+    pub fn handle_mouse(&mut self, input: MouseInput) -> Option<UserEvent> {
+        match self.view_state {
+            ViewState::Default if self.non_empty() => handle_list_mouse_input(
+                input,
+                &mut self.list_state,
+                Some(UserEvent::RecentLocationsSelect),
+                UserEvent::RecentLocationsDown,
+                UserEvent::RecentLocationsUp,
+            ),
+            _ => None,
+        }
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let offset = self.list_state.offset;
+        let selected = self.list_state.selected;
+
+        let list_items = build_list_items(
+            &self.locations,
+            &self.view_indices,
+            self.filter_input_state.input(),
+            &self.ctx.theme,
+            offset,
+            selected,
+            area,
+        );
+
+        let list = ScrollList::new(list_items).theme(&self.ctx.theme);
+        f.render_stateful_widget(list, area, &mut self.list_state);
+
+        if let ViewState::FilterDialog = self.view_state {
+            let filter_dialog = InputDialog::default()
+                .title("Filter")
+                .max_width(30)
+                .theme(&self.ctx.theme);
+            f.render_stateful_widget(filter_dialog, area, &mut self.filter_input_state);
+
+            let (cursor_x, cursor_y) = self.filter_input_state.cursor();
+            f.set_cursor_position((cursor_x, cursor_y));
+        }
+    }
+
+    pub fn helps(&self, mapper: &UserEventMapper) -> Vec<Spans> {
+        build_help_spans(self.help_items(), mapper, self.ctx.theme.help_key_fg)
+    }
+
+    pub fn help_items(&self) -> Vec<BuildHelpsItem> {
+        #[rustfmt::skip]
+        let helps = match self.view_state {
+            ViewState::Default => {
+                vec![
+                    BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+                    BuildHelpsItem::new(UserEvent::RecentLocationsDown, "Select next item"),
+                    BuildHelpsItem::new(UserEvent::RecentLocationsUp, "Select previous item"),
+                    BuildHelpsItem::new(UserEvent::RecentLocationsGoToTop, "Go to top"),
+                    BuildHelpsItem::new(UserEvent::RecentLocationsGoToBottom, "Go to bottom"),
+                    BuildHelpsItem::new(UserEvent::RecentLocationsPageDown, "Scroll page forward"),
+                    BuildHelpsItem::new(UserEvent::RecentLocationsPageUp, "Scroll page backward"),
+                    BuildHelpsItem::new(UserEvent::RecentLocationsSelect, "Jump to location"),
+                    BuildHelpsItem::new(UserEvent::RecentLocationsFilter, "Filter locations"),
+                    BuildHelpsItem::new(UserEvent::RecentLocationsResetFilter, "Clear filter"),
+                    BuildHelpsItem::new(UserEvent::RecentLocationsBack, "Close recent locations"),
+                ]
+            }
+            ViewState::FilterDialog => {
+                vec![
+                    BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+                    BuildHelpsItem::new(UserEvent::InputDialogClose, "Close filter dialog"),
+                    BuildHelpsItem::new(UserEvent::InputDialogApply, "Apply filter"),
+                ]
+            }
+        };
+        helps
+    }
+
+    pub fn short_helps(&self, mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
+        #[rustfmt::skip]
+        let helps = match self.view_state {
+            ViewState::Default => {
+                vec![
+                    BuildShortHelpsItem::single(UserEvent::Quit, "Quit", 0),
+                    BuildShortHelpsItem::group(vec![UserEvent::RecentLocationsDown, UserEvent::RecentLocationsUp], "Select", 3),
+                    BuildShortHelpsItem::single(UserEvent::RecentLocationsSelect, "Jump", 1),
+                    BuildShortHelpsItem::single(UserEvent::RecentLocationsFilter, "Filter", 4),
+                    BuildShortHelpsItem::single(UserEvent::RecentLocationsBack, "Close", 2),
+                    BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+                ]
+            }
+            ViewState::FilterDialog => {
+                vec![
+                    BuildShortHelpsItem::single(UserEvent::InputDialogClose, "Close", 2),
+                    BuildShortHelpsItem::single(UserEvent::InputDialogApply, "Filter", 1),
+                    BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+                ]
+            }
+        };
+        build_short_help_spans(helps, mapper)
+    }
+}
+
+impl RecentLocationsPage {
+    fn open_filter_dialog(&mut self) {
+        self.view_state = ViewState::FilterDialog;
+    }
+
+    fn close_filter_dialog(&mut self) {
+        self.view_state = ViewState::Default;
+        self.reset_filter();
+    }
+
+    fn apply_filter(&mut self) {
+        self.view_state = ViewState::Default;
+
+        self.filter_view_indices();
+    }
+
+    fn reset_filter(&mut self) {
+        self.filter_input_state.clear_input();
+
+        self.filter_view_indices();
+    }
+
+    fn filter_view_indices(&mut self) {
+        let filter = self.filter_input_state.input();
+        self.view_indices = self
+            .locations
+            .iter()
+            .enumerate()
+            .filter(|(_, uri)| uri.contains(filter))
+            .map(|(idx, _)| idx)
+            .collect();
+        // reset list state
+        self.list_state = ScrollListState::new(self.view_indices.len());
+    }
+
+    fn open_selected(&self) {
+        let i = self.view_indices[self.list_state.selected];
+        let uri = &self.locations[i];
+        match parse_location(uri) {
+            Some(location) => {
+                self.tx.send(AppEventType::GoToLocation(location, false));
+            }
+            None => {
+                let msg = format!("Invalid location: {}", uri);
+                self.tx.send(AppEventType::NotifyWarn(msg));
+            }
+        }
+    }
+
+    fn non_empty(&self) -> bool {
+        !self.view_indices.is_empty()
+    }
+}
+
+fn build_list_items<'a>(
+    locations: &'a [String],
+    view_indices: &'a [usize],
+    filter: &'a str,
+    theme: &'a ColorTheme,
+    offset: usize,
+    selected: usize,
+    area: Rect,
+) -> Vec<ListItem<'a>> {
+    let show_item_count = (area.height as usize) - 2 /* border */;
+    view_indices
+        .iter()
+        .map(|&original_idx| &locations[original_idx])
+        .skip(offset)
+        .take(show_item_count)
+        .enumerate()
+        .map(|(idx, uri)| {
+            let selected = idx + offset == selected;
+            build_list_item(uri, selected, filter, area.width, theme)
+        })
+        .collect()
+}
+
+fn build_list_item<'a>(
+    uri: &str,
+    selected: bool,
+    filter: &str,
+    width: u16,
+    theme: &ColorTheme,
+) -> ListItem<'a> {
+    let uri_w = (width as usize).saturating_sub(2 /* spaces */ + 4 /* border + pad */);
+    let pad_uri =
+        console::pad_str(uri, uri_w, console::Alignment::Left, Some(ELLIPSIS)).to_string();
+
+    let line = if filter.is_empty() {
+        Line::from(vec![" ".into(), pad_uri.into(), " ".into()])
+    } else {
+        let i = uri.find(filter).unwrap();
+        let mut spans = highlight_matched_text(pad_uri)
+            .ellipsis(ELLIPSIS)
+            .matched_range(i, i + filter.len())
+            .not_matched_style(Style::default())
+            .matched_style(Style::default().fg(theme.list_filter_match))
+            .into_spans();
+        spans.insert(0, " ".into());
+        spans.push(" ".into());
+        Line::from(spans)
+    };
+
+    let style = if selected {
+        Style::default()
+            .bg(theme.list_selected_bg)
+            .fg(theme.list_selected_fg)
+            .add_modifier(theme.list_selected_modifier)
+    } else {
+        Style::default()
+    };
+    ListItem::new(line).style(style)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{location::Location, set_cells};
+
+    use super::*;
+    use ratatui::{
+        backend::TestBackend, buffer::Buffer, crossterm::event::KeyCode, layout::Position,
+        style::Color, Terminal,
+    };
+
+    #[tokio::test]
+    async fn test_render_without_scroll() -> std::io::Result<()> {
+        let ctx = Rc::default();
+        let tx = sender();
+        let mut terminal = setup_terminal()?;
+
+        let mut page = RecentLocationsPage::new(locations(), ctx, tx);
+        let area = Rect::new(0, 0, 30, 10);
+
+        terminal.draw(|f| {
+            page.render(f, area);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌───────────────────── 1 / 3 ┐",
+            "│  s3://bar/baz/             │",
+            "│  s3://foo/                 │",
+            "│  s3://bar/                 │",
+            "│                            │",
+            "│                            │",
+            "│                            │",
+            "│                            │",
+            "│                            │",
+            "└────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            // selected item
+            (2..28, [1]) => bg: Color::Cyan, fg: Color::Black,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_selected_location() {
+        let ctx = Rc::default();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let tx = Sender::new(tx);
+
+        let mut page = RecentLocationsPage::new(locations(), ctx, tx);
+        page.handle_key(
+            vec![UserEvent::RecentLocationsSelect],
+            KeyEvent::from(KeyCode::Enter),
+        );
+
+        let expected = Location::prefix("bar", "baz/");
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            AppEventType::GoToLocation(location, false) if location == expected
+        ));
+    }
+
+    #[test]
+    fn test_handle_mouse() {
+        let ctx = Rc::default();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let tx = Sender::new(tx);
+        let mut terminal = setup_terminal().unwrap();
+
+        let mut page = RecentLocationsPage::new(locations(), ctx, tx);
+        terminal.draw(|f| page.render(f, f.area())).unwrap();
+
+        let position = Position::new(5, 3);
+        let actual = page.handle_mouse(MouseInput::Click(position));
+        assert_eq!(actual, None);
+        assert_eq!(page.list_state.selected, 2);
+
+        // outside of the items
+        let actual = page.handle_mouse(MouseInput::DoubleClick(Position::new(5, 5)));
+        assert_eq!(actual, None);
+
+        let actual = page.handle_mouse(MouseInput::DoubleClick(position));
+        assert_eq!(actual, Some(UserEvent::RecentLocationsSelect));
+        page.handle_key(actual.into_iter().collect(), KeyEvent::from(KeyCode::Null));
+
+        let expected = Location::bucket("bar");
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            AppEventType::GoToLocation(location, false) if location == expected
+        ));
+    }
+
+    fn setup_terminal() -> std::io::Result<Terminal<TestBackend>> {
+        let backend = TestBackend::new(30, 10);
+        let mut terminal = Terminal::new(backend)?;
+        terminal.clear()?;
+        Ok(terminal)
+    }
+
+    fn sender() -> Sender {
+        let (tx, _) = tokio::sync::mpsc::unbounded_channel();
+        Sender::new(tx)
+    }
+
+    fn locations() -> Vec<String> {
+        vec![
+            "s3://bar/baz/".into(),
+            "s3://foo/".into(),
+            "s3://bar/".into(),
+        ]
+    }
+}
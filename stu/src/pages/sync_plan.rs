@@ -0,0 +1,374 @@
+use std::rc::Rc;
+
+use ratatui::{
+    crossterm::event::KeyEvent,
+    layout::Rect,
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::ListItem,
+    Frame,
+};
+
+use crate::{
+    app::AppContext,
+    color::ColorTheme,
+    event::{AppEventType, Sender},
+    format::{format_object_count, format_size_byte},
+    handle_user_events,
+    help::{
+        build_help_spans, build_short_help_spans, BuildHelpsItem, BuildShortHelpsItem, Spans,
+        SpansWithPriority,
+    },
+    keys::{UserEvent, UserEventMapper},
+    mouse::{handle_list_mouse_input, MouseInput},
+    sync::{SyncAction, SyncDirection, SyncPlan},
+    widget::{ScrollList, ScrollListState},
+};
+
+const ELLIPSIS: &str = "...";
+
+#[derive(Debug)]
+pub struct SyncPlanPage {
+    plan: SyncPlan,
+    rows: Vec<SyncPlanRow>,
+
+    list_state: ScrollListState,
+
+    ctx: Rc<AppContext>,
+    tx: Sender,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum SyncPlanRow {
+    Blank,
+    Header(String),
+    Count(SyncAction, usize),
+    Item(SyncAction, String, usize),
+    Message(String),
+}
+
+impl SyncPlanPage {
+    pub fn new(plan: SyncPlan, ctx: Rc<AppContext>, tx: Sender) -> Self {
+        let rows = build_rows(&plan);
+        let list_state = ScrollListState::new(rows.len());
+        Self {
+            plan,
+            rows,
+            list_state,
+            ctx,
+            tx,
+        }
+    }
+
+    pub fn handle_key(&mut self, user_events: Vec<UserEvent>, _key_event: KeyEvent) {
+        handle_user_events! { user_events =>
+            UserEvent::SyncPlanBack => {
+                self.tx.send(AppEventType::CloseCurrentPage);
+            }
+            UserEvent::SyncPlanDown => {
+                self.list_state.select_next();
+            }
+            UserEvent::SyncPlanUp => {
+                self.list_state.select_prev();
+            }
+            UserEvent::SyncPlanGoToTop => {
+                self.list_state.select_first();
+            }
+            UserEvent::SyncPlanGoToBottom => {
+                self.list_state.select_last();
+            }
+            UserEvent::SyncPlanPageDown => {
+                self.list_state.select_next_page();
+            }
+            UserEvent::SyncPlanPageUp => {
+                self.list_state.select_prev_page();
+            }
+            UserEvent::SyncPlanExecute => {
+                self.execute();
+            }
+            UserEvent::Help => {
+                self.tx.send(AppEventType::OpenHelp);
+            }
+        }
+    }
+
+    pub fn handle_mouse(&mut self, input: MouseInput) -> Option<UserEvent> {
+        handle_list_mouse_input(
+            input,
+            &mut self.list_state,
+            None,
+            UserEvent::SyncPlanDown,
+            UserEvent::SyncPlanUp,
+        )
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let offset = self.list_state.offset;
+        let selected = self.list_state.selected;
+
+        let show_item_count = (area.height as usize) - 2 /* border */;
+        let list_items: Vec<ListItem> = self
+            .rows
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(show_item_count)
+            .map(|(i, row)| build_list_item(row, i == selected, area.width, &self.ctx.theme))
+            .collect();
+
+        let list = ScrollList::new(list_items).theme(&self.ctx.theme);
+        f.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    pub fn helps(&self, mapper: &UserEventMapper) -> Vec<Spans> {
+        build_help_spans(self.help_items(), mapper, self.ctx.theme.help_key_fg)
+    }
+
+    pub fn help_items(&self) -> Vec<BuildHelpsItem> {
+        #[rustfmt::skip]
+        let helps = vec![
+            BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+            BuildHelpsItem::new(UserEvent::SyncPlanDown, "Select next item"),
+            BuildHelpsItem::new(UserEvent::SyncPlanUp, "Select previous item"),
+            BuildHelpsItem::new(UserEvent::SyncPlanGoToTop, "Go to top"),
+            BuildHelpsItem::new(UserEvent::SyncPlanGoToBottom, "Go to bottom"),
+            BuildHelpsItem::new(UserEvent::SyncPlanPageDown, "Scroll page forward"),
+            BuildHelpsItem::new(UserEvent::SyncPlanPageUp, "Scroll page backward"),
+            BuildHelpsItem::new(UserEvent::SyncPlanExecute, "Execute sync"),
+            BuildHelpsItem::new(UserEvent::SyncPlanBack, "Cancel sync"),
+        ];
+        helps
+    }
+
+    pub fn short_helps(&self, mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
+        #[rustfmt::skip]
+        let helps = vec![
+            BuildShortHelpsItem::single(UserEvent::Quit, "Quit", 0),
+            BuildShortHelpsItem::group(vec![UserEvent::SyncPlanDown, UserEvent::SyncPlanUp], "Select", 3),
+            BuildShortHelpsItem::group(vec![UserEvent::SyncPlanGoToTop, UserEvent::SyncPlanGoToBottom], "Top/Bottom", 4),
+            BuildShortHelpsItem::single(UserEvent::SyncPlanExecute, "Execute", 1),
+            BuildShortHelpsItem::single(UserEvent::SyncPlanBack, "Cancel", 2),
+            BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+        ];
+        build_short_help_spans(helps, mapper)
+    }
+}
+
+impl SyncPlanPage {
+    fn execute(&self) {
+        if self.plan.operations().is_empty() {
+            let msg = "Already in sync";
+            self.tx.send(AppEventType::NotifyInfo(msg.into()));
+            return;
+        }
+        self.tx
+            .send(AppEventType::StartExecuteSync(self.plan.clone()));
+    }
+}
+
+fn build_rows(plan: &SyncPlan) -> Vec<SyncPlanRow> {
+    let s3_uri = format!("s3://{}/{}", plan.bucket, plan.prefix);
+    let local_dir = plan.local_dir.to_string_lossy();
+    let header = match plan.direction {
+        SyncDirection::Upload => format!("Upload: {} -> {}", local_dir, s3_uri),
+        SyncDirection::Download => format!("Download: {} -> {}", s3_uri, local_dir),
+    };
+
+    let mut rows = vec![SyncPlanRow::Header(header)];
+    for action in [
+        SyncAction::New,
+        SyncAction::Changed,
+        SyncAction::Deleted,
+        SyncAction::Unchanged,
+    ] {
+        rows.push(SyncPlanRow::Count(action, plan.count(action)));
+    }
+    if plan.skipped > 0 {
+        rows.push(SyncPlanRow::Message(format!(
+            "  Skipped {} with keys that cannot be local paths",
+            format_object_count(plan.skipped)
+        )));
+    }
+
+    rows.push(SyncPlanRow::Blank);
+    rows.push(SyncPlanRow::Header("Operations".into()));
+    let items: Vec<SyncPlanRow> = plan
+        .items
+        .iter()
+        .filter(|item| item.action != SyncAction::Unchanged)
+        .map(|item| SyncPlanRow::Item(item.action, item.relative_path.clone(), item.size_byte))
+        .collect();
+    if items.is_empty() {
+        rows.push(SyncPlanRow::Message("  Already in sync".into()));
+    }
+    rows.extend(items);
+
+    rows
+}
+
+fn build_list_item<'a>(
+    row: &'a SyncPlanRow,
+    selected: bool,
+    width: u16,
+    theme: &ColorTheme,
+) -> ListItem<'a> {
+    let line = match row {
+        SyncPlanRow::Blank => Line::raw(""),
+        SyncPlanRow::Header(label) => Line::from(vec![" ".into(), label.as_str().bold()]),
+        SyncPlanRow::Message(msg) => Line::raw(format!(" {}", msg)),
+        SyncPlanRow::Count(action, count) => {
+            let label = format!("  {}", action_label(*action));
+            build_line(&label, &format_object_count(*count), width)
+        }
+        SyncPlanRow::Item(action, path, size_byte) => {
+            let label = format!("  {} {}", action_mark(*action), path);
+            let line = build_line(&label, &format_size_byte(*size_byte), width);
+            if selected {
+                line
+            } else {
+                line.fg(action_color(*action, theme))
+            }
+        }
+    };
+
+    let style = if selected {
+        Style::default()
+            .bg(theme.list_selected_bg)
+            .fg(theme.list_selected_fg)
+            .add_modifier(theme.list_selected_modifier)
+    } else {
+        Style::default()
+    };
+    ListItem::new(line).style(style)
+}
+
+fn build_line<'a>(label: &str, value: &str, width: u16) -> Line<'a> {
+    let value_w: usize = 16;
+    let label_w: usize = (width as usize) - value_w - 6 /* spaces */ - 4 /* border + pad */;
+
+    let pad_label =
+        console::pad_str(label, label_w, console::Alignment::Left, Some(ELLIPSIS)).to_string();
+    let pad_value = console::pad_str(value, value_w, console::Alignment::Right, None).to_string();
+
+    Line::from(vec![
+        " ".into(),
+        pad_label.into(),
+        "    ".into(),
+        pad_value.into(),
+        " ".into(),
+    ])
+}
+
+fn action_label(action: SyncAction) -> &'static str {
+    match action {
+        SyncAction::New => "New",
+        SyncAction::Changed => "Changed",
+        SyncAction::Deleted => "Deleted",
+        SyncAction::Unchanged => "Unchanged",
+    }
+}
+
+fn action_mark(action: SyncAction) -> char {
+    match action {
+        SyncAction::New => '+',
+        SyncAction::Changed => '~',
+        SyncAction::Deleted => '-',
+        SyncAction::Unchanged => ' ',
+    }
+}
+
+fn action_color(action: SyncAction, theme: &ColorTheme) -> Color {
+    match action {
+        SyncAction::New => theme.status_success,
+        SyncAction::Changed => theme.status_warn,
+        SyncAction::Deleted => theme.status_error,
+        SyncAction::Unchanged => theme.fg,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{event::Sender, set_cells, sync::SyncPlanItem};
+
+    use super::*;
+    use ratatui::{backend::TestBackend, buffer::Buffer, style::Modifier, Terminal};
+
+    #[tokio::test]
+    async fn test_render() -> std::io::Result<()> {
+        let ctx = Rc::default();
+        let tx = sender();
+        let mut terminal = setup_terminal()?;
+
+        terminal.draw(|f| {
+            let plan = SyncPlan {
+                bucket: "bucket".into(),
+                prefix: "dir/".into(),
+                local_dir: PathBuf::from("data"),
+                direction: SyncDirection::Upload,
+                items: vec![
+                    item(SyncAction::New, "a.txt", 1024),
+                    item(SyncAction::Unchanged, "b.txt", 10),
+                    item(SyncAction::Changed, "c/d.txt", 20),
+                ],
+                skipped: 0,
+            };
+            let mut page = SyncPlanPage::new(plan, ctx, tx);
+            let area = Rect::new(0, 0, 50, 12);
+            page.render(f, area);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌───────────────────────────────────────── 1 / 9 ┐",
+            "│  Upload: data -> s3://bucket/dir/              │",
+            "│    New                               1 object  │",
+            "│    Changed                           1 object  │",
+            "│    Deleted                          0 objects  │",
+            "│    Unchanged                         1 object  │",
+            "│                                                │",
+            "│  Operations                                    │",
+            "│    + a.txt                              1 KiB  │",
+            "│    ~ c/d.txt                             20 B  │",
+            "│                                                │",
+            "└────────────────────────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            // selected
+            (2..48, [1]) => bg: Color::Cyan, fg: Color::Black,
+            // header
+            (3..35, [1]) => modifier: Modifier::BOLD,
+            (3..13, [7]) => modifier: Modifier::BOLD,
+            // new
+            (2..48, [8]) => fg: Color::Green,
+            // changed
+            (2..48, [9]) => fg: Color::Yellow,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
+    fn item(action: SyncAction, path: &str, size_byte: usize) -> SyncPlanItem {
+        SyncPlanItem {
+            action,
+            relative_path: path.into(),
+            size_byte,
+            last_modified: None,
+        }
+    }
+
+    fn setup_terminal() -> std::io::Result<Terminal<TestBackend>> {
+        let backend = TestBackend::new(50, 12);
+        let mut terminal = Terminal::new(backend)?;
+        terminal.clear()?;
+        Ok(terminal)
+    }
+
+    fn sender() -> Sender {
+        let (tx, _) = tokio::sync::mpsc::unbounded_channel();
+        Sender::new(tx)
+    }
+}
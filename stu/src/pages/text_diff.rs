@@ -0,0 +1,567 @@
+use std::rc::Rc;
+
+use ratatui::{
+    crossterm::event::KeyEvent,
+    layout::Rect,
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::Block,
+    Frame,
+};
+
+use crate::{
+    app::AppContext,
+    color::ColorTheme,
+    diff::{diff_lines, hunk_starts, side_by_side, DiffLine, DiffLineKind, DiffTarget},
+    event::{AppEventType, Sender},
+    handle_user_events,
+    help::{
+        build_help_spans, build_short_help_spans, BuildHelpsItem, BuildShortHelpsItem, Spans,
+        SpansWithPriority,
+    },
+    keys::{UserEvent, UserEventMapper},
+    mouse::MouseInput,
+    object::RawObject,
+    util::digits,
+    widget::{
+        build_highlighted_lines, drop_control_chars, ScrollLines, ScrollLinesOptions,
+        ScrollLinesState,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffViewMode {
+    Unified,
+    SideBySide,
+}
+
+#[derive(Debug)]
+pub struct TextDiffPage {
+    left: DiffTarget,
+    right: DiffTarget,
+
+    old_lines: Vec<Line<'static>>,
+    new_lines: Vec<Line<'static>>,
+    diff: Vec<DiffLine>,
+
+    mode: DiffViewMode,
+    hunks: Vec<usize>,
+    // the width used to build the side-by-side lines
+    width: u16,
+    scroll_lines_state: ScrollLinesState,
+
+    ctx: Rc<AppContext>,
+    tx: Sender,
+}
+
+impl TextDiffPage {
+    pub fn new(
+        left: DiffTarget,
+        right: DiffTarget,
+        left_object: RawObject,
+        right_object: RawObject,
+        ctx: Rc<AppContext>,
+        tx: Sender,
+    ) -> Self {
+        let old = to_diff_string(&left_object.bytes);
+        let new = to_diff_string(&right_object.bytes);
+        let old_strs: Vec<&str> = old.lines().collect();
+        let new_strs: Vec<&str> = new.lines().collect();
+        let diff = diff_lines(&old_strs, &new_strs);
+
+        let highlight = ctx.config.preview.highlight;
+        let highlight_theme = &ctx.config.preview.highlight_theme;
+        let file_name = right.file_name().to_string();
+        let (old_lines, old_msg) =
+            build_lines(&old, old_strs.len(), &file_name, highlight, highlight_theme);
+        let (new_lines, _) =
+            build_lines(&new, new_strs.len(), &file_name, highlight, highlight_theme);
+        if let Some(msg) = old_msg {
+            tx.send(AppEventType::NotifyWarn(msg));
+        }
+
+        let mut page = Self {
+            left,
+            right,
+            old_lines,
+            new_lines,
+            diff,
+            mode: DiffViewMode::Unified,
+            hunks: Vec::new(),
+            width: 0,
+            scroll_lines_state: ScrollLinesState::default(),
+            ctx,
+            tx,
+        };
+        page.update_lines(0);
+        page
+    }
+
+    pub fn handle_key(&mut self, user_events: Vec<UserEvent>, _key_event: KeyEvent) {
+        handle_user_events! { user_events =>
+            UserEvent::TextDiffBack => {
+                self.tx.send(AppEventType::CloseCurrentPage);
+            }
+            UserEvent::TextDiffDown => {
+                self.scroll_lines_state.scroll_forward();
+            }
+            UserEvent::TextDiffUp => {
+                self.scroll_lines_state.scroll_backward();
+            }
+            UserEvent::TextDiffPageDown => {
+                self.scroll_lines_state.scroll_page_forward();
+            }
+            UserEvent::TextDiffPageUp => {
+                self.scroll_lines_state.scroll_page_backward();
+            }
+            UserEvent::TextDiffGoToTop => {
+                self.scroll_lines_state.scroll_to_top();
+            }
+            UserEvent::TextDiffGoToBottom => {
+                self.scroll_lines_state.scroll_to_end();
+            }
+            UserEvent::TextDiffLeft => {
+                self.scroll_lines_state.scroll_left();
+            }
+            UserEvent::TextDiffRight => {
+                self.scroll_lines_state.scroll_right();
+            }
+            UserEvent::TextDiffNextHunk => {
+                self.next_hunk();
+            }
+            UserEvent::TextDiffPrevHunk => {
+                self.prev_hunk();
+            }
+            UserEvent::TextDiffToggleMode => {
+                self.toggle_mode();
+            }
+            UserEvent::Help => {
+                self.tx.send(AppEventType::OpenHelp);
+            }
+        }
+    }
+
+    pub fn handle_mouse(&mut self, input: MouseInput) -> Option<UserEvent> {
+        match input {
+            MouseInput::ScrollDown(_) => Some(UserEvent::TextDiffDown),
+            MouseInput::ScrollUp(_) => Some(UserEvent::TextDiffUp),
+            MouseInput::Click(_) | MouseInput::DoubleClick(_) => None,
+        }
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        if self.mode == DiffViewMode::SideBySide && self.width != area.width {
+            // the lines are built to fit the width, so rebuild them when the size is changed
+            self.width = area.width;
+            let current = self.scroll_lines_state.current_line();
+            self.update_lines(current);
+        }
+
+        let title = format!(
+            "Diff [{} -> {}] ({} hunks)",
+            self.left.label(),
+            self.right.label(),
+            self.hunks.len()
+        );
+        let scroll_lines = ScrollLines::default()
+            .block(Block::bordered().title(title))
+            .theme(&self.ctx.theme);
+        f.render_stateful_widget(scroll_lines, area, &mut self.scroll_lines_state);
+    }
+
+    pub fn helps(&self, mapper: &UserEventMapper) -> Vec<Spans> {
+        build_help_spans(self.help_items(), mapper, self.ctx.theme.help_key_fg)
+    }
+
+    pub fn help_items(&self) -> Vec<BuildHelpsItem> {
+        #[rustfmt::skip]
+        let helps = vec![
+            BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+            BuildHelpsItem::new(UserEvent::TextDiffDown, "Scroll forward"),
+            BuildHelpsItem::new(UserEvent::TextDiffUp, "Scroll backward"),
+            BuildHelpsItem::new(UserEvent::TextDiffPageDown, "Scroll page forward"),
+            BuildHelpsItem::new(UserEvent::TextDiffPageUp, "Scroll page backward"),
+            BuildHelpsItem::new(UserEvent::TextDiffGoToTop, "Scroll to top"),
+            BuildHelpsItem::new(UserEvent::TextDiffGoToBottom, "Scroll to end"),
+            BuildHelpsItem::new(UserEvent::TextDiffRight, "Scroll right"),
+            BuildHelpsItem::new(UserEvent::TextDiffLeft, "Scroll left"),
+            BuildHelpsItem::new(UserEvent::TextDiffNextHunk, "Go to next hunk"),
+            BuildHelpsItem::new(UserEvent::TextDiffPrevHunk, "Go to previous hunk"),
+            BuildHelpsItem::new(UserEvent::TextDiffToggleMode, "Toggle unified/side-by-side"),
+            BuildHelpsItem::new(UserEvent::TextDiffBack, "Close diff"),
+        ];
+        helps
+    }
+
+    pub fn short_helps(&self, mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
+        #[rustfmt::skip]
+        let helps = vec![
+            BuildShortHelpsItem::single(UserEvent::Quit, "Quit", 0),
+            BuildShortHelpsItem::group(vec![UserEvent::TextDiffDown, UserEvent::TextDiffUp], "Scroll", 4),
+            BuildShortHelpsItem::group(vec![UserEvent::TextDiffGoToTop, UserEvent::TextDiffGoToBottom], "Top/End", 5),
+            BuildShortHelpsItem::group(vec![UserEvent::TextDiffNextHunk, UserEvent::TextDiffPrevHunk], "Next/Prev hunk", 1),
+            BuildShortHelpsItem::single(UserEvent::TextDiffToggleMode, "Mode", 3),
+            BuildShortHelpsItem::single(UserEvent::TextDiffBack, "Close", 2),
+            BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+        ];
+        build_short_help_spans(helps, mapper)
+    }
+}
+
+impl TextDiffPage {
+    fn toggle_mode(&mut self) {
+        self.mode = match self.mode {
+            DiffViewMode::Unified => DiffViewMode::SideBySide,
+            DiffViewMode::SideBySide => DiffViewMode::Unified,
+        };
+        // keep the current hunk (or the top of the view) displayed after switching
+        let current = self.scroll_lines_state.current_line();
+        let hunk = self.hunks.iter().rposition(|&h| h <= current);
+        self.update_lines(0);
+        if let Some(h) = hunk.and_then(|i| self.hunks.get(i)) {
+            self.scroll_lines_state.scroll_to(*h);
+        }
+    }
+
+    fn next_hunk(&mut self) {
+        let current = self.scroll_lines_state.current_line();
+        match self.hunks.iter().find(|&&h| h > current) {
+            Some(h) => self.scroll_lines_state.scroll_to(*h),
+            None => self.notify_no_more_hunks(),
+        }
+    }
+
+    fn prev_hunk(&mut self) {
+        let current = self.scroll_lines_state.current_line();
+        match self.hunks.iter().rev().find(|&&h| h < current) {
+            Some(h) => self.scroll_lines_state.scroll_to(*h),
+            None => self.notify_no_more_hunks(),
+        }
+    }
+
+    fn notify_no_more_hunks(&self) {
+        let msg = if self.hunks.is_empty() {
+            "No differences"
+        } else {
+            "No more hunks"
+        };
+        self.tx.send(AppEventType::NotifyInfo(msg.into()));
+    }
+
+    fn update_lines(&mut self, line: usize) {
+        let theme = &self.ctx.theme;
+        let (lines, hunks) = match self.mode {
+            DiffViewMode::Unified => {
+                let lines =
+                    build_unified_lines(&self.diff, &self.old_lines, &self.new_lines, theme);
+                let hunks = hunk_starts(self.diff.iter().map(DiffLine::is_change));
+                (lines, hunks)
+            }
+            DiffViewMode::SideBySide => {
+                let rows = side_by_side(&self.diff);
+                let lines = build_side_by_side_lines(
+                    &rows,
+                    &self.old_lines,
+                    &self.new_lines,
+                    self.width,
+                    theme,
+                );
+                let hunks = hunk_starts(rows.iter().map(|(l, r)| {
+                    l.is_some_and(|l| l.is_change()) || r.is_some_and(|r| r.is_change())
+                }));
+                (lines, hunks)
+            }
+        };
+        let options = ScrollLinesOptions::new(false, false);
+        self.scroll_lines_state = ScrollLinesState::new(lines, options);
+        self.scroll_lines_state.scroll_to(line);
+        self.hunks = hunks;
+    }
+}
+
+fn to_diff_string(bytes: &[u8]) -> String {
+    // tab is not rendered correctly, so replace it
+    String::from_utf8_lossy(bytes).replace('\t', "    ")
+}
+
+fn build_lines(
+    s: &str,
+    count: usize,
+    file_name: &str,
+    highlight: bool,
+    highlight_theme_name: &str,
+) -> (Vec<Line<'static>>, Option<String>) {
+    match build_highlighted_lines(s, file_name, highlight, highlight_theme_name) {
+        Ok(mut lines) => {
+            // make sure that the lines correspond to the lines of the text
+            lines.resize(count, Line::default());
+            (lines, None)
+        }
+        Err(msg) => {
+            let lines = s.lines().map(drop_control_chars).map(Line::raw).collect();
+            (lines, msg)
+        }
+    }
+}
+
+fn build_unified_lines(
+    diff: &[DiffLine],
+    old_lines: &[Line<'static>],
+    new_lines: &[Line<'static>],
+    theme: &ColorTheme,
+) -> Vec<Line<'static>> {
+    let w = digits(old_lines.len().max(new_lines.len()));
+    diff.iter()
+        .map(|l| {
+            let old_no = format_line_number(l.old, w);
+            let new_no = format_line_number(l.new, w);
+            let content = match l.kind {
+                DiffLineKind::Delete => line_at(old_lines, l.old),
+                _ => line_at(new_lines, l.new),
+            };
+            let mut spans = vec![
+                format!("{} {} ", old_no, new_no).fg(theme.preview_line_number),
+                format!("{} ", kind_mark(l.kind)).fg(kind_color(l.kind, theme)),
+            ];
+            spans.extend(content.spans);
+            Line::from(spans).style(line_style(l.kind, theme))
+        })
+        .collect()
+}
+
+fn build_side_by_side_lines(
+    rows: &[(Option<DiffLine>, Option<DiffLine>)],
+    old_lines: &[Line<'static>],
+    new_lines: &[Line<'static>],
+    width: u16,
+    theme: &ColorTheme,
+) -> Vec<Line<'static>> {
+    let w = digits(old_lines.len().max(new_lines.len()));
+    let half_w =
+        (width as usize).saturating_sub(2 /* border */ + 2 /* padding */ + 3 /* separator */) / 2;
+    let content_w = half_w.saturating_sub(w + 3 /* spaces + mark */);
+
+    let build_half = |line: Option<DiffLine>, index: Option<usize>, lines: &[Line<'static>]| {
+        let Some(line) = line else {
+            return vec![Span::raw(" ".repeat(half_w))];
+        };
+        let style = line_style(line.kind, theme);
+        let content = truncate_spans(line_at(lines, index).spans, content_w)
+            .into_iter()
+            .map(|s| Span::styled(s.content, style.patch(s.style)));
+        let mut spans = vec![
+            format!("{} ", format_line_number(index, w)).fg(theme.preview_line_number),
+            format!("{} ", kind_mark(line.kind)).fg(kind_color(line.kind, theme)),
+        ];
+        spans.extend(content);
+        spans
+    };
+
+    rows.iter()
+        .map(|(l, r)| {
+            let mut spans = build_half(*l, l.and_then(|l| l.old), old_lines);
+            spans.push(" │ ".fg(theme.divider));
+            spans.extend(build_half(*r, r.and_then(|r| r.new), new_lines));
+            Line::from(spans)
+        })
+        .collect()
+}
+
+// truncates (or pads) the spans to the width
+fn truncate_spans(spans: Vec<Span<'static>>, width: usize) -> Vec<Span<'static>> {
+    let mut ret = Vec::new();
+    let mut rest = width;
+    for span in spans {
+        if rest == 0 {
+            break;
+        }
+        let span_w = console::measure_text_width(&span.content);
+        if span_w <= rest {
+            rest -= span_w;
+            ret.push(span);
+        } else {
+            let s = console::truncate_str(&span.content, rest, "").to_string();
+            rest -= console::measure_text_width(&s);
+            ret.push(Span::styled(s, span.style));
+            break;
+        }
+    }
+    if rest > 0 {
+        ret.push(Span::raw(" ".repeat(rest)));
+    }
+    ret
+}
+
+fn line_at(lines: &[Line<'static>], index: Option<usize>) -> Line<'static> {
+    index
+        .and_then(|i| lines.get(i))
+        .cloned()
+        .unwrap_or_default()
+}
+
+fn format_line_number(n: Option<usize>, width: usize) -> String {
+    match n {
+        Some(n) => format!("{:>width$}", n + 1),
+        None => " ".repeat(width),
+    }
+}
+
+fn kind_mark(kind: DiffLineKind) -> char {
+    match kind {
+        DiffLineKind::Equal => ' ',
+        DiffLineKind::Delete => '-',
+        DiffLineKind::Insert => '+',
+    }
+}
+
+fn kind_color(kind: DiffLineKind, theme: &ColorTheme) -> Color {
+    match kind {
+        DiffLineKind::Equal => theme.fg,
+        DiffLineKind::Delete => theme.status_error,
+        DiffLineKind::Insert => theme.status_success,
+    }
+}
+
+// unstyled (not highlighted) text is colored by the kind
+fn line_style(kind: DiffLineKind, theme: &ColorTheme) -> Style {
+    match kind {
+        DiffLineKind::Equal => Style::default(),
+        _ => Style::default().fg(kind_color(kind, theme)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{event::Sender, object::ObjectKey, set_cells};
+
+    use super::*;
+    use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
+
+    #[tokio::test]
+    async fn test_render_unified() -> std::io::Result<()> {
+        let ctx = Rc::default();
+        let tx = sender();
+        let mut terminal = setup_terminal()?;
+
+        let mut page = page(ctx, tx);
+        terminal.draw(|f| {
+            let area = Rect::new(0, 0, 40, 8);
+            page.render(f, area);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌Diff [a.txt (v1) -> a.txt (v2)] (2 hun┐",
+            "│ 1 1   aaa                            │",
+            "│ 2   - bbb                            │",
+            "│   2 + xxx                            │",
+            "│ 3 3   ccc                            │",
+            "│   4 + ddd                            │",
+            "│                                      │",
+            "└──────────────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            // line numbers
+            (2..6, [1, 2, 3, 4, 5]) => fg: Color::DarkGray,
+            // delete
+            (6..11, [2]) => fg: Color::Red,
+            // insert
+            (6..11, [3, 5]) => fg: Color::Green,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_render_side_by_side() -> std::io::Result<()> {
+        let ctx = Rc::default();
+        let tx = sender();
+        let mut terminal = setup_terminal()?;
+
+        let mut page = page(ctx, tx);
+        page.toggle_mode();
+        terminal.draw(|f| {
+            let area = Rect::new(0, 0, 40, 8);
+            page.render(f, area);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌Diff [a.txt (v1) -> a.txt (v2)] (2 hun┐",
+            "│ 1   aaa          │ 1   aaa           │",
+            "│ 2 - bbb          │ 2 + xxx           │",
+            "│ 3   ccc          │ 3   ccc           │",
+            "│                  │ 4 + ddd           │",
+            "│                                      │",
+            "│                                      │",
+            "└──────────────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            // line numbers
+            (2..4, [1, 2, 3]) => fg: Color::DarkGray,
+            // separator and line numbers
+            (18..23, [1, 2, 3, 4]) => fg: Color::DarkGray,
+            // delete
+            (4..18, [2]) => fg: Color::Red,
+            // insert
+            (23..37, [2, 4]) => fg: Color::Green,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hunk_navigation() {
+        let ctx = Rc::default();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let tx = Sender::new(tx);
+        let mut page = page(ctx, tx);
+
+        assert_eq!(page.hunks, vec![1, 4]);
+
+        page.next_hunk();
+        assert_eq!(page.scroll_lines_state.current_line(), 1);
+        page.next_hunk();
+        assert_eq!(page.scroll_lines_state.current_line(), 4);
+        page.next_hunk();
+        assert_eq!(page.scroll_lines_state.current_line(), 4);
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            AppEventType::NotifyInfo(_)
+        ));
+        page.prev_hunk();
+        assert_eq!(page.scroll_lines_state.current_line(), 1);
+    }
+
+    fn page(ctx: Rc<AppContext>, tx: Sender) -> TextDiffPage {
+        let object_key = ObjectKey {
+            bucket_name: "bucket".into(),
+            object_path: vec!["a.txt".into()],
+        };
+        let left = DiffTarget::new(object_key.clone(), Some("v1".into()), 0);
+        let right = DiffTarget::new(object_key, Some("v2".into()), 0);
+        let left_object = RawObject {
+            bytes: "aaa\nbbb\nccc\n".into(),
+        };
+        let right_object = RawObject {
+            bytes: "aaa\nxxx\nccc\nddd\n".into(),
+        };
+        TextDiffPage::new(left, right, left_object, right_object, ctx, tx)
+    }
+
+    fn setup_terminal() -> std::io::Result<Terminal<TestBackend>> {
+        let backend = TestBackend::new(40, 8);
+        let mut terminal = Terminal::new(backend)?;
+        terminal.clear()?;
+        Ok(terminal)
+    }
+
+    fn sender() -> Sender {
+        let (tx, _) = tokio::sync::mpsc::unbounded_channel();
+        Sender::new(tx)
+    }
+}
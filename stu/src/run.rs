@@ -1,11 +1,22 @@
-use ratatui::{backend::Backend, Terminal};
+use ratatui::{
+    backend::Backend,
+    crossterm::{
+        event::{DisableMouseCapture, EnableMouseCapture, KeyCode, KeyEvent},
+        execute,
+        terminal::{enable_raw_mode, EnterAlternateScreen},
+    },
+    Terminal,
+};
 
 use crate::{
     app::{App, Notification},
     client::Client,
+    command::{open_with_command, run_in_terminal},
     event::{AppEventType, Receiver},
+    file::open_editor,
     handle_user_events,
-    keys::UserEvent,
+    keys::{KeyInput, UserEvent},
+    mouse::MouseInput,
     pages::page::Page,
 };
 
@@ -14,6 +25,7 @@ pub async fn run<B: Backend, C: Client>(
     terminal: &mut Terminal<B>,
     mut rx: Receiver,
 ) -> anyhow::Result<()> {
+    let mouse = app.mouse_enabled();
     loop {
         terminal.draw(|f| app.render(f))?;
 
@@ -22,56 +34,31 @@ pub async fn run<B: Backend, C: Client>(
 
         match event {
             AppEventType::Key(key_event) => {
-                let user_events = app.mapper.find_events(key_event);
-
-                handle_user_events! { user_events =>
-                    UserEvent::Quit => {
-                        // Exit regardless of status
+                for input in app.resolve_key_input(key_event) {
+                    if handle_key_input(app, input) {
                         return Ok(());
                     }
                 }
-
-                if app.loading() {
-                    // Ignore key inputs while loading (except quit)
-                    continue;
-                }
-
-                if app.is_showing_notification()
-                    && matches!(app.page_stack.current_page(), Page::Initializing(_))
-                {
-                    return Ok(());
-                }
-
-                if matches!(app.current_notification(), Notification::Error(_)) {
-                    // Clear message and cancel key input
-                    app.clear_notification();
-                    continue;
-                }
-
-                if matches!(
-                    app.current_notification(),
-                    Notification::Info(_) | Notification::Success(_) | Notification::Warn(_)
-                ) {
-                    // Clear message and pass key input as is
-                    app.clear_notification();
+            }
+            AppEventType::Mouse(mouse_event) => {
+                if let Some(input) = app.resolve_mouse_input(mouse_event) {
+                    if handle_mouse_input(app, input) {
+                        return Ok(());
+                    }
                 }
-
-                handle_user_events! { user_events =>
-                    UserEvent::DumpApp => {
-                        app.dump_app();
-                        continue;
+            }
+            AppEventType::KeyInputTimeout(generation) => {
+                if let Some(input) = app.resolve_key_input_timeout(generation) {
+                    if handle_key_input(app, input) {
+                        return Ok(());
                     }
                 }
-
-                app.page_stack
-                    .current_page_mut()
-                    .handle_user_events(user_events, key_event);
             }
             AppEventType::Resize => {
                 // do nothing (only trigger redraw)
             }
-            AppEventType::Initialize(bucket) => {
-                app.initialize(bucket);
+            AppEventType::Initialize(location, preview) => {
+                app.initialize(location, preview);
             }
             AppEventType::CompleteInitialize(result) => {
                 app.complete_initialize(result);
@@ -136,6 +123,133 @@ pub async fn run<B: Backend, C: Client>(
             AppEventType::CompleteDownloadObjects(result) => {
                 app.complete_download_objects(result);
             }
+            AppEventType::StartEditObject(object_key) => {
+                app.start_edit_object(object_key);
+            }
+            AppEventType::EditObject(object_key) => {
+                app.edit_object(object_key);
+            }
+            AppEventType::CompleteLoadEditObject(result) => {
+                if let Some(result) = app.complete_load_edit_object(result) {
+                    let edit_result =
+                        suspend_terminal(terminal, &rx, mouse, || open_editor(&result.path))
+                            .await?;
+                    app.upload_edited_object(result, edit_result);
+                }
+            }
+            AppEventType::CompleteUploadEditedObject(result) => {
+                app.complete_upload_edited_object(result);
+            }
+            AppEventType::ReloadEditedObject(object_key) => {
+                app.reload_edited_object(object_key);
+            }
+            AppEventType::CompleteReloadEditedObject(result) => {
+                app.complete_reload_edited_object(result);
+            }
+            AppEventType::StartOpenObject(object_key, version_id) => {
+                app.start_open_object(object_key, version_id);
+            }
+            AppEventType::OpenObject(object_key, version_id) => {
+                app.open_object(object_key, version_id);
+            }
+            AppEventType::CompleteDownloadOpenObject(result) => {
+                if let Some((command, path)) = app.complete_download_open_object(result) {
+                    let result = suspend_terminal(terminal, &rx, mouse, || {
+                        open_with_command(&command, &path)
+                    })
+                    .await?;
+                    app.complete_open_object(result);
+                }
+            }
+            AppEventType::StartPipeObject(object_key, version_id, command) => {
+                app.start_pipe_object(object_key, version_id, command);
+            }
+            AppEventType::PipeObject(object_key, version_id, command) => {
+                app.pipe_object(object_key, version_id, command);
+            }
+            AppEventType::CompletePipeObject(result) => {
+                app.complete_pipe_object(result);
+            }
+            AppEventType::StartCustomCommand(index, target) => {
+                app.start_custom_command(index, target);
+            }
+            AppEventType::CustomCommand(index, target) => {
+                app.custom_command(index, target);
+            }
+            AppEventType::CompletePrepareCustomCommand(result) => {
+                if let Some(command) = app.complete_prepare_custom_command(result) {
+                    let result =
+                        suspend_terminal(terminal, &rx, mouse, || run_in_terminal(&command))
+                            .await?;
+                    app.complete_custom_command_in_terminal(result);
+                }
+            }
+            AppEventType::CompleteCustomCommand(result) => {
+                app.complete_custom_command(result);
+            }
+            AppEventType::StartLoadSyncPlan(object_key, options) => {
+                app.start_load_sync_plan(object_key, options);
+            }
+            AppEventType::LoadSyncPlan(object_key, options) => {
+                app.load_sync_plan(object_key, options);
+            }
+            AppEventType::CompleteLoadSyncPlan(result) => {
+                app.complete_load_sync_plan(result);
+            }
+            AppEventType::StartExecuteSync(plan) => {
+                app.start_execute_sync(plan);
+            }
+            AppEventType::ExecuteSync(plan) => {
+                app.execute_sync(plan);
+            }
+            AppEventType::CompleteExecuteSync(result) => {
+                app.complete_execute_sync(result);
+            }
+            AppEventType::StartLoadPrefixDiff(left, right) => {
+                app.start_load_prefix_diff(left, right);
+            }
+            AppEventType::LoadPrefixDiff(left, right) => {
+                app.load_prefix_diff(left, right);
+            }
+            AppEventType::CompleteLoadPrefixDiff(result) => {
+                app.complete_load_prefix_diff(result);
+            }
+            AppEventType::CommanderLoadObjects(pane, object_key, name) => {
+                app.commander_load_objects(pane, object_key, name);
+            }
+            AppEventType::CommanderRefresh(pane) => {
+                app.commander_refresh(pane);
+            }
+            AppEventType::CommanderSwitchConnection(pane, name) => {
+                app.commander_switch_connection(pane, name).await;
+            }
+            AppEventType::CompleteCommanderSwitchConnection(pane, result) => {
+                app.complete_commander_switch_connection(pane, result);
+            }
+            AppEventType::CompleteCommanderLoadObjects(pane, name, result) => {
+                app.complete_commander_load_objects(pane, name, result);
+            }
+            AppEventType::CompletePrefixTreeLoadObjects(result) => {
+                app.complete_prefix_tree_load_objects(result);
+            }
+            AppEventType::StartTransferObjects(transfer) => {
+                app.start_transfer_objects(transfer);
+            }
+            AppEventType::TransferObjects(transfer) => {
+                app.transfer_objects(transfer);
+            }
+            AppEventType::CompleteTransferObjects(result) => {
+                app.complete_transfer_objects(result);
+            }
+            AppEventType::StartLoadTextDiff(left, right) => {
+                app.start_load_text_diff(left, right);
+            }
+            AppEventType::LoadTextDiff(left, right) => {
+                app.load_text_diff(left, right);
+            }
+            AppEventType::CompleteLoadTextDiff(result) => {
+                app.complete_load_text_diff(result);
+            }
             AppEventType::PreviewObject(object_key, file_detail, version_id) => {
                 app.preview_object(object_key, file_detail, version_id);
             }
@@ -151,6 +265,84 @@ pub async fn run<B: Backend, C: Client>(
             AppEventType::CompleteSaveObject(result) => {
                 app.complete_save_object(result);
             }
+            AppEventType::GeneratePresignedUrl(
+                object_key,
+                version_id,
+                method,
+                expires_in,
+                content_disposition,
+            ) => {
+                app.generate_presigned_url(
+                    object_key,
+                    version_id,
+                    method,
+                    expires_in,
+                    content_disposition,
+                );
+            }
+            AppEventType::CompleteGeneratePresignedUrl(result) => {
+                app.complete_generate_presigned_url(result);
+            }
+            AppEventType::StartLoadSplitPreview(object_key, dir) => {
+                app.start_load_split_preview(object_key, dir);
+            }
+            AppEventType::LoadSplitPreview(tab_id, object_key, dir) => {
+                app.load_split_preview(tab_id, object_key, dir);
+            }
+            AppEventType::CompleteLoadSplitPreview(tab_id, object_key, result) => {
+                app.complete_load_split_preview(tab_id, object_key, result);
+            }
+            AppEventType::CalculatePrefixSize(object_keys) => {
+                app.calculate_prefix_size(object_keys);
+            }
+            AppEventType::ProgressCalculatePrefixSize(tab_id, object_key, size) => {
+                app.progress_calculate_prefix_size(tab_id, object_key, size);
+            }
+            AppEventType::CompleteCalculatePrefixSize(tab_id, object_key, result) => {
+                app.complete_calculate_prefix_size(tab_id, object_key, result);
+            }
+            AppEventType::StartLoadPrefixAnalytics(object_key) => {
+                app.start_load_prefix_analytics(object_key);
+            }
+            AppEventType::LoadPrefixAnalytics(object_key) => {
+                app.load_prefix_analytics(object_key);
+            }
+            AppEventType::CompleteLoadPrefixAnalytics(result) => {
+                app.complete_load_prefix_analytics(result);
+            }
+            AppEventType::GoToObjectKey(object_key, name) => {
+                app.go_to_object_key(object_key, name);
+            }
+            AppEventType::OpenObjectDetail(object_key) => {
+                app.open_object_detail(object_key);
+            }
+            AppEventType::CompleteGoToObjectKey(result) => {
+                app.complete_go_to_object_key(result);
+            }
+            AppEventType::OpenBookmark(bookmark) => {
+                app.open_bookmark(bookmark);
+            }
+            AppEventType::DeleteBookmark(bookmark) => {
+                app.delete_bookmark(bookmark);
+            }
+            AppEventType::GoToLocation(location, preview) => {
+                app.go_to_location(location, preview);
+            }
+            AppEventType::LoadGoToCandidates(input) => {
+                app.load_go_to_candidates(input);
+            }
+            AppEventType::CompleteLoadGoToCandidates(result) => {
+                app.complete_load_go_to_candidates(result);
+            }
+            AppEventType::StartSwitchConnection(name) => {
+                app.start_switch_connection(name);
+            }
+            AppEventType::SwitchConnection(name) => {
+                app.switch_connection(name).await;
+            }
+            AppEventType::CompleteSwitchConnection(result) => {
+                app.complete_switch_connection(result);
+            }
             AppEventType::BucketListMoveDown(object_key) => {
                 app.bucket_list_move_down(object_key);
             }
@@ -166,6 +358,12 @@ pub async fn run<B: Backend, C: Client>(
             AppEventType::ObjectListRefresh => {
                 app.object_list_refresh();
             }
+            AppEventType::ObjectListToggleSplitPreview => {
+                app.object_list_toggle_split_preview();
+            }
+            AppEventType::OpenCommander => {
+                app.open_commander();
+            }
             AppEventType::BackToBucketList => {
                 app.back_to_bucket_list();
             }
@@ -190,9 +388,19 @@ pub async fn run<B: Backend, C: Client>(
             AppEventType::CloseCurrentPage => {
                 app.close_current_page();
             }
+            AppEventType::OpenConnections => {
+                app.open_connections();
+            }
             AppEventType::OpenHelp => {
                 app.open_help();
             }
+            AppEventType::ExecuteUserEvent(user_event) => {
+                // close the palette and handle the event as if its key was pressed
+                app.close_current_page();
+                if handle_user_event(app, user_event) {
+                    return Ok(());
+                }
+            }
             AppEventType::CopyToClipboard(name, value) => {
                 app.copy_to_clipboard(name, value);
             }
@@ -211,3 +419,166 @@ pub async fn run<B: Backend, C: Client>(
         }
     }
 }
+
+// Returns true if the app should quit
+fn handle_key_input<C: Client>(app: &mut App<C>, input: KeyInput) -> bool {
+    let KeyInput {
+        events: user_events,
+        key_event,
+        count,
+    } = input;
+
+    handle_user_events! { user_events =>
+        UserEvent::Quit => {
+            // Exit regardless of status
+            app.save_session();
+            return true;
+        }
+    }
+
+    if app.loading() {
+        // Ignore key inputs while loading (except quit)
+        return false;
+    }
+
+    if app.is_showing_notification()
+        && matches!(app.page_stack.current_page(), Page::Initializing(_))
+    {
+        return true;
+    }
+
+    if matches!(app.current_notification(), Notification::Error(_)) {
+        // Clear message and cancel key input
+        app.clear_notification();
+        return false;
+    }
+
+    if matches!(
+        app.current_notification(),
+        Notification::Info(_) | Notification::Success(_) | Notification::Warn(_)
+    ) {
+        // Clear message and pass key input as is
+        app.clear_notification();
+    }
+
+    handle_user_events! { user_events =>
+        UserEvent::DumpApp => {
+            app.dump_app();
+            return false;
+        }
+        UserEvent::AddBookmark => {
+            app.add_bookmark();
+            return false;
+        }
+        UserEvent::OpenBookmarks => {
+            app.open_bookmarks();
+            return false;
+        }
+        UserEvent::OpenRecentLocations => {
+            app.open_recent_locations();
+            return false;
+        }
+        UserEvent::OpenConnections => {
+            app.open_connections();
+            return false;
+        }
+        UserEvent::OpenCommandPalette if !app.page_stack.current_page().is_text_input_active() => {
+            app.open_command_palette();
+            return false;
+        }
+        UserEvent::TogglePrefixTree if app.prefix_tree_available() => {
+            app.toggle_prefix_tree();
+            return false;
+        }
+        UserEvent::NewTab => {
+            app.open_tab();
+            return false;
+        }
+        UserEvent::CloseTab => {
+            app.close_tab();
+            return false;
+        }
+        UserEvent::NextTab => {
+            app.switch_to_next_tab();
+            return false;
+        }
+        UserEvent::PrevTab => {
+            app.switch_to_prev_tab();
+            return false;
+        }
+    }
+
+    // the count is applied only to movements, other events are handled once
+    let count = if user_events.iter().any(UserEvent::is_movement) {
+        count
+    } else {
+        1
+    };
+    if app.prefix_tree_focused() {
+        for _ in 0..count {
+            app.handle_prefix_tree_events(user_events.clone());
+        }
+        return false;
+    }
+    for _ in 0..count {
+        app.page_stack
+            .current_page_mut()
+            .handle_user_events(user_events.clone(), key_event);
+    }
+    false
+}
+
+// Handle the event as if its key was pressed, returns true if the app should quit
+fn handle_user_event<C: Client>(app: &mut App<C>, user_event: UserEvent) -> bool {
+    let key_event = app
+        .mapper
+        .find_first_key(user_event)
+        .and_then(|keys| keys.last().copied())
+        .unwrap_or_else(|| KeyEvent::from(KeyCode::Null));
+    let input = KeyInput {
+        events: vec![user_event],
+        key_event,
+        count: 1,
+    };
+    handle_key_input(app, input)
+}
+
+// Returns true if the app should quit
+fn handle_mouse_input<C: Client>(app: &mut App<C>, input: MouseInput) -> bool {
+    if app.loading() {
+        return false;
+    }
+
+    if !matches!(app.current_notification(), Notification::None) {
+        app.clear_notification();
+    }
+
+    match app.handle_mouse_input(input) {
+        Some(user_event) => handle_user_event(app, user_event),
+        None => false,
+    }
+}
+
+// Hand over the terminal to an external process (e.g. editor) while `f` is running
+async fn suspend_terminal<B: Backend, T>(
+    terminal: &mut Terminal<B>,
+    rx: &Receiver,
+    mouse: bool,
+    f: impl FnOnce() -> T,
+) -> anyhow::Result<T> {
+    rx.pause_input().await;
+    execute!(std::io::stdout(), DisableMouseCapture)?;
+    ratatui::try_restore()?;
+
+    let ret = f();
+
+    enable_raw_mode()?;
+    execute!(std::io::stdout(), EnterAlternateScreen)?;
+    if mouse {
+        execute!(std::io::stdout(), EnableMouseCapture)?;
+    }
+    terminal.clear()?;
+    rx.resume_input();
+
+    Ok(ret)
+}
@@ -0,0 +1,166 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::Result,
+    file::{load_toml, save_toml},
+    location::Location,
+    object::ObjectKey,
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub connection: Option<String>,
+    pub bucket_list: Option<ListViewSession>,
+    // in the order of the page stack
+    pub object_lists: Vec<ObjectListSession>,
+    // key of the object whose detail page was opened
+    pub object: Option<String>,
+    // prefix uris, the most recent first
+    pub recent_locations: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ListViewSession {
+    pub selected: Option<String>,
+    pub filter: String,
+    pub sort: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ObjectListSession {
+    pub bucket: String,
+    pub prefix: String,
+    pub view: ListViewSession,
+}
+
+impl ObjectListSession {
+    pub fn new(object_key: &ObjectKey, view: ListViewSession) -> Self {
+        Self {
+            bucket: object_key.bucket_name.clone(),
+            prefix: object_key.joined_object_path(false),
+            view,
+        }
+    }
+
+    pub fn object_key(&self) -> ObjectKey {
+        Location::prefix(&self.bucket, &self.prefix).object_key
+    }
+}
+
+impl Session {
+    // the location to be opened to restore the page stack
+    pub fn location(&self) -> Option<Location> {
+        let last = self.object_lists.last()?;
+        let object = self
+            .object
+            .as_ref()
+            .and_then(|key| Location::object(&last.bucket, key));
+        Some(object.unwrap_or_else(|| Location::prefix(&last.bucket, &last.prefix)))
+    }
+
+    pub fn object_list_view(&self, object_key: &ObjectKey) -> Option<&ListViewSession> {
+        self.object_lists
+            .iter()
+            .find(|s| &s.object_key() == object_key)
+            .map(|s| &s.view)
+    }
+
+    pub fn add_recent_location(&mut self, object_key: &ObjectKey, max: usize) {
+        let uri = object_key.prefix_uri();
+        self.recent_locations.retain(|u| u != &uri);
+        self.recent_locations.insert(0, uri);
+        self.recent_locations.truncate(max);
+    }
+}
+
+pub fn load_session(path: impl AsRef<Path>) -> Result<Session> {
+    load_toml(path, "session")
+}
+
+pub fn save_session(path: impl AsRef<Path>, session: &Session) -> Result<()> {
+    save_toml(path, session, "session")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::file::TestDir;
+
+    use super::*;
+
+    fn object_key(bucket: &str, paths: &[&str]) -> ObjectKey {
+        ObjectKey {
+            bucket_name: bucket.into(),
+            object_path: paths.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_session_location() {
+        let mut session = Session::default();
+        assert_eq!(session.location(), None);
+
+        session.object_lists = vec![
+            ObjectListSession::new(&object_key("bucket", &[]), ListViewSession::default()),
+            ObjectListSession::new(&object_key("bucket", &["a"]), ListViewSession::default()),
+        ];
+        assert_eq!(session.location(), Some(Location::prefix("bucket", "a/")));
+
+        session.object = Some("a/b.txt".into());
+        assert_eq!(session.location(), Location::object("bucket", "a/b.txt"));
+
+        let view = session.object_list_view(&object_key("bucket", &["a"]));
+        assert_eq!(view, Some(&ListViewSession::default()));
+        assert_eq!(
+            session.object_list_view(&object_key("bucket", &["b"])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_add_recent_location() {
+        let mut session = Session::default();
+        session.add_recent_location(&object_key("bucket", &["a"]), 2);
+        session.add_recent_location(&object_key("bucket", &["b"]), 2);
+        session.add_recent_location(&object_key("bucket", &["a"]), 2);
+        session.add_recent_location(&object_key("bucket", &[]), 2);
+        assert_eq!(
+            session.recent_locations,
+            vec!["s3://bucket/", "s3://bucket/a/"]
+        );
+    }
+
+    #[test]
+    fn test_save_and_load_session() {
+        let dir = TestDir::new("session");
+        let path = dir.join("session.toml");
+
+        assert_eq!(load_session(&path).unwrap(), Session::default());
+
+        let session = Session {
+            connection: Some("minio".into()),
+            bucket_list: Some(ListViewSession {
+                selected: Some("bucket".into()),
+                filter: "buc".into(),
+                sort: 1,
+            }),
+            object_lists: vec![ObjectListSession::new(
+                &object_key("bucket", &["a"]),
+                ListViewSession {
+                    selected: Some("b.txt".into()),
+                    filter: String::new(),
+                    sort: 0,
+                },
+            )],
+            object: Some("a/b.txt".into()),
+            recent_locations: vec!["s3://bucket/a/".into()],
+        };
+        save_session(&path, &session).unwrap();
+        assert_eq!(load_session(&path).unwrap(), session);
+        assert!(!dir.join("session.toml.tmp").exists());
+    }
+}
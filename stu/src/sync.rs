@@ -0,0 +1,624 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, Read},
+    path::{Component, Path, PathBuf},
+};
+
+use chrono::{DateTime, Local};
+use md5::{Digest, Md5};
+
+use crate::{
+    error::{AppError, Result},
+    object::ObjectSummary,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncDirection {
+    // local directory -> S3 prefix
+    Upload,
+    // S3 prefix -> local directory
+    Download,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SyncFilter {
+    Include(String),
+    Exclude(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncOptions {
+    pub local_dir: PathBuf,
+    pub direction: SyncDirection,
+    pub delete: bool,
+    filters: Vec<SyncFilter>,
+}
+
+impl SyncOptions {
+    // Parses the input like `<local dir> [--upload|--download] [--delete] [--exclude <glob>] [--include <glob>]`.
+    pub fn parse(input: &str) -> Result<SyncOptions> {
+        let args = split_args(input)?;
+        let mut iter = args.into_iter();
+
+        let mut local_dir = None;
+        let mut direction = SyncDirection::Download;
+        let mut delete = false;
+        let mut filters = Vec::new();
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--upload" => direction = SyncDirection::Upload,
+                "--download" => direction = SyncDirection::Download,
+                "--delete" => delete = true,
+                "--include" | "--exclude" => {
+                    let pattern = iter
+                        .next()
+                        .ok_or_else(|| AppError::msg(format!("{} requires a pattern", arg)))?;
+                    if arg == "--include" {
+                        filters.push(SyncFilter::Include(pattern));
+                    } else {
+                        filters.push(SyncFilter::Exclude(pattern));
+                    }
+                }
+                s if s.starts_with("--") => {
+                    return Err(AppError::msg(format!("Unknown option: {}", s)));
+                }
+                _ if local_dir.is_none() => local_dir = Some(expand_home_dir(&arg)),
+                _ => return Err(AppError::msg(format!("Unexpected argument: {}", arg))),
+            }
+        }
+
+        let local_dir = local_dir.ok_or_else(|| AppError::msg("Local directory is required"))?;
+        Ok(SyncOptions {
+            local_dir,
+            direction,
+            delete,
+            filters,
+        })
+    }
+
+    // Like `aws s3 sync`, all files are included by default and the later filters take precedence.
+    fn is_included(&self, relative_path: &str) -> bool {
+        self.filters
+            .iter()
+            .rev()
+            .find_map(|filter| match filter {
+                SyncFilter::Include(p) if glob_match(p, relative_path) => Some(true),
+                SyncFilter::Exclude(p) if glob_match(p, relative_path) => Some(false),
+                _ => None,
+            })
+            .unwrap_or(true)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LocalFile {
+    // path relative to the sync root, separated by `/`
+    pub relative_path: String,
+    pub size_byte: usize,
+    pub last_modified: DateTime<Local>,
+}
+
+pub fn list_local_files(dir: &Path) -> Result<Vec<LocalFile>> {
+    let mut files = Vec::new();
+    if dir.exists() {
+        collect_local_files(dir, "", &mut files)
+            .map_err(|e| AppError::new("Failed to list local files", e))?;
+    }
+    Ok(files)
+}
+
+fn collect_local_files(dir: &Path, prefix: &str, files: &mut Vec<LocalFile>) -> io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        let relative_path = format!("{}{}", prefix, name);
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() {
+            collect_local_files(&entry.path(), &format!("{}/", relative_path), files)?;
+        } else if metadata.is_file() {
+            files.push(LocalFile {
+                relative_path,
+                size_byte: metadata.len() as usize,
+                last_modified: metadata.modified()?.into(),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncAction {
+    New,
+    Changed,
+    Deleted,
+    Unchanged,
+}
+
+#[derive(Debug, Clone)]
+pub struct SyncPlanItem {
+    pub action: SyncAction,
+    pub relative_path: String,
+    // the size of the source, or the destination for deleted items
+    pub size_byte: usize,
+    // used to keep the timestamp of the downloaded file
+    pub last_modified: Option<DateTime<Local>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SyncPlan {
+    pub bucket: String,
+    pub prefix: String,
+    pub local_dir: PathBuf,
+    pub direction: SyncDirection,
+    pub items: Vec<SyncPlanItem>,
+    // number of the objects whose keys cannot be mapped to paths under the local directory
+    pub skipped: usize,
+}
+
+impl SyncPlan {
+    pub fn count(&self, action: SyncAction) -> usize {
+        self.items.iter().filter(|i| i.action == action).count()
+    }
+
+    pub fn operations(&self) -> Vec<SyncOperation> {
+        self.items
+            .iter()
+            .filter(|i| i.action != SyncAction::Unchanged)
+            .map(|i| SyncOperation {
+                action: i.action,
+                key: self.key(i),
+                local_path: self.local_path(i),
+                last_modified: i.last_modified,
+            })
+            .collect()
+    }
+
+    fn key(&self, item: &SyncPlanItem) -> String {
+        format!("{}{}", self.prefix, item.relative_path)
+    }
+
+    fn local_path(&self, item: &SyncPlanItem) -> PathBuf {
+        item.relative_path
+            .split('/')
+            .fold(self.local_dir.clone(), |path, name| path.join(name))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SyncOperation {
+    pub action: SyncAction,
+    pub key: String,
+    pub local_path: PathBuf,
+    pub last_modified: Option<DateTime<Local>>,
+}
+
+pub fn build_sync_plan<F>(
+    bucket: String,
+    prefix: String,
+    options: &SyncOptions,
+    local_files: Vec<LocalFile>,
+    objects: Vec<ObjectSummary>,
+    local_md5: F,
+) -> SyncPlan
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let locals: Vec<LocalFile> = local_files
+        .into_iter()
+        .filter(|f| options.is_included(&f.relative_path))
+        .collect();
+    let (remotes, unsafe_remotes): (Vec<(String, ObjectSummary)>, Vec<_>) = objects
+        .into_iter()
+        .filter_map(|o| {
+            let relative_path = o.key.strip_prefix(&prefix)?.to_string();
+            Some((relative_path, o))
+        })
+        .filter(|(path, _)| options.is_included(path))
+        .partition(|(path, _)| is_safe_relative_path(path));
+
+    let local_map: HashMap<&str, &LocalFile> = locals
+        .iter()
+        .map(|f| (f.relative_path.as_str(), f))
+        .collect();
+    let remote_map: HashMap<&str, &ObjectSummary> =
+        remotes.iter().map(|(p, o)| (p.as_str(), o)).collect();
+    let find_local = |path: &str| local_map.get(path).copied();
+    let find_remote = |path: &str| remote_map.get(path).copied();
+
+    let mut items = Vec::new();
+    match options.direction {
+        SyncDirection::Upload => {
+            for local in &locals {
+                let action = match find_remote(&local.relative_path) {
+                    None => SyncAction::New,
+                    Some(remote) => compare(
+                        local,
+                        remote,
+                        local.last_modified > remote.last_modified,
+                        &local_md5,
+                    ),
+                };
+                items.push(SyncPlanItem {
+                    action,
+                    relative_path: local.relative_path.clone(),
+                    size_byte: local.size_byte,
+                    last_modified: None,
+                });
+            }
+            if options.delete {
+                for (path, remote) in &remotes {
+                    if find_local(path).is_none() {
+                        items.push(SyncPlanItem {
+                            action: SyncAction::Deleted,
+                            relative_path: path.clone(),
+                            size_byte: remote.size_byte,
+                            last_modified: None,
+                        });
+                    }
+                }
+            }
+        }
+        SyncDirection::Download => {
+            for (path, remote) in &remotes {
+                let action = match find_local(path) {
+                    None => SyncAction::New,
+                    Some(local) => compare(
+                        local,
+                        remote,
+                        remote.last_modified > local.last_modified,
+                        &local_md5,
+                    ),
+                };
+                items.push(SyncPlanItem {
+                    action,
+                    relative_path: path.clone(),
+                    size_byte: remote.size_byte,
+                    last_modified: Some(remote.last_modified),
+                });
+            }
+            if options.delete {
+                for local in &locals {
+                    if find_remote(&local.relative_path).is_none() {
+                        items.push(SyncPlanItem {
+                            action: SyncAction::Deleted,
+                            relative_path: local.relative_path.clone(),
+                            size_byte: local.size_byte,
+                            last_modified: None,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    items.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+
+    SyncPlan {
+        bucket,
+        prefix,
+        local_dir: options.local_dir.clone(),
+        direction: options.direction,
+        items,
+        skipped: unsafe_remotes.len(),
+    }
+}
+
+// Keys like `a/../../b`, `./a`, `a//b` or `dir/` (folder markers) must not be written to the local directory,
+// otherwise a key could point outside of it.
+fn is_safe_relative_path(path: &str) -> bool {
+    path.split('/').all(|name| {
+        let mut components = Path::new(name).components();
+        matches!(
+            (components.next(), components.next()),
+            (Some(Component::Normal(c)), None) if c == name
+        )
+    })
+}
+
+fn compare<F>(
+    local: &LocalFile,
+    remote: &ObjectSummary,
+    source_is_newer: bool,
+    local_md5: &F,
+) -> SyncAction
+where
+    F: Fn(&str) -> Option<String>,
+{
+    if local.size_byte != remote.size_byte {
+        return SyncAction::Changed;
+    }
+    if !source_is_newer {
+        return SyncAction::Unchanged;
+    }
+    // the timestamp alone changes on every upload, so compare the content if the ETag is the MD5 digest
+    // (the ETag of multipart uploads is not)
+    let e_tag = remote.e_tag.trim_matches('"');
+    if is_md5_e_tag(e_tag) && local_md5(&local.relative_path).as_deref() == Some(e_tag) {
+        return SyncAction::Unchanged;
+    }
+    SyncAction::Changed
+}
+
+fn is_md5_e_tag(e_tag: &str) -> bool {
+    e_tag.len() == 32 && e_tag.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+pub fn md5_hex<P: AsRef<Path>>(path: P) -> Option<String> {
+    let mut f = File::open(path).ok()?;
+    let mut hasher = Md5::new();
+    let mut buf = vec![0; 64 * 1024];
+    loop {
+        let n = f.read(&mut buf).ok()?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Some(format!("{:x}", hasher.finalize()))
+}
+
+// `*` matches any sequence of characters (including `/`) and `?` matches any single character.
+fn glob_match(pattern: &str, s: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let s: Vec<char> = s.chars().collect();
+    let (mut pi, mut si) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while si < s.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == s[si]) {
+            pi += 1;
+            si += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            backtrack = Some((pi, si));
+            pi += 1;
+        } else if let Some((bp, bs)) = backtrack {
+            pi = bp + 1;
+            si = bs + 1;
+            backtrack = Some((bp, bs + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|c| *c == '*')
+}
+
+fn split_args(input: &str) -> Result<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut quote: Option<char> = None;
+    for c in input.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.get_or_insert_with(String::new).push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                current.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => {
+                if let Some(arg) = current.take() {
+                    args.push(arg);
+                }
+            }
+            (None, c) => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err(AppError::msg("Unterminated quote"));
+    }
+    args.extend(current);
+    Ok(args)
+}
+
+fn expand_home_dir(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()
+            .map(|home| home.join(rest))
+            .unwrap_or_else(|| PathBuf::from(path)),
+        None => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+
+    use super::*;
+
+    #[test]
+    fn test_sync_options_parse() {
+        let actual =
+            SyncOptions::parse("./data --upload --delete --exclude '*.tmp' --include \"a b/*\"")
+                .unwrap();
+        let expected = SyncOptions {
+            local_dir: PathBuf::from("./data"),
+            direction: SyncDirection::Upload,
+            delete: true,
+            filters: vec![
+                SyncFilter::Exclude("*.tmp".into()),
+                SyncFilter::Include("a b/*".into()),
+            ],
+        };
+        assert_eq!(actual, expected);
+
+        let actual = SyncOptions::parse("data").unwrap();
+        assert_eq!(actual.direction, SyncDirection::Download);
+        assert!(!actual.delete);
+
+        assert!(SyncOptions::parse("").is_err());
+        assert!(SyncOptions::parse("data --exclude").is_err());
+        assert!(SyncOptions::parse("data --force").is_err());
+        assert!(SyncOptions::parse("data 'foo").is_err());
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*.txt", "a.txt"));
+        assert!(glob_match("*.txt", "dir/a.txt"));
+        assert!(glob_match("dir/*", "dir/sub/a.txt"));
+        assert!(glob_match("a?c", "abc"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("*.txt", "a.csv"));
+        assert!(!glob_match("a?c", "ac"));
+    }
+
+    #[test]
+    fn test_is_included() {
+        let options = SyncOptions::parse("data --exclude * --include *.csv").unwrap();
+        assert!(options.is_included("a.csv"));
+        assert!(!options.is_included("a.txt"));
+
+        let options = SyncOptions::parse("data --include *.csv --exclude *").unwrap();
+        assert!(!options.is_included("a.csv"));
+    }
+
+    #[test]
+    fn test_build_sync_plan_upload() {
+        let options = SyncOptions::parse("data --upload --delete --exclude *.tmp").unwrap();
+        let locals = vec![
+            local("new.txt", 10, "2024-01-02 00:00:00"),
+            local("size.txt", 20, "2024-01-01 00:00:00"),
+            local("old.txt", 30, "2024-01-01 00:00:00"),
+            local("same-content.txt", 40, "2024-01-03 00:00:00"),
+            local("modified.txt", 50, "2024-01-03 00:00:00"),
+            local("ignored.tmp", 60, "2024-01-03 00:00:00"),
+        ];
+        let objects = vec![
+            object("dir/size.txt", 21, "2024-01-02 00:00:00", "e1"),
+            object("dir/old.txt", 30, "2024-01-02 00:00:00", "e2"),
+            object("dir/same-content.txt", 40, "2024-01-02 00:00:00", MD5),
+            object("dir/modified.txt", 50, "2024-01-02 00:00:00", "x-2"),
+            object("dir/removed.txt", 70, "2024-01-02 00:00:00", "e3"),
+            object("dir/removed.tmp", 80, "2024-01-02 00:00:00", "e4"),
+        ];
+
+        let plan = build_sync_plan(
+            "bucket".into(),
+            "dir/".into(),
+            &options,
+            locals,
+            objects,
+            md5,
+        );
+
+        let actual: Vec<(&str, SyncAction)> = plan
+            .items
+            .iter()
+            .map(|i| (i.relative_path.as_str(), i.action))
+            .collect();
+        let expected = vec![
+            ("modified.txt", SyncAction::Changed),
+            ("new.txt", SyncAction::New),
+            ("old.txt", SyncAction::Unchanged),
+            ("removed.txt", SyncAction::Deleted),
+            ("same-content.txt", SyncAction::Unchanged),
+            ("size.txt", SyncAction::Changed),
+        ];
+        assert_eq!(actual, expected);
+        assert_eq!(plan.key(&plan.items[1]), "dir/new.txt");
+    }
+
+    #[test]
+    fn test_build_sync_plan_download() {
+        let options = SyncOptions::parse("data").unwrap();
+        let locals = vec![
+            local("a/old.txt", 10, "2024-01-01 00:00:00"),
+            local("local-only.txt", 20, "2024-01-01 00:00:00"),
+        ];
+        let objects = vec![
+            object("a/old.txt", 10, "2024-01-02 00:00:00", "e1"),
+            object("a/new.txt", 30, "2024-01-02 00:00:00", "e2"),
+        ];
+
+        let plan = build_sync_plan("bucket".into(), "".into(), &options, locals, objects, md5);
+
+        let actual: Vec<(&str, SyncAction)> = plan
+            .items
+            .iter()
+            .map(|i| (i.relative_path.as_str(), i.action))
+            .collect();
+        let expected = vec![
+            ("a/new.txt", SyncAction::New),
+            ("a/old.txt", SyncAction::Changed),
+        ];
+        assert_eq!(actual, expected);
+        assert_eq!(
+            plan.local_path(&plan.items[0]),
+            PathBuf::from("data").join("a").join("new.txt")
+        );
+    }
+
+    #[test]
+    fn test_build_sync_plan_skips_unsafe_keys() {
+        let options = SyncOptions::parse("data").unwrap();
+        let objects = vec![
+            object("dir/a.txt", 10, "2024-01-02 00:00:00", "e1"),
+            object("dir/../../.bashrc", 10, "2024-01-02 00:00:00", "e2"),
+            object("dir/b/../c.txt", 10, "2024-01-02 00:00:00", "e3"),
+            object("dir/./d.txt", 10, "2024-01-02 00:00:00", "e4"),
+            object("dir/e//f.txt", 10, "2024-01-02 00:00:00", "e5"),
+            object("dir/g/", 0, "2024-01-02 00:00:00", "e6"),
+        ];
+
+        let plan = build_sync_plan(
+            "bucket".into(),
+            "dir/".into(),
+            &options,
+            vec![],
+            objects,
+            md5,
+        );
+
+        let actual: Vec<&str> = plan
+            .items
+            .iter()
+            .map(|i| i.relative_path.as_str())
+            .collect();
+        assert_eq!(actual, vec!["a.txt"]);
+        assert_eq!(plan.skipped, 5);
+        for op in plan.operations() {
+            assert!(op.local_path.starts_with("data"));
+        }
+    }
+
+    #[test]
+    fn test_is_safe_relative_path() {
+        assert!(is_safe_relative_path("a.txt"));
+        assert!(is_safe_relative_path("a/b.c/..d"));
+        assert!(!is_safe_relative_path(""));
+        assert!(!is_safe_relative_path(".."));
+        assert!(!is_safe_relative_path("a/../b"));
+        assert!(!is_safe_relative_path("./a"));
+        assert!(!is_safe_relative_path("a//b"));
+        assert!(!is_safe_relative_path("a/"));
+    }
+
+    const MD5: &str = "0123456789abcdef0123456789abcdef";
+
+    fn md5(path: &str) -> Option<String> {
+        (path == "same-content.txt").then(|| MD5.to_string())
+    }
+
+    fn local(path: &str, size_byte: usize, last_modified: &str) -> LocalFile {
+        LocalFile {
+            relative_path: path.into(),
+            size_byte,
+            last_modified: parse_datetime(last_modified),
+        }
+    }
+
+    fn object(key: &str, size_byte: usize, last_modified: &str, e_tag: &str) -> ObjectSummary {
+        ObjectSummary {
+            key: key.into(),
+            size_byte,
+            last_modified: parse_datetime(last_modified),
+            storage_class: "STANDARD".into(),
+            e_tag: format!("\"{}\"", e_tag),
+        }
+    }
+
+    fn parse_datetime(s: &str) -> DateTime<Local> {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_local_timezone(Local)
+            .unwrap()
+    }
+}
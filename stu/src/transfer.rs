@@ -0,0 +1,178 @@
+use crate::object::{DownloadObjectInfo, ObjectKey};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferMode {
+    Copy,
+    Move,
+}
+
+impl TransferMode {
+    pub fn verb(&self) -> &'static str {
+        match self {
+            TransferMode::Copy => "copy",
+            TransferMode::Move => "move",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transfer {
+    pub mode: TransferMode,
+    // the selected file or directory
+    pub source: ObjectKey,
+    pub source_is_dir: bool,
+    // the directory into which the source is copied
+    pub destination: ObjectKey,
+    // the connections of the panes, None is the connection specified at startup
+    pub source_connection: Option<String>,
+    pub destination_connection: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferObject {
+    pub source_key: String,
+    pub destination_key: String,
+    pub size_byte: usize,
+}
+
+impl Transfer {
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.is_same_connection() {
+            // the same bucket name refers to different buckets
+            return Ok(());
+        }
+        if self.source.parent().as_ref() == Some(&self.destination) {
+            return Err("The source and the destination are the same".into());
+        }
+        if self.source_is_dir && self.destination.has_prefix(&self.source) {
+            let msg = format!("Cannot {} a folder into itself", self.mode.verb());
+            return Err(msg);
+        }
+        Ok(())
+    }
+
+    pub fn is_same_connection(&self) -> bool {
+        self.source_connection == self.destination_connection
+    }
+
+    // the keys of the source objects, the destination key keeps the path relative to the parent of the source
+    pub fn objects(&self, sources: Vec<DownloadObjectInfo>) -> Vec<TransferObject> {
+        let source_parent = self
+            .source
+            .parent()
+            .map(|key| key.joined_object_path(false))
+            .unwrap_or_default();
+        let destination_prefix = self.destination.joined_object_path(false);
+        sources
+            .into_iter()
+            .filter_map(|source| {
+                let relative = source.key.strip_prefix(&source_parent)?;
+                let destination_key = format!("{}{}", destination_prefix, relative);
+                Some(TransferObject {
+                    source_key: source.key,
+                    destination_key,
+                    size_byte: source.size_byte,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let dir = |uri: &str| ObjectKey::from_prefix_uri(uri).unwrap();
+
+        let transfer = Transfer {
+            mode: TransferMode::Copy,
+            source: dir("s3://bucket/a/b"),
+            source_is_dir: true,
+            destination: dir("s3://bucket/c/"),
+            source_connection: None,
+            destination_connection: None,
+        };
+        assert_eq!(transfer.validate(), Ok(()));
+
+        let transfer = Transfer {
+            destination: dir("s3://bucket/a/"),
+            ..transfer
+        };
+        assert_eq!(
+            transfer.validate(),
+            Err("The source and the destination are the same".into())
+        );
+
+        let transfer = Transfer {
+            mode: TransferMode::Move,
+            destination: dir("s3://bucket/a/b/c/"),
+            ..transfer
+        };
+        assert_eq!(
+            transfer.validate(),
+            Err("Cannot move a folder into itself".into())
+        );
+
+        let transfer = Transfer {
+            destination: dir("s3://other/a/b/c/"),
+            ..transfer
+        };
+        assert_eq!(transfer.validate(), Ok(()));
+
+        let transfer = Transfer {
+            destination: dir("s3://bucket/a/"),
+            destination_connection: Some("minio".into()),
+            ..transfer
+        };
+        assert_eq!(transfer.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_objects() {
+        let transfer = Transfer {
+            mode: TransferMode::Copy,
+            source: ObjectKey::from_prefix_uri("s3://bucket-a/x/dir").unwrap(),
+            source_is_dir: true,
+            destination: ObjectKey::from_prefix_uri("s3://bucket-b/y/").unwrap(),
+            source_connection: None,
+            destination_connection: None,
+        };
+        let actual = transfer.objects(vec![source("x/dir/a.txt", 1), source("x/dir/sub/b.txt", 2)]);
+        let expected = vec![
+            TransferObject {
+                source_key: "x/dir/a.txt".into(),
+                destination_key: "y/dir/a.txt".into(),
+                size_byte: 1,
+            },
+            TransferObject {
+                source_key: "x/dir/sub/b.txt".into(),
+                destination_key: "y/dir/sub/b.txt".into(),
+                size_byte: 2,
+            },
+        ];
+        assert_eq!(actual, expected);
+
+        let transfer = Transfer {
+            source: ObjectKey::from_prefix_uri("s3://bucket-a/file.txt").unwrap(),
+            source_is_dir: false,
+            destination: ObjectKey::bucket("bucket-b"),
+            ..transfer
+        };
+        let actual = transfer.objects(vec![source("file.txt", 3)]);
+        let expected = vec![TransferObject {
+            source_key: "file.txt".into(),
+            destination_key: "file.txt".into(),
+            size_byte: 3,
+        }];
+        assert_eq!(actual, expected);
+    }
+
+    fn source(key: &str, size_byte: usize) -> DownloadObjectInfo {
+        DownloadObjectInfo {
+            key: key.into(),
+            size_byte,
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 pub fn prune_strings_to_fit_width(
     words_with_priority: &[(String, usize)],
     max_width: usize,
@@ -47,6 +49,38 @@ pub fn digits(n: usize) -> usize {
     c
 }
 
+// Case-insensitive subsequence match.
+// Returns the score (higher is better) and the char indices of the matched characters.
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<(usize, Vec<usize>)> {
+    let mut pattern_chars = pattern.chars().filter(|c| !c.is_whitespace()).peekable();
+    let mut score = 0;
+    let mut indices = Vec::new();
+    let mut prev: Option<char> = None;
+    for (i, c) in text.chars().enumerate() {
+        let Some(p) = pattern_chars.peek() else {
+            break;
+        };
+        if c.to_lowercase().eq(p.to_lowercase()) {
+            score += 1;
+            if indices.last().is_some_and(|&last| last + 1 == i) {
+                // consecutive match
+                score += 5;
+            }
+            if prev.map_or(true, |c| !c.is_alphanumeric()) {
+                // start of a word
+                score += 3;
+            }
+            indices.push(i);
+            pattern_chars.next();
+        }
+        prev = Some(c);
+    }
+    if pattern_chars.peek().is_some() {
+        return None;
+    }
+    Some((score, indices))
+}
+
 pub fn extension_from_file_name(filename: &str) -> String {
     filename
         .split('.')
@@ -55,6 +89,24 @@ pub fn extension_from_file_name(filename: &str) -> String {
         .unwrap_or_default()
 }
 
+// parse duration like "30s", "15m", "2h", "3d" (seconds if no unit)
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    let (n, unit) = match s.find(|c: char| !c.is_ascii_digit()) {
+        Some(i) => s.split_at(i),
+        None => (s, "s"),
+    };
+    let n: u64 = n.parse().ok()?;
+    let unit_secs = match unit.trim() {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 60 * 60 * 24,
+        _ => return None,
+    };
+    n.checked_mul(unit_secs).map(Duration::from_secs)
+}
+
 #[cfg(test)]
 mod tests {
     use rstest::rstest;
@@ -104,6 +156,39 @@ mod tests {
         assert_eq!(digits(10000), 5);
     }
 
+    #[test]
+    fn test_fuzzy_match() {
+        assert_eq!(fuzzy_match("", "Download"), Some((0, vec![])));
+        assert_eq!(fuzzy_match("dl", "Download"), Some((5, vec![0, 4])));
+        assert_eq!(fuzzy_match("DOW", "Download"), Some((16, vec![0, 1, 2])));
+        assert_eq!(fuzzy_match("so", "Download as"), None);
+
+        // consecutive and word start matches are preferred
+        let (s1, _) = fuzzy_match("copy", "Copy details").unwrap();
+        let (s2, _) = fuzzy_match("copy", "Convert to plain yaml").unwrap();
+        assert!(s1 > s2);
+    }
+
+    #[rstest]
+    #[case("30", Some(30))]
+    #[case("30s", Some(30))]
+    #[case("15m", Some(15 * 60))]
+    #[case("2h", Some(2 * 60 * 60))]
+    #[case("3d", Some(3 * 24 * 60 * 60))]
+    #[case(" 10 m ", Some(10 * 60))]
+    #[case("", None)]
+    #[case("m", None)]
+    #[case("10w", None)]
+    #[case("1.5h", None)]
+    #[case("18446744073709551615", Some(u64::MAX))]
+    #[case("18446744073709551615m", None)]
+    #[case("300000000000000000d", None)]
+    #[trace]
+    fn test_parse_duration(#[case] s: &str, #[case] expected: Option<u64>) {
+        let expected = expected.map(Duration::from_secs);
+        assert_eq!(parse_duration(s), expected);
+    }
+
     #[test]
     fn test_extension_from_file_name() {
         assert_eq!(extension_from_file_name("a.txt"), "txt");
@@ -1,29 +1,44 @@
 mod bar;
 mod common;
 mod confirm_dialog;
+mod connection_dialog;
 mod copy_detail_dialog;
 mod dialog;
 mod divider;
+mod go_to_dialog;
 mod header;
 mod image_preview;
 mod input_dialog;
 mod loading_dialog;
+mod prefix_size_dialog;
+mod prefix_tree;
+mod presigned_url_dialog;
 mod scroll;
 mod scroll_lines;
 mod scroll_list;
 mod sort_list_dialog;
 mod status;
+mod tab_bar;
 mod text_preview;
 
 pub use bar::Bar;
+pub use common::calc_centered_dialog_rect;
 pub use confirm_dialog::{ConfirmDialog, ConfirmDialogState};
+pub use connection_dialog::{ConnectionDialog, ConnectionDialogState};
 pub use copy_detail_dialog::{CopyDetailDialog, CopyDetailDialogState};
 pub use dialog::Dialog;
 pub use divider::Divider;
+pub use go_to_dialog::{GoToDialog, GoToDialogState};
 pub use header::Header;
 pub use image_preview::{ImagePicker, ImagePreview, ImagePreviewState};
 pub use input_dialog::{InputDialog, InputDialogState};
 pub use loading_dialog::LoadingDialog;
+pub use prefix_size_dialog::PrefixSizeDialog;
+pub use prefix_tree::{PrefixTree, PrefixTreeState};
+pub use presigned_url_dialog::{
+    render_presigned_url_dialog, PresignedUrlDialogAction, PresignedUrlDialogState,
+    PresignedUrlParams,
+};
 pub use scroll::ScrollBar;
 pub use scroll_lines::{ScrollLines, ScrollLinesOptions, ScrollLinesState};
 pub use scroll_list::{ScrollList, ScrollListState};
@@ -32,4 +47,8 @@ pub use sort_list_dialog::{
     ObjectListSortDialogState, ObjectListSortType,
 };
 pub use status::{Status, StatusType};
-pub use text_preview::{EncodingDialog, EncodingDialogState, TextPreview, TextPreviewState};
+pub use tab_bar::TabBar;
+pub use text_preview::{
+    build_highlighted_lines, drop_control_chars, EncodingDialog, EncodingDialogState,
+    SearchDirection, SearchOptions, TextPreview, TextPreviewState,
+};
@@ -1,4 +1,4 @@
-use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::layout::{Constraint, Layout, Margin, Position, Rect};
 
 pub fn calc_centered_dialog_rect(r: Rect, dialog_width: u16, dialog_height: u16) -> Rect {
     let vertical_pad = r.height.saturating_sub(dialog_height) / 2;
@@ -17,3 +17,18 @@ pub fn calc_centered_dialog_rect(r: Rect, dialog_width: u16, dialog_height: u16)
     ]))
     .split(vertical_layout[1])[1]
 }
+
+// Returns the index of the item at the position in a bordered dialog, each item is `item_height` lines
+pub fn dialog_item_index_at(
+    dialog_area: Rect,
+    item_height: u16,
+    item_count: usize,
+    position: Position,
+) -> Option<usize> {
+    let inner_area = dialog_area.inner(Margin::new(1, 1));
+    if !inner_area.contains(position) {
+        return None;
+    }
+    let index = ((position.y - inner_area.y) / item_height) as usize;
+    (index < item_count).then_some(index)
+}
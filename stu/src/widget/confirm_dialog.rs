@@ -2,7 +2,7 @@ use itsuki::zero_indexed_enum;
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::{Color, Stylize},
+    style::{Color, Modifier, Stylize},
     text::Line,
     widgets::{block::Title, Block, BorderType, Padding, Paragraph, StatefulWidget, WidgetRef},
 };
@@ -41,6 +41,7 @@ struct ConfirmDialogColor {
     block: Color,
     text: Color,
     selected: Color,
+    selected_modifier: Modifier,
     divider: Color,
 }
 
@@ -51,6 +52,7 @@ impl ConfirmDialogColor {
             block: theme.fg,
             text: theme.fg,
             selected: theme.dialog_selected,
+            selected_modifier: theme.list_selected_modifier,
             divider: theme.divider,
         }
     }
@@ -121,14 +123,19 @@ fn build_select_lines<'a>(
 ) -> Vec<Line<'a>> {
     let line = match state.selected {
         ActionType::Ok => Line::from(vec![
-            "OK".fg(color.selected).bold(),
+            "OK".fg(color.selected)
+                .bold()
+                .add_modifier(color.selected_modifier),
             "    ".into(),
             "Cancel".fg(color.text),
         ]),
         ActionType::Cancel => Line::from(vec![
             "OK".fg(color.text),
             "    ".into(),
-            "Cancel".fg(color.selected).bold(),
+            "Cancel"
+                .fg(color.selected)
+                .bold()
+                .add_modifier(color.selected_modifier),
         ]),
     };
     vec![line]
@@ -0,0 +1,238 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Position, Rect},
+    style::{Color, Modifier, Stylize},
+    text::Line,
+    widgets::{
+        block::Title, Block, BorderType, List, ListItem, Padding, StatefulWidget, WidgetRef,
+    },
+};
+
+use crate::{
+    color::ColorTheme,
+    widget::{
+        common::{calc_centered_dialog_rect, dialog_item_index_at},
+        Dialog,
+    },
+};
+
+#[derive(Debug, Default)]
+pub struct ConnectionDialogState {
+    names: Vec<String>,
+    selected: usize,
+    offset: usize,
+}
+
+impl ConnectionDialogState {
+    pub fn new(names: Vec<String>) -> Self {
+        Self {
+            names,
+            ..Default::default()
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.names.len() {
+            self.selected += 1;
+        } else {
+            self.selected = 0;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        if self.selected > 0 {
+            self.selected -= 1;
+        } else {
+            self.selected = self.names.len().saturating_sub(1);
+        }
+    }
+
+    pub fn select_index(&mut self, index: usize) {
+        if index < self.names.len() {
+            self.selected = index;
+        }
+    }
+
+    pub fn item_index_at(&self, area: Rect, position: Position) -> Option<usize> {
+        let show_height = connection_dialog_show_height(area, self.names.len());
+        let dialog_area = connection_dialog_area(area, show_height);
+        dialog_item_index_at(dialog_area, 1, show_height, position)
+            .map(|i| i + self.offset)
+            .filter(|i| *i < self.names.len())
+    }
+
+    pub fn selected_name(&self) -> Option<&str> {
+        self.names.get(self.selected).map(String::as_str)
+    }
+}
+
+#[derive(Debug, Default)]
+struct ConnectionDialogColor {
+    bg: Color,
+    block: Color,
+    text: Color,
+    selected: Color,
+    selected_modifier: Modifier,
+}
+
+impl ConnectionDialogColor {
+    fn new(theme: &ColorTheme) -> Self {
+        Self {
+            bg: theme.bg,
+            block: theme.fg,
+            text: theme.fg,
+            selected: theme.dialog_selected,
+            selected_modifier: theme.list_selected_modifier,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ConnectionDialog {
+    color: ConnectionDialogColor,
+}
+
+impl ConnectionDialog {
+    pub fn theme(mut self, theme: &ColorTheme) -> Self {
+        self.color = ConnectionDialogColor::new(theme);
+        self
+    }
+}
+
+impl StatefulWidget for ConnectionDialog {
+    type State = ConnectionDialogState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let show_height = connection_dialog_show_height(area, state.names.len());
+
+        if state.selected < state.offset {
+            state.offset = state.selected;
+        } else if state.selected >= state.offset + show_height {
+            state.offset = state.selected + 1 - show_height;
+        }
+
+        let list_items: Vec<ListItem> = state
+            .names
+            .iter()
+            .enumerate()
+            .skip(state.offset)
+            .take(show_height)
+            .map(|(i, name)| {
+                let item = ListItem::new(Line::raw(name));
+                if i == state.selected {
+                    item.fg(self.color.selected)
+                        .add_modifier(self.color.selected_modifier)
+                } else {
+                    item.fg(self.color.text)
+                }
+            })
+            .collect();
+
+        let area = connection_dialog_area(area, show_height);
+
+        let title = Title::from("Connections");
+        let list = List::new(list_items).block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .title(title)
+                .padding(Padding::horizontal(1))
+                .bg(self.color.bg)
+                .fg(self.color.block),
+        );
+        let dialog = Dialog::new(Box::new(list), self.color.bg);
+        dialog.render_ref(area, buf);
+    }
+}
+
+fn connection_dialog_show_height(area: Rect, len: usize) -> usize {
+    let max_height = area.height.saturating_sub(4) as usize;
+    len.min(max_height).max(1)
+}
+
+fn connection_dialog_area(area: Rect, show_height: usize) -> Rect {
+    let dialog_width = (area.width - 4).min(40);
+    let dialog_height = show_height as u16 + 2 /* border */;
+    calc_centered_dialog_rect(area, dialog_width, dialog_height)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{config::ThemeConfig, set_cells};
+
+    use super::*;
+
+    #[test]
+    fn test_render_connection_dialog() {
+        let theme = ColorTheme::default();
+        let mut state =
+            ConnectionDialogState::new(vec!["aws-prod".into(), "aws-dev".into(), "minio".into()]);
+        state.select_next();
+        let dialog = ConnectionDialog::default().theme(&theme);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 7));
+        dialog.render(buf.area, &mut buf, &mut state);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "                              ",
+            "  ╭Connections─────────────╮  ",
+            "  │ aws-prod               │  ",
+            "  │ aws-dev                │  ",
+            "  │ minio                  │  ",
+            "  ╰────────────────────────╯  ",
+            "                              ",
+        ]);
+        set_cells! { expected =>
+            // selected item
+            (4..26, [3]) => fg: Color::Cyan,
+        }
+
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_render_connection_dialog_no_color() {
+        let theme = ColorTheme::load(&ThemeConfig::default(), true);
+        let mut state = ConnectionDialogState::new(vec!["aws-prod".into(), "aws-dev".into()]);
+        state.select_next();
+        let dialog = ConnectionDialog::default().theme(&theme);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 6));
+        dialog.render(buf.area, &mut buf, &mut state);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "                              ",
+            "  ╭Connections─────────────╮  ",
+            "  │ aws-prod               │  ",
+            "  │ aws-dev                │  ",
+            "  ╰────────────────────────╯  ",
+            "                              ",
+        ]);
+        set_cells! { expected =>
+            // selected item
+            (4..26, [3]) => modifier: Modifier::REVERSED,
+        }
+
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_scroll_connection_dialog() {
+        let theme = ColorTheme::default();
+        let mut state = ConnectionDialogState::new(vec![
+            "conn-1".into(),
+            "conn-2".into(),
+            "conn-3".into(),
+            "conn-4".into(),
+        ]);
+        state.select_prev(); // wrap around to the last item
+        let dialog = ConnectionDialog::default().theme(&theme);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 6));
+        dialog.render(buf.area, &mut buf, &mut state);
+
+        assert_eq!(state.selected_name(), Some("conn-4"));
+        assert_eq!(state.offset, 2);
+    }
+}
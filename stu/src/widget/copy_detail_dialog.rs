@@ -1,7 +1,7 @@
 use itsuki::zero_indexed_enum;
 use ratatui::{
     buffer::Buffer,
-    layout::Rect,
+    layout::{Position, Rect},
     style::{Color, Modifier, Stylize},
     text::Line,
     widgets::{
@@ -12,14 +12,20 @@ use ratatui::{
 use crate::{
     color::ColorTheme,
     object::{BucketItem, FileDetail, FileVersion, ObjectItem},
-    widget::{common::calc_centered_dialog_rect, Dialog},
+    widget::{
+        common::{calc_centered_dialog_rect, dialog_item_index_at},
+        Dialog,
+    },
 };
 
+const PRESIGNED_URL_VALUE: &str = "(Select to generate)";
+
 #[derive(Default)]
 #[zero_indexed_enum]
 enum BucketListItemType {
     #[default]
     Name,
+    Region,
     S3Uri,
     Arn,
     ObjectUrl,
@@ -29,6 +35,7 @@ impl BucketListItemType {
     fn name_and_value(&self, bucket_item: &BucketItem) -> (String, String) {
         let (name, value) = match self {
             Self::Name => ("Name", &bucket_item.name),
+            Self::Region => ("Region", &bucket_item.region),
             Self::S3Uri => ("S3 URI", &bucket_item.s3_uri),
             Self::Arn => ("ARN", &bucket_item.arn),
             Self::ObjectUrl => ("Object URL", &bucket_item.object_url),
@@ -47,6 +54,7 @@ enum ObjectListFileItemType {
     Arn,
     ObjectUrl,
     Etag,
+    PresignedUrl,
 }
 
 impl ObjectListFileItemType {
@@ -68,6 +76,7 @@ impl ObjectListFileItemType {
                 Self::Arn => ("ARN", arn),
                 Self::ObjectUrl => ("Object URL", object_url),
                 Self::Etag => ("ETag", e_tag),
+                Self::PresignedUrl => ("Presigned URL", &PRESIGNED_URL_VALUE.to_string()),
             },
         };
         (name.into(), value.into())
@@ -115,6 +124,7 @@ enum ObjectDetailItemType {
     Arn,
     ObjectUrl,
     Etag,
+    PresignedUrl,
 }
 
 impl ObjectDetailItemType {
@@ -126,6 +136,7 @@ impl ObjectDetailItemType {
             Self::Arn => ("ARN", &file_detail.arn),
             Self::ObjectUrl => ("Object URL", &file_detail.object_url),
             Self::Etag => ("ETag", &file_detail.e_tag),
+            Self::PresignedUrl => ("Presigned URL", &PRESIGNED_URL_VALUE.to_string()),
         };
         (name.into(), value.into())
     }
@@ -141,6 +152,7 @@ enum ObjectVersionItemType {
     Arn,
     ObjectUrl,
     Etag,
+    PresignedUrl,
 }
 
 impl ObjectVersionItemType {
@@ -156,6 +168,7 @@ impl ObjectVersionItemType {
             Self::Arn => ("ARN", &file_detail.arn),
             Self::ObjectUrl => ("Object URL", &file_version.object_url(file_detail)),
             Self::Etag => ("ETag", &file_version.e_tag),
+            Self::PresignedUrl => ("Presigned URL", &PRESIGNED_URL_VALUE.to_string()),
         };
         (name.into(), value.into())
     }
@@ -213,6 +226,27 @@ impl CopyDetailDialogState {
         }
     }
 
+    pub fn select_index(&mut self, index: usize) {
+        fn select<T: TryFrom<usize>>(selected: &mut T, index: usize) {
+            if let Ok(s) = T::try_from(index) {
+                *selected = s;
+            }
+        }
+        match self {
+            Self::BucketList(selected, _) => select(selected, index),
+            Self::ObjectDetail(selected, _) => select(selected, index),
+            Self::ObjectVersion(selected, _, _) => select(selected, index),
+            Self::ObjectListFile(selected, _) => select(selected, index),
+            Self::ObjectListDir(selected, _) => select(selected, index),
+        }
+    }
+
+    pub fn item_index_at(&self, area: Rect, position: Position) -> Option<usize> {
+        let len = self.item_type_len();
+        let dialog_area = copy_detail_dialog_area(area, len);
+        dialog_item_index_at(dialog_area, 2, len, position)
+    }
+
     fn selected_value(&self) -> usize {
         match self {
             Self::BucketList(selected, _) => selected.val(),
@@ -235,6 +269,15 @@ impl CopyDetailDialogState {
         }
     }
 
+    pub fn is_presigned_url_selected(&self) -> bool {
+        match self {
+            Self::ObjectDetail(selected, _) => *selected == ObjectDetailItemType::PresignedUrl,
+            Self::ObjectVersion(selected, _, _) => *selected == ObjectVersionItemType::PresignedUrl,
+            Self::ObjectListFile(selected, _) => *selected == ObjectListFileItemType::PresignedUrl,
+            Self::BucketList(_, _) | Self::ObjectListDir(_, _) => false,
+        }
+    }
+
     fn name_and_value_vec(&self) -> Vec<(String, String)> {
         match self {
             Self::BucketList(_, bucket_item) => BucketListItemType::vars_array()
@@ -279,6 +322,7 @@ struct CopyDetailDialogColor {
     block: Color,
     text: Color,
     selected: Color,
+    selected_modifier: Modifier,
 }
 
 impl CopyDetailDialogColor {
@@ -288,6 +332,7 @@ impl CopyDetailDialogColor {
             block: theme.fg,
             text: theme.fg,
             selected: theme.dialog_selected,
+            selected_modifier: theme.list_selected_modifier,
         }
     }
 }
@@ -316,9 +361,7 @@ impl StatefulWidget for CopyDetailDialog {
             .map(|(i, (name, value))| self.build_list_item(i, selected, (name, value)))
             .collect();
 
-        let dialog_width = (area.width - 4).min(80);
-        let dialog_height = state.item_type_len() * 2 + 2 /* border */;
-        let area = calc_centered_dialog_rect(area, dialog_width, dialog_height as u16);
+        let area = copy_detail_dialog_area(area, state.item_type_len());
 
         let title = Title::from("Copy");
         let list = List::new(list_items).block(
@@ -334,6 +377,12 @@ impl StatefulWidget for CopyDetailDialog {
     }
 }
 
+fn copy_detail_dialog_area(area: Rect, item_type_len: usize) -> Rect {
+    let dialog_width = (area.width - 4).min(80);
+    let dialog_height = item_type_len * 2 + 2 /* border */;
+    calc_centered_dialog_rect(area, dialog_width, dialog_height as u16)
+}
+
 impl CopyDetailDialog {
     fn build_list_item<'a>(
         &self,
@@ -347,6 +396,7 @@ impl CopyDetailDialog {
         ]);
         if i == selected {
             item.fg(self.color.selected)
+                .add_modifier(self.color.selected_modifier)
         } else {
             item.fg(self.color.text)
         }
@@ -373,7 +423,6 @@ mod tests {
 
         #[rustfmt::skip]
         let mut expected = Buffer::with_lines([
-            "                                        ",
             "                                        ",
             "                                        ",
             "  ╭Copy──────────────────────────────╮  ",
@@ -389,26 +438,29 @@ mod tests {
             "  │   https://bucket-1.s3.ap-northea │  ",
             "  │ ETag:                            │  ",
             "  │   bef684de-a260-48a4-8178-8a535e │  ",
+            "  │ Presigned URL:                   │  ",
+            "  │   (Select to generate)           │  ",
             "  ╰──────────────────────────────────╯  ",
             "                                        ",
             "                                        ",
-            "                                        ",
         ]);
         set_cells! { expected =>
             // "Name" is bold
-            (4..9, [4]) => modifier: Modifier::BOLD,
+            (4..9, [3]) => modifier: Modifier::BOLD,
             // "Key" is bold
-            (4..8, [6]) => modifier: Modifier::BOLD,
+            (4..8, [5]) => modifier: Modifier::BOLD,
             // "S3 URI" is bold
-            (4..11, [8]) => modifier: Modifier::BOLD,
+            (4..11, [7]) => modifier: Modifier::BOLD,
             // "ARN" is bold
-            (4..8, [10]) => modifier: Modifier::BOLD,
+            (4..8, [9]) => modifier: Modifier::BOLD,
             // "Object URL" is bold
-            (4..15, [12]) => modifier: Modifier::BOLD,
+            (4..15, [11]) => modifier: Modifier::BOLD,
             // "ETag" is bold
-            (4..9, [14]) => modifier: Modifier::BOLD,
+            (4..9, [13]) => modifier: Modifier::BOLD,
+            // "Presigned URL" is bold
+            (4..18, [15]) => modifier: Modifier::BOLD,
             // selected item
-            (4..36, [4, 5]) => fg: Color::Cyan,
+            (4..36, [3, 4]) => fg: Color::Cyan,
         }
 
         assert_eq!(buf, expected);
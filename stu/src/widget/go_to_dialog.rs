@@ -0,0 +1,173 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::KeyEvent,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Stylize},
+    text::Line,
+    widgets::{block::Title, Block, BorderType, Padding, Paragraph, StatefulWidget, WidgetRef},
+};
+
+use crate::{
+    color::ColorTheme,
+    widget::{common::calc_centered_dialog_rect, Dialog, InputDialog, InputDialogState},
+};
+
+const MAX_WIDTH: u16 = 60;
+const MAX_CANDIDATES: usize = 8;
+
+#[derive(Debug, Default)]
+pub struct GoToDialogState {
+    input_state: InputDialogState,
+    candidates: Vec<String>,
+}
+
+impl GoToDialogState {
+    pub fn input(&self) -> &str {
+        self.input_state.input()
+    }
+
+    pub fn cursor(&self) -> (u16, u16) {
+        self.input_state.cursor()
+    }
+
+    pub fn set_completion(&mut self, input: String, candidates: Vec<String>) {
+        self.input_state.set_input(input);
+        self.candidates = candidates;
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent) {
+        self.input_state.handle_key_event(key);
+        self.candidates.clear();
+    }
+}
+
+#[derive(Debug, Default)]
+struct GoToDialogColor {
+    bg: Color,
+    block: Color,
+    text: Color,
+}
+
+impl GoToDialogColor {
+    fn new(theme: &ColorTheme) -> GoToDialogColor {
+        GoToDialogColor {
+            bg: theme.bg,
+            block: theme.fg,
+            text: theme.fg,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GoToDialog {
+    input_dialog: InputDialog,
+    color: GoToDialogColor,
+}
+
+impl Default for GoToDialog {
+    fn default() -> Self {
+        Self {
+            input_dialog: InputDialog::default().title("Go to").max_width(MAX_WIDTH),
+            color: GoToDialogColor::default(),
+        }
+    }
+}
+
+impl GoToDialog {
+    pub fn theme(mut self, theme: &ColorTheme) -> Self {
+        self.input_dialog = self.input_dialog.theme(theme);
+        self.color = GoToDialogColor::new(theme);
+        self
+    }
+}
+
+impl StatefulWidget for GoToDialog {
+    type State = GoToDialogState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        self.input_dialog.render(area, buf, &mut state.input_state);
+
+        if state.candidates.is_empty() {
+            return;
+        }
+
+        let dialog_width = (area.width - 4).min(MAX_WIDTH);
+        let lines: Vec<Line> = if state.candidates.len() > MAX_CANDIDATES {
+            let rest = state.candidates.len() - (MAX_CANDIDATES - 1);
+            state.candidates[..MAX_CANDIDATES - 1]
+                .iter()
+                .map(|c| Line::raw(c.as_str()))
+                .chain([Line::raw(format!("... ({} more)", rest))])
+                .collect()
+        } else {
+            state
+                .candidates
+                .iter()
+                .map(|c| Line::raw(c.as_str()))
+                .collect()
+        };
+        let candidates_height = lines.len() as u16 + 2;
+
+        // place the candidates just below the input dialog
+        let input_area = calc_centered_dialog_rect(area, dialog_width, 3);
+        let [_, candidates_area, _] = Layout::vertical([
+            Constraint::Length(input_area.bottom() - area.top()),
+            Constraint::Length(candidates_height),
+            Constraint::Min(0),
+        ])
+        .areas(area);
+        let candidates_area = Rect {
+            x: input_area.x,
+            width: input_area.width,
+            ..candidates_area
+        };
+
+        let title = Title::from("Candidates");
+        let content = Paragraph::new(lines).fg(self.color.text).block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .title(title)
+                .bg(self.color.bg)
+                .fg(self.color.block)
+                .padding(Padding::horizontal(1)),
+        );
+        let dialog = Dialog::new(Box::new(content), self.color.bg);
+        dialog.render_ref(candidates_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_go_to_dialog_with_candidates() {
+        let theme = ColorTheme::default();
+        let mut state = GoToDialogState::default();
+        state.set_completion(
+            "s3://bucket/fo".into(),
+            vec!["foo/".into(), "foobar.txt".into()],
+        );
+        let dialog = GoToDialog::default().theme(&theme);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 40, 10));
+        dialog.render(buf.area, &mut buf, &mut state);
+
+        #[rustfmt::skip]
+        let expected = Buffer::with_lines([
+            "                                        ",
+            "                                        ",
+            "                                        ",
+            "  ╭Go to─────────────────────────────╮  ",
+            "  │ s3://bucket/fo                   │  ",
+            "  ╰──────────────────────────────────╯  ",
+            "  ╭Candidates────────────────────────╮  ",
+            "  │ foo/                             │  ",
+            "  │ foobar.txt                       │  ",
+            "  ╰──────────────────────────────────╯  ",
+        ]);
+
+        assert_eq!(buf, expected);
+        assert_eq!(state.cursor(), (18, 4));
+    }
+}
@@ -1,6 +1,6 @@
 use ratatui::{
     buffer::Buffer,
-    layout::{Margin, Rect},
+    layout::{Margin, Position, Rect},
     style::{Color, Stylize},
     widgets::{Block, Padding, Paragraph, Widget},
 };
@@ -52,10 +52,39 @@ impl Header {
     const DELIMITER: &'static str = " / ";
     const ELLIPSIS: &'static str = "...";
 
-    fn render_header(self, area: Rect, buf: &mut Buffer) {
+    // Returns the index of the breadcrumb segment at the position
+    pub fn segment_index_at(&self, area: Rect, position: Position) -> Option<usize> {
+        let text_area = Self::text_area(area);
+        if !text_area.contains(position) {
+            return None;
+        }
+        let delimiter_width = console::measure_text_width(Self::DELIMITER) as u16;
+        let mut x = text_area.x;
+        for (i, (segment, index)) in self
+            .build_segments(text_area.width as usize)
+            .into_iter()
+            .enumerate()
+        {
+            if i > 0 {
+                x += delimiter_width;
+            }
+            let width = console::measure_text_width(&segment) as u16;
+            if (x..x + width).contains(&position.x) {
+                return index;
+            }
+            x += width;
+        }
+        None
+    }
+
+    fn text_area(area: Rect) -> Rect {
         let inner_area = area.inner(Margin::new(1, 1));
+        inner_area.inner(Margin::new(1, 0))
+    }
+
+    fn render_header(self, area: Rect, buf: &mut Buffer) {
         let pad = Padding::horizontal(1);
-        let max_width = (inner_area.width - pad.left - pad.right) as usize;
+        let max_width = Self::text_area(area).width as usize;
 
         let block_color = self.color.block;
         let text_color = self.color.text;
@@ -71,14 +100,29 @@ impl Header {
         paragraph.render(area, buf);
     }
 
-    fn build_current_key_str(self, max_width: usize) -> String {
-        if self.breadcrumb.is_empty() {
-            return "".to_string();
+    fn build_current_key_str(&self, max_width: usize) -> String {
+        self.build_segments(max_width)
+            .into_iter()
+            .map(|(segment, _)| segment)
+            .collect::<Vec<String>>()
+            .join(Self::DELIMITER)
+    }
+
+    // Returns the segments to show with their indices in the breadcrumb (the ellipsis has no index)
+    fn build_segments(&self, max_width: usize) -> Vec<(String, Option<usize>)> {
+        let segments: Vec<(String, Option<usize>)> = self
+            .breadcrumb
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (p.clone(), Some(i)))
+            .collect();
+        if segments.is_empty() {
+            return segments;
         }
 
         let current_key = self.breadcrumb.join(Self::DELIMITER);
         if console::measure_text_width(&current_key) <= max_width {
-            return current_key;
+            return segments;
         }
 
         //   string: <bucket> / ... / s1 / s2 / s3 / s4 / s5
@@ -86,16 +130,23 @@ impl Header {
         let bl = self.breadcrumb.len();
         let mut bs: Vec<(String, usize)> = self
             .breadcrumb
-            .into_iter()
+            .iter()
             .enumerate()
-            .map(|(i, p)| (p, bl - i - 1))
+            .map(|(i, p)| (p.clone(), bl - i - 1))
             .collect();
         bs.insert(1, (Self::ELLIPSIS.to_string(), 0));
         bs.first_mut().unwrap().1 = 1;
         bs.last_mut().unwrap().1 = 0;
 
         let keys = prune_strings_to_fit_width(&bs, max_width, Self::DELIMITER);
-        keys.join(Self::DELIMITER)
+
+        // the kept keys are in the original order
+        let mut candidates = segments;
+        candidates.insert(1, (Self::ELLIPSIS.to_string(), None));
+        let mut candidates = candidates.into_iter();
+        keys.into_iter()
+            .filter_map(|key| candidates.find(|(segment, _)| *segment == key))
+            .collect()
     }
 }
 
@@ -143,6 +194,27 @@ mod tests {
         assert_eq!(buf, expected);
     }
 
+    #[test]
+    fn test_segment_index_at() {
+        let breadcrumb = ["bucket", "key01", "key02a", "key03"]
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect();
+        let header = Header::new(breadcrumb);
+        let area = Rect::new(0, 0, 30 + 4, 3);
+
+        // "│ bucket / ... / key02a / key03  │"
+        let index_at = |x| header.segment_index_at(area, Position::new(x, 1));
+        assert_eq!(index_at(2), Some(0));
+        assert_eq!(index_at(7), Some(0));
+        assert_eq!(index_at(9), None); // delimiter
+        assert_eq!(index_at(12), None); // ellipsis
+        assert_eq!(index_at(17), Some(2));
+        assert_eq!(index_at(30), Some(3));
+        assert_eq!(index_at(31), None);
+        assert_eq!(header.segment_index_at(area, Position::new(2, 0)), None);
+    }
+
     #[test]
     fn test_render_header_empty() {
         let theme = ColorTheme::default();
@@ -35,6 +35,10 @@ impl InputDialogState {
         self.input.reset();
     }
 
+    pub fn set_input(&mut self, input: String) {
+        self.input = Input::new(input);
+    }
+
     pub fn cursor(&self) -> (u16, u16) {
         self.cursor
     }
@@ -64,14 +68,14 @@ impl InputDialogColor {
 
 #[derive(Debug, Default)]
 pub struct InputDialog {
-    title: &'static str,
+    title: String,
     max_width: Option<u16>,
     color: InputDialogColor,
 }
 
 impl InputDialog {
-    pub fn title(mut self, title: &'static str) -> Self {
-        self.title = title;
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
         self
     }
 
@@ -0,0 +1,142 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Stylize},
+    text::Line,
+    widgets::{block::Title, Block, BorderType, Padding, Paragraph, Widget, WidgetRef},
+};
+
+use crate::{
+    color::ColorTheme,
+    format::{format_object_count, format_size_byte},
+    object::PrefixSize,
+    widget::{common::calc_centered_dialog_rect, Dialog},
+};
+
+#[derive(Debug, Default)]
+struct PrefixSizeDialogColor {
+    bg: Color,
+    block: Color,
+    text: Color,
+    info: Color,
+}
+
+impl PrefixSizeDialogColor {
+    fn new(theme: &ColorTheme) -> Self {
+        Self {
+            bg: theme.bg,
+            block: theme.fg,
+            text: theme.fg,
+            info: theme.status_info,
+        }
+    }
+}
+
+pub struct PrefixSizeDialog<'a> {
+    name: &'a str,
+    size: Option<&'a PrefixSize>,
+    color: PrefixSizeDialogColor,
+}
+
+impl<'a> PrefixSizeDialog<'a> {
+    pub fn new(name: &'a str, size: Option<&'a PrefixSize>) -> Self {
+        Self {
+            name,
+            size,
+            color: PrefixSizeDialogColor::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: &ColorTheme) -> Self {
+        self.color = PrefixSizeDialogColor::new(theme);
+        self
+    }
+}
+
+impl Widget for PrefixSizeDialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let default_size = PrefixSize::default();
+        let size = self.size.unwrap_or(&default_size);
+
+        let mut lines = vec![
+            Line::from("Total:".add_modifier(Modifier::BOLD)),
+            Line::from(format!(
+                "  {} ({})",
+                format_size_byte(size.size_byte),
+                format_object_count(size.object_count)
+            )),
+        ];
+        if !size.storage_classes.is_empty() {
+            lines.push(Line::from("Storage class:".add_modifier(Modifier::BOLD)));
+            for (storage_class, (size_byte, count)) in &size.storage_classes {
+                lines.push(Line::from(format!(
+                    "  {}: {} ({})",
+                    storage_class,
+                    format_size_byte(*size_byte),
+                    format_object_count(*count)
+                )));
+            }
+        }
+        if !size.completed {
+            lines.push(Line::from("Calculating...".fg(self.color.info)));
+        }
+
+        let dialog_width = (area.width - 4).min(50);
+        let dialog_height = lines.len() as u16 + 2 /* border */;
+        let area = calc_centered_dialog_rect(area, dialog_width, dialog_height);
+
+        let title = Title::from(format!("Size of {}/", self.name));
+        let paragraph = Paragraph::new(lines).fg(self.color.text).block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .title(title)
+                .padding(Padding::horizontal(1))
+                .bg(self.color.bg)
+                .fg(self.color.block),
+        );
+        let dialog = Dialog::new(Box::new(paragraph), self.color.bg);
+        dialog.render_ref(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::set_cells;
+
+    use super::*;
+
+    #[test]
+    fn test_render_prefix_size_dialog() {
+        let theme = ColorTheme::default();
+        let mut size = PrefixSize::default();
+        size.add("STANDARD", 2048);
+        size.add("STANDARD", 1024);
+        size.add("GLACIER", 100);
+        size.completed = true;
+        let dialog = PrefixSizeDialog::new("dir", Some(&size)).theme(&theme);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 40, 9));
+        dialog.render(buf.area, &mut buf);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "                                        ",
+            "  ╭Size of dir/──────────────────────╮  ",
+            "  │ Total:                           │  ",
+            "  │   3.10 KiB (3 objects)           │  ",
+            "  │ Storage class:                   │  ",
+            "  │   GLACIER: 100 B (1 object)      │  ",
+            "  │   STANDARD: 3 KiB (2 objects)    │  ",
+            "  ╰──────────────────────────────────╯  ",
+            "                                        ",
+        ]);
+        set_cells! { expected =>
+            // "Total:" is bold
+            (4..10, [2]) => modifier: Modifier::BOLD,
+            // "Storage class:" is bold
+            (4..18, [4]) => modifier: Modifier::BOLD,
+        }
+
+        assert_eq!(buf, expected);
+    }
+}
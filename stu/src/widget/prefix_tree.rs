@@ -0,0 +1,433 @@
+use std::collections::HashSet;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{ListItem, StatefulWidget},
+};
+
+use crate::{
+    color::ColorTheme,
+    keys::UserEvent,
+    mouse::{handle_list_mouse_input, MouseInput},
+    object::ObjectKey,
+    widget::{ScrollList, ScrollListState},
+};
+
+const ELLIPSIS: &str = "...";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixTreeRow {
+    pub key: ObjectKey,
+    depth: usize,
+    expanded: bool,
+    // None if the objects under the prefix have not been loaded yet
+    has_children: Option<bool>,
+}
+
+impl PrefixTreeRow {
+    fn name(&self) -> String {
+        match self.key.object_path.last() {
+            Some(name) => format!("{}/", name),
+            None => self.key.bucket_name.clone(),
+        }
+    }
+
+    fn marker(&self) -> &'static str {
+        match (self.expanded, self.has_children) {
+            (true, _) => "▾",
+            (false, Some(false)) => " ",
+            (false, _) => "▸",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PrefixTreeState {
+    visible: bool,
+    focused: bool,
+    rows: Vec<PrefixTreeRow>,
+    expanded: HashSet<ObjectKey>,
+    // the directory of the current page, the selection follows it when it changes
+    current: Option<ObjectKey>,
+    list_state: ScrollListState,
+}
+
+impl PrefixTreeState {
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.visible && self.focused
+    }
+
+    // show and focus the tree first, then hide it
+    pub fn toggle(&mut self) {
+        if !self.visible {
+            self.visible = true;
+            self.focused = true;
+        } else if !self.focused {
+            self.focused = true;
+        } else {
+            self.visible = false;
+            self.focused = false;
+        }
+    }
+
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    // rebuild the rows from the bucket names and the directory names of the loaded prefixes
+    pub fn update(
+        &mut self,
+        bucket_names: Vec<String>,
+        current: Option<&ObjectKey>,
+        dir_names: impl Fn(&ObjectKey) -> Option<Vec<String>>,
+    ) {
+        let follow = self.current.as_ref() != current;
+        if follow {
+            self.current = current.cloned();
+            if let Some(key) = current {
+                // open the ancestors so that the current directory is shown
+                let mut hierarchy = key.hierarchy();
+                hierarchy.pop();
+                self.expanded.extend(hierarchy);
+            }
+        }
+
+        let selected_key = self.selected_row().map(|row| row.key.clone());
+
+        let mut rows = Vec::new();
+        for name in bucket_names {
+            push_rows(
+                &mut rows,
+                ObjectKey::bucket(name),
+                0,
+                &self.expanded,
+                &dir_names,
+            );
+        }
+        self.rows = rows;
+        self.list_state.set_total(self.rows.len());
+
+        let target = if follow && self.current.is_some() {
+            self.current.as_ref()
+        } else {
+            selected_key.as_ref()
+        };
+        if let Some(i) = target.and_then(|key| self.index_of(key)) {
+            self.list_state.select_index(i);
+        }
+    }
+
+    pub fn selected_row(&self) -> Option<&PrefixTreeRow> {
+        self.rows.get(self.list_state.selected)
+    }
+
+    pub fn select_next(&mut self) {
+        self.list_state.select_next();
+    }
+
+    pub fn select_prev(&mut self) {
+        self.list_state.select_prev();
+    }
+
+    pub fn select_first(&mut self) {
+        self.list_state.select_first();
+    }
+
+    pub fn select_last(&mut self) {
+        self.list_state.select_last();
+    }
+
+    pub fn select_next_page(&mut self) {
+        self.list_state.select_next_page();
+    }
+
+    pub fn select_prev_page(&mut self) {
+        self.list_state.select_prev_page();
+    }
+
+    // returns the key of the selected prefix if its objects need to be loaded to expand it
+    pub fn expand_selected(&mut self) -> Option<ObjectKey> {
+        let row = self.selected_row()?;
+        let key = row.key.clone();
+        match row.has_children {
+            None => Some(key),
+            Some(true) => {
+                self.expanded.insert(key);
+                None
+            }
+            Some(false) => None,
+        }
+    }
+
+    pub fn expand(&mut self, key: ObjectKey) {
+        self.expanded.insert(key);
+    }
+
+    // collapse the selected prefix, or select its parent if it is not expanded
+    pub fn collapse_selected(&mut self) {
+        let Some(row) = self.selected_row() else {
+            return;
+        };
+        if row.expanded {
+            let key = row.key.clone();
+            self.expanded.remove(&key);
+        } else if let Some(i) = row.key.parent().and_then(|key| self.index_of(&key)) {
+            self.list_state.select_index(i);
+        }
+    }
+
+    pub fn handle_mouse_input(&mut self, input: MouseInput) -> Option<UserEvent> {
+        handle_list_mouse_input(
+            input,
+            &mut self.list_state,
+            Some(UserEvent::PrefixTreeSelect),
+            UserEvent::PrefixTreeDown,
+            UserEvent::PrefixTreeUp,
+        )
+    }
+
+    fn index_of(&self, key: &ObjectKey) -> Option<usize> {
+        self.rows.iter().position(|row| row.key == *key)
+    }
+}
+
+fn push_rows(
+    rows: &mut Vec<PrefixTreeRow>,
+    key: ObjectKey,
+    depth: usize,
+    expanded: &HashSet<ObjectKey>,
+    dir_names: &impl Fn(&ObjectKey) -> Option<Vec<String>>,
+) {
+    let children = dir_names(&key);
+    let is_expanded = children.is_some() && expanded.contains(&key);
+    rows.push(PrefixTreeRow {
+        key: key.clone(),
+        depth,
+        expanded: is_expanded,
+        has_children: children.as_ref().map(|names| !names.is_empty()),
+    });
+    if let (true, Some(names)) = (is_expanded, children) {
+        for name in names {
+            push_rows(rows, key.child(name), depth + 1, expanded, dir_names);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PrefixTree<'a> {
+    theme: &'a ColorTheme,
+}
+
+impl<'a> PrefixTree<'a> {
+    pub fn new(theme: &'a ColorTheme) -> Self {
+        Self { theme }
+    }
+}
+
+impl StatefulWidget for PrefixTree<'_> {
+    type State = PrefixTreeState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let show_item_count = (area.height as usize).saturating_sub(2 /* border */);
+        state.list_state.fit_height(show_item_count);
+
+        let offset = state.list_state.offset;
+        let selected = state.list_state.selected;
+        let items = state
+            .rows
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(show_item_count)
+            .map(|(i, row)| {
+                let current = state.current.as_ref() == Some(&row.key);
+                build_list_item(
+                    row,
+                    i == selected,
+                    state.focused,
+                    current,
+                    area.width,
+                    self.theme,
+                )
+            })
+            .collect();
+
+        let list = ScrollList::new(items).theme(self.theme);
+        StatefulWidget::render(list, area, buf, &mut state.list_state);
+    }
+}
+
+fn build_list_item<'a>(
+    row: &PrefixTreeRow,
+    selected: bool,
+    focused: bool,
+    current: bool,
+    width: u16,
+    theme: &ColorTheme,
+) -> ListItem<'a> {
+    let text = format!("{}{} {}", "  ".repeat(row.depth), row.marker(), row.name());
+    let text_w = (width as usize).saturating_sub(2 /* spaces */ + 4 /* border + pad */);
+    let pad_text =
+        console::pad_str(&text, text_w, console::Alignment::Left, Some(ELLIPSIS)).to_string();
+    let line = Line::from(vec![" ".into(), pad_text.into(), " ".into()]);
+
+    let mut style = if selected && focused {
+        Style::default()
+            .bg(theme.list_selected_bg)
+            .fg(theme.list_selected_fg)
+            .add_modifier(theme.list_selected_modifier)
+    } else if selected {
+        Style::default()
+            .bg(theme.list_selected_inactive_bg)
+            .fg(theme.list_selected_inactive_fg)
+            .add_modifier(theme.list_selected_modifier)
+    } else {
+        Style::default()
+    };
+    if current {
+        style = style.add_modifier(Modifier::BOLD);
+    }
+    ListItem::new(line).style(style)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use ratatui::{backend::TestBackend, style::Color, Terminal};
+
+    use crate::set_cells;
+
+    use super::*;
+
+    #[test]
+    fn test_update_follows_current_dir() {
+        let dirs = dirs();
+        let mut state = PrefixTreeState::default();
+
+        let current = key("bucket-1", &["a", "b"]);
+        state.update(bucket_names(), Some(&current), |key| dirs.get(key).cloned());
+
+        assert_eq!(
+            row_texts(&state),
+            vec!["▾ bucket-1", "  ▾ a/", "    ▸ b/", "    c/", "▸ bucket-2"]
+        );
+        assert_eq!(state.selected_row().unwrap().key, current);
+
+        // the selection is kept while the current directory is not changed
+        state.select_first();
+        state.update(bucket_names(), Some(&current), |key| dirs.get(key).cloned());
+        assert_eq!(state.selected_row().unwrap().key, key("bucket-1", &[]));
+    }
+
+    #[test]
+    fn test_expand_and_collapse() {
+        let dirs = dirs();
+        let mut state = PrefixTreeState::default();
+        // the height is known after rendered
+        state.list_state.fit_height(10);
+        state.update(bucket_names(), None, |key| dirs.get(key).cloned());
+        assert_eq!(row_texts(&state), vec!["▸ bucket-1", "▸ bucket-2"]);
+
+        // loaded prefix is expanded immediately
+        assert_eq!(state.expand_selected(), None);
+        state.update(bucket_names(), None, |key| dirs.get(key).cloned());
+        assert_eq!(
+            row_texts(&state),
+            vec!["▾ bucket-1", "  ▸ a/", "    c/", "▸ bucket-2"]
+        );
+
+        state.select_next();
+        assert_eq!(state.expand_selected(), None);
+        state.update(bucket_names(), None, |key| dirs.get(key).cloned());
+        assert_eq!(
+            row_texts(&state),
+            vec!["▾ bucket-1", "  ▾ a/", "    ▸ b/", "    c/", "▸ bucket-2"]
+        );
+
+        // not loaded prefix is returned to be loaded
+        state.select_last();
+        assert_eq!(state.expand_selected(), Some(key("bucket-2", &[])));
+
+        // a collapsed prefix selects its parent, and then the parent is collapsed
+        state.select_first();
+        state.select_next();
+        state.select_next();
+        state.collapse_selected();
+        assert_eq!(state.selected_row().unwrap().key, key("bucket-1", &["a"]));
+        state.collapse_selected();
+        state.update(bucket_names(), None, |key| dirs.get(key).cloned());
+        assert_eq!(
+            row_texts(&state),
+            vec!["▾ bucket-1", "  ▸ a/", "    c/", "▸ bucket-2"]
+        );
+    }
+
+    #[test]
+    fn test_render() -> std::io::Result<()> {
+        let theme = ColorTheme::default();
+        let dirs = dirs();
+        let mut state = PrefixTreeState::default();
+        state.toggle();
+        let current = key("bucket-1", &["a"]);
+        state.update(bucket_names(), Some(&current), |key| dirs.get(key).cloned());
+
+        let mut terminal = Terminal::new(TestBackend::new(20, 6))?;
+        terminal.draw(|f| {
+            let tree = PrefixTree::new(&theme);
+            f.render_stateful_widget(tree, f.area(), &mut state);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌─────────── 2 / 4 ┐",
+            "│  ▾ bucket-1      │",
+            "│    ▸ a/          │",
+            "│      c/          │",
+            "│  ▸ bucket-2      │",
+            "└──────────────────┘",
+        ]);
+        set_cells! { expected =>
+            // selected item
+            (2..18, [2]) => bg: Color::Cyan, fg: Color::Black, modifier: Modifier::BOLD,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
+    fn row_texts(state: &PrefixTreeState) -> Vec<String> {
+        state
+            .rows
+            .iter()
+            .map(|row| format!("{}{} {}", "  ".repeat(row.depth), row.marker(), row.name()))
+            .collect()
+    }
+
+    fn bucket_names() -> Vec<String> {
+        vec!["bucket-1".into(), "bucket-2".into()]
+    }
+
+    fn dirs() -> HashMap<ObjectKey, Vec<String>> {
+        HashMap::from([
+            (key("bucket-1", &[]), vec!["a".into(), "c".into()]),
+            (key("bucket-1", &["a"]), vec!["b".into()]),
+            (key("bucket-1", &["c"]), vec![]),
+        ])
+    }
+
+    fn key(bucket_name: &str, object_path: &[&str]) -> ObjectKey {
+        ObjectKey {
+            bucket_name: bucket_name.to_string(),
+            object_path: object_path.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
@@ -0,0 +1,529 @@
+use std::time::Duration;
+
+use itsuki::zero_indexed_enum;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::KeyEvent,
+    layout::Rect,
+    style::{Color, Modifier, Stylize},
+    text::Line,
+    widgets::{
+        block::Title, Block, BorderType, List, ListItem, Padding, StatefulWidget, WidgetRef,
+    },
+    Frame,
+};
+
+use crate::{
+    color::ColorTheme,
+    event::{AppEventType, Sender},
+    handle_user_events, handle_user_events_with_default,
+    help::{BuildHelpsItem, BuildShortHelpsItem},
+    keys::UserEvent,
+    object::PresignedUrlMethod,
+    util::parse_duration,
+    widget::{common::calc_centered_dialog_rect, Dialog, InputDialog, InputDialogState},
+};
+
+#[derive(Default)]
+#[zero_indexed_enum]
+pub enum PresignedUrlExpiry {
+    #[default]
+    Minutes15,
+    Hour1,
+    Day1,
+    Days7,
+    Custom,
+}
+
+impl PresignedUrlExpiry {
+    pub fn duration(&self) -> Option<Duration> {
+        match self {
+            Self::Minutes15 => Some(Duration::from_secs(15 * 60)),
+            Self::Hour1 => Some(Duration::from_secs(60 * 60)),
+            Self::Day1 => Some(Duration::from_secs(24 * 60 * 60)),
+            Self::Days7 => Some(Duration::from_secs(7 * 24 * 60 * 60)),
+            Self::Custom => None,
+        }
+    }
+
+    fn str(&self) -> &'static str {
+        match self {
+            Self::Minutes15 => "15 minutes",
+            Self::Hour1 => "1 hour",
+            Self::Day1 => "1 day",
+            Self::Days7 => "7 days",
+            Self::Custom => "Custom",
+        }
+    }
+}
+
+#[derive(Default)]
+#[zero_indexed_enum]
+pub enum PresignedUrlContentDisposition {
+    #[default]
+    Default,
+    Inline,
+    Attachment,
+}
+
+impl PresignedUrlContentDisposition {
+    pub fn header_value(&self, file_name: &str) -> Option<String> {
+        match self {
+            Self::Default => None,
+            Self::Inline => Some("inline".into()),
+            Self::Attachment => Some(format!("attachment; filename=\"{}\"", file_name)),
+        }
+    }
+
+    fn str(&self) -> &'static str {
+        match self {
+            Self::Default => "Default",
+            Self::Inline => "inline",
+            Self::Attachment => "attachment",
+        }
+    }
+}
+
+#[derive(Default)]
+#[zero_indexed_enum]
+enum PresignedUrlDialogItemType {
+    #[default]
+    Method,
+    Expiry,
+    ContentDisposition,
+}
+
+#[derive(Debug)]
+pub enum PresignedUrlDialogAction {
+    Close,
+    Generate(PresignedUrlParams),
+}
+
+#[derive(Debug)]
+pub struct PresignedUrlParams {
+    pub method: PresignedUrlMethod,
+    pub expires_in: Duration,
+    content_disposition: PresignedUrlContentDisposition,
+}
+
+impl PresignedUrlParams {
+    pub fn content_disposition(&self, file_name: &str) -> Option<String> {
+        self.content_disposition.header_value(file_name)
+    }
+}
+
+// shared by the object list and object detail pages,
+// the expiration is asked in the input dialog when "Custom" is selected
+#[derive(Debug, Default)]
+pub struct PresignedUrlDialogState {
+    selected: PresignedUrlDialogItemType,
+    method: PresignedUrlMethod,
+    expiry: PresignedUrlExpiry,
+    content_disposition: PresignedUrlContentDisposition,
+    expiry_input: Option<InputDialogState>,
+}
+
+impl PresignedUrlDialogState {
+    pub fn handle_key(
+        &mut self,
+        user_events: Vec<UserEvent>,
+        key_event: KeyEvent,
+        tx: &Sender,
+    ) -> Option<PresignedUrlDialogAction> {
+        let mut action = None;
+        if self.expiry_input.is_some() {
+            self.handle_expiry_input_key(user_events, key_event, tx, &mut action);
+            return action;
+        }
+        handle_user_events! { user_events =>
+            UserEvent::SelectDialogClose => {
+                action = Some(PresignedUrlDialogAction::Close);
+            }
+            UserEvent::SelectDialogSelect => {
+                match self.expiry.duration() {
+                    Some(expires_in) => {
+                        action = Some(self.generate(expires_in));
+                    }
+                    None => {
+                        self.expiry_input = Some(InputDialogState::default());
+                    }
+                }
+            }
+            UserEvent::SelectDialogDown => {
+                self.select_next();
+            }
+            UserEvent::SelectDialogUp => {
+                self.select_prev();
+            }
+            UserEvent::SelectDialogRight => {
+                self.select_next_value();
+            }
+            UserEvent::SelectDialogLeft => {
+                self.select_prev_value();
+            }
+            UserEvent::Help => {
+                tx.send(AppEventType::OpenHelp);
+            }
+        }
+        action
+    }
+
+    fn handle_expiry_input_key(
+        &mut self,
+        user_events: Vec<UserEvent>,
+        key_event: KeyEvent,
+        tx: &Sender,
+        action: &mut Option<PresignedUrlDialogAction>,
+    ) {
+        handle_user_events_with_default! { user_events =>
+            UserEvent::InputDialogClose => {
+                self.expiry_input = None;
+            }
+            UserEvent::InputDialogApply => {
+                let input = self
+                    .expiry_input
+                    .as_ref()
+                    .map(|s| s.input().to_string())
+                    .unwrap_or_default();
+                match parse_duration(&input) {
+                    Some(expires_in) => {
+                        *action = Some(self.generate(expires_in));
+                    }
+                    None => {
+                        let msg = format!("Invalid expiration: {}", input.trim());
+                        tx.send(AppEventType::NotifyWarn(msg));
+                    }
+                }
+            }
+            UserEvent::Help => {
+                tx.send(AppEventType::OpenHelp);
+            }
+            => {
+                if let Some(state) = self.expiry_input.as_mut() {
+                    state.handle_key_event(key_event);
+                }
+            }
+        }
+    }
+
+    fn generate(&self, expires_in: Duration) -> PresignedUrlDialogAction {
+        PresignedUrlDialogAction::Generate(PresignedUrlParams {
+            method: self.method,
+            expires_in,
+            content_disposition: self.content_disposition(),
+        })
+    }
+
+    // keys are typed into the input as is, without key sequences and counts
+    pub fn is_text_input_active(&self) -> bool {
+        self.expiry_input.is_some()
+    }
+
+    pub fn help_items(&self) -> Vec<BuildHelpsItem> {
+        #[rustfmt::skip]
+        let helps = match self.expiry_input {
+            None => vec![
+                BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+                BuildHelpsItem::new(UserEvent::SelectDialogClose, "Close presigned URL dialog"),
+                BuildHelpsItem::new(UserEvent::SelectDialogDown, "Select next item"),
+                BuildHelpsItem::new(UserEvent::SelectDialogUp, "Select previous item"),
+                BuildHelpsItem::new(UserEvent::SelectDialogRight, "Select next value"),
+                BuildHelpsItem::new(UserEvent::SelectDialogLeft, "Select previous value"),
+                BuildHelpsItem::new(UserEvent::SelectDialogSelect, "Copy presigned URL to clipboard"),
+            ],
+            Some(_) => vec![
+                BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+                BuildHelpsItem::new(UserEvent::InputDialogClose, "Close expiration dialog"),
+                BuildHelpsItem::new(UserEvent::InputDialogApply, "Copy presigned URL to clipboard"),
+            ],
+        };
+        helps
+    }
+
+    pub fn short_help_items(&self) -> Vec<BuildShortHelpsItem> {
+        #[rustfmt::skip]
+        let helps = match self.expiry_input {
+            None => vec![
+                BuildShortHelpsItem::single(UserEvent::SelectDialogClose, "Close", 2),
+                BuildShortHelpsItem::group(vec![UserEvent::SelectDialogDown, UserEvent::SelectDialogUp], "Select", 3),
+                BuildShortHelpsItem::group(vec![UserEvent::SelectDialogLeft, UserEvent::SelectDialogRight], "Change", 4),
+                BuildShortHelpsItem::single(UserEvent::SelectDialogSelect, "Copy", 1),
+                BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+            ],
+            Some(_) => vec![
+                BuildShortHelpsItem::single(UserEvent::InputDialogClose, "Close", 2),
+                BuildShortHelpsItem::single(UserEvent::InputDialogApply, "Copy", 1),
+                BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+            ],
+        };
+        helps
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = self.selected.next();
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.prev();
+    }
+
+    pub fn select_next_value(&mut self) {
+        match self.selected {
+            PresignedUrlDialogItemType::Method => self.method = self.method.next(),
+            PresignedUrlDialogItemType::Expiry => self.expiry = self.expiry.next(),
+            PresignedUrlDialogItemType::ContentDisposition => {
+                self.content_disposition = self.content_disposition.next()
+            }
+        }
+    }
+
+    pub fn select_prev_value(&mut self) {
+        match self.selected {
+            PresignedUrlDialogItemType::Method => self.method = self.method.prev(),
+            PresignedUrlDialogItemType::Expiry => self.expiry = self.expiry.prev(),
+            PresignedUrlDialogItemType::ContentDisposition => {
+                self.content_disposition = self.content_disposition.prev()
+            }
+        }
+    }
+
+    pub fn content_disposition(&self) -> PresignedUrlContentDisposition {
+        match self.method {
+            PresignedUrlMethod::Get => self.content_disposition,
+            // response-content-disposition is only applicable to GET
+            PresignedUrlMethod::Put => PresignedUrlContentDisposition::Default,
+        }
+    }
+
+    fn name_and_value_vec(&self) -> Vec<(&'static str, &'static str)> {
+        let content_disposition = match self.method {
+            PresignedUrlMethod::Get => self.content_disposition.str(),
+            PresignedUrlMethod::Put => "-",
+        };
+        vec![
+            ("Method", self.method.str()),
+            ("Expires in", self.expiry.str()),
+            ("Content-Disposition", content_disposition),
+        ]
+    }
+}
+
+#[derive(Debug, Default)]
+struct PresignedUrlDialogColor {
+    bg: Color,
+    block: Color,
+    text: Color,
+    selected: Color,
+    selected_modifier: Modifier,
+}
+
+impl PresignedUrlDialogColor {
+    fn new(theme: &ColorTheme) -> Self {
+        Self {
+            bg: theme.bg,
+            block: theme.fg,
+            text: theme.fg,
+            selected: theme.dialog_selected,
+            selected_modifier: theme.list_selected_modifier,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PresignedUrlDialog {
+    color: PresignedUrlDialogColor,
+}
+
+impl PresignedUrlDialog {
+    pub fn theme(mut self, theme: &ColorTheme) -> Self {
+        self.color = PresignedUrlDialogColor::new(theme);
+        self
+    }
+}
+
+// renders the expiration input instead of the dialog while it is open
+pub fn render_presigned_url_dialog(
+    f: &mut Frame,
+    area: Rect,
+    state: &mut PresignedUrlDialogState,
+    theme: &ColorTheme,
+) {
+    match state.expiry_input {
+        Some(ref mut input_state) => {
+            let expiry_dialog = InputDialog::default()
+                .title("Expires in (e.g. 30m, 12h, 3d)")
+                .max_width(40)
+                .theme(theme);
+            f.render_stateful_widget(expiry_dialog, area, input_state);
+
+            let (cursor_x, cursor_y) = input_state.cursor();
+            f.set_cursor_position((cursor_x, cursor_y));
+        }
+        None => {
+            let presigned_url_dialog = PresignedUrlDialog::default().theme(theme);
+            f.render_stateful_widget(presigned_url_dialog, area, state);
+        }
+    }
+}
+
+impl StatefulWidget for PresignedUrlDialog {
+    type State = PresignedUrlDialogState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let selected = state.selected.val();
+        let list_items: Vec<ListItem> = state
+            .name_and_value_vec()
+            .into_iter()
+            .enumerate()
+            .map(|(i, (name, value))| self.build_list_item(i, selected, name, value))
+            .collect();
+
+        let dialog_width = (area.width - 4).min(40);
+        let dialog_height = PresignedUrlDialogItemType::len() * 2 + 2 /* border */;
+        let area = calc_centered_dialog_rect(area, dialog_width, dialog_height as u16);
+
+        let title = Title::from("Presigned URL");
+        let list = List::new(list_items).block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .title(title)
+                .bg(self.color.bg)
+                .fg(self.color.block)
+                .padding(Padding::horizontal(1)),
+        );
+        let dialog = Dialog::new(Box::new(list), self.color.bg);
+        dialog.render_ref(area, buf);
+    }
+}
+
+impl PresignedUrlDialog {
+    fn build_list_item<'a>(
+        &self,
+        i: usize,
+        selected: usize,
+        name: &'a str,
+        value: &'a str,
+    ) -> ListItem<'a> {
+        let item = ListItem::new(vec![
+            Line::from(format!("{}:", name).add_modifier(Modifier::BOLD)),
+            Line::from(format!("  < {} >", value)),
+        ]);
+        if i == selected {
+            item.fg(self.color.selected)
+                .add_modifier(self.color.selected_modifier)
+        } else {
+            item.fg(self.color.text)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::set_cells;
+
+    use super::*;
+    use ratatui::crossterm::event::KeyCode;
+
+    #[test]
+    fn test_render_presigned_url_dialog() {
+        let theme = ColorTheme::default();
+        let mut state = PresignedUrlDialogState::default();
+        state.select_next();
+        state.select_next_value();
+        state.select_next_value();
+        let dialog = PresignedUrlDialog::default().theme(&theme);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 40, 10));
+        dialog.render(buf.area, &mut buf, &mut state);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "                                        ",
+            "  ╭Presigned URL─────────────────────╮  ",
+            "  │ Method:                          │  ",
+            "  │   < GET >                        │  ",
+            "  │ Expires in:                      │  ",
+            "  │   < 1 day >                      │  ",
+            "  │ Content-Disposition:             │  ",
+            "  │   < Default >                    │  ",
+            "  ╰──────────────────────────────────╯  ",
+            "                                        ",
+        ]);
+        set_cells! { expected =>
+            // "Method" is bold
+            (4..11, [2]) => modifier: Modifier::BOLD,
+            // "Expires in" is bold
+            (4..15, [4]) => modifier: Modifier::BOLD,
+            // "Content-Disposition" is bold
+            (4..24, [6]) => modifier: Modifier::BOLD,
+            // selected item
+            (4..36, [4, 5]) => fg: Color::Cyan,
+        }
+
+        assert_eq!(buf, expected);
+    }
+
+    #[tokio::test]
+    async fn test_custom_expiry() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let tx = Sender::new(tx);
+        let mut state = PresignedUrlDialogState::default();
+        state.select_next();
+        state.select_prev_value(); // Custom
+
+        let action = state.handle_key(vec![UserEvent::SelectDialogSelect], key_event(), &tx);
+        assert!(action.is_none());
+        assert!(state.is_text_input_active());
+
+        let action = state.handle_key(vec![UserEvent::InputDialogClose], key_event(), &tx);
+        assert!(action.is_none());
+        assert!(!state.is_text_input_active());
+
+        state.handle_key(vec![UserEvent::SelectDialogSelect], key_event(), &tx);
+        for c in "2x".chars() {
+            state.handle_key(vec![], KeyEvent::from(KeyCode::Char(c)), &tx);
+        }
+        let action = state.handle_key(vec![UserEvent::InputDialogApply], key_event(), &tx);
+        assert!(action.is_none());
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            AppEventType::NotifyWarn(msg) if msg == "Invalid expiration: 2x"
+        ));
+
+        state.handle_key(vec![], KeyEvent::from(KeyCode::Backspace), &tx);
+        state.handle_key(vec![], KeyEvent::from(KeyCode::Char('h')), &tx);
+        let action = state.handle_key(vec![UserEvent::InputDialogApply], key_event(), &tx);
+        match action {
+            Some(PresignedUrlDialogAction::Generate(params)) => {
+                assert_eq!(params.method, PresignedUrlMethod::Get);
+                assert_eq!(params.expires_in, Duration::from_secs(2 * 60 * 60));
+                assert_eq!(params.content_disposition("a.txt"), None);
+            }
+            _ => panic!("presigned URL is not generated"),
+        }
+    }
+
+    #[test]
+    fn test_content_disposition_is_ignored_for_put() {
+        let mut state = PresignedUrlDialogState::default();
+        state.select_next();
+        state.select_next();
+        state.select_next_value();
+        assert_eq!(
+            state.content_disposition(),
+            PresignedUrlContentDisposition::Inline
+        );
+
+        state.select_next();
+        state.select_next_value();
+        assert_eq!(state.method, PresignedUrlMethod::Put);
+        assert_eq!(
+            state.content_disposition(),
+            PresignedUrlContentDisposition::Default
+        );
+    }
+
+    fn key_event() -> KeyEvent {
+        KeyEvent::from(KeyCode::Null)
+    }
+}
@@ -2,9 +2,10 @@ use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Layout, Rect},
     style::{Color, Style, Stylize},
-    text::Line,
+    text::{Line, Span},
     widgets::{block::BlockExt, Block, Borders, Padding, Paragraph, StatefulWidget, Widget, Wrap},
 };
+use regex::Regex;
 
 use crate::{color::ColorTheme, util::digits};
 
@@ -20,6 +21,8 @@ enum ScrollEvent {
     End,
     Right,
     Left,
+    // scroll horizontally so that the selected match is shown (without wrap)
+    CurrentMatch,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -40,6 +43,14 @@ impl Default for ScrollLinesOptions {
     }
 }
 
+// byte range of a search match in a line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LineMatch {
+    line: usize,
+    start: usize,
+    end: usize,
+}
+
 #[derive(Debug, Default)]
 pub struct ScrollLinesState {
     lines: Vec<Line<'static>>,
@@ -49,6 +60,8 @@ pub struct ScrollLinesState {
     h_offset: usize,
     options: ScrollLinesOptions,
     scroll_event: ScrollEvent,
+    matches: Vec<LineMatch>,
+    current_match: Option<usize>,
 }
 
 impl ScrollLinesState {
@@ -97,6 +110,15 @@ impl ScrollLinesState {
         self.scroll_event = ScrollEvent::Left;
     }
 
+    pub fn scroll_to(&mut self, line: usize) {
+        self.v_offset = line.min(self.lines.len().saturating_sub(1));
+        self.scroll_event = ScrollEvent::None;
+    }
+
+    pub fn current_line(&self) -> usize {
+        self.v_offset
+    }
+
     pub fn toggle_wrap(&mut self) {
         self.options.wrap = !self.options.wrap;
         self.h_offset = 0;
@@ -109,12 +131,73 @@ impl ScrollLinesState {
     pub fn current_options(&self) -> ScrollLinesOptions {
         self.options
     }
+
+    // Returns the number of matches
+    pub fn search(&mut self, re: &Regex) -> usize {
+        self.matches = self
+            .lines
+            .iter()
+            .enumerate()
+            .flat_map(|(i, line)| {
+                // byte offsets must correspond to the span contents
+                let line_str: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+                re.find_iter(&line_str)
+                    .filter(|m| !m.is_empty())
+                    .map(|m| LineMatch {
+                        line: i,
+                        start: m.start(),
+                        end: m.end(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        self.current_match = None;
+        self.matches.len()
+    }
+
+    pub fn clear_search(&mut self) {
+        self.matches.clear();
+        self.current_match = None;
+    }
+
+    // Selects the next (or previous) match and scrolls to its line.
+    // If no match is selected yet, the search starts from the top line of the view.
+    pub fn select_match(&mut self, forward: bool) {
+        let n = self.matches.len();
+        if n == 0 {
+            return;
+        }
+        let i = match (self.current_match, forward) {
+            (Some(i), true) => (i + 1) % n,
+            (Some(i), false) => (i + n - 1) % n,
+            (None, true) => self
+                .matches
+                .iter()
+                .position(|m| m.line >= self.v_offset)
+                .unwrap_or(0),
+            (None, false) => self
+                .matches
+                .iter()
+                .rposition(|m| m.line < self.v_offset)
+                .unwrap_or(n - 1),
+        };
+        self.current_match = Some(i);
+        self.scroll_to(self.matches[i].line);
+        self.scroll_event = ScrollEvent::CurrentMatch;
+    }
+
+    // Returns the 1-based position of the selected match and the number of matches
+    pub fn match_position(&self) -> Option<(usize, usize)> {
+        self.current_match.map(|i| (i + 1, self.matches.len()))
+    }
 }
 
 #[derive(Debug, Default)]
 struct ScrollLinesColor {
     block: Color,
     line_number: Color,
+    match_style: Style,
+    current_match_style: Style,
 }
 
 impl ScrollLinesColor {
@@ -122,6 +205,14 @@ impl ScrollLinesColor {
         Self {
             block: theme.fg,
             line_number: theme.preview_line_number,
+            match_style: Style::default()
+                .bg(theme.list_selected_inactive_bg)
+                .fg(theme.list_selected_inactive_fg)
+                .add_modifier(theme.list_selected_modifier),
+            current_match_style: Style::default()
+                .bg(theme.list_selected_bg)
+                .fg(theme.list_selected_fg)
+                .add_modifier(theme.list_selected_modifier),
         }
     }
 }
@@ -173,7 +264,7 @@ impl StatefulWidget for ScrollLines {
             show_lines_count,
             self.color.line_number,
         );
-        let lines_paragraph = build_lines_paragraph(state, show_lines_count, self.color.block);
+        let lines_paragraph = build_lines_paragraph(state, show_lines_count, &self.color);
 
         self.block.map(|b| b.fg(self.color.block)).render(area, buf);
         line_numbers_paragraph.render(chunks[0], buf);
@@ -186,7 +277,7 @@ fn build_line_numbers_paragraph(
     text_area_width: usize,
     show_lines_count: usize,
     line_number_color: Color,
-) -> Paragraph {
+) -> Paragraph<'_> {
     // may not be correct because the wrap of the text is calculated separately...
     let line_heights = wrapped_line_width_iter(
         &state.lines,
@@ -218,24 +309,43 @@ fn build_line_numbers_paragraph(
     )
 }
 
-fn build_lines_paragraph(
-    state: &ScrollLinesState,
+fn build_lines_paragraph<'a>(
+    state: &'a ScrollLinesState,
     show_lines_count: usize,
-    block_color: Color,
-) -> Paragraph {
+    color: &ScrollLinesColor,
+) -> Paragraph<'a> {
     let lines_content: Vec<Line> = state
         .lines
         .iter()
+        .enumerate()
         .skip(state.v_offset)
         .take(show_lines_count)
-        .cloned()
+        .map(|(i, line)| {
+            let from = state.matches.partition_point(|m| m.line < i);
+            let to = state.matches.partition_point(|m| m.line <= i);
+            if from == to {
+                return line.clone();
+            }
+            let ranges: Vec<_> = (from..to)
+                .map(|j| {
+                    let m = state.matches[j];
+                    let style = if state.current_match == Some(j) {
+                        color.current_match_style
+                    } else {
+                        color.match_style
+                    };
+                    (m.start, m.end, style)
+                })
+                .collect();
+            highlight_line(line, &ranges)
+        })
         .collect();
 
     let lines_paragraph = Paragraph::new(lines_content).block(
         Block::default()
             .borders(Borders::NONE)
             .padding(Padding::horizontal(1))
-            .fg(block_color),
+            .fg(color.block),
     );
 
     if state.options.wrap {
@@ -328,6 +438,20 @@ fn handle_scroll_events(state: &mut ScrollLinesState, width: usize, height: usiz
                 state.h_offset = state.h_offset.saturating_sub(1);
             }
         }
+        ScrollEvent::CurrentMatch => {
+            if let Some(m) = state.current_match.map(|i| state.matches[i]) {
+                if !state.options.wrap {
+                    let line_str = line_to_string(&state.lines[m.line]);
+                    let start = Line::raw(&line_str[..m.start]).width();
+                    let end = Line::raw(&line_str[..m.end]).width();
+                    if start < state.h_offset {
+                        state.h_offset = start;
+                    } else if end > state.h_offset + width {
+                        state.h_offset = end.saturating_sub(width).min(start);
+                    }
+                }
+            }
+        }
     }
     // reset the scroll event
     state.scroll_event = ScrollEvent::None;
@@ -374,6 +498,39 @@ fn wrapped_reversed_line_width_iter<'a>(
         })
 }
 
+// Splits the spans at the byte ranges and patches the styles of the ranges,
+// so that the original (e.g. syntax highlighting) styles are kept outside of the ranges
+fn highlight_line(line: &Line<'static>, ranges: &[(usize, usize, Style)]) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut span_start = 0;
+    for span in &line.spans {
+        let content = span.content.as_ref();
+        let span_end = span_start + content.len();
+        let mut pos = span_start;
+        for &(start, end, style) in ranges {
+            let (start, end) = (start.max(pos), end.min(span_end));
+            if start >= end {
+                continue;
+            }
+            if pos < start {
+                let s = &content[(pos - span_start)..(start - span_start)];
+                spans.push(Span::styled(s.to_string(), span.style));
+            }
+            let s = &content[(start - span_start)..(end - span_start)];
+            spans.push(Span::styled(s.to_string(), span.style.patch(style)));
+            pos = end;
+        }
+        if pos < span_end {
+            let s = &content[(pos - span_start)..];
+            spans.push(Span::styled(s.to_string(), span.style));
+        }
+        span_start = span_end;
+    }
+    let mut highlighted = line.clone();
+    highlighted.spans = spans;
+    highlighted
+}
+
 fn line_to_string(line: &Line) -> String {
     line.styled_graphemes(Style::default())
         .map(|g| g.symbol)
@@ -711,6 +868,123 @@ mod tests {
         assert_eq!(buf, expected);
     }
 
+    #[test]
+    fn test_scroll_lines_search() {
+        let mut state = state(true, true);
+
+        let count = state.search(&Regex::new("bbb").unwrap());
+        assert_eq!(count, 7);
+        assert_eq!(state.match_position(), None);
+
+        state.select_match(true);
+        assert_eq!(state.match_position(), Some((1, 7)));
+
+        let buf = render_scroll_lines(&mut state);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌TITLE─────────────┐",
+            "│  1 aaa bbb ccc   │",
+            "│    ddd           │",
+            "│  2 aaa bbb ccc   │",
+            "│  3 aaa           │",
+            "│  4 aaa bbb       │",
+            "└──────────────────┘",
+        ]);
+        set_cells! { expected =>
+            ([2, 3], [1, 3, 4, 5]) => fg: Color::DarkGray,
+            // current match
+            (9..12, [1]) => bg: Color::Cyan, fg: Color::Black,
+            // other matches
+            (9..12, [3, 5]) => bg: Color::DarkGray, fg: Color::Black,
+        }
+
+        assert_eq!(buf, expected);
+
+        state.select_match(true);
+        assert_eq!(state.match_position(), Some((2, 7)));
+        assert_eq!(state.current_line(), 1);
+
+        state.select_match(false);
+        state.select_match(false);
+        assert_eq!(state.match_position(), Some((7, 7)));
+        assert_eq!(state.current_line(), 13);
+
+        state.clear_search();
+        assert_eq!(state.match_position(), None);
+
+        // the search starts from the current line
+        state.scroll_to(4);
+        state.search(&Regex::new("aaa").unwrap());
+        state.select_match(true);
+        assert_eq!(state.current_line(), 4);
+        state.search(&Regex::new("aaa").unwrap());
+        state.select_match(false);
+        assert_eq!(state.current_line(), 3);
+    }
+
+    #[test]
+    fn test_scroll_lines_search_no_wrap() {
+        let mut state = state(false, false);
+
+        state.search(&Regex::new("ggg").unwrap());
+        state.select_match(true);
+        let buf = render_scroll_lines(&mut state);
+
+        // scrolled to the right so that the match beyond the width is shown
+        assert_eq!(state.current_line(), 13);
+        assert_eq!(state.h_offset, 11);
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌TITLE─────────────┐",
+            "│  ddd eee fff ggg │",
+            "│                  │",
+            "│                  │",
+            "│                  │",
+            "│                  │",
+            "└──────────────────┘",
+        ]);
+        set_cells! { expected =>
+            // current match
+            (15..18, [1]) => bg: Color::Cyan, fg: Color::Black,
+        }
+        assert_eq!(buf, expected);
+
+        // scrolled back to the left
+        state.search(&Regex::new("aaa").unwrap());
+        state.scroll_to(0);
+        state.select_match(true);
+        render_scroll_lines(&mut state);
+        assert_eq!(state.h_offset, 0);
+
+        // not scrolled if the match is in the view
+        state.scroll_right();
+        render_scroll_lines(&mut state);
+        state.search(&Regex::new("ccc").unwrap());
+        state.select_match(true);
+        render_scroll_lines(&mut state);
+        assert_eq!(state.h_offset, 1);
+    }
+
+    #[test]
+    fn test_highlight_line() {
+        let style = Style::default().bg(Color::Red);
+        let line = Line::from(vec![
+            Span::styled("foo", Style::default().fg(Color::Blue)),
+            Span::styled("bar baz", Style::default().fg(Color::Green)),
+        ]);
+
+        let actual = highlight_line(&line, &[(1, 5, style), (7, 10, style)]);
+        let expected = Line::from(vec![
+            Span::styled("f", Style::default().fg(Color::Blue)),
+            Span::styled("oo", Style::default().fg(Color::Blue).bg(Color::Red)),
+            Span::styled("ba", Style::default().fg(Color::Green).bg(Color::Red)),
+            Span::styled("r ", Style::default().fg(Color::Green)),
+            Span::styled("baz", Style::default().fg(Color::Green).bg(Color::Red)),
+        ]);
+        assert_eq!(actual, expected);
+    }
+
     fn state(number: bool, wrap: bool) -> ScrollLinesState {
         let lines: Vec<Line> = [
             "aaa bbb ccc ddd",
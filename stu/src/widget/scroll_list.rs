@@ -1,6 +1,6 @@
 use ratatui::{
     buffer::Buffer,
-    layout::{Alignment, Margin, Rect},
+    layout::{Alignment, Margin, Position, Rect},
     style::{Color, Stylize},
     widgets::{Block, List, ListItem, Padding, StatefulWidget, Widget},
 };
@@ -15,6 +15,8 @@ pub struct ScrollListState {
     pub offset: usize,
     total: usize,
     height: usize,
+    // the area of the items at the last render, used to find the clicked item
+    area: Rect,
 }
 
 impl ScrollListState {
@@ -110,6 +112,47 @@ impl ScrollListState {
             self.offset = self.total - self.height;
         }
     }
+
+    pub fn select_index(&mut self, index: usize) {
+        if index >= self.total {
+            return;
+        }
+        self.selected = index;
+        if self.height > 0 {
+            self.fit_height(self.height);
+        }
+    }
+
+    // keep the selection and the scroll position as much as possible when the items are replaced
+    pub fn set_total(&mut self, total: usize) {
+        self.total = total;
+        self.selected = self.selected.min(total.saturating_sub(1));
+        self.offset = self.offset.min(self.selected);
+        if self.height > 0 {
+            self.fit_height(self.height);
+        }
+    }
+
+    pub fn index_at(&self, position: Position) -> Option<usize> {
+        if !self.area.contains(position) {
+            return None;
+        }
+        let index = self.offset + (position.y - self.area.y) as usize;
+        (index < self.total).then_some(index)
+    }
+
+    // adjust offset so that the selected item is visible within the given height
+    pub fn fit_height(&mut self, height: usize) {
+        self.height = height;
+        if height == 0 {
+            return;
+        }
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if self.selected >= self.offset + height {
+            self.offset = self.selected + 1 - height;
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -152,6 +195,7 @@ impl StatefulWidget for ScrollList<'_> {
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         state.height = area.height as usize - 2 /* border */;
+        state.area = area.inner(Margin::new(1, 1));
 
         let title = format_list_count(state.total, state.selected);
         let list = List::new(self.items).block(
@@ -188,6 +232,62 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_select_index() {
+        let mut state = ScrollListState::new(20);
+
+        // height is not known until rendered
+        state.select_index(15);
+        state.fit_height(10);
+        assert_eq!((state.selected, state.offset), (15, 6));
+
+        state.select_index(3);
+        assert_eq!((state.selected, state.offset), (3, 3));
+
+        state.select_index(20);
+        assert_eq!((state.selected, state.offset), (3, 3));
+    }
+
+    #[test]
+    fn test_set_total() {
+        let mut state = ScrollListState::new(20);
+        state.fit_height(10);
+        state.select_index(15);
+        assert_eq!((state.selected, state.offset), (15, 6));
+
+        state.set_total(25);
+        assert_eq!((state.selected, state.offset), (15, 6));
+
+        state.set_total(10);
+        assert_eq!((state.selected, state.offset), (9, 6));
+
+        state.set_total(0);
+        assert_eq!((state.selected, state.offset), (0, 0));
+    }
+
+    #[test]
+    fn test_index_at() {
+        let mut state = ScrollListState::new(20);
+
+        // nothing is clickable until rendered
+        assert_eq!(state.index_at(Position::new(2, 1)), None);
+
+        let _ = render_scroll_list(&mut state);
+        state.select_index(15);
+
+        assert_eq!(state.index_at(Position::new(2, 1)), Some(6));
+        assert_eq!(state.index_at(Position::new(2, 10)), Some(15));
+        // border
+        assert_eq!(state.index_at(Position::new(0, 1)), None);
+        assert_eq!(state.index_at(Position::new(2, 0)), None);
+        assert_eq!(state.index_at(Position::new(2, 11)), None);
+
+        let mut state = ScrollListState::new(3);
+        let _ = render_scroll_list(&mut state);
+        assert_eq!(state.index_at(Position::new(2, 3)), Some(2));
+        assert_eq!(state.index_at(Position::new(2, 4)), None);
+    }
+
     #[test]
     fn test_render_scroll_list_without_scroll() {
         let theme = ColorTheme::default();
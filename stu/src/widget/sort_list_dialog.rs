@@ -1,15 +1,18 @@
 use itsuki::zero_indexed_enum;
 use ratatui::{
     buffer::Buffer,
-    layout::Rect,
-    style::{Color, Stylize},
+    layout::{Position, Rect},
+    style::{Color, Modifier, Stylize},
     text::Line,
     widgets::{block::Title, Block, BorderType, List, ListItem, Padding, Widget, WidgetRef},
 };
 
 use crate::{
     color::ColorTheme,
-    widget::{common::calc_centered_dialog_rect, Dialog},
+    widget::{
+        common::{calc_centered_dialog_rect, dialog_item_index_at},
+        Dialog,
+    },
 };
 
 #[derive(Default)]
@@ -49,9 +52,23 @@ impl BucketListSortDialogState {
         self.selected = BucketListSortType::Default;
     }
 
+    pub fn set_selected(&mut self, selected: BucketListSortType) {
+        self.selected = selected;
+    }
+
     pub fn selected(&self) -> BucketListSortType {
         self.selected
     }
+
+    pub fn select_index(&mut self, index: usize) {
+        if let Ok(selected) = BucketListSortType::try_from(index) {
+            self.selected = selected;
+        }
+    }
+
+    pub fn item_index_at(&self, area: Rect, position: Position) -> Option<usize> {
+        list_sort_dialog_item_index_at(area, BucketListSortType::len(), position)
+    }
 }
 
 pub struct BucketListSortDialog {
@@ -131,9 +148,23 @@ impl ObjectListSortDialogState {
         self.selected = ObjectListSortType::Default;
     }
 
+    pub fn set_selected(&mut self, selected: ObjectListSortType) {
+        self.selected = selected;
+    }
+
     pub fn selected(&self) -> ObjectListSortType {
         self.selected
     }
+
+    pub fn select_index(&mut self, index: usize) {
+        if let Ok(selected) = ObjectListSortType::try_from(index) {
+            self.selected = selected;
+        }
+    }
+
+    pub fn item_index_at(&self, area: Rect, position: Position) -> Option<usize> {
+        list_sort_dialog_item_index_at(area, ObjectListSortType::len(), position)
+    }
 }
 
 pub struct ObjectListSortDialog {
@@ -174,6 +205,7 @@ struct ListSortDialogColor {
     block: Color,
     text: Color,
     selected: Color,
+    selected_modifier: Modifier,
 }
 
 impl ListSortDialogColor {
@@ -183,6 +215,7 @@ impl ListSortDialogColor {
             block: theme.fg,
             text: theme.fg,
             selected: theme.dialog_selected,
+            selected_modifier: theme.list_selected_modifier,
         }
     }
 }
@@ -213,15 +246,14 @@ impl Widget for ListSortDialog {
                 let item = ListItem::new(Line::raw(*label));
                 if i == self.selected {
                     item.fg(self.color.selected)
+                        .add_modifier(self.color.selected_modifier)
                 } else {
                     item.fg(self.color.text)
                 }
             })
             .collect();
 
-        let dialog_width = (area.width - 4).min(30);
-        let dialog_height = self.labels.len() as u16 + 2 /* border */;
-        let area = calc_centered_dialog_rect(area, dialog_width, dialog_height);
+        let area = list_sort_dialog_area(area, self.labels.len());
 
         let title = Title::from("Sort");
         let list = List::new(list_items).block(
@@ -236,3 +268,14 @@ impl Widget for ListSortDialog {
         dialog.render_ref(area, buf);
     }
 }
+
+fn list_sort_dialog_area(area: Rect, len: usize) -> Rect {
+    let dialog_width = (area.width - 4).min(30);
+    let dialog_height = len as u16 + 2 /* border */;
+    calc_centered_dialog_rect(area, dialog_width, dialog_height)
+}
+
+fn list_sort_dialog_item_index_at(area: Rect, len: usize, position: Position) -> Option<usize> {
+    let dialog_area = list_sort_dialog_area(area, len);
+    dialog_item_index_at(dialog_area, 1, len, position)
+}
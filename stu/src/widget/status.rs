@@ -14,6 +14,7 @@ use crate::{
 #[derive(Debug)]
 pub enum StatusType {
     Help(Vec<SpansWithPriority>),
+    PendingKeys(String),
     Info(String),
     Success(String),
     Warn(String),
@@ -79,6 +80,7 @@ impl Status {
                 let spans = prune_spans_to_fit_width(&spans, max_width, delimiter);
                 Line::from(spans).fg(self.color.help)
             }
+            StatusType::PendingKeys(keys) => Line::from(keys).fg(self.color.info).bold(),
             StatusType::Info(msg) => Line::from(msg).fg(self.color.info),
             StatusType::Success(msg) => Line::from(msg).fg(self.color.success).bold(),
             StatusType::Warn(msg) => Line::from(msg).fg(self.color.warn).bold(),
@@ -0,0 +1,146 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Margin, Position, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Widget,
+};
+
+use crate::color::ColorTheme;
+
+const MAX_TITLE_WIDTH: usize = 24;
+const ELLIPSIS: &str = "...";
+
+#[derive(Debug, Default)]
+struct TabBarColor {
+    text: Color,
+    selected_bg: Color,
+    selected_fg: Color,
+}
+
+impl TabBarColor {
+    fn new(theme: &ColorTheme) -> TabBarColor {
+        TabBarColor {
+            text: theme.fg,
+            selected_bg: theme.list_selected_bg,
+            selected_fg: theme.list_selected_fg,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct TabBar {
+    titles: Vec<String>,
+    selected: usize,
+    color: TabBarColor,
+}
+
+impl TabBar {
+    pub fn new(titles: Vec<String>, selected: usize) -> TabBar {
+        TabBar {
+            titles,
+            selected,
+            ..Default::default()
+        }
+    }
+
+    pub fn theme(mut self, theme: &ColorTheme) -> Self {
+        self.color = TabBarColor::new(theme);
+        self
+    }
+
+    // Returns the index of the tab at the position
+    pub fn tab_index_at(&self, area: Rect, position: Position) -> Option<usize> {
+        let text_area = Self::text_area(area);
+        if !text_area.contains(position) {
+            return None;
+        }
+        let mut x = text_area.x;
+        for (i, label) in self.build_labels().iter().enumerate() {
+            let width = console::measure_text_width(label) as u16;
+            if (x..x + width).contains(&position.x) {
+                return Some(i);
+            }
+            x += width + 1 /* gap */;
+        }
+        None
+    }
+
+    fn text_area(area: Rect) -> Rect {
+        area.inner(Margin::new(1, 0))
+    }
+
+    fn build_labels(&self) -> Vec<String> {
+        self.titles
+            .iter()
+            .enumerate()
+            .map(|(i, title)| {
+                let title = console::truncate_str(title, MAX_TITLE_WIDTH, ELLIPSIS);
+                format!(" {}: {} ", i + 1, title)
+            })
+            .collect()
+    }
+}
+
+impl Widget for TabBar {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut spans = Vec::new();
+        for (i, label) in self.build_labels().into_iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::raw(" "));
+            }
+            let style = if i == self.selected {
+                Style::default()
+                    .bg(self.color.selected_bg)
+                    .fg(self.color.selected_fg)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(self.color.text)
+            };
+            spans.push(Span::styled(label, style));
+        }
+        Line::from(spans).render(Self::text_area(area), buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::set_cells;
+
+    use super::*;
+
+    #[test]
+    fn test_render_tab_bar() {
+        let theme = ColorTheme::default();
+        let titles = vec!["bucket".into(), "logs/".into(), "config".into()];
+        let tab_bar = TabBar::new(titles, 1).theme(&theme);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 40, 1));
+        tab_bar.render(buf.area, &mut buf);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "  1: bucket   2: logs/   3: config      ",
+        ]);
+        set_cells! { expected =>
+            // selected tab
+            (13..23, [0]) => bg: Color::Cyan, fg: Color::Black, modifier: Modifier::BOLD,
+        }
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_tab_index_at() {
+        let titles = vec!["bucket".into(), "logs/".into(), "config".into()];
+        let tab_bar = TabBar::new(titles, 0);
+        let area = Rect::new(0, 5, 40, 1);
+
+        assert_eq!(tab_bar.tab_index_at(area, Position::new(0, 5)), None);
+        assert_eq!(tab_bar.tab_index_at(area, Position::new(1, 5)), Some(0));
+        assert_eq!(tab_bar.tab_index_at(area, Position::new(11, 5)), Some(0));
+        assert_eq!(tab_bar.tab_index_at(area, Position::new(12, 5)), None);
+        assert_eq!(tab_bar.tab_index_at(area, Position::new(13, 5)), Some(1));
+        assert_eq!(tab_bar.tab_index_at(area, Position::new(34, 5)), Some(2));
+        assert_eq!(tab_bar.tab_index_at(area, Position::new(35, 5)), None);
+        assert_eq!(tab_bar.tab_index_at(area, Position::new(13, 4)), None);
+    }
+}
@@ -3,13 +3,14 @@ use itsuki::zero_indexed_enum;
 use once_cell::sync::Lazy;
 use ratatui::{
     buffer::Buffer,
-    layout::Rect,
-    style::{Color, Stylize},
+    layout::{Position, Rect},
+    style::{Color, Modifier, Stylize},
     text::Line,
     widgets::{
         block::Title, Block, BorderType, List, ListItem, Padding, StatefulWidget, Widget, WidgetRef,
     },
 };
+use regex::RegexBuilder;
 use syntect::{
     easy::HighlightLines,
     highlighting::ThemeSet,
@@ -24,8 +25,8 @@ use crate::{
     object::{FileDetail, RawObject},
     util::extension_from_file_name,
     widget::{
-        common::calc_centered_dialog_rect, Dialog, ScrollLines, ScrollLinesOptions,
-        ScrollLinesState,
+        common::{calc_centered_dialog_rect, dialog_item_index_at},
+        Dialog, ScrollLines, ScrollLinesOptions, ScrollLinesState,
     },
 };
 
@@ -282,6 +283,17 @@ impl EncodingDialogState {
     pub fn selected(&self) -> EncodingType {
         self.encodings[self.selected]
     }
+
+    pub fn select_index(&mut self, index: usize) {
+        if index < self.encodings.len() {
+            self.selected = index;
+        }
+    }
+
+    pub fn item_index_at(&self, area: Rect, position: Position) -> Option<usize> {
+        let len = self.encodings.len();
+        dialog_item_index_at(encoding_dialog_area(area, len), 1, len, position)
+    }
 }
 
 #[derive(Debug, Default)]
@@ -290,6 +302,7 @@ struct EncodingDialogColor {
     block: Color,
     text: Color,
     selected: Color,
+    selected_modifier: Modifier,
 }
 
 impl EncodingDialogColor {
@@ -299,6 +312,7 @@ impl EncodingDialogColor {
             block: theme.fg,
             text: theme.fg,
             selected: theme.dialog_selected,
+            selected_modifier: theme.list_selected_modifier,
         }
     }
 }
@@ -335,15 +349,14 @@ impl Widget for EncodingDialog<'_> {
                 let item = ListItem::new(Line::raw(*label));
                 if i == self.state.selected {
                     item.fg(self.color.selected)
+                        .add_modifier(self.color.selected_modifier)
                 } else {
                     item.fg(self.color.text)
                 }
             })
             .collect();
 
-        let dialog_width = (area.width - 4).min(20);
-        let dialog_height = self.labels.len() as u16 + 2 /* border */;
-        let area = calc_centered_dialog_rect(area, dialog_width, dialog_height);
+        let area = encoding_dialog_area(area, self.labels.len());
 
         let title = Title::from("Encoding");
         let list = List::new(list_items).block(
@@ -359,10 +372,37 @@ impl Widget for EncodingDialog<'_> {
     }
 }
 
+fn encoding_dialog_area(area: Rect, len: usize) -> Rect {
+    let dialog_width = (area.width - 4).min(20);
+    let dialog_height = len as u16 + 2 /* border */;
+    calc_centered_dialog_rect(area, dialog_width, dialog_height)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SearchDirection {
+    #[default]
+    Forward,
+    Backward,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchOptions {
+    pub regex: bool,
+    pub ignore_case: bool,
+}
+
+#[derive(Debug, Clone)]
+struct TextSearch {
+    query: String,
+    options: SearchOptions,
+    direction: SearchDirection,
+}
+
 #[derive(Debug)]
 pub struct TextPreviewState {
     pub scroll_lines_state: ScrollLinesState,
     pub encoding: EncodingType,
+    search: Option<TextSearch>,
 }
 
 impl TextPreviewState {
@@ -376,6 +416,7 @@ impl TextPreviewState {
         let mut state = Self {
             scroll_lines_state: ScrollLinesState::new(vec![], ScrollLinesOptions::default()),
             encoding: default_encoding,
+            search: None,
         };
         let warn_msg = state.update_lines(file_detail, object, highlight, highlight_theme_name);
         (state, warn_msg)
@@ -410,9 +451,72 @@ impl TextPreviewState {
         let options = self.scroll_lines_state.current_options();
         self.scroll_lines_state = ScrollLinesState::new(lines, options);
 
+        // keep highlighting the matches of the last search with the new lines
+        if let Some(search) = self.search.clone() {
+            if let Ok(re) = build_search_regex(&search.query, search.options) {
+                self.scroll_lines_state.search(&re);
+            }
+        }
+
         warn_msg
     }
 
+    // An empty query clears the current search
+    pub fn search(
+        &mut self,
+        query: &str,
+        options: SearchOptions,
+        direction: SearchDirection,
+    ) -> Result<(), String> {
+        if query.is_empty() {
+            self.search = None;
+            self.scroll_lines_state.clear_search();
+            return Ok(());
+        }
+
+        let re = build_search_regex(query, options)?;
+        let count = self.scroll_lines_state.search(&re);
+        self.search = Some(TextSearch {
+            query: query.into(),
+            options,
+            direction,
+        });
+        if count == 0 {
+            return Err(format!("Pattern not found: {}", query));
+        }
+        self.scroll_lines_state
+            .select_match(direction == SearchDirection::Forward);
+        Ok(())
+    }
+
+    // Moves to the next match in the direction of the last search (or the opposite if `reverse`)
+    pub fn search_next(&mut self, reverse: bool) -> Result<(), String> {
+        let Some(search) = &self.search else {
+            return Err("No previous search".into());
+        };
+        let forward = (search.direction == SearchDirection::Forward) != reverse;
+        self.scroll_lines_state.select_match(forward);
+        if self.scroll_lines_state.match_position().is_none() {
+            return Err(format!("Pattern not found: {}", search.query));
+        }
+        Ok(())
+    }
+
+    // e.g. "[3/10] /pattern"
+    pub fn search_status(&self) -> Option<String> {
+        let search = self.search.as_ref()?;
+        let (i, n) = self.scroll_lines_state.match_position()?;
+        let prefix = match search.direction {
+            SearchDirection::Forward => '/',
+            SearchDirection::Backward => '?',
+        };
+        Some(format!("[{}/{}] {}{}", i, n, prefix, search.query))
+    }
+
+    pub fn last_search_query(&self) -> Option<&str> {
+        self.search.as_ref().map(|s| s.query.as_str())
+    }
+
     fn to_preview_string(&self, bytes: &[u8]) -> String {
         let encoding: &encoding_rs::Encoding = self.encoding.into();
         let (s, _, _) = encoding.decode(bytes);
@@ -426,11 +530,23 @@ impl TextPreviewState {
     }
 }
 
-fn drop_control_chars(s: &str) -> String {
+fn build_search_regex(query: &str, options: SearchOptions) -> Result<regex::Regex, String> {
+    let pattern = if options.regex {
+        query.into()
+    } else {
+        regex::escape(query)
+    };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(options.ignore_case)
+        .build()
+        .map_err(|_| format!("Invalid regex: {}", query))
+}
+
+pub fn drop_control_chars(s: &str) -> String {
     s.chars().filter(|c| !c.is_control()).collect()
 }
 
-fn build_highlighted_lines(
+pub fn build_highlighted_lines(
     s: &str,
     file_name: &str,
     highlight: bool,